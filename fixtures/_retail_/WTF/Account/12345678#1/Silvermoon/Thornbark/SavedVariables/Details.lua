@@ -0,0 +1,2 @@
+Details_Config = {
+}
@@ -0,0 +1,7 @@
+ChronoBindChar = {
+	["Zephyra-Silvermoon"] = {
+		["classID"] = 8,
+		["level"] = 80,
+		["guid"] = "Player-1096-0A1B2C3D",
+	},
+}
@@ -0,0 +1,3 @@
+Details_Config = {
+	["enabled"] = true,
+}
@@ -0,0 +1,121 @@
+use std::collections::HashSet;
+use std::io::Read;
+use std::path::Path;
+
+use itertools::Itertools;
+
+use crate::{
+    backend::{
+        vfs::FileSystem,
+        zip_rw::{ChronoZipReader, ChronoZipWriter},
+    },
+    files::AnyResult,
+    wow::WoWInstall,
+};
+
+/// Name of the file listing which addons are enabled for a character.
+pub const ADDONS_FILE_NAME: &str = "AddOns.txt";
+
+/// Path within a character backup of the snapshot of the character's `AddOns.txt`.
+const ADDONS_SNAPSHOT_PATH: &str = ".chronobind/AddOns.txt";
+/// Path within a character backup of the names of the addons enabled when it was made.
+const ADDON_MANIFEST_PATH: &str = ".chronobind/enabled-addons.txt";
+
+/// Get the names of the enabled addons from the contents of an `AddOns.txt` file,
+/// which lists one `Name: enabled` or `Name: disabled` entry per line.
+#[must_use]
+pub fn parse_enabled_addons(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .filter(|(_, state)| state.trim().eq_ignore_ascii_case("enabled"))
+        .map(|(name, _)| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Snapshot the character's `AddOns.txt` and the names of its enabled addons into the archive.
+///
+/// Lets the addon setup be checked when the backup is restored.
+/// Characters without an `AddOns.txt` are skipped.
+/// # Errors
+/// Returns an error if writing to the archive fails.
+pub fn write_addon_manifest(
+    zip: &mut ChronoZipWriter<'_>,
+    fs: &dyn FileSystem,
+    char_path: &Path,
+) -> AnyResult<()> {
+    let addons_file = char_path.join(ADDONS_FILE_NAME);
+    let mut contents = String::new();
+    if let Err(e) = fs
+        .open(&addons_file)
+        .and_then(|mut file| file.read_to_string(&mut contents))
+    {
+        log::debug!("No enabled addons to include in the backup: {e}");
+        return Ok(());
+    }
+
+    let enabled = parse_enabled_addons(&contents);
+    zip.write_file(ADDONS_SNAPSHOT_PATH, contents.as_bytes())?;
+    zip.write_file(ADDON_MANIFEST_PATH, enabled.join("\n").as_bytes())?;
+    log::info!("Included {} enabled addons in the backup", enabled.len());
+    Ok(())
+}
+
+/// Read the names of the addons enabled when the backup was made, if it includes them.
+#[must_use]
+pub fn read_addon_manifest(archive: &mut ChronoZipReader<'_>) -> Option<Vec<String>> {
+    let mut entry = archive.by_name(ADDON_MANIFEST_PATH).ok()?;
+    let mut contents = String::new();
+    entry.read_to_string(&mut contents).ok()?;
+    Some(
+        contents
+            .lines()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(ToString::to_string)
+            .collect(),
+    )
+}
+
+/// Get the names of the addons installed in the given installation, in lowercase as
+/// addon directories are matched case-insensitively by the game.
+#[must_use]
+pub fn installed_addons(fs: &dyn FileSystem, install: &WoWInstall) -> HashSet<String> {
+    fs.read_dir(&install.get_addons_path())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|path| fs.is_dir(path))
+        .filter_map(|path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().to_lowercase())
+        })
+        .collect()
+}
+
+/// Warn about any addon enabled in the backup which isn't installed, as restoring the
+/// backup won't reproduce the character's UI without it.
+pub fn warn_missing_addons(
+    archive: &mut ChronoZipReader<'_>,
+    fs: &dyn FileSystem,
+    install: &WoWInstall,
+) {
+    let Some(enabled) = read_addon_manifest(archive) else {
+        return;
+    };
+    let installed = installed_addons(fs, install);
+    let missing = enabled
+        .iter()
+        .filter(|name| !installed.contains(&name.to_lowercase()))
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        return;
+    }
+    log::warn!(
+        "{} addon{} enabled in the backup {} not installed: {}",
+        missing.len(),
+        if missing.len() == 1 { "" } else { "s" },
+        if missing.len() == 1 { "is" } else { "are" },
+        missing.iter().join(", ")
+    );
+}
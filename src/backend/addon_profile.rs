@@ -0,0 +1,317 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    backend::{
+        CharWithInstallLocal,
+        archive_manifest::{AddonProfileManifest, is_archive_metadata},
+        backup_character_selected_async, character_summary_name,
+        task::{IOProgress, IOTask, IOTaskKind, ProgressSender},
+        vfs::filesystem_for,
+        zip_rw::{ChronoZipReader, ChronoZipWriter, is_directory_entry, normalize_entry_name},
+    },
+    config::RuntimeSettings,
+    files::AnyResult,
+    tui_log::mock_prefix,
+    wow::{SAVED_VARIABLES_DIR, addon_name_from_file_name},
+};
+
+/// Where within `WTF` the files of an addon profile are exported from and imported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ProfileLocation {
+    /// The character's own `SavedVariables`.
+    Character,
+    /// The account-wide `SavedVariables` of the character's account.
+    Account,
+}
+
+impl ProfileLocation {
+    /// Both locations, in the order they are exported.
+    const ALL: [Self; 2] = [Self::Character, Self::Account];
+
+    /// Directory within the addon profile archive holding the files of this location.
+    #[inline]
+    #[must_use]
+    const fn archive_dir(self) -> &'static str {
+        match self {
+            Self::Character => "Character/",
+            Self::Account => "Account/",
+        }
+    }
+
+    /// Path of the `SavedVariables` directory of this location for the given character.
+    #[must_use]
+    fn saved_variables_path(self, character: &CharWithInstallLocal) -> PathBuf {
+        match self {
+            Self::Character => character.get_character_path().join(SAVED_VARIABLES_DIR),
+            Self::Account => character
+                .character
+                .get_account_saved_variables_path(&character.install),
+        }
+    }
+
+    /// Get the location and file name an archive entry is imported to, `None` if the entry
+    /// isn't a file directly within either location's directory.
+    #[must_use]
+    fn of_entry(entry_name: &str) -> Option<(Self, String)> {
+        let name = normalize_entry_name(entry_name);
+        Self::ALL.into_iter().find_map(|location| {
+            let file_name = name.strip_prefix(location.archive_dir())?;
+            (!file_name.is_empty() && !file_name.contains('/') && file_name != "..")
+                .then(|| (location, file_name.to_string()))
+        })
+    }
+}
+
+/// Returns `true` if the `SavedVariables` file name belongs to the given addon.
+#[inline]
+#[must_use]
+fn belongs_to_addon(file_name: &str, addon: &str) -> bool {
+    addon_name_from_file_name(file_name).eq_ignore_ascii_case(addon)
+}
+
+/// Find the `SavedVariables` files of the given addon for the character, along with the
+/// archive entry names they are exported as.
+fn addon_profile_files(character: &CharWithInstallLocal, addon: &str) -> Vec<(String, PathBuf)> {
+    let mut files = Vec::new();
+    for location in ProfileLocation::ALL {
+        let Ok(entries) = std::fs::read_dir(location.saved_variables_path(character)) else {
+            continue;
+        };
+        files.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.is_file())
+                .filter_map(|path| {
+                    let file_name = path.file_name()?.to_string_lossy().to_string();
+                    belongs_to_addon(&file_name, addon)
+                        .then(|| (format!("{}{file_name}", location.archive_dir()), path))
+                }),
+        );
+    }
+    files.sort();
+    files
+}
+
+/// Export the character and account-wide `SavedVariables` of a single addon for the given
+/// `WoW` character into a small archive, to share or import onto another character.
+/// # Errors
+/// Returns an error if the character has no `SavedVariables` for the addon.
+pub fn export_addon_profile_async(
+    character: CharWithInstallLocal,
+    addon: String,
+    export_path: PathBuf,
+    settings: &RuntimeSettings,
+    mock_mode: bool,
+) -> AnyResult<IOTask> {
+    let files = addon_profile_files(&character, &addon);
+    if files.is_empty() {
+        return Err(format!(
+            "No saved variables found for addon `{addon}` on {}",
+            character_summary_name(settings, &character.character)
+        )
+        .into());
+    }
+
+    let name = format!("Exporting {addon} profile");
+    let settings = settings.clone();
+    Ok(IOTask::new(move |tx| {
+        let total = files.len();
+        tx.send(IOProgress::Started { total: Some(total) })?;
+
+        let mut zip = ChronoZipWriter::new(&export_path, mock_mode)?;
+        AddonProfileManifest::for_character(&addon, &character.character).write_to(&mut zip)?;
+
+        let mut progress = ProgressSender::new(tx, total, settings.progress_interval);
+        for (exported, (entry_name, path)) in files.iter().enumerate() {
+            zip.copy_file(entry_name.as_str(), path)?;
+            log::info!(
+                "{}Exported `{entry_name}` to the addon profile",
+                mock_prefix(mock_mode)
+            );
+            progress.advance(exported.saturating_add(1), || Some(entry_name.clone()))?;
+        }
+        progress.flush()?;
+        zip.finish()?;
+
+        tx.send(IOProgress::Summary(format!(
+            "{}Exported {addon} profile of {}: {total} files, to `{}`",
+            mock_prefix(mock_mode),
+            character_summary_name(&settings, &character.character),
+            export_path.display()
+        )))?;
+        Ok(())
+    })
+    .name(name))
+}
+
+/// Get the names of the entries of an addon profile archive which aren't `SavedVariables`
+/// files of the given addon, leaving out directories and archive metadata.
+fn unexpected_profile_entries(archive: &mut ChronoZipReader<'_>, addon: &str) -> Vec<String> {
+    archive
+        .file_names()
+        .filter(|name| !is_directory_entry(name) && !is_archive_metadata(name))
+        .filter(|name| {
+            ProfileLocation::of_entry(name)
+                .is_none_or(|(_, file_name)| !belongs_to_addon(&file_name, addon))
+        })
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Import an addon profile archive onto the given `WoW` character and its account.
+///
+/// The character's current files of the addon are backed up first. Files within the archive which don't belong to the profile's addon are skipped with a warning.
+/// # Errors
+/// Returns an error if the archive cannot be read, isn't an addon profile export, or has no
+/// files of its addon.
+pub fn import_addon_profile_async(
+    character: CharWithInstallLocal,
+    archive_path: PathBuf,
+    settings: &RuntimeSettings,
+    mock_mode: bool,
+) -> AnyResult<IOTask> {
+    let (manifest, file_count, unexpected) = {
+        let mut archive = ChronoZipReader::new(&archive_path)?;
+        let Some(manifest) = AddonProfileManifest::read_from(&mut archive) else {
+            return Err(format!(
+                "`{}` is not an addon profile export",
+                crate::files::file_name_str(&archive_path)
+            )
+            .into());
+        };
+        let unexpected = unexpected_profile_entries(&mut archive, &manifest.addon);
+        let file_count = archive
+            .file_names()
+            .filter(|name| !is_directory_entry(name) && !is_archive_metadata(name))
+            .count();
+        (manifest, file_count, unexpected)
+    };
+    if file_count == unexpected.len() {
+        return Err(format!(
+            "Addon profile `{}` has no files of addon `{}`",
+            crate::files::file_name_str(&archive_path),
+            manifest.addon
+        )
+        .into());
+    }
+    if !unexpected.is_empty() {
+        log::warn!(
+            "Addon profile `{}` contains {} files not belonging to `{}`, skipping them: {}",
+            crate::files::file_name_str(&archive_path),
+            unexpected.len(),
+            manifest.addon,
+            unexpected.join(", ")
+        );
+    }
+    if manifest.branch != character.character.branch {
+        log::info!(
+            "Importing {} profile exported from {} on `{}` onto `{}`",
+            manifest.addon,
+            manifest.character,
+            manifest.branch,
+            character.character.branch
+        );
+    }
+
+    let char_path = character.get_character_path();
+    let existing_files = addon_profile_files(&character, &manifest.addon)
+        .into_iter()
+        .filter_map(|(_, path)| path.strip_prefix(&char_path).ok().map(Path::to_path_buf))
+        .collect::<Vec<_>>();
+
+    let import_task = import_addon_profile_task(
+        character.clone(),
+        archive_path,
+        manifest,
+        settings,
+        mock_mode,
+    );
+    if mock_mode || existing_files.is_empty() {
+        return Ok(import_task);
+    }
+    log::debug!("Backing up addon files before importing the profile...");
+    Ok(backup_character_selected_async(
+        character,
+        &existing_files,
+        true,
+        false,
+        settings,
+        mock_mode,
+    )
+    .then(import_task))
+}
+
+/// Create the task extracting the files of the profile's addon from an addon profile archive
+/// into the given `WoW` character's and its account's `SavedVariables`.
+fn import_addon_profile_task(
+    character: CharWithInstallLocal,
+    archive_path: PathBuf,
+    manifest: AddonProfileManifest,
+    settings: &RuntimeSettings,
+    mock_mode: bool,
+) -> IOTask {
+    let name = format!("Importing {} profile", manifest.addon);
+    let settings = settings.clone();
+    IOTask::new(move |tx| {
+        let fs = filesystem_for(mock_mode);
+        let mut archive = ChronoZipReader::new_with_fs(&archive_path, fs.as_ref())?;
+
+        let total = archive.len();
+        tx.send(IOProgress::Started { total: Some(total) })?;
+        let mut progress = ProgressSender::new(tx, total, settings.progress_interval);
+        let (mut character_files, mut account_files, mut skipped) = (0, 0, 0);
+        for i in 0..total {
+            let Some(name) = archive.name_for_index(i).map(ToString::to_string) else {
+                continue;
+            };
+            let target = ProfileLocation::of_entry(&name)
+                .filter(|(_, file_name)| belongs_to_addon(file_name, &manifest.addon));
+            let Some((location, file_name)) = target else {
+                if !is_directory_entry(&name) && !is_archive_metadata(&name) {
+                    log::warn!(
+                        "Skipped `{name}`, as it isn't a file of `{}`",
+                        manifest.addon
+                    );
+                    skipped += 1;
+                }
+                progress.advance(i.saturating_add(1), || None)?;
+                continue;
+            };
+
+            let dest_dir = location.saved_variables_path(&character);
+            fs.ensure_directory(&dest_dir)?;
+            if !mock_mode {
+                let mut entry = archive.by_index(i)?;
+                let mut outfile = fs.create(&dest_dir.join(&file_name))?;
+                std::io::copy(&mut entry, &mut outfile)?;
+            }
+            match location {
+                ProfileLocation::Character => character_files += 1,
+                ProfileLocation::Account => account_files += 1,
+            }
+            log::info!(
+                "{}Imported `{name}` from the addon profile",
+                mock_prefix(mock_mode)
+            );
+            progress.advance(i.saturating_add(1), || Some(file_name))?;
+        }
+        progress.flush()?;
+
+        let skipped_summary = if skipped > 0 {
+            format!(", skipped {skipped} files of other addons")
+        } else {
+            String::new()
+        };
+        tx.send(IOProgress::Summary(format!(
+            "{}Imported {} profile to {}: {character_files} character files, \
+            {account_files} account files{skipped_summary}",
+            mock_prefix(mock_mode),
+            manifest.addon,
+            character_summary_name(&settings, &character.character),
+        )))?;
+        Ok(())
+    })
+    .name(name)
+    .kind(IOTaskKind::Destructive)
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use crate::backend::{
+    archive_manifest::{BackupManifest, is_archive_metadata},
+    zip_rw::{ChronoZipReader, is_directory_entry},
+};
+use crate::files::AnyResult;
+
+/// Shared, thread-safe archive cache.
+pub type SharedArchiveCache = Arc<Mutex<ArchiveCache>>;
+
+/// Details of a backup archive, read from its contents.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchiveDetails {
+    /// Names of the files within the archive, leaving out directories and archive metadata.
+    pub files: Vec<String>,
+    /// The archive's manifest, if it has one.
+    pub manifest: Option<BackupManifest>,
+    /// Size of the archive in bytes.
+    pub size: u64,
+}
+
+impl ArchiveDetails {
+    /// Read the details of the archive at the given path.
+    /// # Errors
+    /// Returns an error if the archive cannot be opened or is not a valid ZIP archive.
+    pub fn read(path: &Path, size: u64) -> AnyResult<Self> {
+        let mut archive = ChronoZipReader::new(path)?;
+        let files = archive
+            .file_names()
+            .filter(|name| !is_directory_entry(name) && !is_archive_metadata(name))
+            .map(str::to_string)
+            .collect();
+        let manifest = BackupManifest::read_from(&mut archive);
+        Ok(Self {
+            files,
+            manifest,
+            size,
+        })
+    }
+
+    /// The note written when the backup was made, if any.
+    #[inline]
+    #[must_use]
+    pub fn note(&self) -> Option<&str> {
+        self.manifest.as_ref()?.note.as_deref()
+    }
+}
+
+/// A cached archive's details, along with the file state they were read from.
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    /// When the archive was last modified as of reading it.
+    modified: SystemTime,
+    /// Size of the archive as of reading it.
+    size: u64,
+    /// The details read from the archive.
+    details: Arc<ArchiveDetails>,
+}
+
+/// In-memory cache of the details of backup archives, keyed by path, so archives are only read
+/// again once they change rather than each time the backup manager is opened.
+#[derive(Debug, Default)]
+pub struct ArchiveCache {
+    /// Cached details by archive path.
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+impl ArchiveCache {
+    /// Create a new, empty, shared archive cache.
+    #[must_use]
+    pub fn new_shared() -> SharedArchiveCache {
+        Arc::new(Mutex::new(Self::default()))
+    }
+
+    /// Get the details of the archive at the given path, reading the archive only if it isn't
+    /// cached yet or was modified or resized since it was cached.
+    /// Returns `None`, dropping any cached entry, if the archive can't be read.
+    pub fn details(&mut self, path: &Path) -> Option<Arc<ArchiveDetails>> {
+        let Ok(metadata) = std::fs::metadata(path) else {
+            self.entries.remove(path);
+            return None;
+        };
+        let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        let size = metadata.len();
+        if let Some(entry) = self.entries.get(path)
+            && entry.modified == modified
+            && entry.size == size
+        {
+            return Some(Arc::clone(&entry.details));
+        }
+
+        match ArchiveDetails::read(path, size) {
+            Ok(details) => {
+                let details = Arc::new(details);
+                self.entries.insert(
+                    path.to_path_buf(),
+                    CacheEntry {
+                        modified,
+                        size,
+                        details: Arc::clone(&details),
+                    },
+                );
+                Some(details)
+            }
+            Err(e) => {
+                log::warn!("Failed to read backup archive `{}`: {e}", path.display());
+                self.entries.remove(path);
+                None
+            }
+        }
+    }
+
+    /// Get the details of each of the given archives which can be read, keyed by path.
+    pub fn details_for<'a>(
+        &mut self,
+        paths: impl IntoIterator<Item = &'a Path>,
+    ) -> HashMap<PathBuf, Arc<ArchiveDetails>> {
+        paths
+            .into_iter()
+            .filter_map(|path| Some((path.to_path_buf(), self.details(path)?)))
+            .collect()
+    }
+}
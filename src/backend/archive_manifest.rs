@@ -0,0 +1,176 @@
+use std::io::Read;
+
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    backend::zip_rw::{ChronoZipReader, ChronoZipWriter},
+    files::AnyResult,
+    wow::WoWCharacter,
+};
+
+/// Directory within a character backup holding `ChronoBind`'s own metadata,
+/// which is never extracted into the character's directory.
+pub const ARCHIVE_METADATA_DIR: &str = ".chronobind/";
+/// Path within a character backup of its manifest.
+const BACKUP_MANIFEST_PATH: &str = ".chronobind/manifest.ron";
+/// Path within a backup export of its manifest.
+const EXPORT_MANIFEST_PATH: &str = ".chronobind/export.ron";
+/// Path within an addon profile export of its manifest.
+const ADDON_PROFILE_MANIFEST_PATH: &str = ".chronobind/addon_profile.ron";
+
+/// Returns `true` if the archive entry is `ChronoBind` metadata rather than a character file.
+#[inline]
+#[must_use]
+pub fn is_archive_metadata(entry_name: &str) -> bool {
+    entry_name.starts_with(ARCHIVE_METADATA_DIR)
+}
+
+/// Describes where a character backup was made, stored within the backup so archives
+/// moved or shared outside their backups directory can still be identified.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BackupManifest {
+    /// The branch of the installation the character was backed up from, I.e. `retail`.
+    pub branch: String,
+    /// The name of the character.
+    pub name: String,
+    /// The realm of the character.
+    pub realm: String,
+    /// Names of the entries whose contents are encrypted with the backup passphrase.
+    #[serde(default)]
+    pub encrypted_entries: Vec<String>,
+    /// Note entered when the backup was made, I.e. `pre-patch UI`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl BackupManifest {
+    /// Create the manifest for a backup of the given character.
+    #[must_use]
+    pub fn for_character(character: &WoWCharacter) -> Self {
+        Self {
+            branch: character.branch.clone(),
+            name: character.name.clone(),
+            realm: character.realm.clone(),
+            encrypted_entries: Vec::new(),
+            note: None,
+        }
+    }
+
+    /// Set the names of the entries whose contents are encrypted with the backup passphrase.
+    #[must_use]
+    pub fn with_encrypted_entries(mut self, encrypted_entries: Vec<String>) -> Self {
+        self.encrypted_entries = encrypted_entries;
+        self
+    }
+
+    /// Set the note entered when the backup was made.
+    #[must_use]
+    pub fn with_note(mut self, note: Option<String>) -> Self {
+        self.note = note;
+        self
+    }
+
+    /// Write the manifest into the backup archive.
+    /// # Errors
+    /// Returns an error if serialising the manifest or writing to the archive fails.
+    pub fn write_to(&self, zip: &mut ChronoZipWriter<'_>) -> AnyResult<()> {
+        let manifest = ron::ser::to_string_pretty(self, PrettyConfig::default())?;
+        zip.write_file(BACKUP_MANIFEST_PATH, manifest.as_bytes())
+    }
+
+    /// Read the manifest of a backup archive, `None` if the archive doesn't have one,
+    /// such as backups made before manifests were added or archives not made by `ChronoBind`.
+    #[must_use]
+    pub fn read_from(archive: &mut ChronoZipReader<'_>) -> Option<Self> {
+        let mut entry = archive.by_name(BACKUP_MANIFEST_PATH).ok()?;
+        let mut contents = String::new();
+        entry.read_to_string(&mut contents).ok()?;
+        ron::from_str(&contents)
+            .inspect_err(|e| log::warn!("Ignoring unreadable backup manifest: {e}"))
+            .ok()
+    }
+}
+
+/// Describes how a backup export was made, stored within the export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct ExportManifest {
+    /// Unix time, in seconds, of the previous export the exported files were changed since,
+    /// `None` for full exports.
+    #[serde(default)]
+    pub modified_since: Option<u64>,
+}
+
+impl ExportManifest {
+    /// Create the manifest for an export of the files changed since the given unix time.
+    #[must_use]
+    pub const fn changed_since(modified_since: u64) -> Self {
+        Self {
+            modified_since: Some(modified_since),
+        }
+    }
+
+    /// Write the manifest into the export archive.
+    /// # Errors
+    /// Returns an error if serialising the manifest or writing to the archive fails.
+    pub fn write_to(&self, zip: &mut ChronoZipWriter<'_>) -> AnyResult<()> {
+        let manifest = ron::ser::to_string_pretty(self, PrettyConfig::default())?;
+        zip.write_file(EXPORT_MANIFEST_PATH, manifest.as_bytes())
+    }
+
+    /// Read the manifest of an export archive, `None` if the archive doesn't have one,
+    /// such as full exports or exports made before manifests were added.
+    #[must_use]
+    pub fn read_from(archive: &mut ChronoZipReader<'_>) -> Option<Self> {
+        let mut entry = archive.by_name(EXPORT_MANIFEST_PATH).ok()?;
+        let mut contents = String::new();
+        entry.read_to_string(&mut contents).ok()?;
+        ron::from_str(&contents)
+            .inspect_err(|e| log::warn!("Ignoring unreadable export manifest: {e}"))
+            .ok()
+    }
+}
+
+/// Describes which addon an addon profile export holds the `SavedVariables` of,
+/// and where it was exported from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AddonProfileManifest {
+    /// The name of the addon, I.e. `WeakAuras`.
+    pub addon: String,
+    /// The branch of the installation the profile was exported from, I.e. `retail`.
+    pub branch: String,
+    /// The name of the character the profile was exported from.
+    pub character: String,
+}
+
+impl AddonProfileManifest {
+    /// Create the manifest for an export of the given addon's files from the given character.
+    #[must_use]
+    pub fn for_character(addon: &str, character: &WoWCharacter) -> Self {
+        Self {
+            addon: addon.to_string(),
+            branch: character.branch.clone(),
+            character: character.name.clone(),
+        }
+    }
+
+    /// Write the manifest into the addon profile archive.
+    /// # Errors
+    /// Returns an error if serialising the manifest or writing to the archive fails.
+    pub fn write_to(&self, zip: &mut ChronoZipWriter<'_>) -> AnyResult<()> {
+        let manifest = ron::ser::to_string_pretty(self, PrettyConfig::default())?;
+        zip.write_file(ADDON_PROFILE_MANIFEST_PATH, manifest.as_bytes())
+    }
+
+    /// Read the manifest of an addon profile archive, `None` if the archive doesn't have one,
+    /// such as archives which aren't addon profile exports.
+    #[must_use]
+    pub fn read_from(archive: &mut ChronoZipReader<'_>) -> Option<Self> {
+        let mut entry = archive.by_name(ADDON_PROFILE_MANIFEST_PATH).ok()?;
+        let mut contents = String::new();
+        entry.read_to_string(&mut contents).ok()?;
+        ron::from_str(&contents)
+            .inspect_err(|e| log::warn!("Ignoring unreadable addon profile manifest: {e}"))
+            .ok()
+    }
+}
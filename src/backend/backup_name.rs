@@ -0,0 +1,372 @@
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use itertools::Itertools;
+
+use crate::backend::{BACKUP_FILE_TIME_FORMAT, format_timestamp_for_filename};
+
+/// Default template for backup file names, I.e. `{name}_{timestamp}`.
+pub const DEFAULT_BACKUP_NAME_TEMPLATE: &str = "{name}_{timestamp}";
+
+/// Characters which may not appear in the literal text of a backup name template.
+const INVALID_TEMPLATE_CHARS: [char; 9] = ['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Separator between the templated name and the suffixes of a backup file name.
+/// Substituted fields never contain it, so the end of the templated name can always be found.
+const SUFFIX_SEPARATOR: char = '_';
+
+/// Placeholders which can be used in a backup name template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BackupNamePlaceholder {
+    /// The character's name.
+    Name,
+    /// The character's realm.
+    Realm,
+    /// The branch of the `WoW` installation, I.e. `retail`.
+    Branch,
+    /// The time the backup was created.
+    Timestamp,
+}
+
+impl BackupNamePlaceholder {
+    /// All placeholders, in the order they are listed to the user.
+    pub const ALL: [Self; 4] = [Self::Name, Self::Realm, Self::Branch, Self::Timestamp];
+
+    /// Get the key of the placeholder, as written between braces in a template.
+    #[inline]
+    #[must_use]
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Realm => "realm",
+            Self::Branch => "branch",
+            Self::Timestamp => "timestamp",
+        }
+    }
+
+    /// Get the placeholder for the given key, if it is valid.
+    #[inline]
+    #[must_use]
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.key() == key)
+    }
+}
+
+/// A part of a backup name template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplatePart {
+    /// Literal text, copied as is.
+    Literal(String),
+    /// A placeholder, substituted when formatting.
+    Placeholder(BackupNamePlaceholder),
+}
+
+/// The values substituted into a backup name template.
+#[derive(Debug, Clone, Copy)]
+pub struct BackupNameFields<'a> {
+    pub name: &'a str,
+    pub realm: &'a str,
+    pub branch: &'a str,
+    pub timestamp: DateTime<Local>,
+}
+
+/// The result of matching a backup file stem against a template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupNameMatch {
+    /// The character name.
+    pub name: String,
+    /// The time the backup was created.
+    pub timestamp: DateTime<Local>,
+    /// Length of the templated part of the stem, after which only suffixes follow.
+    pub len: usize,
+}
+
+/// A validated template for backup file names, such as `{name}-{realm}-{timestamp}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupNameTemplate {
+    parts: Vec<TemplatePart>,
+}
+
+impl Default for BackupNameTemplate {
+    fn default() -> Self {
+        Self::parse(DEFAULT_BACKUP_NAME_TEMPLATE).expect("Default backup name template is valid")
+    }
+}
+
+impl BackupNameTemplate {
+    /// Parse and validate a backup name template.
+    /// # Errors
+    /// Returns an error if the template uses an unknown or repeated placeholder, is missing
+    /// the `{name}` or `{timestamp}` placeholders, has two placeholders without text between them,
+    /// or contains characters which are not allowed in file names.
+    pub fn parse(template: &str) -> Result<Self, String> {
+        let mut parts = Vec::new();
+        let mut rest = template;
+
+        while !rest.is_empty() {
+            if let Some(after_brace) = rest.strip_prefix('{') {
+                let (key, after) = after_brace
+                    .split_once('}')
+                    .ok_or_else(|| format!("Unclosed `{{` in backup name template `{template}`"))?;
+                let placeholder = BackupNamePlaceholder::from_key(key).ok_or_else(|| {
+                    format!(
+                        "Unknown placeholder `{{{key}}}` in backup name template, expected one of: {}",
+                        BackupNamePlaceholder::ALL
+                            .iter()
+                            .map(|p| format!("{{{}}}", p.key()))
+                            .join(", ")
+                    )
+                })?;
+                if parts.contains(&TemplatePart::Placeholder(placeholder)) {
+                    return Err(format!(
+                        "Placeholder `{{{key}}}` is used more than once in backup name template"
+                    ));
+                }
+                if matches!(parts.last(), Some(TemplatePart::Placeholder(_))) {
+                    return Err(format!(
+                        "Placeholder `{{{key}}}` must be separated from the previous placeholder \
+                        in backup name template `{template}`"
+                    ));
+                }
+                parts.push(TemplatePart::Placeholder(placeholder));
+                rest = after;
+            } else {
+                let end = rest.find('{').unwrap_or(rest.len());
+                let literal = &rest[..end];
+                if literal.contains('}') {
+                    return Err(format!(
+                        "Unmatched `}}` in backup name template `{template}`"
+                    ));
+                }
+                if let Some(c) = literal.chars().find(|c| INVALID_TEMPLATE_CHARS.contains(c)) {
+                    return Err(format!(
+                        "Character `{c}` is not allowed in backup name template `{template}`"
+                    ));
+                }
+                parts.push(TemplatePart::Literal(literal.to_string()));
+                rest = &rest[end..];
+            }
+        }
+
+        for required in [
+            BackupNamePlaceholder::Name,
+            BackupNamePlaceholder::Timestamp,
+        ] {
+            if !parts.contains(&TemplatePart::Placeholder(required)) {
+                return Err(format!(
+                    "Backup name template `{template}` must contain `{{{}}}`",
+                    required.key()
+                ));
+            }
+        }
+
+        Ok(Self { parts })
+    }
+
+    /// Format a backup file stem from the template, without any suffixes.
+    #[must_use]
+    pub fn format(&self, fields: &BackupNameFields<'_>) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                TemplatePart::Literal(text) => text.clone(),
+                TemplatePart::Placeholder(BackupNamePlaceholder::Name) => sanitize(fields.name),
+                TemplatePart::Placeholder(BackupNamePlaceholder::Realm) => sanitize(fields.realm),
+                TemplatePart::Placeholder(BackupNamePlaceholder::Branch) => {
+                    sanitize(fields.branch.trim_matches(SUFFIX_SEPARATOR))
+                }
+                TemplatePart::Placeholder(BackupNamePlaceholder::Timestamp) => {
+                    format_timestamp_for_filename(fields.timestamp)
+                }
+            })
+            .collect()
+    }
+
+    /// Match the start of a backup file stem against the template.
+    /// The templated part must be followed by the end of the stem, or by suffixes.
+    #[must_use]
+    pub fn match_stem(&self, stem: &str) -> Option<BackupNameMatch> {
+        let mut captured = Captured::default();
+        let len = match_parts(&self.parts, stem, 0, &mut captured)?;
+        Some(BackupNameMatch {
+            name: captured.name?,
+            timestamp: captured.timestamp?,
+            len,
+        })
+    }
+}
+
+/// Fields captured while matching a stem against a template.
+#[derive(Debug, Default)]
+struct Captured {
+    name: Option<String>,
+    timestamp: Option<DateTime<Local>>,
+}
+
+/// Match the template parts against the stem from `pos`, backtracking over placeholder lengths.
+/// Returns the length of the matched part of the stem.
+fn match_parts(
+    parts: &[TemplatePart],
+    stem: &str,
+    pos: usize,
+    captured: &mut Captured,
+) -> Option<usize> {
+    let Some((part, remaining_parts)) = parts.split_first() else {
+        let rest = &stem[pos..];
+        return (rest.is_empty() || rest.starts_with(SUFFIX_SEPARATOR)).then_some(pos);
+    };
+
+    match part {
+        TemplatePart::Literal(text) => stem[pos..]
+            .starts_with(text.as_str())
+            .then(|| match_parts(remaining_parts, stem, pos + text.len(), captured))
+            .flatten(),
+        TemplatePart::Placeholder(placeholder) => {
+            let ends = stem[pos..]
+                .char_indices()
+                .skip(1)
+                .map(|(i, _)| pos + i)
+                .chain(std::iter::once(stem.len()));
+            for end in ends {
+                let value = &stem[pos..end];
+                match placeholder {
+                    BackupNamePlaceholder::Timestamp => {
+                        let Some(timestamp) =
+                            NaiveDateTime::parse_from_str(value, BACKUP_FILE_TIME_FORMAT)
+                                .ok()
+                                .and_then(|date| Local.from_local_datetime(&date).earliest())
+                        else {
+                            continue;
+                        };
+                        captured.timestamp = Some(timestamp);
+                    }
+                    _ if value.contains(SUFFIX_SEPARATOR) => break,
+                    BackupNamePlaceholder::Name => captured.name = Some(value.to_string()),
+                    BackupNamePlaceholder::Realm | BackupNamePlaceholder::Branch => {}
+                }
+                if let Some(len) = match_parts(remaining_parts, stem, end, captured) {
+                    return Some(len);
+                }
+            }
+            None
+        }
+    }
+}
+
+/// Make a field safe to substitute into a template, so it can be told apart from the suffixes.
+fn sanitize(field: &str) -> String {
+    field.replace(SUFFIX_SEPARATOR, "-")
+}
+
+/// Match a backup file stem against the configured template, falling back to the default
+/// template so backups named before the template was changed are still found.
+#[must_use]
+pub fn match_backup_stem(template: &BackupNameTemplate, stem: &str) -> Option<BackupNameMatch> {
+    template.match_stem(stem).or_else(|| {
+        let default = BackupNameTemplate::default();
+        (*template != default)
+            .then(|| default.match_stem(stem))
+            .flatten()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timestamp() -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2025, 3, 14, 15, 9, 26)
+            .earliest()
+            .expect("Timestamp is valid")
+    }
+
+    fn fields<'a>(name: &'a str, realm: &'a str, branch: &'a str) -> BackupNameFields<'a> {
+        BackupNameFields {
+            name,
+            realm,
+            branch,
+            timestamp: timestamp(),
+        }
+    }
+
+    fn parse_error(template: &str) -> String {
+        BackupNameTemplate::parse(template).expect_err("Template should be rejected")
+    }
+
+    #[test]
+    fn parse_rejects_unclosed_brace() {
+        assert!(parse_error("{name}_{timestamp").contains("Unclosed `{`"));
+    }
+
+    #[test]
+    fn parse_rejects_unmatched_closing_brace() {
+        assert!(parse_error("{name}}_{timestamp}").contains("Unmatched `}`"));
+    }
+
+    #[test]
+    fn parse_rejects_unknown_placeholder() {
+        let error = parse_error("{name}_{server}_{timestamp}");
+        assert!(error.contains("Unknown placeholder `{server}`"));
+        assert!(error.contains("{name}, {realm}, {branch}, {timestamp}"));
+    }
+
+    #[test]
+    fn parse_rejects_repeated_placeholder() {
+        assert!(parse_error("{name}_{name}_{timestamp}").contains("more than once"));
+    }
+
+    #[test]
+    fn parse_rejects_adjacent_placeholders() {
+        assert!(parse_error("{name}{realm}_{timestamp}").contains("must be separated"));
+    }
+
+    #[test]
+    fn parse_rejects_missing_required_placeholders() {
+        assert!(parse_error("{realm}_{timestamp}").contains("must contain `{name}`"));
+        assert!(parse_error("{name}_{realm}").contains("must contain `{timestamp}`"));
+    }
+
+    #[test]
+    fn parse_rejects_invalid_file_name_chars() {
+        assert!(parse_error("{name}:{timestamp}").contains("Character `:` is not allowed"));
+    }
+
+    #[test]
+    fn match_stem_reads_back_formatted_names() {
+        for template in [
+            DEFAULT_BACKUP_NAME_TEMPLATE,
+            "{name}-{realm}-{timestamp}",
+            "{timestamp} {branch} {name}",
+            "backup of {name} on {realm}.{timestamp}",
+        ] {
+            let template = BackupNameTemplate::parse(template).expect("Template is valid");
+            let stem = template.format(&fields("Thrall", "Argent_Dawn", "_retail_"));
+            let matched = template
+                .match_stem(&stem)
+                .unwrap_or_else(|| panic!("`{stem}` should match its template"));
+            assert_eq!(matched.name, "Thrall");
+            assert_eq!(matched.timestamp, timestamp());
+            assert_eq!(matched.len, stem.len());
+        }
+    }
+
+    #[test]
+    fn match_stem_stops_before_suffixes() {
+        let template = BackupNameTemplate::parse("{name}-{realm}-{timestamp}").unwrap();
+        let stem = template.format(&fields("Jaina", "Silvermoon", "_retail_"));
+        let matched = template
+            .match_stem(&format!("{stem}_paste_pinned"))
+            .expect("Suffixed stem should match");
+        assert_eq!(matched.name, "Jaina");
+        assert_eq!(matched.len, stem.len());
+    }
+
+    #[test]
+    fn match_backup_stem_falls_back_to_default_template() {
+        let default_stem = BackupNameTemplate::default().format(&fields("Anduin", "", ""));
+        let template = BackupNameTemplate::parse("{realm}-{name}-{timestamp}").unwrap();
+        assert!(template.match_stem(&default_stem).is_none());
+        let matched =
+            match_backup_stem(&template, &default_stem).expect("Default name should match");
+        assert_eq!(matched.name, "Anduin");
+    }
+}
@@ -0,0 +1,352 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{Receiver as MPSCReceiver, Sender as MPSCSender, TryRecvError};
+
+use crate::backend::task::{BackendTask, BackendTaskPtr};
+use crate::config::RuntimeSettings;
+use crate::ui::messages::AppMessage;
+use crate::wow::{WoWCharacter, WoWInstall};
+
+/// Progress updates sent from a background character scan.
+#[derive(Debug)]
+enum ScanProgress {
+    /// A character was found, with its files and backups loaded unless mapping was skipped.
+    Found(Box<FoundCharacter>),
+    /// Every character of an installation was found.
+    InstallLoaded,
+    /// The characters of the installation at the given path could not be read.
+    InstallFailed(String),
+    /// The installation at the given path has no `WTF/Account` directory,
+    /// I.e. the game has never been logged into on it.
+    NoAccountDir(String),
+}
+
+/// A character found by a background scan.
+#[derive(Debug)]
+pub struct FoundCharacter {
+    /// The character found.
+    pub character: WoWCharacter,
+    /// Whether the character's files and backups were loaded, `false` if the scan was cancelled
+    /// before reaching it, leaving only its name.
+    pub files_mapped: bool,
+}
+
+/// Progress of a background character scan, shared between the scanning thread and the app.
+#[derive(Debug, Default)]
+struct ScanStatus {
+    /// Number of character directories found so far.
+    discovered: AtomicUsize,
+    /// Number of characters whose files and backups have been loaded.
+    mapped: AtomicUsize,
+    /// Whether loading the files of the remaining characters should be skipped.
+    skip_mapping: AtomicBool,
+    /// Whether the scanning thread has finished.
+    finished: AtomicBool,
+}
+
+/// A scan of the characters on a branch, running in the background so the UI stays responsive
+/// on installs with many characters. Characters are streamed to the app as they're found.
+#[derive(Debug)]
+pub struct CharacterScan {
+    /// The branch being scanned.
+    branch: String,
+    /// Receiver for the scan's progress updates.
+    rx: MPSCReceiver<ScanProgress>,
+    /// Progress shared with the scanning thread.
+    status: Arc<ScanStatus>,
+    /// Number of installations being scanned.
+    install_count: usize,
+    /// Number of installations whose characters were all found.
+    loaded_installs: usize,
+    /// Number of installations without a `WTF/Account` directory.
+    installs_without_accounts: usize,
+    /// Number of characters found so far.
+    found: usize,
+    /// Whether the scan has finished.
+    finished: bool,
+}
+
+impl CharacterScan {
+    /// Start scanning the given installations of a branch for characters.
+    /// `aliases` maps previous character names to their current names, so that backups made
+    /// under an old name are associated with the renamed character. Character files are listed
+    /// with the given settings.
+    #[must_use]
+    pub fn start(
+        branch: &str,
+        installs: Vec<WoWInstall>,
+        aliases: BTreeMap<String, String>,
+        settings: RuntimeSettings,
+    ) -> Self {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let install_count = installs.len();
+        let status = Arc::new(ScanStatus::default());
+        let thread_status = Arc::clone(&status);
+        std::thread::spawn(move || {
+            scan_installs(&tx, &installs, &aliases, &settings, &thread_status);
+            thread_status.finished.store(true, Ordering::Relaxed);
+        });
+        Self {
+            branch: branch.to_string(),
+            rx,
+            status,
+            install_count,
+            loaded_installs: 0,
+            installs_without_accounts: 0,
+            found: 0,
+            finished: false,
+        }
+    }
+
+    /// Take the characters found since the last poll, marking the scan finished once the
+    /// scanning thread is done.
+    pub fn poll(&mut self) -> Vec<FoundCharacter> {
+        let mut characters = Vec::new();
+        loop {
+            match self.rx.try_recv() {
+                Ok(ScanProgress::Found(character)) => characters.push(*character),
+                Ok(ScanProgress::InstallLoaded) => self.loaded_installs += 1,
+                Ok(ScanProgress::InstallFailed(install_path)) => {
+                    log::error!("Failed to find characters in installation at {install_path}");
+                }
+                Ok(ScanProgress::NoAccountDir(install_path)) => {
+                    self.installs_without_accounts += 1;
+                    log::warn!(
+                        "No `WTF/Account` directory in installation at {install_path}, \
+                        have you logged into the game on it at least once?"
+                    );
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.finished = true;
+                    break;
+                }
+            }
+        }
+        self.found += characters.len();
+        characters
+    }
+
+    /// Create a task tracking the progress of the scan, for showing it in a progress popup.
+    /// Cancelling the task skips loading the files of the characters not yet reached, so the
+    /// scan finishes quickly with the names of every character.
+    #[must_use]
+    pub fn progress_task(&self) -> BackendTaskPtr {
+        Box::new(CharacterScanTask {
+            status: Arc::clone(&self.status),
+            cancelled: false,
+            next: None,
+            after_messages: Vec::new(),
+            finished_messages: Vec::new(),
+        })
+    }
+
+    /// The branch being scanned.
+    #[must_use]
+    pub fn branch(&self) -> &str {
+        &self.branch
+    }
+
+    /// Number of installations being scanned.
+    #[must_use]
+    pub const fn install_count(&self) -> usize {
+        self.install_count
+    }
+
+    /// Number of installations whose characters were all found.
+    #[must_use]
+    pub const fn loaded_installs(&self) -> usize {
+        self.loaded_installs
+    }
+
+    /// Number of installations without a `WTF/Account` directory.
+    #[must_use]
+    pub const fn installs_without_accounts(&self) -> usize {
+        self.installs_without_accounts
+    }
+
+    /// Number of characters found so far.
+    #[must_use]
+    pub const fn found(&self) -> usize {
+        self.found
+    }
+
+    /// Returns `true` once every installation has been scanned.
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+/// Find the characters of every installation, sending each as soon as its files are loaded.
+/// Once mapping is skipped, the remaining characters are sent with their names only.
+/// Stops early if the scan was dropped, I.e. when switching branch mid-scan.
+fn scan_installs(
+    tx: &MPSCSender<ScanProgress>,
+    installs: &[WoWInstall],
+    aliases: &BTreeMap<String, String>,
+    settings: &RuntimeSettings,
+    status: &ScanStatus,
+) {
+    for install in installs {
+        install.log_resolved_paths();
+        if !install.has_account_dir() {
+            if tx
+                .send(ScanProgress::NoAccountDir(install.install_path.clone()))
+                .is_err()
+            {
+                return;
+            }
+            continue;
+        }
+        let Some(characters) = install.find_all_characters() else {
+            if tx
+                .send(ScanProgress::InstallFailed(install.install_path.clone()))
+                .is_err()
+            {
+                return;
+            }
+            continue;
+        };
+        status
+            .discovered
+            .fetch_add(characters.len(), Ordering::Relaxed);
+        for mut character in characters {
+            character.set_previous_names_from_aliases(aliases);
+            let files_mapped = !status.skip_mapping.load(Ordering::Relaxed);
+            if files_mapped {
+                character.refresh_character_info(install, settings);
+                status.mapped.fetch_add(1, Ordering::Relaxed);
+            }
+            let found = FoundCharacter {
+                character,
+                files_mapped,
+            };
+            if tx.send(ScanProgress::Found(Box::new(found))).is_err() {
+                return;
+            }
+        }
+        if tx.send(ScanProgress::InstallLoaded).is_err() {
+            return;
+        }
+    }
+}
+
+/// Progress task of a background character scan, shown in the progress popup.
+/// The scan itself is owned by the app, this only reports its progress and skips mapping the
+/// remaining characters' files when cancelled.
+#[derive(Debug)]
+struct CharacterScanTask {
+    /// Progress shared with the scanning thread.
+    status: Arc<ScanStatus>,
+    /// Whether the task was cancelled.
+    cancelled: bool,
+    /// Optional next task to be executed after this one.
+    next: Option<BackendTaskPtr>,
+    /// Messages to be sent after the task completes.
+    after_messages: Vec<AppMessage>,
+    /// Messages to be sent once the task's chain ends, however it ends.
+    finished_messages: Vec<AppMessage>,
+}
+
+impl BackendTask for CharacterScanTask {
+    fn task_name(&self) -> String {
+        "Scanning for characters".to_string()
+    }
+    fn task_label(&self) -> Option<String> {
+        let discovered = self.status.discovered.load(Ordering::Relaxed);
+        Some(format!(
+            "Found {discovered} character{}",
+            if discovered == 1 { "" } else { "s" }
+        ))
+    }
+
+    fn run(&mut self) -> bool {
+        // The scan is already running on its own thread.
+        true
+    }
+
+    fn cancel(&mut self) {
+        if self.finished() {
+            return;
+        }
+        self.status.skip_mapping.store(true, Ordering::Relaxed);
+        self.next = None;
+        self.cancelled = true;
+    }
+    fn cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    fn poll(&mut self) {}
+
+    fn started(&self) -> bool {
+        true
+    }
+    fn finished(&self) -> bool {
+        self.cancelled || self.status.finished.load(Ordering::Relaxed)
+    }
+    fn error(&self) -> Option<String> {
+        None
+    }
+    fn summary(&self) -> Option<String> {
+        self.cancelled.then(|| {
+            "Skipped loading the files of the remaining characters, \
+            they are loaded once selected"
+                .to_string()
+        })
+    }
+
+    fn completed_count(&self) -> Option<usize> {
+        Some(self.status.mapped.load(Ordering::Relaxed))
+    }
+    fn total_count(&self) -> Option<usize> {
+        Some(self.status.discovered.load(Ordering::Relaxed))
+    }
+    #[allow(clippy::cast_precision_loss)]
+    fn progress(&self) -> Option<f32> {
+        let total = self.status.discovered.load(Ordering::Relaxed);
+        if total == 0 {
+            None
+        } else {
+            Some(self.status.mapped.load(Ordering::Relaxed) as f32 / total as f32)
+        }
+    }
+
+    fn next_task(&mut self) -> Option<BackendTaskPtr> {
+        std::mem::take(&mut self.next)
+    }
+    fn add_next(&mut self, task: BackendTaskPtr) {
+        if let Some(next) = self.next.as_mut() {
+            next.as_mut().add_next(task);
+        } else {
+            self.next = Some(task);
+        }
+    }
+
+    fn add_on_all_complete(&mut self, msg: AppMessage) {
+        if let Some(next) = self.next.as_mut() {
+            next.as_mut().add_on_all_complete(msg);
+        } else {
+            self.add_after_message(msg);
+        }
+    }
+    fn add_after_message(&mut self, msg: AppMessage) {
+        self.after_messages.push(msg);
+    }
+    fn after_messages(&mut self) -> Option<Vec<AppMessage>> {
+        if self.after_messages.is_empty() {
+            None
+        } else {
+            Some(std::mem::take(&mut self.after_messages))
+        }
+    }
+
+    fn add_on_finished(&mut self, msg: AppMessage) {
+        self.finished_messages.push(msg);
+    }
+    fn take_finished_messages(&mut self) -> Vec<AppMessage> {
+        std::mem::take(&mut self.finished_messages)
+    }
+}
@@ -0,0 +1,89 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver as MPSCReceiver, TryRecvError};
+use std::time::{Duration, Instant};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::wow::BACKUPS_DIR_NAME;
+
+/// How long a watched directory must go without changes before they are reported,
+/// so a burst of writes, such as the game saving on logout, causes a single refresh.
+pub const WATCH_DEBOUNCE: Duration = Duration::from_millis(750);
+
+/// Watches a character's directory for changes made outside of `ChronoBind`, I.e. by the game.
+#[derive(Debug)]
+pub struct CharacterWatcher {
+    /// The directory being watched.
+    path: PathBuf,
+    /// The underlying filesystem watcher, which stops watching once dropped.
+    _watcher: RecommendedWatcher,
+    /// Receiver for the watcher's events.
+    rx: MPSCReceiver<notify::Result<notify::Event>>,
+    /// When the most recent unreported change was seen.
+    last_change: Option<Instant>,
+}
+
+impl CharacterWatcher {
+    /// Start watching the given character directory, including its subdirectories.
+    /// # Errors
+    /// Returns an error if the directory cannot be watched.
+    pub fn watch(path: &Path) -> notify::Result<Self> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            tx.send(event).ok();
+        })?;
+        watcher.watch(path, RecursiveMode::Recursive)?;
+        log::debug!("Watching `{}` for changes", path.display());
+        Ok(Self {
+            path: path.to_path_buf(),
+            _watcher: watcher,
+            rx,
+            last_change: None,
+        })
+    }
+
+    /// The directory being watched.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Collect the events seen since the last poll, returning `true` once changes have settled
+    /// for [`WATCH_DEBOUNCE`], I.e. when the character should be refreshed.
+    pub fn poll(&mut self) -> bool {
+        loop {
+            match self.rx.try_recv() {
+                Ok(Ok(event)) => {
+                    if self.is_relevant(&event) {
+                        self.last_change = Some(Instant::now());
+                    }
+                }
+                Ok(Err(e)) => {
+                    log::warn!("Error watching `{}`: {e}", self.path.display());
+                }
+                Err(TryRecvError::Empty | TryRecvError::Disconnected) => break,
+            }
+        }
+
+        if self
+            .last_change
+            .is_some_and(|changed_at| changed_at.elapsed() >= WATCH_DEBOUNCE)
+        {
+            self.last_change = None;
+            return true;
+        }
+        false
+    }
+
+    /// Returns `true` if the event changes the character's files, ignoring reads and backups
+    /// kept within the character's directory.
+    fn is_relevant(&self, event: &notify::Event) -> bool {
+        if matches!(event.kind, EventKind::Access(_)) {
+            return false;
+        }
+        event.paths.iter().any(|path| {
+            path.strip_prefix(&self.path)
+                .map_or(true, |relative| !relative.starts_with(BACKUPS_DIR_NAME))
+        })
+    }
+}
@@ -0,0 +1,52 @@
+use std::fmt;
+use std::path::Path;
+
+/// Environment variable the passphrase may be given with, instead of being prompted for.
+pub const PASSPHRASE_ENV_VAR: &str = "CHRONOBIND_PASSPHRASE";
+
+/// Passphrase the encrypted files of backups are encrypted and decrypted with.
+/// Never printed, its debug output is redacted.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Passphrase(String);
+
+impl Passphrase {
+    /// Create a passphrase, `None` if it is empty.
+    #[must_use]
+    pub fn new(passphrase: String) -> Option<Self> {
+        (!passphrase.is_empty()).then_some(Self(passphrase))
+    }
+
+    /// Get the passphrase text.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Passphrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Passphrase(<redacted>)")
+    }
+}
+
+/// Returns `true` if the file, relative to the character's directory, is one of the
+/// `encrypted_files`.
+///
+/// Encrypted files are given as paths relative to the character's directory
+/// (I.e. `SavedVariables/Addon.lua`) or file names matching in any directory.
+#[must_use]
+pub fn is_encrypted_file(encrypted_files: &[String], relative_path: &Path) -> bool {
+    if encrypted_files.is_empty() {
+        return false;
+    }
+    let path = relative_path.to_string_lossy().replace('\\', "/");
+    let file_name = relative_path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    encrypted_files.iter().any(|file| {
+        let file = file.replace('\\', "/");
+        file.eq_ignore_ascii_case(&path) || file.eq_ignore_ascii_case(&file_name)
+    })
+}
@@ -0,0 +1,178 @@
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::{Deserialize, Serialize};
+use xxhash_rust::{xxh3::Xxh3, xxh64::Xxh64};
+
+use crate::backend::vfs::FileSystem;
+
+/// Size of the buffer file contents are read into while hashing.
+const HASH_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Fast, non-cryptographic algorithms used to hash file contents, I.e. to compare files.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum HashAlgorithm {
+    /// 64-bit XXH3, the fastest on modern CPUs.
+    #[default]
+    Xxh3,
+    /// 64-bit XXH64, for matching hashes made by other tools.
+    Xxh64,
+}
+
+impl HashAlgorithm {
+    /// Get the display name of the algorithm.
+    #[inline]
+    #[must_use]
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::Xxh3 => "XXH3",
+            Self::Xxh64 => "XXH64",
+        }
+    }
+
+    /// Hash the given bytes.
+    #[must_use]
+    pub fn hash_bytes(self, bytes: &[u8]) -> u64 {
+        match self {
+            Self::Xxh3 => xxhash_rust::xxh3::xxh3_64(bytes),
+            Self::Xxh64 => xxhash_rust::xxh64::xxh64(bytes, 0),
+        }
+    }
+
+    /// Hash everything read from the given reader, without reading it into memory at once.
+    /// # Errors
+    /// Returns an error if reading fails.
+    pub fn hash_reader(self, reader: &mut dyn Read) -> io::Result<u64> {
+        let mut hasher = StreamingHasher::new(self);
+        let mut buffer = vec![0; HASH_BUFFER_SIZE];
+        loop {
+            let read = match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            hasher.update(&buffer[..read]);
+        }
+        Ok(hasher.digest())
+    }
+}
+
+/// Incremental hasher for any of the supported algorithms.
+enum StreamingHasher {
+    Xxh3(Box<Xxh3>),
+    Xxh64(Xxh64),
+}
+
+impl StreamingHasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Xxh3 => Self::Xxh3(Box::new(Xxh3::new())),
+            HashAlgorithm::Xxh64 => Self::Xxh64(Xxh64::new(0)),
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Self::Xxh3(hasher) => hasher.update(bytes),
+            Self::Xxh64(hasher) => hasher.update(bytes),
+        }
+    }
+
+    fn digest(&self) -> u64 {
+        match self {
+            Self::Xxh3(hasher) => hasher.digest(),
+            Self::Xxh64(hasher) => hasher.digest(),
+        }
+    }
+}
+
+/// Settings for hashing file contents, shared by every feature which compares files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HashSettings {
+    /// The algorithm files are hashed with.
+    pub algorithm: HashAlgorithm,
+    /// Maximum number of files hashed at once, `0` to use every available core.
+    pub threads: usize,
+}
+
+impl HashSettings {
+    /// Number of threads to hash the given number of files with.
+    #[must_use]
+    pub fn thread_count(&self, files: usize) -> usize {
+        let threads = if self.threads == 0 {
+            std::thread::available_parallelism().map_or(1, std::num::NonZero::get)
+        } else {
+            self.threads
+        };
+        threads.min(files).max(1)
+    }
+}
+
+/// Hash the contents of a file within the given filesystem.
+/// # Errors
+/// Returns an error if the file cannot be opened or read.
+pub fn hash_file(fs: &dyn FileSystem, path: &Path, algorithm: HashAlgorithm) -> io::Result<u64> {
+    let mut file = fs.open(path)?;
+    algorithm.hash_reader(&mut file)
+}
+
+/// Hash the contents of every given file in parallel, with the given settings.
+///
+/// Files are hashed on a bounded number of threads. Results are in the same order as `paths`,
+/// and independent of the number of threads.
+#[must_use]
+pub fn hash_files_with(
+    fs: &dyn FileSystem,
+    paths: &[PathBuf],
+    settings: HashSettings,
+) -> Vec<io::Result<u64>> {
+    let threads = settings.thread_count(paths.len());
+    if threads <= 1 {
+        return paths
+            .iter()
+            .map(|path| hash_file(fs, path, settings.algorithm))
+            .collect();
+    }
+
+    // Workers take the next unhashed file until none remain, so slow files don't hold up others.
+    let next = AtomicUsize::new(0);
+    let mut results = std::thread::scope(|scope| {
+        let workers = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut hashed = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(path) = paths.get(index) else {
+                            break hashed;
+                        };
+                        hashed.push((index, hash_file(fs, path, settings.algorithm)));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker.join().unwrap_or_else(|_| {
+                    log::error!("A file hashing thread panicked");
+                    Vec::new()
+                })
+            })
+            .collect::<Vec<_>>()
+    });
+
+    // Put the results back in the order of the paths, filling in any lost to a panicked thread.
+    results.sort_by_key(|(index, _)| *index);
+    let mut results = results.into_iter().peekable();
+    (0..paths.len())
+        .map(|index| match results.next_if(|(i, _)| *i == index) {
+            Some((_, result)) => result,
+            None => Err(io::Error::other("File hashing thread panicked")),
+        })
+        .collect()
+}
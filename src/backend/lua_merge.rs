@@ -0,0 +1,160 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    lua_table_parser::{LuaKey, LuaTableParser, LuaValue},
+    lua_table_writer::write_globals,
+};
+
+/// Returns `true` if the archive entry is an addon's `SavedVariables` Lua file, which can be merged.
+#[inline]
+#[must_use]
+pub fn is_mergeable_file(entry_name: &str) -> bool {
+    let entry_name = entry_name.replace('\\', "/");
+    entry_name
+        .strip_prefix(crate::wow::SAVED_VARIABLES_DIR)
+        .is_some_and(|rest| rest.starts_with('/'))
+        && entry_name.to_lowercase().ends_with(".lua")
+}
+
+/// Merge the contents of a `SavedVariables` file from a backup into the current contents of the file.
+///
+/// Tables are merged recursively. The backup's value wins wherever both have a value, and keys
+/// only present in the current file, such as settings added since the backup, are kept.
+/// Returns `None` if either file can't be parsed, in which case the file should be overwritten.
+#[must_use]
+pub fn merge_saved_variables(current: &str, backup: &str) -> Option<String> {
+    let current = LuaTableParser::new(current).parse_globals()?;
+    let backup = LuaTableParser::new(backup).parse_globals()?;
+    Some(write_globals(&merge_globals(current, backup)))
+}
+
+/// Merge the backup's global variables over the current global variables.
+fn merge_globals(
+    mut current: HashMap<String, LuaValue>,
+    backup: HashMap<String, LuaValue>,
+) -> HashMap<String, LuaValue> {
+    for (name, value) in backup {
+        let merged = match current.remove(&name) {
+            Some(existing) => merge_values(existing, value),
+            None => value,
+        };
+        current.insert(name, merged);
+    }
+    current
+}
+
+/// Returns `true` if the table is a list, a non-empty table with only integer keys.
+/// Empty tables aren't lists, as there is nothing to tell them apart from empty maps.
+#[inline]
+#[must_use]
+fn is_list(table: &BTreeMap<LuaKey, LuaValue>) -> bool {
+    !table.is_empty() && table.keys().all(|key| matches!(key, LuaKey::Integer(_)))
+}
+
+/// Merge a value from a backup over the current value, recursing into tables present in both.
+/// Lists are taken from the backup as a whole, so entries removed since the current file was
+/// written don't linger.
+fn merge_values(current: LuaValue, backup: LuaValue) -> LuaValue {
+    match (current, backup) {
+        (LuaValue::Table(mut current), LuaValue::Table(backup)) if !is_list(&backup) => {
+            for (key, value) in backup {
+                let merged = match current.remove(&key) {
+                    Some(existing) => merge_values(existing, value),
+                    None => value,
+                };
+                current.insert(key, merged);
+            }
+            LuaValue::Table(current)
+        }
+        (_, backup) => backup,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn merge(current: &str, backup: &str) -> HashMap<String, LuaValue> {
+        let merged = merge_saved_variables(current, backup).expect("Both files parse");
+        LuaTableParser::new(&merged)
+            .parse_globals()
+            .expect("Merged file parses")
+    }
+
+    fn table(entries: &[(LuaKey, LuaValue)]) -> LuaValue {
+        LuaValue::Table(entries.iter().cloned().collect())
+    }
+
+    fn string_key(key: &str) -> LuaKey {
+        LuaKey::String(key.to_string())
+    }
+
+    #[test]
+    fn backup_values_win_and_current_only_keys_are_kept() {
+        let merged = merge(
+            "Settings = { scale = 1, added = true }",
+            "Settings = { scale = 2 }",
+        );
+        assert_eq!(
+            merged["Settings"],
+            table(&[
+                (string_key("added"), LuaValue::Boolean(true)),
+                (string_key("scale"), LuaValue::Number(2.0)),
+            ])
+        );
+    }
+
+    #[test]
+    fn lists_are_taken_from_the_backup_as_a_whole() {
+        let merged = merge("List = { 1, 2, 3 }", "List = { 4 }");
+        assert_eq!(
+            merged["List"],
+            table(&[(LuaKey::Integer(1), LuaValue::Number(4.0))])
+        );
+    }
+
+    #[test]
+    fn empty_backup_tables_keep_the_current_entries() {
+        let merged = merge("Settings = { scale = 1 }", "Settings = {}");
+        assert_eq!(
+            merged["Settings"],
+            table(&[(string_key("scale"), LuaValue::Number(1.0))])
+        );
+    }
+
+    #[test]
+    fn mixed_tables_are_merged_by_key() {
+        let merged = merge(
+            "Mixed = { 1, 2, name = \"current\", kept = true }",
+            "Mixed = { 3, name = \"backup\" }",
+        );
+        assert_eq!(
+            merged["Mixed"],
+            table(&[
+                (LuaKey::Integer(1), LuaValue::Number(3.0)),
+                (LuaKey::Integer(2), LuaValue::Number(2.0)),
+                (string_key("kept"), LuaValue::Boolean(true)),
+                (string_key("name"), LuaValue::String("backup".to_string())),
+            ])
+        );
+    }
+
+    #[test]
+    fn strings_which_are_not_utf8_round_trip_through_a_merge() {
+        let backup = r#"Profile = { blob = "\001\255\xC3(\"", name = "backup" }"#;
+        let merged = merge("Profile = { blob = \"old\", kept = true }", backup);
+        assert_eq!(
+            merged["Profile"],
+            table(&[
+                (
+                    string_key("blob"),
+                    LuaValue::Bytes(b"\x01\xFF\xC3(\"".to_vec())
+                ),
+                (string_key("kept"), LuaValue::Boolean(true)),
+                (string_key("name"), LuaValue::String("backup".to_string())),
+            ])
+        );
+        let rewritten = merge_saved_variables(backup, backup).expect("Both files parse");
+        assert!(rewritten.contains(r#""\001\255\195(\"""#), "{rewritten}");
+    }
+}
@@ -0,0 +1,298 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lua_table_parser::{LuaKey, LuaTableParser, LuaValue},
+    lua_table_writer::write_globals,
+};
+
+/// Text string values matching a sanitise pattern are replaced with.
+pub const SANITIZED_VALUE: &str = "<removed>";
+
+/// Name account folders are renamed to within sanitised exports, followed by their number.
+pub const SANITIZED_ACCOUNT_NAME: &str = "ACCOUNT";
+
+/// Config file settings holding the account logged into, removed when sanitising.
+const ACCOUNT_SETTINGS: [&str; 2] = ["accountName", "accountList"];
+
+/// A pattern of personal data removed from `SavedVariables` files when sanitising them.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum SanitizePattern {
+    /// Email-like strings, I.e. `name@example.com`.
+    Email,
+    /// `BattleTag`-like strings, I.e. `Name#1234`.
+    BattleTag,
+    /// The given text, matched case-insensitively, such as an account name.
+    Text(String),
+}
+
+impl SanitizePattern {
+    /// The patterns sanitised by default.
+    #[must_use]
+    pub fn defaults() -> Vec<Self> {
+        vec![Self::Email, Self::BattleTag]
+    }
+
+    /// Returns `true` if the pattern is found anywhere within the string.
+    #[must_use]
+    pub fn is_found_in(&self, s: &str) -> bool {
+        match self {
+            Self::Email => contains_email(s),
+            Self::BattleTag => contains_battletag(s),
+            Self::Text(text) => !text.is_empty() && s.to_lowercase().contains(&text.to_lowercase()),
+        }
+    }
+}
+
+/// Returns `true` if the file is a `SavedVariables` Lua file, which can be sanitised.
+#[inline]
+#[must_use]
+pub fn is_sanitizable_file(path: &Path) -> bool {
+    path.parent()
+        .and_then(Path::file_name)
+        .is_some_and(|dir| dir == crate::wow::SAVED_VARIABLES_DIR)
+        && crate::files::cmp_extension(path, "lua")
+}
+
+/// Sanitise the contents of a `SavedVariables` file, so it can be shared without personal data.
+///
+/// Globals and table entries whose names match any of the patterns are removed, and string
+/// values matching any of the patterns are replaced with [`SANITIZED_VALUE`].
+/// Returns `None` if the file can't be parsed, in which case the file should be left out
+/// rather than shared unsanitised.
+#[must_use]
+pub fn sanitize_lua(content: &str, patterns: &[SanitizePattern]) -> Option<String> {
+    let globals = LuaTableParser::new(content).parse_globals()?;
+    let matches = |s: &str| patterns.iter().any(|pattern| pattern.is_found_in(s));
+    let sanitized = globals
+        .into_iter()
+        .filter(|(name, _)| !matches(name))
+        .map(|(name, value)| (name, sanitize_value(value, &matches)))
+        .collect::<HashMap<_, _>>();
+    Some(write_globals(&sanitized))
+}
+
+/// Returns `true` if the file is a `.wtf` config file, such as `Config.wtf`, which can be sanitised.
+#[inline]
+#[must_use]
+pub fn is_config_file(path: &Path) -> bool {
+    crate::files::cmp_extension(path, "wtf")
+}
+
+/// Sanitise the contents of a `.wtf` config file, so it can be shared without personal data.
+///
+/// Lines setting the account logged into, such as `SET accountName "..."`, are removed, along
+/// with any line matching any of the patterns.
+#[must_use]
+pub fn sanitize_config(content: &str, patterns: &[SanitizePattern]) -> String {
+    content
+        .lines()
+        .filter(|line| {
+            let setting = line.split_whitespace().nth(1).unwrap_or_default();
+            !ACCOUNT_SETTINGS
+                .iter()
+                .any(|account_setting| setting.eq_ignore_ascii_case(account_setting))
+                && !patterns.iter().any(|pattern| pattern.is_found_in(line))
+        })
+        .flat_map(|line| [line, "\n"])
+        .collect()
+}
+
+/// Renames the account folders within the paths of a `WTF` folder, so a sanitised export doesn't
+/// carry account names. Accounts are numbered in order of their names, so each account keeps
+/// its own folder.
+#[derive(Debug, Clone, Default)]
+pub struct AccountMask {
+    /// Masked names of the account folders, keyed by their names.
+    masked_names: BTreeMap<String, String>,
+}
+
+impl AccountMask {
+    /// Create a mask for the account folders within the given paths, relative to the `WTF` folder.
+    #[must_use]
+    pub fn new<'a>(relative_paths: impl IntoIterator<Item = &'a Path>) -> Self {
+        let accounts = relative_paths
+            .into_iter()
+            .filter_map(Self::account_name)
+            .collect::<std::collections::BTreeSet<_>>();
+        let masked_names = accounts
+            .into_iter()
+            .enumerate()
+            .map(|(i, account)| (account, format!("{SANITIZED_ACCOUNT_NAME}{}", i + 1)))
+            .collect();
+        Self { masked_names }
+    }
+
+    /// Get the path, relative to the `WTF` folder, with its account folder renamed.
+    #[must_use]
+    pub fn mask(&self, relative_path: &Path) -> PathBuf {
+        let Some(masked) =
+            Self::account_name(relative_path).and_then(|account| self.masked_names.get(&account))
+        else {
+            return relative_path.to_path_buf();
+        };
+        relative_path
+            .components()
+            .enumerate()
+            .map(|(i, component)| {
+                if i == 1 {
+                    Component::Normal(masked.as_ref())
+                } else {
+                    component
+                }
+            })
+            .collect()
+    }
+
+    /// Get the name of the account folder a path relative to the `WTF` folder is within, if any.
+    fn account_name(relative_path: &Path) -> Option<String> {
+        let mut components = relative_path.components();
+        let (Some(Component::Normal(dir)), Some(Component::Normal(account)), Some(_)) =
+            (components.next(), components.next(), components.next())
+        else {
+            return None;
+        };
+        (dir == crate::wow::ACCOUNT_DIR).then(|| account.to_string_lossy().into_owned())
+    }
+}
+
+/// Sanitise a value, recursing into tables.
+fn sanitize_value(value: LuaValue, matches: &impl Fn(&str) -> bool) -> LuaValue {
+    match value {
+        LuaValue::String(s) if matches(&s) => LuaValue::String(SANITIZED_VALUE.to_string()),
+        LuaValue::Bytes(bytes) if matches(&String::from_utf8_lossy(&bytes)) => {
+            LuaValue::String(SANITIZED_VALUE.to_string())
+        }
+        LuaValue::Table(table) => LuaValue::Table(
+            table
+                .into_iter()
+                .filter(|(key, _)| !matches!(key, LuaKey::String(key) if matches(key)))
+                .map(|(key, value)| (key, sanitize_value(value, matches)))
+                .collect::<BTreeMap<_, _>>(),
+        ),
+        value => value,
+    }
+}
+
+/// Returns `true` if the string contains an email address, a `@` with a name before it
+/// and a domain with a dot after it.
+fn contains_email(s: &str) -> bool {
+    let is_name_char = |c: char| c.is_alphanumeric() || "._%+-".contains(c);
+    let is_domain_char = |c: char| c.is_alphanumeric() || ".-".contains(c);
+    s.match_indices('@').any(|(at, _)| {
+        let has_name = s[..at].chars().next_back().is_some_and(is_name_char);
+        let domain = s[at + 1..]
+            .split(|c: char| !is_domain_char(c))
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches('.');
+        has_name
+            && domain
+                .split_once('.')
+                .is_some_and(|(host, tld)| !host.is_empty() && !tld.is_empty())
+    })
+}
+
+/// Returns `true` if the string contains a `BattleTag`, a name of 2 to 12 characters starting
+/// with a letter, then a `#` and 4 or more digits.
+fn contains_battletag(s: &str) -> bool {
+    s.match_indices('#').any(|(hash, _)| {
+        let name = s[..hash]
+            .chars()
+            .rev()
+            .take_while(|c| c.is_alphanumeric())
+            .collect::<Vec<_>>();
+        let digits = s[hash + 1..]
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .count();
+        (2..=12).contains(&name.len())
+            && name.last().is_some_and(|c| c.is_alphabetic())
+            && digits >= 4
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sanitized_globals(content: &str) -> HashMap<String, LuaValue> {
+        let sanitized = sanitize_lua(content, &SanitizePattern::defaults())
+            .expect("Content should be sanitised");
+        LuaTableParser::new(&sanitized)
+            .parse_globals()
+            .expect("Sanitised content should parse")
+    }
+
+    #[test]
+    fn battletags_are_masked_while_other_keys_survive() {
+        let globals = sanitized_globals(
+            r##"
+            ProfileDB = {
+                ["owner"] = "Player#1234",
+                ["note"] = "Raid with Healer#98765 on Tuesdays",
+                ["Friend#4321"] = true,
+                ["scale"] = 1.5,
+                ["channel"] = "#general",
+            }
+            "##,
+        );
+        let profile = &globals["ProfileDB"];
+        let string = |key| match profile.get(key) {
+            Some(LuaValue::String(s)) => Some(s.as_str()),
+            _ => None,
+        };
+        assert_eq!(string("owner"), Some(SANITIZED_VALUE));
+        assert_eq!(string("note"), Some(SANITIZED_VALUE));
+        assert_eq!(profile.get("Friend#4321"), None);
+        assert_eq!(profile.get("scale"), Some(&LuaValue::Number(1.5)));
+        assert_eq!(string("channel"), Some("#general"));
+    }
+
+    #[test]
+    fn account_settings_are_removed_from_config_files() {
+        let config = [
+            "SET locale \"enUS\"",
+            "SET accountName \"NAME@EXAMPLE.COM\"",
+            "SET accountList \"!WoW1|WoW2|\"",
+            "SET lastCharacterIndex \"2\"",
+            "SET realmName \"Draenor\"",
+            "SET lastAddonVersion \"Player#1234\"",
+        ]
+        .join("\n");
+        assert_eq!(
+            sanitize_config(&config, &SanitizePattern::defaults()),
+            "SET locale \"enUS\"\nSET lastCharacterIndex \"2\"\nSET realmName \"Draenor\"\n"
+        );
+    }
+
+    #[test]
+    fn account_folders_are_numbered_in_order_of_their_names() {
+        let paths = [
+            Path::new("Config.wtf"),
+            Path::new("Account/ZEPH/Draenor/Alpha/config-cache.wtf"),
+            Path::new("Account/12345678#1/SavedVariables/Details.lua"),
+            Path::new("Account/ZEPH/bindings-cache.wtf"),
+        ];
+        let mask = AccountMask::new(paths);
+        assert_eq!(
+            paths.map(|path| mask.mask(path)),
+            [
+                PathBuf::from("Config.wtf"),
+                PathBuf::from("Account/ACCOUNT2/Draenor/Alpha/config-cache.wtf"),
+                PathBuf::from("Account/ACCOUNT1/SavedVariables/Details.lua"),
+                PathBuf::from("Account/ACCOUNT2/bindings-cache.wtf"),
+            ]
+        );
+    }
+
+    #[test]
+    fn content_which_fails_to_parse_is_left_out() {
+        assert_eq!(
+            sanitize_lua("ProfileDB = {", &SanitizePattern::defaults()),
+            None
+        );
+    }
+}
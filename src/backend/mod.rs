@@ -1,36 +1,63 @@
+pub mod addon_manifest;
+pub mod addon_profile;
+pub mod archive_cache;
+pub mod archive_manifest;
+pub mod backup_name;
+pub mod character_scan;
+pub mod character_watcher;
+pub mod encryption;
+pub mod hashing;
+pub mod lua_merge;
+pub mod lua_sanitize;
 pub mod task;
+pub mod vfs;
 pub mod zip_rw;
 
 use std::{
+    collections::HashSet,
+    io::{Read, Write},
     path::Path,
     sync::{Arc, Mutex, mpsc::Sender as MPSCSender},
+    time::Duration,
 };
 
-use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use chrono::{DateTime, Local};
 use itertools::Itertools;
 
 use crate::{
     backend::{
-        task::{IOProgress, IOTask, TaskBuilder},
-        zip_rw::{ChronoZipReader, ChronoZipWriter},
+        archive_manifest::{BackupManifest, ExportManifest, is_archive_metadata},
+        backup_name::{BackupNameFields, BackupNameTemplate, match_backup_stem},
+        encryption::Passphrase,
+        hashing::HashSettings,
+        lua_sanitize::{AccountMask, SanitizePattern},
+        task::{IOProgress, IOTask, IOTaskKind, ProgressSender, TaskBuilder},
+        vfs::{FileSystem, FileSystemPtr, FileSystemWalk, filesystem_for},
+        zip_rw::{
+            ChronoZipReader, ChronoZipWriter, entry_relative_path, is_directory_entry,
+            normalize_entry_name,
+        },
     },
+    config::{ChronoBindAppConfig, RuntimeSettings},
     files::AnyResult,
     tui_log::mock_prefix,
     wow::{
-        CHARACTER_BACKUPS_DIR, CHRONOBIND_DIR, INTERFACE_DIR, USER_DIR, WoWCharacter,
-        WoWCharacterBackup, WoWInstall, WoWInstalls,
+        BackupLayout, BackupTier, CHARACTER_BACKUPS_DIR, CHRONOBIND_DIR, INTERFACE_DIR,
+        SAVED_VARIABLES_DIR, USER_DIR, WoWCharacter, WoWCharacterBackup, WoWInstall, WoWInstalls,
     },
 };
 
 use std::fs as filesystem;
 use std::path::PathBuf;
 
-use crate::files::{ensure_directory, walk_dir_recursive};
+use crate::files::{ensure_directory, walk_dir_iter};
 
 /// Suffix to append to backup files created during a paste operation.
 const PASTE_IDENT: &str = "RESTORE";
 /// Suffix to append to backup files that are pinned to not be auto-removed.
 const PINNED_IDENT: &str = "PINNED";
+/// Suffix to append to backup files that are protected from all but explicit removal.
+const PROTECTED_IDENT: &str = "PROTECTED";
 
 /// Time format used in backup file names.
 pub const BACKUP_FILE_TIME_FORMAT: &str = "%Y%m%d-%H%M%S";
@@ -39,6 +66,8 @@ pub const DISPLAY_TIME_FORMAT: &str = "%d/%m/%y %H:%M";
 
 /// Default filename for backup exports.
 pub const DEFAULT_EXPORT_FILENAME: &str = "chronobind_export";
+/// Name of the `ChronoBind` settings file embedded at the root of install exports.
+pub const EXPORT_SETTINGS_FILE_NAME: &str = "chronobind.config";
 /// Default filename for full branch backups.
 pub const DEFAULT_BRANCH_BACKUP_FILENAME: &str = "chronobind_backup";
 
@@ -59,38 +88,114 @@ pub fn date_now_as_filename_timestamp() -> String {
     format_timestamp_for_filename(Local::now())
 }
 
-/// Generate a backup file name for the given parameters.
+/// Get the current unix time, in seconds.
 #[inline]
 #[must_use]
-pub fn get_backup_name_from(
-    char_name: &str,
-    timestamp: DateTime<Local>,
-    paste: bool,
-    pinned: bool,
-) -> String {
-    let ts_str = format_timestamp_for_filename(timestamp);
+pub fn unix_time_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Format a unix time, in seconds, for display.
+#[must_use]
+pub fn format_unix_time(secs: u64) -> String {
+    i64::try_from(secs)
+        .ok()
+        .and_then(|secs| DateTime::from_timestamp(secs, 0))
+        .map_or_else(
+            || secs.to_string(),
+            |time| {
+                time.with_timezone(&Local)
+                    .format(DISPLAY_TIME_FORMAT)
+                    .to_string()
+            },
+        )
+}
+
+/// Get the suffixes appended to a backup file name for its paste state and protection tier.
+#[inline]
+#[must_use]
+fn backup_name_suffix(paste: bool, tier: BackupTier) -> String {
     format!(
-        "{}_{}{}{}.{BACKUP_FILE_EXTENSION}",
-        char_name,
-        ts_str,
+        "{}{}",
         if paste {
             format!("_{PASTE_IDENT}")
         } else {
             String::new()
         },
-        if pinned {
-            format!("_{PINNED_IDENT}")
-        } else {
-            String::new()
+        match tier {
+            BackupTier::Normal => String::new(),
+            BackupTier::Pinned => format!("_{PINNED_IDENT}"),
+            BackupTier::Protected => format!("_{PROTECTED_IDENT}"),
         }
     )
 }
 
-/// Generate a backup file name for the given `WoW` character.
+/// Generate a backup file name for the given parameters, using the given name template.
+#[inline]
+#[must_use]
+pub fn get_backup_name_from(
+    template: &BackupNameTemplate,
+    fields: &BackupNameFields<'_>,
+    paste: bool,
+    tier: BackupTier,
+) -> String {
+    format!(
+        "{}{}.{BACKUP_FILE_EXTENSION}",
+        template.format(fields),
+        backup_name_suffix(paste, tier)
+    )
+}
+
+/// Generate a backup file name for the given `WoW` character, using the configured name template.
 #[inline]
 #[must_use]
-pub fn get_backup_name(character: &WoWCharacter, paste: bool, pinned: bool) -> String {
-    get_backup_name_from(&character.name, Local::now(), paste, pinned)
+pub fn get_backup_name(
+    settings: &RuntimeSettings,
+    character: &WoWCharacter,
+    paste: bool,
+    pinned: bool,
+) -> String {
+    get_backup_name_from(
+        &settings.backup_name_template,
+        &BackupNameFields {
+            name: &character.name,
+            realm: &settings.realm_display_name(&character.realm),
+            branch: &character.branch,
+            timestamp: Local::now(),
+        },
+        paste,
+        BackupTier::from_pinned(pinned),
+    )
+}
+
+/// Highest counter appended to a backup's file name to tell it apart from existing backups.
+const MAX_BACKUP_NAME_COUNTER: usize = 1000;
+
+/// Get a path for a new backup which doesn't collide with an existing backup, such as one made
+/// within the same second, by appending a counter to the file name if it's already taken.
+fn unique_backup_path(fs: &dyn FileSystem, backup_dir: &Path, backup_file_name: &str) -> PathBuf {
+    let path = backup_dir.join(backup_file_name);
+    if !fs.exists(&path) {
+        return path;
+    }
+    let stem = Path::new(backup_file_name)
+        .file_stem()
+        .map_or_else(String::new, |stem| stem.to_string_lossy().to_string());
+    (2..MAX_BACKUP_NAME_COUNTER)
+        .map(|counter| backup_dir.join(format!("{stem}_{counter}.{BACKUP_FILE_EXTENSION}")))
+        .find(|path| !fs.exists(path))
+        .unwrap_or(path)
+}
+
+/// Get the counter appended to a backup's suffixes to tell it apart from a backup made within
+/// the same second, including its separator, or an empty string if it has none.
+fn backup_name_counter(suffixes: &str) -> String {
+    suffixes
+        .split('_')
+        .find(|segment| !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit()))
+        .map_or_else(String::new, |counter| format!("_{counter}"))
 }
 
 /// A structure representing a `WoW` character along with its associated install,
@@ -108,10 +213,11 @@ impl CharWithInstallLocal {
         self.character.get_character_path(&self.install)
     }
 
-    /// Get the character's backups directory path.
+    /// Get the path of the directory new backups of the character are stored in, with the
+    /// given backup layout.
     #[must_use]
-    pub fn get_backups_dir(&self) -> PathBuf {
-        self.character.get_backups_dir(&self.install)
+    pub fn get_backups_dir(&self, layout: BackupLayout) -> PathBuf {
+        self.character.get_backups_dir(&self.install, layout)
     }
 }
 
@@ -124,49 +230,394 @@ impl From<crate::ui::CharacterWithInstall<'_>> for CharWithInstallLocal {
     }
 }
 
-fn backup_character_async_internal(
-    tx: &MPSCSender<IOProgress>,
-    src_char: &CharWithInstallLocal,
-    selected_files: Option<&[PathBuf]>,
-    paste: bool,
-    pinned: bool,
-    mock_mode: bool,
+/// Default free space to leave on the backup volume beyond a backup's estimated size, in bytes.
+pub const DEFAULT_BACKUP_SPACE_MARGIN: u64 = 100 * 1024 * 1024;
+
+/// Sizes of a completed backup, used to report how well it compressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackupStats {
+    /// Number of files backed up.
+    pub files: usize,
+    /// Total size of the backed up files, in bytes.
+    pub uncompressed_size: u64,
+    /// Size of the finished archive in bytes, `None` in mock mode where nothing is written.
+    pub archive_size: Option<u64>,
+}
+
+impl std::fmt::Display for BackupStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} files, {} → ",
+            self.files,
+            format_size(self.uncompressed_size)
+        )?;
+        match self.archive_size {
+            None => write!(f, "mock"),
+            Some(archive_size) if self.uncompressed_size == 0 => {
+                write!(f, "{}", format_size(archive_size))
+            }
+            #[allow(clippy::cast_precision_loss)]
+            Some(archive_size) => write!(
+                f,
+                "{} ({:.0}%)",
+                format_size(archive_size),
+                archive_size as f64 / self.uncompressed_size as f64 * 100.0
+            ),
+        }
+    }
+}
+
+/// Get the name a character is referred to by in task summaries, I.e. `Name - Realm`.
+fn character_summary_name(settings: &RuntimeSettings, character: &WoWCharacter) -> String {
+    format!(
+        "{} - {}",
+        character.name,
+        settings.realm_display_name(&character.realm)
+    )
+}
+
+/// Format a size in bytes for display, I.e. `84 MiB`.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if size < 10.0 {
+        format!("{size:.1} {}", UNITS[unit])
+    } else {
+        format!("{size:.0} {}", UNITS[unit])
+    }
+}
+
+/// Refuse to start a backup that may not fit on the backup volume, as running out of space part
+/// way through leaves a truncated archive. The uncompressed size is used as a pessimistic estimate.
+/// # Errors
+/// Returns an error if the free space is below the estimate plus the `margin`, in bytes.
+fn ensure_space_for_backup(
+    fs: &dyn FileSystem,
+    backup_dir: &Path,
+    uncompressed_size: u64,
+    margin: u64,
 ) -> AnyResult<()> {
-    let char_path = src_char.get_character_path();
-    let backup_dir = src_char.get_backups_dir();
+    const MIB: u64 = 1024 * 1024;
 
-    ensure_directory(&backup_dir, mock_mode)?;
+    let available = match fs.available_space(backup_dir) {
+        Ok(available) => available,
+        Err(e) => {
+            log::warn!(
+                "Could not determine free space for `{}`, backing up anyway: {e}",
+                backup_dir.display()
+            );
+            return Ok(());
+        }
+    };
 
-    let mut dir_iter = walk_dir_recursive(&char_path, &[crate::wow::BACKUPS_DIR_NAME])?;
-    if let Some(selected) = selected_files {
-        let fully_qualified_paths: Vec<PathBuf> =
-            selected.iter().map(|p| char_path.join(p)).collect();
-        dir_iter.retain(|p| fully_qualified_paths.contains(p));
+    let required = uncompressed_size.saturating_add(margin);
+    if available < required {
+        return Err(format!(
+            "Not enough disk space to back up to `{}`: {} MiB free, but {} MiB needed \
+            ({} MiB of files plus a {} MiB margin)",
+            backup_dir.display(),
+            available / MIB,
+            required.div_ceil(MIB),
+            uncompressed_size.div_ceil(MIB),
+            margin / MIB
+        )
+        .into());
     }
+    Ok(())
+}
+
+/// Message shown when the backups directory can't be created or written to.
+pub const BACKUP_LOCATION_UNAVAILABLE: &str =
+    "Backup location unavailable — is the drive connected?";
 
-    let total = dir_iter.len();
+/// Name of the file written to probe whether the backups directory is writable.
+const BACKUP_PROBE_FILE_NAME: &str = ".chronobind_probe";
 
-    let backup_file_name = get_backup_name(&src_char.character, paste, pinned);
-    let backup_file_path = backup_dir.join(backup_file_name);
+/// Check that the backups directory can be created and written to before starting a backup.
+///
+/// Reports a missing drive up front rather than part way through a backup.
+/// Nothing is written in mock mode.
+/// # Errors
+/// Returns [`BACKUP_LOCATION_UNAVAILABLE`] if the directory can't be created or written to.
+pub fn probe_backup_location(backup_dir: &Path, mock_mode: bool) -> AnyResult<()> {
+    if mock_mode {
+        return Ok(());
+    }
+    let probe = || -> AnyResult<()> {
+        ensure_directory(backup_dir, mock_mode)?;
+        let probe_path = backup_dir.join(BACKUP_PROBE_FILE_NAME);
+        filesystem::write(&probe_path, [])?;
+        filesystem::remove_file(&probe_path)?;
+        Ok(())
+    };
+    probe().map_err(|e| {
+        log::debug!(
+            "Backup location `{}` is unavailable: {e}",
+            backup_dir.display()
+        );
+        BACKUP_LOCATION_UNAVAILABLE.into()
+    })
+}
 
-    let mut zip = ChronoZipWriter::new(&backup_file_path, mock_mode)?;
+/// Removes a partially written backup archive when dropped, unless it was disarmed after the
+/// archive finished writing. Keeps cancelled or failed backups out of the backup manager.
+struct PartialArchiveGuard<'a> {
+    /// Path of the archive being written.
+    path: &'a Path,
+    /// Filesystem the archive is written to.
+    fs: &'a FileSystemPtr,
+    /// Whether the archive should be removed on drop.
+    armed: bool,
+}
 
-    for (files_backed_up, file_path) in dir_iter.iter().enumerate() {
+impl<'a> PartialArchiveGuard<'a> {
+    const fn new(path: &'a Path, fs: &'a FileSystemPtr) -> Self {
+        Self {
+            path,
+            fs,
+            armed: true,
+        }
+    }
+
+    /// Keep the archive, called once it has been fully written.
+    const fn disarm(&mut self) {
+        self.armed = false;
+    }
+}
+
+impl Drop for PartialArchiveGuard<'_> {
+    fn drop(&mut self) {
+        if !self.armed || self.fs.is_mock() {
+            return;
+        }
+        match self.fs.remove_file(self.path) {
+            Ok(()) => log::warn!(
+                "Removed incomplete backup archive `{}`",
+                self.path.display()
+            ),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => log::error!(
+                "Failed to remove incomplete backup archive `{}`: {e}",
+                self.path.display()
+            ),
+        }
+    }
+}
+
+/// What a character backup contains and how it is named.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct BackupOptions<'a> {
+    /// Files to back up, relative to the character's directory, `None` for every file.
+    pub selected_files: Option<&'a [PathBuf]>,
+    /// Whether the backup is made automatically before pasting or restoring.
+    pub paste: bool,
+    /// Whether the backup is pinned, keeping it from being removed automatically.
+    pub pinned: bool,
+    /// Note stored in the backup's manifest.
+    pub note: Option<&'a str>,
+}
+
+/// Walk the selected files of the character directory lazily, leaving out its backups folder.
+/// Walks visit files in the same order, so a second walk visits the files counted by the first.
+fn selected_backup_files<'a>(
+    fs: &'a dyn FileSystem,
+    char_path: &Path,
+    is_selected: &'a impl Fn(&Path) -> bool,
+) -> std::io::Result<impl Iterator<Item = std::io::Result<PathBuf>> + 'a> {
+    Ok(
+        FileSystemWalk::new(fs, char_path, &[crate::wow::BACKUPS_DIR_NAME])?
+            .filter(|file_path| file_path.as_ref().map_or(true, |path| is_selected(path))),
+    )
+}
+
+/// Count the selected files of the character directory included in a backup and their total
+/// size, and collect the selected files withheld from it, relative to the character directory.
+///
+/// This is a first walk over the directory, so the total is known before compression starts
+/// without holding every included path in memory.
+fn count_backup_files(
+    fs: &dyn FileSystem,
+    char_path: &Path,
+    is_selected: &impl Fn(&Path) -> bool,
+    is_withheld: &impl Fn(&Path) -> bool,
+) -> AnyResult<(usize, u64, Vec<PathBuf>)> {
+    let mut included = 0;
+    let mut uncompressed_size = 0;
+    let mut withheld = Vec::new();
+    for file_path in selected_backup_files(fs, char_path, is_selected)? {
+        let file_path = file_path?;
+        if is_withheld(&file_path) {
+            withheld.push(
+                file_path
+                    .strip_prefix(char_path)
+                    .map_or_else(|_| file_path.clone(), Path::to_path_buf),
+            );
+        } else {
+            uncompressed_size += fs.file_size(&file_path).unwrap_or(0);
+            included += 1;
+        }
+    }
+    Ok((included, uncompressed_size, withheld))
+}
+
+/// Warn about the files left out of a backup, as no passphrase was given to encrypt them.
+/// # Errors
+/// Returns an error if any are withheld from an automatic backup made before pasting or
+/// restoring, as the files would be overwritten without any copy of them being kept.
+fn check_withheld_files(withheld: &[PathBuf], paste: bool) -> AnyResult<()> {
+    if withheld.is_empty() {
+        return Ok(());
+    }
+    let files = withheld
+        .iter()
+        .map(|path| format!("`{}`", path.display()))
+        .join(", ");
+    if paste {
+        return Err(format!(
+            "Cannot back up {files} before they are overwritten, as no passphrase was given to \
+            encrypt them. Set `{}` or enter the passphrase on startup, or remove them from \
+            `encrypted_files`",
+            encryption::PASSPHRASE_ENV_VAR
+        )
+        .into());
+    }
+    log::warn!("Leaving {files} out of the backup, as no passphrase was given to encrypt them");
+    Ok(())
+}
+
+/// Create a backup ZIP archive of the given `WoW` character's data within the given filesystem,
+/// optionally backing up only the selected files, and with a note stored in its manifest.
+/// # Errors
+/// Returns an error if any file operations fail.
+pub(crate) fn backup_character_internal(
+    tx: &MPSCSender<IOProgress>,
+    src_char: &CharWithInstallLocal,
+    options: BackupOptions<'_>,
+    settings: &RuntimeSettings,
+    fs: &FileSystemPtr,
+) -> AnyResult<()> {
+    let BackupOptions {
+        selected_files,
+        paste,
+        pinned,
+        note,
+    } = options;
+    let char_path = src_char.get_character_path();
+    let backup_dir = src_char.get_backups_dir(settings.backup_layout);
+
+    fs.ensure_directory(&backup_dir)?;
+
+    let fully_qualified_paths: Option<HashSet<PathBuf>> =
+        selected_files.map(|selected| selected.iter().map(|p| char_path.join(p)).collect());
+    let passphrase = settings.passphrase.as_ref();
+    // Files to encrypt are left out entirely without a passphrase, rather than kept in plain text.
+    let is_withheld = |path: &Path| {
+        passphrase.is_none()
+            && path.strip_prefix(&char_path).is_ok_and(|relative| {
+                encryption::is_encrypted_file(&settings.encrypted_files, relative)
+            })
+    };
+    let is_selected = |path: &Path| {
+        fully_qualified_paths
+            .as_ref()
+            .is_none_or(|selected| selected.contains(path))
+    };
+
+    let (total, uncompressed_size, withheld) =
+        count_backup_files(fs.as_ref(), &char_path, &is_selected, &is_withheld)?;
+    check_withheld_files(&withheld, paste)?;
+    if total == 0 {
+        // None of the files exist yet, such as files new to a paste destination or a character
+        // with an empty directory, so there is nothing worth keeping in an archive.
+        log::info!("No files to back up, skipping empty backup.");
+        return Ok(());
+    }
+    ensure_space_for_backup(
+        fs.as_ref(),
+        &backup_dir,
+        uncompressed_size,
+        settings.backup_space_margin,
+    )?;
+    tx.send(IOProgress::Started { total: Some(total) })?;
+
+    let backup_file_name = get_backup_name(settings, &src_char.character, paste, pinned);
+    let backup_file_path = unique_backup_path(fs.as_ref(), &backup_dir, &backup_file_name);
+
+    // Declared before the writer so the writer is dropped, closing the file, before any cleanup.
+    let mut archive_guard = PartialArchiveGuard::new(&backup_file_path, fs);
+    let mut zip = ChronoZipWriter::new_with_fs(&backup_file_path, fs.clone())?;
+
+    let mut progress = ProgressSender::new(tx, total, settings.progress_interval);
+    let mut files_backed_up = 0;
+    let mut encrypted_entries = Vec::new();
+    // Files are compressed as a second walk visits them, rather than from a list of paths.
+    for file_path in selected_backup_files(fs.as_ref(), &char_path, &is_selected)? {
+        let file_path = file_path?;
+        if is_withheld(&file_path) {
+            continue;
+        }
         let relative_path = file_path.strip_prefix(&char_path)?;
-        zip.copy_file(relative_path.to_string_lossy(), file_path)?;
+        let entry_name = relative_path.to_string_lossy();
+        match passphrase {
+            Some(passphrase)
+                if encryption::is_encrypted_file(&settings.encrypted_files, relative_path) =>
+            {
+                zip.copy_file_encrypted(entry_name.as_ref(), &file_path, passphrase.as_str())?;
+                encrypted_entries.push(normalize_entry_name(&entry_name));
+            }
+            _ => zip.copy_file(entry_name.as_ref(), &file_path)?,
+        }
+        files_backed_up += 1;
 
         log::info!("Backed up `{}`", relative_path.display());
-        tx.send(IOProgress::Advanced {
-            completed: files_backed_up.saturating_add(1),
-            total,
-            label: Some(relative_path.display().to_string()),
+        progress.advance(files_backed_up, || {
+            Some(relative_path.display().to_string())
         })?;
     }
+    progress.flush()?;
+
+    if !encrypted_entries.is_empty() {
+        log::info!("Encrypted {} files in the backup", encrypted_entries.len());
+    }
+    BackupManifest::for_character(&src_char.character)
+        .with_encrypted_entries(encrypted_entries)
+        .with_note(note.map(str::to_string))
+        .write_to(&mut zip)?;
+    if settings.include_addon_manifest {
+        addon_manifest::write_addon_manifest(&mut zip, fs.as_ref(), &char_path)?;
+    }
 
     zip.finish()?;
+    archive_guard.disarm();
 
     log::debug!("Finished backup to `{}`", backup_file_path.display());
 
+    let stats = BackupStats {
+        files: files_backed_up,
+        uncompressed_size,
+        archive_size: if fs.is_mock() {
+            None
+        } else {
+            fs.file_size(&backup_file_path).ok()
+        },
+    };
+    tx.send(IOProgress::Summary(format!(
+        "Backed up {}: {stats}, to `{}`",
+        character_summary_name(settings, &src_char.character),
+        backup_file_path.display()
+    )))?;
+
     Ok(())
 }
 
@@ -177,10 +628,40 @@ pub fn backup_character_all_async(
     src_char: CharWithInstallLocal,
     paste: bool,
     pinned: bool,
+    settings: &RuntimeSettings,
     mock_mode: bool,
 ) -> IOTask {
+    let settings = settings.clone();
     IOTask::new(move |tx| {
-        backup_character_async_internal(tx, &src_char, None, paste, pinned, mock_mode)
+        let fs = filesystem_for(mock_mode);
+        let options = BackupOptions {
+            paste,
+            pinned,
+            ..BackupOptions::default()
+        };
+        backup_character_internal(tx, &src_char, options, &settings, &fs)
+    })
+    .name("Backing up all files")
+}
+
+/// Create a backup ZIP archive of the given `WoW` character's data, with the note, if any,
+/// stored in the backup's manifest.
+/// # Errors
+/// Returns an error if any file operations fail.
+pub fn backup_character_all_with_note_async(
+    src_char: CharWithInstallLocal,
+    note: Option<String>,
+    settings: &RuntimeSettings,
+    mock_mode: bool,
+) -> IOTask {
+    let settings = settings.clone();
+    IOTask::new(move |tx| {
+        let fs = filesystem_for(mock_mode);
+        let options = BackupOptions {
+            note: note.as_deref(),
+            ..BackupOptions::default()
+        };
+        backup_character_internal(tx, &src_char, options, &settings, &fs)
     })
     .name("Backing up all files")
 }
@@ -194,12 +675,21 @@ pub fn backup_character_selected_async(
     selected_files: &[PathBuf],
     paste: bool,
     pinned: bool,
+    settings: &RuntimeSettings,
     mock_mode: bool,
 ) -> IOTask {
     let sel_files = selected_files.to_vec();
+    let settings = settings.clone();
 
     IOTask::new(move |tx| {
-        backup_character_async_internal(tx, &src_char, Some(&sel_files), paste, pinned, mock_mode)
+        let fs = filesystem_for(mock_mode);
+        let options = BackupOptions {
+            selected_files: Some(&sel_files),
+            paste,
+            pinned,
+            note: None,
+        };
+        backup_character_internal(tx, &src_char, options, &settings, &fs)
     })
     .name("Backing up selected files")
 }
@@ -212,37 +702,100 @@ pub fn backup_character_async(
     selected_files: Option<&[PathBuf]>,
     paste: bool,
     pinned: bool,
+    settings: &RuntimeSettings,
     mock_mode: bool,
 ) -> IOTask {
     selected_files.map_or_else(
-        || backup_character_all_async(src_char.into(), paste, pinned, mock_mode),
+        || backup_character_all_async(src_char.into(), paste, pinned, settings, mock_mode),
         |selected| {
-            backup_character_selected_async(src_char.into(), selected, paste, pinned, mock_mode)
+            backup_character_selected_async(
+                src_char.into(),
+                selected,
+                paste,
+                pinned,
+                settings,
+                mock_mode,
+            )
         },
     )
 }
 
+/// How pasting a file would change the destination character's copy of the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PasteFileStatus {
+    /// The destination has no copy of the file.
+    New,
+    /// The destination's copy of the file differs, and would be overwritten.
+    Overwrite,
+    /// The destination's copy of the file has the same contents.
+    Identical,
+}
+
+impl PasteFileStatus {
+    /// Get the display name of the status.
+    #[inline]
+    #[must_use]
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::New => "New",
+            Self::Overwrite => "Overwrite",
+            Self::Identical => "Identical",
+        }
+    }
+}
+
+/// Compare a file to be pasted with the destination's copy of it, by size and then by hash.
+/// Files which can't be read are treated as differing, so they are pasted.
+#[must_use]
+pub fn paste_file_status(
+    hash_settings: HashSettings,
+    src_path: &Path,
+    dest_path: &Path,
+) -> PasteFileStatus {
+    let Ok(dest_metadata) = filesystem::metadata(dest_path) else {
+        return PasteFileStatus::New;
+    };
+    let same_size = filesystem::metadata(src_path)
+        .is_ok_and(|src_metadata| src_metadata.len() == dest_metadata.len());
+    if !same_size {
+        return PasteFileStatus::Overwrite;
+    }
+
+    let hashes = hashing::hash_files_with(
+        &vfs::RealFileSystem,
+        &[src_path.into(), dest_path.into()],
+        hash_settings,
+    );
+    match hashes.as_slice() {
+        [Ok(src_hash), Ok(dest_hash)] if src_hash == dest_hash => PasteFileStatus::Identical,
+        _ => PasteFileStatus::Overwrite,
+    }
+}
+
 fn paste_character_files_async_internal(
     dest_character: CharWithInstallLocal,
     src_character: CharWithInstallLocal,
     selected_files: &[PathBuf],
+    settings: &RuntimeSettings,
     mock_mode: bool,
 ) -> IOTask {
     let sel_files = selected_files.to_vec();
+    let settings = settings.clone();
 
     IOTask::new(move |tx| {
+        let fs = filesystem_for(mock_mode);
         let dest_char_path = dest_character.get_character_path();
         let src_char_path = src_character.get_character_path();
 
         let total = sel_files.len();
+        tx.send(IOProgress::Started { total: Some(total) })?;
 
+        let mut progress = ProgressSender::new(tx, total, settings.progress_interval);
         for (files_copied, relative_path) in sel_files.iter().enumerate() {
             let src_file_path = src_char_path.join(relative_path);
             let dest_file_path = dest_char_path.join(relative_path);
 
-            if !mock_mode {
-                filesystem::copy(&src_file_path, &dest_file_path)?;
-            }
+            fs.copy(&src_file_path, &dest_file_path)?;
 
             log::info!(
                 "{}Copied `{}` to `{}`",
@@ -250,16 +803,23 @@ fn paste_character_files_async_internal(
                 relative_path.display(),
                 dest_file_path.display()
             );
-            tx.send(IOProgress::Advanced {
-                completed: files_copied.saturating_add(1),
-                total,
-                label: Some(relative_path.display().to_string()),
+            progress.advance(files_copied.saturating_add(1), || {
+                Some(relative_path.display().to_string())
             })?;
         }
+        progress.flush()?;
 
+        tx.send(IOProgress::Summary(format!(
+            "{}Pasted {total} files from {} to {}, in `{}`",
+            mock_prefix(mock_mode),
+            character_summary_name(&settings, &src_character.character),
+            character_summary_name(&settings, &dest_character.character),
+            dest_char_path.display()
+        )))?;
         Ok(())
     })
     .name("Pasting character files")
+    .kind(IOTaskKind::Destructive)
 }
 
 /// Paste the selected files from the source `WoW` character to the destination `WoW` character.
@@ -270,11 +830,13 @@ fn paste_character_files_async_internal(
 /// - `dest_character`: The destination character to which files will be pasted.
 /// - `src_character`: The source character from which files will be copied.
 /// - `selected_files`: A list of relative file paths to be copied.
+/// - `settings`: The settings the backup and copy are made with.
 /// - `mock_mode`: If true, no actual file operations will be performed; only logging will occur.
 pub fn paste_character_files_async(
     dest_character: CharWithInstallLocal,
     src_character: CharWithInstallLocal,
     selected_files: &[PathBuf],
+    settings: &RuntimeSettings,
     mock_mode: bool,
 ) -> IOTask {
     let first_task = if mock_mode {
@@ -286,6 +848,7 @@ pub fn paste_character_files_async(
             selected_files,
             true,
             false,
+            settings,
             mock_mode,
         ))
     };
@@ -294,6 +857,7 @@ pub fn paste_character_files_async(
         dest_character,
         src_character,
         selected_files,
+        settings,
         mock_mode,
     );
 
@@ -304,142 +868,734 @@ pub fn paste_character_files_async(
     }
 }
 
-/// Extract the character name and timestamp from a backup file path.
+/// Extract the character name, timestamp, paste state and protection tier from a backup file path.
+/// The name is matched against the given name template, or the default template.
 #[must_use]
-pub fn extract_backup_name(backup_filestem: &str) -> Option<(String, DateTime<Local>, bool, bool)> {
-    let segments = backup_filestem.split('_').collect::<Vec<&str>>();
-    if segments.len() < 2 {
-        return None;
-    }
-    let name = segments[0].to_string();
-    let date = NaiveDateTime::parse_from_str(segments[1], BACKUP_FILE_TIME_FORMAT).ok()?;
-    let remaining_segments = segments.len().saturating_sub(2);
+pub fn extract_backup_name(
+    template: &BackupNameTemplate,
+    backup_filestem: &str,
+) -> Option<(String, DateTime<Local>, bool, BackupTier)> {
+    let name_match = match_backup_stem(template, backup_filestem)?;
 
     let mut paste = false;
-    let mut pinned = false;
+    let mut tier = BackupTier::Normal;
 
-    for i in 0..remaining_segments {
-        match segments[2 + i] {
+    for segment in backup_filestem[name_match.len..].split('_') {
+        match segment {
             PASTE_IDENT => paste = true,
-            PINNED_IDENT => pinned = true,
+            PINNED_IDENT => tier = tier.max(BackupTier::Pinned),
+            PROTECTED_IDENT => tier = BackupTier::Protected,
             _ => {}
         }
     }
 
-    Some((
-        name,
-        Local.from_local_datetime(&date).unwrap(),
-        paste,
-        pinned,
-    ))
+    Some((name_match.name, name_match.timestamp, paste, tier))
+}
+
+/// Which files of a backup to restore.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RestoreScope {
+    /// Every file in the backup.
+    #[default]
+    All,
+    /// Only the config files at the root of the character directory, such as keybindings.
+    Config,
+    /// Only the addon files, under the `SavedVariables` directory.
+    Addons,
+    /// Only the addon files, merging the settings of `SavedVariables` Lua files into the
+    /// current files rather than overwriting them. Experimental.
+    MergeAddons,
+}
+
+impl RestoreScope {
+    /// Get the display name of the restore scope.
+    #[inline]
+    #[must_use]
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::All => "all files",
+            Self::Config => "config files",
+            Self::Addons => "addon files",
+            Self::MergeAddons => "addon files (merged)",
+        }
+    }
+}
+
+/// Get the names of the files in the archive within the given restore scope, excluding directories.
+fn scoped_archive_files(archive: &mut ChronoZipReader<'_>, scope: RestoreScope) -> Vec<String> {
+    let addon_files = archive
+        .files_in_directory(SAVED_VARIABLES_DIR)
+        .into_iter()
+        .collect::<HashSet<_>>();
+    archive
+        .file_names()
+        .filter(|name| !is_directory_entry(name) && !is_archive_metadata(name))
+        .filter(|name| match scope {
+            RestoreScope::All => true,
+            RestoreScope::Config => !addon_files.contains(*name),
+            RestoreScope::Addons | RestoreScope::MergeAddons => addon_files.contains(*name),
+        })
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Restore a backup for the given `WoW` character from the specified backup file path.
+/// # Errors
+/// Returns an error if any file operations fail.
+pub fn restore_backup_async(
+    character: CharWithInstallLocal,
+    backup_path: PathBuf,
+    settings: &RuntimeSettings,
+    mock_mode: bool,
+) -> IOTask {
+    restore_backup_scope_task(
+        character,
+        backup_path,
+        RestoreScope::All,
+        settings,
+        mock_mode,
+    )
+}
+
+/// Create the task restoring the files of a backup within the given scope.
+fn restore_backup_scope_task(
+    character: CharWithInstallLocal,
+    backup_path: PathBuf,
+    scope: RestoreScope,
+    settings: &RuntimeSettings,
+    mock_mode: bool,
+) -> IOTask {
+    let settings = settings.clone();
+    IOTask::new(move |tx| {
+        let fs = filesystem_for(mock_mode);
+        restore_backup_internal(tx, &character, &backup_path, scope, &settings, fs.as_ref())
+    })
+    .name("Restoring backup")
+    .kind(IOTaskKind::Destructive)
+}
+
+/// Restore only the config or addon files of a backup for the given `WoW` character,
+/// backing up the character's current copies of the affected files first.
+/// # Errors
+/// Returns an error if the backup cannot be read, or has no files within the scope.
+pub fn restore_backup_scoped_async(
+    character: CharWithInstallLocal,
+    backup_path: PathBuf,
+    scope: RestoreScope,
+    settings: &RuntimeSettings,
+    mock_mode: bool,
+) -> AnyResult<IOTask> {
+    let affected_files = {
+        let mut archive = ChronoZipReader::new(&backup_path)?;
+        scoped_archive_files(&mut archive, scope)
+    };
+    if affected_files.is_empty() {
+        return Err(format!(
+            "Backup `{}` has no {} to restore",
+            crate::files::file_name_str(&backup_path),
+            scope.display_name()
+        )
+        .into());
+    }
+
+    let restore_task =
+        restore_backup_scope_task(character.clone(), backup_path, scope, settings, mock_mode).name(
+            match scope {
+                RestoreScope::All => "Restoring backup",
+                RestoreScope::Config => "Restoring config files",
+                RestoreScope::Addons => "Restoring addon files",
+                RestoreScope::MergeAddons => "Merging addon files",
+            },
+        );
+
+    let char_path = character.get_character_path();
+    let existing_files = affected_files
+        .iter()
+        .map(PathBuf::from)
+        .filter(|path| char_path.join(path).is_file())
+        .collect::<Vec<_>>();
+    if mock_mode || existing_files.is_empty() {
+        return Ok(restore_task);
+    }
+
+    log::debug!(
+        "Backing up {} before restoring them...",
+        scope.display_name()
+    );
+    Ok(backup_character_selected_async(
+        character,
+        &existing_files,
+        true,
+        false,
+        settings,
+        mock_mode,
+    )
+    .then(restore_task))
+}
+
+/// Files found at the root of every `ChronoBind` character backup.
+const CHARACTER_BACKUP_MARKERS: [&str; 2] = ["config-cache.wtf", "macros-cache.txt"];
+
+/// What could be determined about an archive chosen to restore from outside the backups directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalArchiveInfo {
+    /// The character name the archive was backed up from, if its file name follows
+    /// the `ChronoBind` backup naming scheme.
+    pub char_name: Option<String>,
+    /// Whether the archive contains the files expected in a character backup.
+    pub is_character_backup: bool,
+    /// Number of files in the archive.
+    pub file_count: usize,
+    /// Names of the files in the archive whose contents are encrypted.
+    pub encrypted_files: Vec<String>,
+}
+
+/// Inspect an archive before restoring from it, to detect backups of another character
+/// or archives not created by `ChronoBind`. File names are matched against the given template.
+/// # Errors
+/// Returns an error if the archive cannot be opened or is not a valid ZIP archive.
+pub fn inspect_external_archive(
+    template: &BackupNameTemplate,
+    archive_path: &Path,
+) -> AnyResult<ExternalArchiveInfo> {
+    let mut archive = ChronoZipReader::new(archive_path)?;
+
+    let char_name = archive_path
+        .file_stem()
+        .and_then(|stem| extract_backup_name(template, &stem.to_string_lossy()))
+        .map(|(name, ..)| name);
+    let is_character_backup = archive
+        .file_names()
+        .any(|name| CHARACTER_BACKUP_MARKERS.contains(&name));
+    let file_count = archive
+        .file_names()
+        .filter(|name| !is_directory_entry(name) && !is_archive_metadata(name))
+        .count();
+    let mut encrypted_files = Vec::new();
+    for i in 0..archive.len() {
+        if archive.is_encrypted(i)?
+            && let Some(name) = archive.name_for_index(i)
+        {
+            encrypted_files.push(name.to_string());
+        }
+    }
+
+    Ok(ExternalArchiveInfo {
+        char_name,
+        is_character_backup,
+        file_count,
+        encrypted_files,
+    })
+}
+
+/// Restore an arbitrary archive, such as a backup shared by someone else, into the given
+/// `WoW` character's directory, backing up the character's current files first.
+/// # Errors
+/// Returns an error if any file operations fail.
+pub fn restore_backup_from_file_async(
+    character: CharWithInstallLocal,
+    archive_path: PathBuf,
+    settings: &RuntimeSettings,
+    mock_mode: bool,
+) -> IOTask {
+    let restore_task = restore_backup_async(character.clone(), archive_path, settings, mock_mode)
+        .name("Restoring from file");
+
+    if mock_mode {
+        restore_task
+    } else {
+        log::debug!("Backing up files before restoring from file...");
+        backup_character_all_async(character, true, false, settings, mock_mode).then(restore_task)
+    }
+}
+
+/// Extract the files within `scope` of the backup at `backup_path` into the given `WoW`
+/// character's directory within the given filesystem.
+/// # Errors
+/// Returns an error if any file operations fail.
+pub(crate) fn restore_backup_internal(
+    tx: &MPSCSender<IOProgress>,
+    character: &CharWithInstallLocal,
+    backup_path: &Path,
+    scope: RestoreScope,
+    settings: &RuntimeSettings,
+    fs: &dyn FileSystem,
+) -> AnyResult<()> {
+    let mock_mode = fs.is_mock();
+    let mut archive = ChronoZipReader::new_with_fs(backup_path, fs)?;
+
+    let scoped_files = (scope != RestoreScope::All).then(|| {
+        scoped_archive_files(&mut archive, scope)
+            .into_iter()
+            .collect::<HashSet<_>>()
+    });
+    // Every entry within scope is one step, including directory entries and skipped entries,
+    // so the progress reaches its total exactly once the last entry is handled.
+    let total_steps = scoped_files.as_ref().map_or_else(
+        || {
+            archive
+                .file_names()
+                .filter(|name| !is_archive_metadata(name))
+                .count()
+        },
+        HashSet::len,
+    );
+    tx.send(IOProgress::Started {
+        total: Some(total_steps),
+    })?;
+
+    let dest_root = character.get_character_path();
+    fs.ensure_directory(&dest_root)?;
+
+    let mut progress = ProgressSender::new(tx, total_steps, settings.progress_interval);
+    let passphrase = settings.passphrase.as_ref().map(Passphrase::as_str);
+    let (mut steps, mut files_restored, mut directories_created) = (0, 0, 0);
+    let mut encrypted_skipped = 0;
+    for i in 0..archive.len() {
+        let Some(name) = archive.name_for_index(i).map(ToString::to_string) else {
+            continue;
+        };
+        if is_archive_metadata(&name)
+            || scoped_files
+                .as_ref()
+                .is_some_and(|files| !files.contains(&name))
+        {
+            continue;
+        }
+        steps += 1;
+
+        let encrypted = archive.is_encrypted(i)?;
+        let mut entry = match archive.by_index_with_passphrase(i, passphrase) {
+            Ok(entry) => entry,
+            Err(e) if encrypted => {
+                log::warn!("Skipped encrypted file `{name}`: {e}");
+                encrypted_skipped += 1;
+                progress.advance(steps, || None)?;
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+
+        let Some(rel_path) = entry_relative_path(&name) else {
+            log::warn!(
+                "{}Skipped extracting file with invalid path: `{name}`",
+                mock_prefix(mock_mode),
+            );
+            progress.advance(steps, || None)?;
+            continue;
+        };
+
+        let out_path = dest_root.join(&rel_path);
+        if is_directory_entry(&name) {
+            fs.ensure_directory(&out_path)?;
+            directories_created += 1;
+            progress.advance(steps, || Some(format!("{}/", rel_path.display())))?;
+            continue;
+        }
+
+        if let Some(parent) = out_path.parent() {
+            fs.ensure_directory(parent)?;
+        }
+
+        if !mock_mode {
+            let merge = scope == RestoreScope::MergeAddons && lua_merge::is_mergeable_file(&name);
+            write_restored_file(fs, &mut entry, &out_path, merge)?;
+        }
+        files_restored += 1;
+
+        progress.advance(steps, || Some(rel_path.display().to_string()))?;
+
+        log::info!(
+            "{}Restored file `{}`",
+            mock_prefix(mock_mode),
+            rel_path.display()
+        );
+    }
+    progress.flush()?;
+
+    if scope != RestoreScope::Config {
+        addon_manifest::warn_missing_addons(&mut archive, fs, &character.install);
+    }
+
+    let encrypted_summary = if encrypted_skipped > 0 {
+        format!(", skipped {encrypted_skipped} encrypted files")
+    } else {
+        String::new()
+    };
+    let backup_name = backup_path.file_name().map_or_else(
+        || backup_path.to_string_lossy(),
+        |name| name.to_string_lossy(),
+    );
+    tx.send(IOProgress::Summary(format!(
+        "{}Restored {} from `{backup_name}`: {files_restored} files, \
+        created {directories_created} directories{encrypted_summary}, in `{}`",
+        mock_prefix(mock_mode),
+        character_summary_name(settings, &character.character),
+        dest_root.display()
+    )))?;
+
+    Ok(())
+}
+
+/// Write a file extracted from a backup, merging its Lua tables into the current file
+/// if `merge` is set and the file exists, or overwriting the file otherwise.
+/// Falls back to overwriting the file if either file can't be parsed.
+/// # Errors
+/// Returns an error if reading the entry or the current file, or writing the file fails.
+fn write_restored_file(
+    fs: &dyn FileSystem,
+    entry: &mut impl Read,
+    out_path: &Path,
+    merge: bool,
+) -> AnyResult<()> {
+    if !merge || !fs.is_file(out_path) {
+        let mut outfile = fs.create(out_path)?;
+        std::io::copy(entry, &mut outfile)?;
+        return Ok(());
+    }
+
+    let mut backup = Vec::new();
+    entry.read_to_end(&mut backup)?;
+    let mut current = Vec::new();
+    fs.open(out_path)?.read_to_end(&mut current)?;
+
+    let merged = std::str::from_utf8(&current)
+        .ok()
+        .zip(std::str::from_utf8(&backup).ok())
+        .and_then(|(current, backup)| lua_merge::merge_saved_variables(current, backup));
+    let contents = if let Some(merged) = &merged {
+        log::info!("Merged settings into `{}`", out_path.display());
+        merged.as_bytes()
+    } else {
+        log::warn!(
+            "Could not merge `{}`, overwriting it instead",
+            out_path.display()
+        );
+        &backup
+    };
+    fs.create(out_path)?.write_all(contents)?;
+    Ok(())
+}
+
+/// Run a file operation on a backup, retrying once after clearing the backup's read-only
+/// attribute if the operation is denied, as happens with read-only or cloud-synced files.
+/// # Errors
+/// Returns an error if the operation fails, with a clear message if the backup is still locked.
+fn retry_if_read_only<T>(
+    fs: &dyn FileSystem,
+    backup: &WoWCharacterBackup,
+    mut operation: impl FnMut() -> std::io::Result<T>,
+) -> AnyResult<T> {
+    let locked_error = |e: std::io::Error| {
+        format!(
+            "Backup `{}` is locked, possibly by cloud sync: {e}",
+            backup.formatted_name()
+        )
+    };
+
+    match operation() {
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            fs.clear_read_only(&backup.path)
+                .map_err(|_| locked_error(e))?;
+            log::info!(
+                "{}Cleared read-only attribute of backup `{}`, retrying",
+                mock_prefix(fs.is_mock()),
+                backup.formatted_name()
+            );
+            operation().map_err(|e| {
+                if e.kind() == std::io::ErrorKind::PermissionDenied {
+                    locked_error(e).into()
+                } else {
+                    e.into()
+                }
+            })
+        }
+        result => Ok(result?),
+    }
+}
+
+/// Change the protection tier of a backup for the given `WoW` character, whose name is matched
+/// against the given name template.
+/// # Errors
+/// Returns an error if any file operations fail.
+pub fn change_backup_tier(
+    backup: &WoWCharacterBackup,
+    tier: BackupTier,
+    template: &BackupNameTemplate,
+    mock_mode: bool,
+) -> AnyResult<()> {
+    if backup.tier == tier {
+        log::debug!(
+            "Backup `{}` is already `{}`, no change needed.",
+            backup.formatted_name(),
+            tier.display_name()
+        );
+        return Ok(());
+    }
+
+    let og_path = crate::files::file_name_str(&backup.path);
+    let stem = backup
+        .path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let Some(name_match) = match_backup_stem(template, &stem) else {
+        return Err(format!("Backup `{og_path}` does not have a recognised backup name").into());
+    };
+    // Keep the templated part of the existing name, as the template may have changed since,
+    // and any counter telling it apart from a backup made within the same second.
+    let new_backup_name = format!(
+        "{}{}{}.{BACKUP_FILE_EXTENSION}",
+        &stem[..name_match.len],
+        backup_name_suffix(backup.is_paste, tier),
+        backup_name_counter(&stem[name_match.len..])
+    );
+
+    let fs = filesystem_for(mock_mode);
+    let new_path = backup.path.with_file_name(&new_backup_name);
+    retry_if_read_only(fs.as_ref(), backup, || fs.rename(&backup.path, &new_path))?;
+
+    log::info!(
+        "{}Renamed backup `{}` from `{}` to `{}`",
+        mock_prefix(mock_mode),
+        backup.formatted_name(),
+        og_path,
+        new_backup_name
+    );
+
+    Ok(())
 }
 
-/// Restore a backup for the given `WoW` character from the specified backup file path.
+/// Rename the backups made under a previous name of the given `WoW` character to its current name.
+///
+/// Each backup keeps its timestamp, paste state and protection tier, and is moved into the
+/// directory new backups of the character are stored in.
 /// # Errors
-/// Returns an error if any file operations fail.
-pub fn restore_backup_async(
-    character: CharWithInstallLocal,
-    backup_path: PathBuf,
+/// Returns an error if there are no backups under the previous name.
+pub fn migrate_backups_async(
+    old_name: &str,
+    new_char: CharWithInstallLocal,
+    settings: &RuntimeSettings,
     mock_mode: bool,
-) -> IOTask {
-    IOTask::new(move |tx| {
-        let mut archive = ChronoZipReader::new(&backup_path)?;
-
-        let backup_files_count = archive.file_names().count();
-
-        let dest_root = character.get_character_path();
-        ensure_directory(&dest_root, mock_mode)?;
+) -> AnyResult<IOTask> {
+    let backups = new_char.character.backups_for_name(
+        &new_char.install,
+        &settings.backup_name_template,
+        old_name,
+    );
+    if backups.is_empty() {
+        return Err(format!("No backups found under the name `{old_name}`").into());
+    }
 
-        let mut files_restored = 0;
-        for i in 0..archive.len() {
-            let mut entry = archive.by_index(i)?;
-            let Some(rel_path) = entry.enclosed_name() else {
-                log::warn!(
-                    "{}Skipped extracting file with invalid path: `{}`",
+    let old_name = old_name.to_string();
+    let name = format!("Migrating backups of {old_name}");
+    let settings = settings.clone();
+    Ok(IOTask::new(move |tx| {
+        let fs = filesystem_for(mock_mode);
+        let character = &new_char.character;
+        let dest_dir = new_char.get_backups_dir(settings.backup_layout);
+        fs.create_dir_all(&dest_dir)?;
+
+        let total = backups.len();
+        tx.send(IOProgress::Started { total: Some(total) })?;
+        let mut progress = ProgressSender::new(tx, total, settings.progress_interval);
+        let mut migrated = 0;
+        for (i, backup) in backups.iter().enumerate() {
+            let og_path = crate::files::file_name_str(&backup.path);
+            let stem = backup
+                .path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if let Some(name_match) = match_backup_stem(&settings.backup_name_template, &stem) {
+                let fields = BackupNameFields {
+                    name: &character.name,
+                    realm: &settings.realm_display_name(&character.realm),
+                    branch: &character.branch,
+                    timestamp: name_match.timestamp,
+                };
+                // Rebuild the name rather than replacing the old name within it, keeping any
+                // counter telling it apart from a backup made within the same second.
+                let new_backup_name = format!(
+                    "{}{}{}.{BACKUP_FILE_EXTENSION}",
+                    settings.backup_name_template.format(&fields),
+                    backup_name_suffix(backup.is_paste, backup.tier),
+                    backup_name_counter(&stem[name_match.len..])
+                );
+                let new_path = unique_backup_path(fs.as_ref(), &dest_dir, &new_backup_name);
+                retry_if_read_only(fs.as_ref(), backup, || fs.rename(&backup.path, &new_path))?;
+                migrated += 1;
+                log::info!(
+                    "{}Migrated backup `{og_path}` to `{}`",
                     mock_prefix(mock_mode),
-                    entry.name()
+                    new_path.display()
                 );
-                continue;
-            };
-
-            let out_path = dest_root.join(&rel_path);
-            if entry.name().ends_with('/') {
-                ensure_directory(&out_path, mock_mode)?;
-                continue;
-            }
-
-            if let Some(parent) = out_path.parent() {
-                ensure_directory(parent, mock_mode)?;
-            }
-
-            if !mock_mode {
-                let mut outfile = filesystem::File::create(&out_path)?;
-                std::io::copy(&mut entry, &mut outfile)?;
-                files_restored += 1;
+            } else {
+                log::warn!("Skipped backup `{og_path}`, as its name isn't recognised");
             }
-
-            tx.send(IOProgress::Advanced {
-                completed: files_restored,
-                total: backup_files_count,
-                label: Some(rel_path.display().to_string()),
-            })?;
-
-            log::info!(
-                "{}Restored file `{}`",
-                mock_prefix(mock_mode),
-                rel_path.display()
-            );
+            progress.advance(i.saturating_add(1), || Some(backup.formatted_name()))?;
         }
+        progress.flush()?;
 
+        tx.send(IOProgress::Summary(format!(
+            "{}Migrated {migrated} backups of `{old_name}` to {}",
+            mock_prefix(mock_mode),
+            character_summary_name(&settings, character)
+        )))?;
         Ok(())
     })
-    .name("Restoring backup")
+    .name(name)
+    .kind(IOTaskKind::Destructive))
 }
 
-/// Change the pinned status of a backup for the given `WoW` character at the specified index.
+/// Change the pinned status of a backup for the given `WoW` character.
+/// Protected backups must be explicitly downgraded before they can be unpinned.
 /// # Errors
-/// Returns an error if any file operations fail.
+/// Returns an error if any file operations fail, or the backup is protected.
 pub fn change_backup_pin_state(
     backup: &WoWCharacterBackup,
     pinned: bool,
+    template: &BackupNameTemplate,
     mock_mode: bool,
 ) -> AnyResult<()> {
-    if backup.is_pinned == pinned {
-        log::debug!(
-            "Pinned state is already `{pinned}` on backup `{}` no change needed.",
+    if backup.is_protected() {
+        return Err(format!(
+            "Backup `{}` is protected, remove its protection before changing its pin",
             backup.formatted_name()
-        );
-        return Ok(());
+        )
+        .into());
     }
+    change_backup_tier(backup, BackupTier::from_pinned(pinned), template, mock_mode)
+}
 
-    let og_path = crate::files::file_name_str(&backup.path);
-    let new_backup_name =
-        get_backup_name_from(&backup.char_name, backup.timestamp, backup.is_paste, pinned);
+/// Toggle the pinned status of a backup for the given `WoW` character.
+/// # Errors
+/// Returns an error if any file operations fail, or the backup is protected.
+pub fn toggle_backup_pin(
+    backup: &WoWCharacterBackup,
+    template: &BackupNameTemplate,
+    mock_mode: bool,
+) -> AnyResult<()> {
+    let new_pinned = !backup.is_pinned();
+    change_backup_pin_state(backup, new_pinned, template, mock_mode)
+}
 
-    if !mock_mode {
-        std::fs::rename(
-            backup.path.as_path(),
-            backup.path.with_file_name(&new_backup_name),
-        )?;
-    }
+/// Toggle the protected status of a backup for the given `WoW` character.
+/// Removing protection downgrades the backup to pinned, rather than removing all protection.
+/// # Errors
+/// Returns an error if any file operations fail.
+pub fn toggle_backup_protection(
+    backup: &WoWCharacterBackup,
+    template: &BackupNameTemplate,
+    mock_mode: bool,
+) -> AnyResult<()> {
+    let new_tier = if backup.is_protected() {
+        BackupTier::Pinned
+    } else {
+        BackupTier::Protected
+    };
+    change_backup_tier(backup, new_tier, template, mock_mode)
+}
 
+/// Get the oldest unpinned automatic backups of a character beyond the maximum allowed number.
+fn auto_backups_to_clean(
+    character: &WoWCharacter,
+    max_auto_backups: usize,
+) -> Vec<WoWCharacterBackup> {
+    let auto_backups = character.unpinned_auto_backups();
+    let backups_to_clean_count = auto_backups.len().saturating_sub(max_auto_backups);
+    auto_backups
+        .into_iter()
+        .sorted_by(|a, b| a.timestamp.cmp(&b.timestamp))
+        .take(backups_to_clean_count)
+        .collect()
+}
+
+/// Remove the oldest unpinned automatic backups beyond the maximum from every given character.
+/// Reports the space freed once finished.
+/// Returns `None` if no character has too many automatic backups.
+#[must_use]
+pub fn manage_all_character_backups<'a>(
+    characters: impl IntoIterator<Item = &'a WoWCharacter>,
+    max_auto_backups: usize,
+    mock_mode: bool,
+) -> Option<IOTask> {
+    let backups_to_clean = characters
+        .into_iter()
+        .flat_map(|character| auto_backups_to_clean(character, max_auto_backups))
+        .collect::<Vec<_>>();
+    if backups_to_clean.is_empty() {
+        return None;
+    }
     log::info!(
-        "{}Renamed backup `{}` from `{}` to `{}`",
-        mock_prefix(mock_mode),
-        backup.formatted_name(),
-        og_path,
-        new_backup_name
+        "Removing {} automatic backups beyond the maximum of {max_auto_backups} across all characters...",
+        backups_to_clean.len()
     );
 
-    Ok(())
+    Some(
+        IOTask::new(move |tx| {
+            let fs = filesystem_for(mock_mode);
+            remove_backups_internal(tx, &backups_to_clean, true, fs.as_ref())
+        })
+        .name("Cleaning automatic backups on all characters"),
+    )
 }
 
-/// Toggle the pinned status of a backup for the given `WoW` character at the specified index.
+/// Delete the given backups of any characters at once, reporting the space freed.
+/// Pinned and protected backups are skipped, as they are never removed in bulk.
+pub fn delete_backups_async(backups: Vec<WoWCharacterBackup>, mock_mode: bool) -> IOTask {
+    IOTask::new(move |tx| {
+        let fs = filesystem_for(mock_mode);
+        remove_backups_internal(tx, &backups, false, fs.as_ref())
+    })
+    .name("Deleting backups")
+}
+
+/// Remove each of the given backups from the given filesystem, skipping pinned and protected
+/// backups, and report how many were removed and the space freed.
 /// # Errors
 /// Returns an error if any file operations fail.
-pub fn toggle_backup_pin(backup: &WoWCharacterBackup, mock_mode: bool) -> AnyResult<()> {
-    let new_pinned = !backup.is_pinned;
-    change_backup_pin_state(backup, new_pinned, mock_mode)
+pub(crate) fn remove_backups_internal(
+    tx: &MPSCSender<IOProgress>,
+    backups: &[WoWCharacterBackup],
+    auto_removed: bool,
+    fs: &dyn FileSystem,
+) -> AnyResult<()> {
+    let mock_mode = fs.is_mock();
+    tx.send(IOProgress::Started {
+        total: Some(backups.len()),
+    })?;
+
+    let (mut removed_count, mut freed) = (0, 0);
+    for (i, backup) in backups.iter().enumerate() {
+        if backup.is_pinned() {
+            log::warn!(
+                "{}Skipped removing {} backup `{}`",
+                mock_prefix(mock_mode),
+                backup.tier.display_name().to_lowercase(),
+                backup.formatted_name()
+            );
+        } else {
+            let size = fs.file_size(&backup.path).unwrap_or_default();
+            if delete_backup_file_with_fs(backup, auto_removed, fs)? {
+                removed_count += 1;
+                freed += size;
+            }
+        }
+
+        tx.send(IOProgress::Advanced {
+            completed: i + 1,
+            total: backups.len(),
+            label: Some(backup.formatted_name()),
+        })?;
+    }
+
+    tx.send(IOProgress::Summary(format!(
+        "{}Removed {removed_count} backups, freed {}",
+        mock_prefix(mock_mode),
+        format_size(freed)
+    )))?;
+    Ok(())
 }
 
 /// Manage automatic backups for the given `WoW` character, removing oldest unpinned backups
@@ -474,13 +1630,12 @@ pub fn manage_character_backups(
         return None;
     }
 
+    let backups_to_clean = auto_backups_to_clean(&character.0.character, max_auto_backups);
     Some(
         IOTask::new(move |tx| {
-            let backups_to_clean = auto_backups
-                .iter()
-                .sorted_by(|a, b| a.timestamp.cmp(&b.timestamp))
-                .take(backups_to_clean_count)
-                .collect::<Vec<_>>();
+            tx.send(IOProgress::Started {
+                total: Some(backups_to_clean_count),
+            })?;
 
             let mut removed_count = 0;
             for backup in &backups_to_clean {
@@ -510,9 +1665,22 @@ pub fn delete_backup_file(
     auto_removed: bool,
     mock_mode: bool,
 ) -> AnyResult<bool> {
-    let bad_removal = auto_removed && backup.is_pinned;
-    if !mock_mode && !bad_removal {
-        std::fs::remove_file(&backup.path)?;
+    delete_backup_file_with_fs(backup, auto_removed, filesystem_for(mock_mode).as_ref())
+}
+
+/// Delete the given backup file from the given filesystem, refusing to auto-remove pinned backups.
+/// Returns `false` if the removal was skipped.
+/// # Errors
+/// Returns an error if any file operations fail.
+pub(crate) fn delete_backup_file_with_fs(
+    backup: &WoWCharacterBackup,
+    auto_removed: bool,
+    fs: &dyn FileSystem,
+) -> AnyResult<bool> {
+    let mock_mode = fs.is_mock();
+    let bad_removal = auto_removed && backup.is_pinned();
+    if !bad_removal {
+        retry_if_read_only(fs, backup, || fs.remove_file(&backup.path))?;
     }
     if bad_removal {
         log::warn!(
@@ -533,6 +1701,7 @@ pub fn delete_backup_file(
 
 /// Options for what to back up in a complete `WoW` install backup.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct InstallBackupOptions {
     /// Whether to backup the `WTF` folder.
     pub include_wtf: bool,
@@ -540,6 +1709,8 @@ pub struct InstallBackupOptions {
     pub include_interface: bool,
     /// Whether to backup the `ChronoBind` character backups.
     pub include_character_backups: bool,
+    /// Whether to include the `ChronoBind` settings.
+    pub include_settings: bool,
 }
 
 impl InstallBackupOptions {
@@ -551,20 +1722,22 @@ impl InstallBackupOptions {
             include_wtf: true,
             include_interface: true,
             include_character_backups: true,
+            include_settings: true,
         }
     }
 
-    /// Returns an `InstallBackupOptions` with only character backups enabled.
+    /// Returns an `InstallBackupOptions` with only character backups and settings enabled.
     #[must_use]
     pub const fn character_backups() -> Self {
         Self {
             include_wtf: false,
             include_interface: false,
             include_character_backups: true,
+            include_settings: true,
         }
     }
 
-    /// Returns `true` if all backup options are disabled.
+    /// Returns `true` if all backup options for install folders are disabled.
     #[must_use]
     pub const fn are_all_disabled(&self) -> bool {
         !self.include_character_backups && !self.include_interface && !self.include_wtf
@@ -597,13 +1770,65 @@ impl InstallBackupOptions {
     }
 }
 
-/// Export a folder to the given ZIP writer as part of a backup export.
+/// Returns `true` if the file was modified after the given unix time, in seconds.
+/// Files whose modification time can't be read count as modified.
+fn modified_after(path: &Path, since: u64) -> bool {
+    let since = std::time::UNIX_EPOCH + std::time::Duration::from_secs(since);
+    filesystem::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map_or(true, |modified| modified > since)
+}
+
+/// Export a file to the given ZIP writer as part of a backup export.
+/// If `sanitize_patterns` are given, `SavedVariables` and `.wtf` config files are sanitised of
+/// personal data matching them, and left out if they can't be sanitised.
+/// # Errors
+/// Returns an error if any file operations fail.
+fn export_file_to_zip(
+    zip: &mut ChronoZipWriter<'static>,
+    entry_name: &Path,
+    file_path: &Path,
+    sanitize_patterns: Option<&[SanitizePattern]>,
+) -> AnyResult<()> {
+    let Some(patterns) = sanitize_patterns else {
+        return zip.copy_file(entry_name.to_string_lossy(), file_path);
+    };
+    let sanitized = if lua_sanitize::is_sanitizable_file(file_path) {
+        std::fs::read_to_string(file_path)
+            .ok()
+            .and_then(|contents| lua_sanitize::sanitize_lua(&contents, patterns))
+    } else if lua_sanitize::is_config_file(file_path) {
+        std::fs::read_to_string(file_path)
+            .ok()
+            .map(|contents| lua_sanitize::sanitize_config(&contents, patterns))
+    } else {
+        return zip.copy_file(entry_name.to_string_lossy(), file_path);
+    };
+    let Some(sanitized) = sanitized else {
+        log::warn!(
+            "Leaving `{}` out of the export, as it couldn't be read to remove personal data",
+            entry_name.display()
+        );
+        return Ok(());
+    };
+    zip.write_file(entry_name.to_string_lossy(), sanitized.as_bytes())
+}
+
+/// Export a folder to the given ZIP writer as part of a backup export, leaving out folders with
+/// any of the `excluded_dir_names` at any depth.
+/// If `modified_since` is given, only files modified after that unix time are exported.
+/// If `sanitize_patterns` are given, `SavedVariables` and `.wtf` config files are sanitised of
+/// personal data, and account folders are renamed so the export doesn't carry account names.
 /// # Errors
 /// Returns an error if any file operations fail.
 fn export_folder_to_zip(
     zip_writer: Arc<Mutex<ChronoZipWriter<'static>>>,
     folder_path: PathBuf,
     zip_base_path: PathBuf,
+    excluded_dir_names: &'static [&'static str],
+    modified_since: Option<u64>,
+    sanitize_patterns: Option<Vec<SanitizePattern>>,
+    progress_interval: Duration,
 ) -> IOTask {
     IOTask::new(move |tx| {
         if !folder_path.exists() {
@@ -614,20 +1839,45 @@ fn export_folder_to_zip(
             return Ok(());
         }
 
-        let dir_iter = walk_dir_recursive::<&str>(&folder_path, &[])?;
+        let mut dir_iter = walk_dir_iter::<&str>(&folder_path, &[])?
+            .excluding_names(excluded_dir_names)
+            .collect::<Result<Vec<_>, _>>()?;
+        if let Some(since) = modified_since {
+            dir_iter.retain(|file| modified_after(file, since));
+            log::debug!(
+                "{} files changed in `{}` since the last export",
+                dir_iter.len(),
+                folder_path.display()
+            );
+        }
         let total = dir_iter.len();
+        tx.send(IOProgress::Started { total: Some(total) })?;
+        let account_mask = sanitize_patterns.is_some().then(|| {
+            AccountMask::new(
+                dir_iter
+                    .iter()
+                    .filter_map(|file| file.strip_prefix(&folder_path).ok()),
+            )
+        });
 
         if let Ok(mut zip) = zip_writer.lock() {
+            let mut progress = ProgressSender::new(tx, total, progress_interval);
             for (backups_completed, current_backup_file) in dir_iter.iter().enumerate() {
-                let relative_path =
-                    zip_base_path.join(current_backup_file.strip_prefix(&folder_path)?);
-                zip.copy_file(relative_path.to_string_lossy(), current_backup_file)?;
+                let relative_path = current_backup_file.strip_prefix(&folder_path)?;
+                let relative_path = zip_base_path.join(account_mask.as_ref().map_or_else(
+                    || relative_path.to_path_buf(),
+                    |mask| mask.mask(relative_path),
+                ));
+                export_file_to_zip(
+                    &mut zip,
+                    &relative_path,
+                    current_backup_file,
+                    sanitize_patterns.as_deref(),
+                )?;
 
                 log::info!("Exported backup `{}`", relative_path.display());
-                tx.send(IOProgress::Advanced {
-                    completed: backups_completed.saturating_add(1),
-                    total,
-                    label: Some(crate::files::file_stem_str(relative_path)),
+                progress.advance(backups_completed.saturating_add(1), || {
+                    Some(crate::files::file_stem_str(relative_path))
                 })?;
             }
 
@@ -638,12 +1888,77 @@ fn export_folder_to_zip(
     })
 }
 
+/// Write the `ChronoBind` settings to the root of the given ZIP writer as part of a backup export.
+fn export_settings_to_zip(
+    zip_writer: Arc<Mutex<ChronoZipWriter<'static>>>,
+    settings: String,
+) -> IOTask {
+    IOTask::new(move |tx| {
+        tx.send(IOProgress::Started { total: Some(1) })?;
+        if let Ok(mut zip) = zip_writer.lock() {
+            zip.write_file(EXPORT_SETTINGS_FILE_NAME, settings.as_bytes())?;
+            log::info!("Exported ChronoBind settings");
+        }
+        tx.send(IOProgress::Advanced {
+            completed: 1,
+            total: 1,
+            label: Some(EXPORT_SETTINGS_FILE_NAME.to_string()),
+        })?;
+        Ok(())
+    })
+    .name("Backing up ChronoBind settings")
+}
+
+/// Write the manifest of an export of the files changed since the given unix time,
+/// as part of a backup export.
+fn export_manifest_to_zip(
+    zip_writer: Arc<Mutex<ChronoZipWriter<'static>>>,
+    modified_since: u64,
+) -> IOTask {
+    IOTask::new(move |tx| {
+        tx.send(IOProgress::Started { total: Some(1) })?;
+        if let Ok(mut zip) = zip_writer.lock() {
+            ExportManifest::changed_since(modified_since).write_to(&mut zip)?;
+        }
+        tx.send(IOProgress::Advanced {
+            completed: 1,
+            total: 1,
+            label: None,
+        })?;
+        Ok(())
+    })
+    .name("Writing export manifest")
+}
+
+/// Add a task exporting the `ChronoBind` settings, if enabled in the backup options.
+fn add_settings_export(
+    task_builder: &mut TaskBuilder<IOTask>,
+    zip_writer: &Arc<Mutex<ChronoZipWriter<'static>>>,
+    backup_options: InstallBackupOptions,
+    settings: &ChronoBindAppConfig,
+) {
+    if !backup_options.include_settings {
+        return;
+    }
+    match settings.to_export_string() {
+        Ok(settings) => {
+            task_builder.add_task(export_settings_to_zip(zip_writer.clone(), settings));
+        }
+        Err(e) => log::error!("Failed to serialise settings for export, skipping them: {e}"),
+    }
+}
+
 /// Create a backup of the given `WoW` install, backing up the selected contents of the install.
 #[must_use]
+/// `SavedVariables` and config files within the `WTF` folder are sanitised of personal data
+/// matching `sanitize_patterns`, if given, along with its account folder names, and the
+/// configured backup folders within it are left out.
 fn export_install_internal(
     zip_writer: &Arc<Mutex<ChronoZipWriter<'static>>>,
     install: &WoWInstall,
     backup_options: InstallBackupOptions,
+    settings: &ChronoBindAppConfig,
+    modified_since: Option<u64>,
 ) -> Option<IOTask> {
     let branch_dir = PathBuf::from(install.get_product_dir_name());
 
@@ -655,6 +1970,12 @@ fn export_install_internal(
                 zip_writer.clone(),
                 wtf_path,
                 InstallBackupOptions::wtf_relative_dir(&branch_dir),
+                settings.export_excluded_dir_names(),
+                modified_since,
+                settings
+                    .export_sanitize_patterns()
+                    .map(<[SanitizePattern]>::to_vec),
+                settings.progress_interval(),
             )
             .name(format!(
                 "Backing up WTF for {}",
@@ -671,6 +1992,10 @@ fn export_install_internal(
                 zip_writer.clone(),
                 interface_path,
                 InstallBackupOptions::interface_relative_dir(&branch_dir),
+                &[],
+                modified_since,
+                None,
+                settings.progress_interval(),
             )
             .name(format!(
                 "Backing up Interface (Addons) for {}",
@@ -687,6 +2012,10 @@ fn export_install_internal(
                 zip_writer.clone(),
                 backups_path,
                 InstallBackupOptions::char_backups_relative_dir(&branch_dir),
+                &[],
+                modified_since,
+                None,
+                settings.progress_interval(),
             )
             .name(format!(
                 "Backing up character backups for {}",
@@ -700,11 +2029,16 @@ fn export_install_internal(
 }
 
 /// Create a backup of the given `WoW` install, backing up the selected contents of the install.
+///
+/// If `modified_since` is given, only files modified after that unix time are exported,
+/// and the time is recorded in the export's manifest.
 #[must_use]
 pub fn export_install(
     install: &WoWInstall,
     backup_options: InstallBackupOptions,
+    settings: &ChronoBindAppConfig,
     final_zip_path: &Path,
+    modified_since: Option<u64>,
     mock_mode: bool,
 ) -> Option<IOTask> {
     if !crate::files::cmp_extension(final_zip_path, BACKUP_FILE_EXTENSION) {
@@ -725,7 +2059,21 @@ pub fn export_install(
 
     let zip_writer = ChronoZipWriter::new_arc(final_zip_path, mock_mode)?;
 
-    export_install_internal(&zip_writer, install, backup_options)
+    let mut task_builder = TaskBuilder::<IOTask>::new();
+    if let Some(install_task) = export_install_internal(
+        &zip_writer,
+        install,
+        backup_options,
+        settings,
+        modified_since,
+    ) {
+        task_builder.add_task(install_task);
+    }
+    if let Some(since) = modified_since {
+        task_builder.add_task(export_manifest_to_zip(zip_writer.clone(), since));
+    }
+    add_settings_export(&mut task_builder, &zip_writer, backup_options, settings);
+    task_builder.build()
 }
 
 /// Create a backup of the given `WoW` install, backing up the selected contents of the install.
@@ -733,6 +2081,7 @@ pub fn export_install(
 pub fn export_all_installs(
     installs: &WoWInstalls,
     backup_options: InstallBackupOptions,
+    settings: &ChronoBindAppConfig,
     final_zip_path: &Path,
     mock_mode: bool,
 ) -> Option<IOTask> {
@@ -755,7 +2104,9 @@ pub fn export_all_installs(
 
     let mut task_builder = TaskBuilder::<IOTask>::new();
     for install in installs.iter() {
-        if let Some(install_task) = export_install_internal(&zip_writer, install, backup_options) {
+        if let Some(install_task) =
+            export_install_internal(&zip_writer, install, backup_options, settings, None)
+        {
             task_builder.add_task(
                 install_task
                     .name(format!("Backing up {}", install.display_branch_name()))
@@ -763,10 +2114,105 @@ pub fn export_all_installs(
             );
         }
     }
+    add_settings_export(&mut task_builder, &zip_writer, backup_options, settings);
 
     task_builder.build()
 }
 
+/// Number of files and total size of the contents of an export.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExportEstimate {
+    /// Number of files the export would contain.
+    pub files: usize,
+    /// Total uncompressed size of the files, in bytes.
+    pub size: u64,
+}
+
+/// Estimate the contents of an export of the given installs, by walking the folders it includes.
+/// Folders which can't be read, or are left out of exports, are left out of the estimate.
+#[must_use]
+pub fn estimate_export<'a>(
+    installs: impl IntoIterator<Item = &'a WoWInstall>,
+    backup_options: InstallBackupOptions,
+    settings: &ChronoBindAppConfig,
+) -> ExportEstimate {
+    let mut estimate = ExportEstimate::default();
+    for install in installs {
+        let folders = [
+            (
+                backup_options.include_wtf,
+                install.get_wtf_path(),
+                settings.export_excluded_dir_names(),
+            ),
+            (
+                backup_options.include_interface,
+                install.get_interface_path(),
+                &[],
+            ),
+            (
+                backup_options.include_character_backups,
+                install.get_character_backups_dir(),
+                &[],
+            ),
+        ];
+        for (_, folder, excluded_dir_names) in
+            folders.into_iter().filter(|(included, ..)| *included)
+        {
+            let Ok(files) = walk_dir_iter::<&str>(&folder, &[]).and_then(|walk| {
+                Ok(walk
+                    .excluding_names(excluded_dir_names)
+                    .collect::<Result<Vec<_>, _>>()?)
+            }) else {
+                continue;
+            };
+            estimate.files += files.len();
+            estimate.size += files
+                .iter()
+                .filter_map(|file| filesystem::metadata(file).ok())
+                .map(|metadata| metadata.len())
+                .sum::<u64>();
+        }
+    }
+    estimate
+}
+
+/// Refuse to start an export that may not fit beside the export path, as with backups.
+/// # Errors
+/// Returns an error if the free space is below the estimated size plus the configured margin.
+pub fn ensure_space_for_export(
+    final_zip_path: &Path,
+    estimate: ExportEstimate,
+    settings: &RuntimeSettings,
+    mock_mode: bool,
+) -> AnyResult<()> {
+    let export_dir = final_zip_path.parent().unwrap_or(final_zip_path);
+    ensure_space_for_backup(
+        filesystem_for(mock_mode).as_ref(),
+        export_dir,
+        estimate.size,
+        settings.backup_space_margin,
+    )
+}
+
+/// Read the `ChronoBind` settings embedded in an install export, `None` if it has none.
+/// # Errors
+/// Returns an error if the export cannot be read.
+pub fn read_exported_settings<P: AsRef<Path>>(export_path: P) -> AnyResult<Option<String>> {
+    let mut archive = ChronoZipReader::new(export_path.as_ref())?;
+    if !archive
+        .file_names()
+        .any(|name| name == EXPORT_SETTINGS_FILE_NAME)
+    {
+        return Ok(None);
+    }
+    let mut settings = String::new();
+    std::io::Read::read_to_string(
+        &mut archive.by_name(EXPORT_SETTINGS_FILE_NAME)?,
+        &mut settings,
+    )?;
+    Ok(Some(settings))
+}
+
 /// Generate an export filename based on the base name and timestamp.
 #[inline]
 #[must_use]
@@ -784,6 +2230,7 @@ fn copy_zip_contents_in_path_to(
     zip_archive: Arc<Mutex<ChronoZipReader<'static>>>,
     source_path_in_zip: PathBuf,
     dest_path: PathBuf,
+    progress_interval: Duration,
     mock_mode: bool,
 ) -> IOTask {
     IOTask::new(move |tx| {
@@ -796,11 +2243,13 @@ fn copy_zip_contents_in_path_to(
 
             let files_to_copy = zip.files_in_directory(source_path_in_zip.clone());
             let total = files_to_copy.len();
+            tx.send(IOProgress::Started { total: Some(total) })?;
 
+            let mut progress = ProgressSender::new(tx, total, progress_interval);
             for (files_copied, file_name) in files_to_copy.iter().enumerate() {
                 let mut entry = zip.by_name(file_name)?;
 
-                let Some(rel_path) = entry.enclosed_name() else {
+                let Some(rel_path) = entry_relative_path(file_name) else {
                     log::warn!(
                         "{}Skipped extracting file with invalid path: `{}`",
                         mock_prefix(mock_mode),
@@ -813,7 +2262,7 @@ fn copy_zip_contents_in_path_to(
                     .unwrap_or(&rel_path);
 
                 let out_path = dest_path.join(rel_path);
-                if entry.name().ends_with('/') {
+                if is_directory_entry(file_name) {
                     ensure_directory(&out_path, mock_mode)?;
                     continue;
                 }
@@ -833,12 +2282,11 @@ fn copy_zip_contents_in_path_to(
                     rel_path.display(),
                     out_path.display()
                 );
-                tx.send(IOProgress::Advanced {
-                    completed: files_copied.saturating_add(1),
-                    total,
-                    label: Some(rel_path.display().to_string()),
+                progress.advance(files_copied.saturating_add(1), || {
+                    Some(rel_path.display().to_string())
                 })?;
             }
+            progress.flush()?;
         }
 
         Ok(())
@@ -850,6 +2298,7 @@ fn import_chronobind_backups_for_install(
     zip_archive: &Arc<Mutex<ChronoZipReader<'static>>>,
     install: &WoWInstall,
     import_options: InstallBackupOptions,
+    progress_interval: Duration,
     mock_mode: bool,
 ) -> Option<IOTask> {
     let product_dir_name = install.get_product_dir_name();
@@ -864,6 +2313,7 @@ fn import_chronobind_backups_for_install(
                 zip_archive.clone(),
                 source_path_in_zip,
                 dest_path,
+                progress_interval,
                 mock_mode,
             )
             .name(format!(
@@ -883,6 +2333,7 @@ fn import_chronobind_backups_for_install(
                 zip_archive.clone(),
                 source_path_in_zip,
                 dest_path,
+                progress_interval,
                 mock_mode,
             )
             .name(format!(
@@ -902,6 +2353,7 @@ fn import_chronobind_backups_for_install(
                 zip_archive.clone(),
                 source_path_in_zip,
                 dest_path,
+                progress_interval,
                 mock_mode,
             )
             .name(format!(
@@ -921,20 +2373,38 @@ pub fn import_chronobind_backup<P: AsRef<Path>>(
     import_path: P,
     wow_installs: &WoWInstalls,
     import_options: InstallBackupOptions,
+    progress_interval: Duration,
     mock_mode: bool,
 ) -> Option<IOTask> {
     let archive = ChronoZipReader::new_arc(import_path.as_ref())?;
     let installs = wow_installs.clone();
 
-    let dirs_in_root = archive.lock().ok()?.directories_in_root();
+    let (dirs_in_root, manifest) = {
+        let mut archive = archive.lock().ok()?;
+        (
+            archive.directories_in_root(),
+            ExportManifest::read_from(&mut archive),
+        )
+    };
+    if let Some(since) = manifest.and_then(|manifest| manifest.modified_since) {
+        log::info!(
+            "`{}` only holds files changed since {}",
+            import_path.as_ref().display(),
+            format_unix_time(since)
+        );
+    }
 
     let mut task_builder = TaskBuilder::<IOTask>::new();
     for dir in dirs_in_root {
         let branch_ident = dir_name_to_branch_ident(&dir);
         if let Some(install) = installs.find_branch(&branch_ident) {
-            if let Some(import_task) =
-                import_chronobind_backups_for_install(&archive, install, import_options, mock_mode)
-            {
+            if let Some(import_task) = import_chronobind_backups_for_install(
+                &archive,
+                install,
+                import_options,
+                progress_interval,
+                mock_mode,
+            ) {
                 task_builder.add_task(import_task.show_task_label(true));
             }
         } else {
@@ -974,3 +2444,493 @@ pub fn get_branches_in_export<P: AsRef<Path>>(export_path: P) -> AnyResult<Vec<S
 
     Ok(branch_idents)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::mpsc, time::Instant};
+
+    use super::*;
+    use crate::{
+        backend::{task::BackendTask, vfs::MemoryFileSystem},
+        wow::BACKUPS_DIR_NAME,
+    };
+
+    fn alpha() -> CharWithInstallLocal {
+        CharWithInstallLocal {
+            character: WoWCharacter {
+                account: "ACC".to_string(),
+                realm: "Draenor".to_string(),
+                name: "Alpha".to_string(),
+                ..WoWCharacter::default()
+            },
+            install: WoWInstall {
+                branch_ident: "retail".to_string(),
+                install_path: "/wow".to_string(),
+                ..WoWInstall::default()
+            },
+        }
+    }
+
+    /// An in-memory filesystem holding the character's directory with the given files.
+    fn filesystem_with(
+        fs: MemoryFileSystem,
+        character: &CharWithInstallLocal,
+        files: &[(&str, &str)],
+    ) -> MemoryFileSystem {
+        let char_path = character.get_character_path();
+        for (path, contents) in files {
+            fs.insert_file(char_path.join(path), *contents);
+        }
+        fs
+    }
+
+    fn backup(fs: &MemoryFileSystem, character: &CharWithInstallLocal) -> AnyResult<()> {
+        backup_with(fs, character, &RuntimeSettings::default())
+    }
+
+    fn backup_with(
+        fs: &MemoryFileSystem,
+        character: &CharWithInstallLocal,
+        settings: &RuntimeSettings,
+    ) -> AnyResult<()> {
+        let (tx, _rx) = mpsc::channel();
+        let fs: FileSystemPtr = Arc::new(fs.clone());
+        backup_character_internal(&tx, character, BackupOptions::default(), settings, &fs)
+    }
+
+    /// Settings encrypting `SavedVariables/Secrets.lua` within backups, with the given passphrase.
+    fn encrypting_settings(passphrase: Option<&str>) -> RuntimeSettings {
+        RuntimeSettings {
+            encrypted_files: vec!["SavedVariables/Secrets.lua".to_string()],
+            ..RuntimeSettings::default()
+        }
+        .with_passphrase(passphrase.and_then(|passphrase| Passphrase::new(passphrase.to_string())))
+    }
+
+    fn archives(fs: &MemoryFileSystem) -> Vec<PathBuf> {
+        fs.file_paths()
+            .into_iter()
+            .filter(|path| path.extension().is_some_and(|ext| ext == "zip"))
+            .collect()
+    }
+
+    #[test]
+    fn backup_and_restore_round_trip_within_memory() {
+        let character = alpha();
+        let fs = filesystem_with(
+            MemoryFileSystem::new(),
+            &character,
+            &[
+                ("config-cache.wtf", "SET cameraDistance \"15\""),
+                ("SavedVariables/WeakAuras.lua", "WeakAurasSaved = {}"),
+            ],
+        );
+        backup(&fs, &character).expect("Backup should succeed");
+
+        let [archive_path] = &archives(&fs)[..] else {
+            panic!("Exactly one archive should be written");
+        };
+        let mut archive = ChronoZipReader::new_with_fs(archive_path, &fs).expect("Archive opens");
+        let entries = archive
+            .file_names()
+            .filter(|name| !is_archive_metadata(name))
+            .map(str::to_string)
+            .sorted()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entries,
+            ["SavedVariables/WeakAuras.lua", "config-cache.wtf"]
+        );
+
+        let char_path = character.get_character_path();
+        fs.insert_file(
+            char_path.join("config-cache.wtf"),
+            "SET cameraDistance \"50\"",
+        );
+        fs.remove_file(&char_path.join("SavedVariables/WeakAuras.lua"))
+            .expect("File exists");
+        let (tx, _rx) = mpsc::channel();
+        restore_backup_internal(
+            &tx,
+            &character,
+            archive_path,
+            RestoreScope::All,
+            &RuntimeSettings::default(),
+            &fs,
+        )
+        .expect("Restore should succeed");
+
+        assert_eq!(
+            fs.read_file(&char_path.join("config-cache.wtf")).as_deref(),
+            Some(b"SET cameraDistance \"15\"".as_slice())
+        );
+        assert_eq!(
+            fs.read_file(&char_path.join("SavedVariables/WeakAuras.lua"))
+                .as_deref(),
+            Some(b"WeakAurasSaved = {}".as_slice())
+        );
+    }
+
+    #[test]
+    fn backup_is_refused_without_space_for_the_margin() {
+        let character = alpha();
+        let fs = filesystem_with(
+            MemoryFileSystem::new().with_available_space(1024),
+            &character,
+            &[("config-cache.wtf", "SET cameraDistance \"15\"")],
+        );
+        let error = backup(&fs, &character).expect_err("Backup should be refused");
+        assert!(
+            error.to_string().starts_with("Not enough disk space")
+                && error.to_string().contains("MiB margin"),
+            "Unexpected error: {error}"
+        );
+        assert!(archives(&fs).is_empty());
+    }
+
+    #[test]
+    fn failed_backup_leaves_no_partial_archive() {
+        let character = alpha();
+        let fs = filesystem_with(
+            MemoryFileSystem::new(),
+            &character,
+            &[
+                ("bindings-cache.wtf", "bind W MOVEFORWARD"),
+                ("config-cache.wtf", "SET cameraDistance \"15\""),
+                ("macros-cache.txt", "MACRO 1 \"Hello\""),
+            ],
+        );
+        fs.mark_unreadable(character.get_character_path().join("config-cache.wtf"));
+
+        backup(&fs, &character).expect_err("Backup should fail on the unreadable file");
+        assert!(archives(&fs).is_empty());
+    }
+
+    #[test]
+    fn withheld_files_fail_only_safety_backups() {
+        let withheld = [PathBuf::from("SavedVariables/Secrets.lua")];
+        assert!(check_withheld_files(&withheld, false).is_ok());
+        assert!(check_withheld_files(&withheld, true).is_err());
+        assert!(check_withheld_files(&[], true).is_ok());
+    }
+
+    #[test]
+    fn encrypted_files_round_trip_with_the_passphrase() {
+        let character = alpha();
+        let fs = filesystem_with(
+            MemoryFileSystem::new(),
+            &character,
+            &[
+                ("config-cache.wtf", "SET cameraDistance \"15\""),
+                ("SavedVariables/Secrets.lua", "Secrets = { pin = 1234 }"),
+            ],
+        );
+        let settings = encrypting_settings(Some("correct horse"));
+        backup_with(&fs, &character, &settings).expect("Backup should succeed");
+
+        let [archive_path] = &archives(&fs)[..] else {
+            panic!("Exactly one archive should be written");
+        };
+        let mut archive = ChronoZipReader::new_with_fs(archive_path, &fs).expect("Archive opens");
+        let index_of = |archive: &ChronoZipReader<'_>, name: &str| {
+            (0..archive.len())
+                .find(|&i| archive.name_for_index(i) == Some(name))
+                .expect("Entry should be in the archive")
+        };
+        let secrets = index_of(&archive, "SavedVariables/Secrets.lua");
+        let config = index_of(&archive, "config-cache.wtf");
+        assert!(
+            archive
+                .is_encrypted(secrets)
+                .expect("Entry should be readable")
+        );
+        assert!(
+            !archive
+                .is_encrypted(config)
+                .expect("Entry should be readable")
+        );
+        assert!(archive.by_index_with_passphrase(secrets, None).is_err());
+        assert!(archive.by_index_decrypt(secrets, "wrong horse").is_err());
+        let mut contents = String::new();
+        std::io::Read::read_to_string(
+            &mut archive
+                .by_index_decrypt(secrets, "correct horse")
+                .expect("Entry should decrypt with the passphrase"),
+            &mut contents,
+        )
+        .expect("Decrypted entry should be readable");
+        assert_eq!(contents, "Secrets = { pin = 1234 }");
+
+        let secrets_path = character
+            .get_character_path()
+            .join("SavedVariables/Secrets.lua");
+        fs.remove_file(&secrets_path).expect("File exists");
+        let (tx, _rx) = mpsc::channel();
+        restore_backup_internal(
+            &tx,
+            &character,
+            archive_path,
+            RestoreScope::All,
+            &settings,
+            &fs,
+        )
+        .expect("Restore should succeed");
+        assert_eq!(
+            fs.read_file(&secrets_path).as_deref(),
+            Some(b"Secrets = { pin = 1234 }".as_slice())
+        );
+    }
+
+    #[test]
+    fn restore_without_the_passphrase_skips_only_encrypted_files() {
+        let character = alpha();
+        let fs = filesystem_with(
+            MemoryFileSystem::new(),
+            &character,
+            &[
+                ("config-cache.wtf", "SET cameraDistance \"15\""),
+                ("SavedVariables/Secrets.lua", "Secrets = { pin = 1234 }"),
+            ],
+        );
+        backup_with(&fs, &character, &encrypting_settings(Some("correct horse")))
+            .expect("Backup should succeed");
+        let [archive_path] = &archives(&fs)[..] else {
+            panic!("Exactly one archive should be written");
+        };
+
+        let char_path = character.get_character_path();
+        fs.insert_file(
+            char_path.join("config-cache.wtf"),
+            "SET cameraDistance \"50\"",
+        );
+        fs.insert_file(
+            char_path.join("SavedVariables/Secrets.lua"),
+            "Secrets = { pin = 0 }",
+        );
+        let (tx, rx) = mpsc::channel();
+        restore_backup_internal(
+            &tx,
+            &character,
+            archive_path,
+            RestoreScope::All,
+            &encrypting_settings(None),
+            &fs,
+        )
+        .expect("Restore should succeed without the passphrase");
+
+        assert_eq!(
+            fs.read_file(&char_path.join("config-cache.wtf")).as_deref(),
+            Some(b"SET cameraDistance \"15\"".as_slice())
+        );
+        assert_eq!(
+            fs.read_file(&char_path.join("SavedVariables/Secrets.lua"))
+                .as_deref(),
+            Some(b"Secrets = { pin = 0 }".as_slice())
+        );
+        let summary = rx
+            .try_iter()
+            .find_map(|progress| match progress {
+                IOProgress::Summary(summary) => Some(summary),
+                _ => None,
+            })
+            .expect("Restore should report a summary");
+        assert!(
+            summary.contains("1 files") && summary.contains("skipped 1 encrypted files"),
+            "Unexpected summary: {summary}"
+        );
+    }
+
+    #[test]
+    fn denied_backup_operations_are_retried_once_after_clearing_read_only() {
+        let fs = MemoryFileSystem::new();
+        let backup = WoWCharacterBackup {
+            path: PathBuf::from("/backups/Alpha.zip"),
+            ..WoWCharacterBackup::default()
+        };
+        fs.insert_file(&backup.path, "zip");
+        let denied = || std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+
+        let mut attempts = 0;
+        let result = retry_if_read_only(&fs, &backup, || {
+            attempts += 1;
+            if attempts == 1 { Err(denied()) } else { Ok(()) }
+        });
+        assert!(result.is_ok());
+        assert_eq!(attempts, 2);
+
+        let error = retry_if_read_only(&fs, &backup, || Err::<(), _>(denied()))
+            .expect_err("A backup still denied should fail");
+        assert!(
+            error
+                .to_string()
+                .contains("is locked, possibly by cloud sync")
+        );
+    }
+
+    #[test]
+    fn backup_of_a_character_without_files_is_skipped() {
+        let character = alpha();
+        let fs = MemoryFileSystem::new();
+        fs.create_dir_all(&character.get_character_path())
+            .expect("Directory should be created");
+
+        backup(&fs, &character).expect("Empty backup should be skipped, not fail");
+        assert!(archives(&fs).is_empty());
+    }
+
+    #[test]
+    fn archives_with_backslash_entries_restore_into_nested_directories() {
+        let character = alpha();
+        let fs = MemoryFileSystem::new();
+        let archive_path = PathBuf::from("/exports/windows.zip");
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (name, contents) in [
+            (r"SavedVariables\WeakAuras.lua", "WeakAurasSaved = {}"),
+            ("config-cache.wtf", "SET cameraDistance \"15\""),
+        ] {
+            zip.start_file(name, zip::write::SimpleFileOptions::default())
+                .expect("Entry should start");
+            zip.write_all(contents.as_bytes())
+                .expect("Entry should be written");
+        }
+        let bytes = zip.finish().expect("Archive should finish").into_inner();
+        fs.insert_file(&archive_path, bytes);
+
+        let (tx, _rx) = mpsc::channel();
+        restore_backup_internal(
+            &tx,
+            &character,
+            &archive_path,
+            RestoreScope::All,
+            &RuntimeSettings::default(),
+            &fs,
+        )
+        .expect("Restore should succeed");
+
+        let char_path = character.get_character_path();
+        assert_eq!(
+            fs.read_file(&char_path.join("SavedVariables").join("WeakAuras.lua"))
+                .as_deref(),
+            Some(b"WeakAurasSaved = {}".as_slice())
+        );
+        assert!(fs.is_file(&char_path.join("config-cache.wtf")));
+    }
+
+    /// Export a `WTF` folder holding the given files and contents to an archive under `root`.
+    fn export_wtf_folder(
+        root: &Path,
+        files: &[(String, &str)],
+        sanitize_patterns: Option<Vec<SanitizePattern>>,
+    ) -> ChronoZipReader<'static> {
+        let wtf_path = root.join("WTF");
+        for (file, contents) in files {
+            let path = wtf_path.join(file);
+            std::fs::create_dir_all(path.parent().expect("File should have a parent"))
+                .expect("Folder should be created");
+            std::fs::write(&path, contents).expect("File should be written");
+        }
+
+        let archive_path = root.join("export.zip");
+        let zip_writer = Arc::new(Mutex::new(
+            ChronoZipWriter::new(&archive_path, false).expect("Export archive should be created"),
+        ));
+        let mut task = export_folder_to_zip(
+            zip_writer.clone(),
+            wtf_path,
+            PathBuf::from("_retail_/WTF"),
+            ChronoBindAppConfig::default().export_excluded_dir_names(),
+            None,
+            sanitize_patterns,
+            Duration::ZERO,
+        );
+        assert!(task.run());
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !task.finished() && Instant::now() < deadline {
+            task.poll();
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(task.error(), None);
+        zip_writer
+            .lock()
+            .expect("Export archive should not be poisoned")
+            .finish()
+            .expect("Export archive should be finished");
+        ChronoZipReader::new(&archive_path).expect("Export archive should be readable")
+    }
+
+    #[test]
+    fn exported_wtf_folders_leave_out_backup_folders() {
+        let root = std::env::temp_dir().join(format!("chronobind-export-{}", std::process::id()));
+        let char_dir = "Account/ACC/Draenor/Alpha";
+        let files = [
+            ("Config.wtf".to_string(), ""),
+            (format!("{char_dir}/config-cache.wtf"), ""),
+            (
+                format!("{char_dir}/{BACKUPS_DIR_NAME}/Alpha-backup.zip"),
+                "",
+            ),
+            (format!("{CHRONOBIND_DIR}/notes.txt"), ""),
+        ];
+        let exported = export_wtf_folder(&root, &files, None)
+            .file_names()
+            .sorted()
+            .map(str::to_string)
+            .collect_vec();
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(
+            exported,
+            [
+                "_retail_/WTF/Account/ACC/Draenor/Alpha/config-cache.wtf",
+                "_retail_/WTF/Config.wtf",
+            ]
+        );
+    }
+
+    #[test]
+    fn sanitized_exports_leave_out_account_names() {
+        let root = std::env::temp_dir().join(format!(
+            "chronobind-export-sanitized-{}",
+            std::process::id()
+        ));
+        let files = [
+            (
+                "Config.wtf".to_string(),
+                "SET accountName \"NAME@EXAMPLE.COM\"\nSET accountList \"!WoW1|\"\nSET locale \"enUS\"\n",
+            ),
+            (
+                "Account/NAME/Draenor/Alpha/config-cache.wtf".to_string(),
+                "",
+            ),
+            (
+                "Account/NAME/SavedVariables/Addon.lua".to_string(),
+                "Addon = {}\n",
+            ),
+        ];
+        let mut archive = export_wtf_folder(&root, &files, Some(SanitizePattern::defaults()));
+        let exported = archive
+            .file_names()
+            .sorted()
+            .map(str::to_string)
+            .collect_vec();
+        let mut config = String::new();
+        std::io::Read::read_to_string(
+            &mut archive
+                .by_name("_retail_/WTF/Config.wtf")
+                .expect("Config file should be exported"),
+            &mut config,
+        )
+        .expect("Config file should be readable");
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(
+            exported,
+            [
+                "_retail_/WTF/Account/ACCOUNT1/Draenor/Alpha/config-cache.wtf",
+                "_retail_/WTF/Account/ACCOUNT1/SavedVariables/Addon.lua",
+                "_retail_/WTF/Config.wtf",
+            ]
+        );
+        assert_eq!(config, "SET locale \"enUS\"\n");
+    }
+}
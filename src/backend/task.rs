@@ -1,7 +1,8 @@
 use std::collections::VecDeque;
 use std::fmt::Debug;
-use std::sync::mpsc::{Receiver as MPSCReceiver, Sender as MPSCSender};
+use std::sync::mpsc::{Receiver as MPSCReceiver, SendError, Sender as MPSCSender};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use crate::files::AnyResult;
 use crate::ui::messages::AppMessage;
@@ -54,9 +55,22 @@ pub trait BackendTask: Debug + Send + Sync {
         }
     }
 
+    /// Returns the kind of the task, I.e. whether it modifies live data.
+    #[must_use]
+    fn task_kind(&self) -> IOTaskKind {
+        IOTaskKind::NonDestructive
+    }
+
     /// Run the task.
     fn run(&mut self) -> bool;
 
+    /// Cancel the task, skipping any remaining tasks in the chain.
+    /// Work already performed by the task is not undone.
+    fn cancel(&mut self);
+    /// Returns `true` if the task was cancelled.
+    #[must_use]
+    fn cancelled(&self) -> bool;
+
     /// Poll the task for updates.
     fn poll(&mut self);
 
@@ -69,6 +83,11 @@ pub trait BackendTask: Debug + Send + Sync {
     /// Returns any error message from the task.
     #[must_use]
     fn error(&self) -> Option<String>;
+    /// Returns a summary of the work completed by the task, if it reported one.
+    #[must_use]
+    fn summary(&self) -> Option<String> {
+        None
+    }
 
     /// Returns the number of items completed.
     #[must_use]
@@ -100,13 +119,40 @@ pub trait BackendTask: Debug + Send + Sync {
     /// Returns a message to be sent after the task completes, if applicable.
     #[must_use]
     fn after_messages(&mut self) -> Option<Vec<AppMessage>>;
+
+    /// Pushes a message to be sent once the task's chain ends, whether it completed, failed or
+    /// was cancelled.
+    fn add_on_finished(&mut self, msg: AppMessage);
+    /// Takes the messages to be sent once the task's chain ends.
+    #[must_use]
+    fn take_finished_messages(&mut self) -> Vec<AppMessage>;
+}
+
+/// Kind of an I/O task, used to decide how carefully it must be interrupted.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IOTaskKind {
+    /// Task only reads live data, I.e. backups and exports. Safe to cancel at any time.
+    #[default]
+    NonDestructive,
+    /// Task overwrites live data, I.e. restores and pastes.
+    /// Cancelling part way may leave files in an inconsistent state.
+    Destructive,
+}
+
+impl IOTaskKind {
+    /// Returns `true` if the task overwrites live data.
+    #[inline]
+    #[must_use]
+    pub const fn is_destructive(self) -> bool {
+        matches!(self, Self::Destructive)
+    }
 }
 
 /// Represents progress updates for I/O operations.
 #[derive(Debug)]
 pub enum IOProgress {
-    /// IO operation has started.
-    Started,
+    /// IO operation has started, with the total number of items to complete, if known.
+    Started { total: Option<usize> },
     /// IO operation has advanced with the number of completed items and total items,
     /// and an optional string, representing a context label message for the work just completed.
     Advanced {
@@ -114,12 +160,94 @@ pub enum IOProgress {
         total: usize,
         label: Option<String>,
     },
+    /// IO operation has reported a summary of the completed work, shown once it finishes.
+    Summary(String),
     /// IO operation has finished.
     Finished,
     /// IO operation encountered an error with an attached message.
     Error(String),
 }
 
+/// Default minimum time between the progress updates sent by a task, in milliseconds.
+pub const DEFAULT_PROGRESS_INTERVAL_MS: u64 = 50;
+
+/// Sends the progress of a task, coalescing updates so at most one is sent per progress interval.
+///
+/// Tasks over many small files would otherwise flood the channel between polls.
+/// Updates reaching the total are always sent, and `flush` sends the last coalesced update.
+#[derive(Debug)]
+pub struct ProgressSender<'a> {
+    /// The channel the progress is sent through.
+    tx: &'a MPSCSender<IOProgress>,
+    /// Total number of items to complete.
+    total: usize,
+    /// Minimum time between sent updates.
+    interval: Duration,
+    /// When the last update was sent, `None` if none has been sent yet.
+    last_sent: Option<Instant>,
+    /// The number of completed items of the last update, if it was coalesced rather than sent.
+    unsent: Option<usize>,
+}
+
+impl<'a> ProgressSender<'a> {
+    /// Create a progress sender for a task with the given total number of items, sending at
+    /// most one update per `interval`.
+    #[must_use]
+    pub const fn new(tx: &'a MPSCSender<IOProgress>, total: usize, interval: Duration) -> Self {
+        Self {
+            tx,
+            total,
+            interval,
+            last_sent: None,
+            unsent: None,
+        }
+    }
+
+    /// Report the number of completed items, building the label only if the update is sent.
+    /// The total grows to the number of completed items if they exceed it.
+    /// # Errors
+    /// Returns an error if the progress channel has been closed.
+    pub fn advance(
+        &mut self,
+        completed: usize,
+        label: impl FnOnce() -> Option<String>,
+    ) -> Result<(), SendError<IOProgress>> {
+        self.total = self.total.max(completed);
+        let due = completed == self.total
+            || self
+                .last_sent
+                .is_none_or(|sent_at| sent_at.elapsed() >= self.interval);
+        if !due {
+            self.unsent = Some(completed);
+            return Ok(());
+        }
+        self.send(completed, label())
+    }
+
+    /// Send the last coalesced update, if any, so the final count shown is accurate.
+    /// # Errors
+    /// Returns an error if the progress channel has been closed.
+    pub fn flush(&mut self) -> Result<(), SendError<IOProgress>> {
+        self.unsent
+            .map_or(Ok(()), |completed| self.send(completed, None))
+    }
+
+    /// Send an update with the given number of completed items.
+    fn send(
+        &mut self,
+        completed: usize,
+        label: Option<String>,
+    ) -> Result<(), SendError<IOProgress>> {
+        self.last_sent = Some(Instant::now());
+        self.unsent = None;
+        self.tx.send(IOProgress::Advanced {
+            completed,
+            total: self.total,
+            label,
+        })
+    }
+}
+
 /// State of an I/O task.
 #[derive(Debug, Default, Clone)]
 pub struct IOTaskState {
@@ -131,10 +259,14 @@ pub struct IOTaskState {
     pub started: bool,
     /// Whether the task has finished.
     pub finished: bool,
+    /// Whether the task was cancelled before finishing.
+    pub cancelled: bool,
     /// Label from the task progress.
     pub label: Option<String>,
     /// Any error message from the task.
     pub error: Option<String>,
+    /// Summary of the completed work, if reported by the task.
+    pub summary: Option<String>,
 }
 
 /// Type alias for a task creation function.
@@ -162,7 +294,7 @@ impl SingleUseTaskFn {
         self.func.take().map_or_else(
             || None,
             |func| {
-                tx.send(IOProgress::Started).ok();
+                tx.send(IOProgress::Started { total: None }).ok();
                 std::thread::spawn(move || {
                     if let Err(e) = func(&tx) {
                         tx.send(IOProgress::Error(format!("{e:?}"))).ok();
@@ -184,6 +316,8 @@ pub struct IOTask {
 
     /// Name of the task.
     pub name: Option<String>,
+    /// Kind of the task.
+    pub kind: IOTaskKind,
     /// Label for the task.
     pub label: Option<String>,
     /// Thread-safe receiver for IO progress updates.
@@ -194,6 +328,8 @@ pub struct IOTask {
     pub next: Option<BackendTaskPtr>,
     /// Optional messages to be sent after task completion.
     pub after_messages: Vec<AppMessage>,
+    /// Messages to be sent once the task's chain ends, however it ends.
+    pub finished_messages: Vec<AppMessage>,
     /// Whether to show the labels provided from the `IOTask` in the progress display.
     pub show_task_label: bool,
 }
@@ -203,10 +339,12 @@ impl Debug for IOTask {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("IOTask")
             .field("name", &self.name)
+            .field("kind", &self.kind)
             .field("label", &self.label)
             .field("state", &self.state)
             .field("next", &self.next)
             .field("after_messages", &self.after_messages)
+            .field("finished_messages", &self.finished_messages)
             .finish()
     }
 }
@@ -223,11 +361,13 @@ impl IOTask {
         Self {
             task_function: SingleUseTaskFn::new(Box::new(task_fn)),
             name: None,
+            kind: IOTaskKind::default(),
             label: None,
             rx: None,
             state: IOTaskState::default(),
             next: None,
             after_messages: Vec::new(),
+            finished_messages: Vec::new(),
             show_task_label: false,
         }
     }
@@ -253,6 +393,14 @@ impl IOTask {
         self
     }
 
+    /// Adds a message to be sent once the task's chain ends, whether it completed, failed or
+    /// was cancelled.
+    #[inline]
+    pub fn on_finished(mut self, msg: AppMessage) -> Self {
+        self.add_on_finished(msg);
+        self
+    }
+
     /// Assign a name to the task.
     #[inline]
     pub fn name<T: Into<String>>(mut self, name: T) -> Self {
@@ -260,6 +408,13 @@ impl IOTask {
         self
     }
 
+    /// Assign a kind to the task.
+    #[inline]
+    pub const fn kind(mut self, kind: IOTaskKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
     /// Assign a label to the task.
     #[inline]
     pub fn label<T: Into<String>>(mut self, label: T) -> Self {
@@ -328,6 +483,10 @@ impl BackendTask for IOTask {
         )
     }
 
+    fn task_kind(&self) -> IOTaskKind {
+        self.kind
+    }
+
     fn run(&mut self) -> bool {
         if let Some(rx) = self.task_function.create_task() {
             self.rx = Some(wrap_rx(rx));
@@ -337,14 +496,40 @@ impl BackendTask for IOTask {
         }
     }
 
+    fn cancel(&mut self) {
+        if self.state.finished {
+            return;
+        }
+        // Dropping the receiver makes the task's next progress update fail,
+        // which stops the task thread at its next checkpoint.
+        self.rx = None;
+        self.next = None;
+        self.state.cancelled = true;
+        self.state.finished = true;
+    }
+    fn cancelled(&self) -> bool {
+        self.state.cancelled
+    }
+
     fn poll(&mut self) {
         if let Some(rx) = &self.rx
             && let Ok(receiver) = rx.try_lock()
         {
             while let Ok(progress) = receiver.try_recv() {
                 match progress {
-                    IOProgress::Started => {
+                    IOProgress::Started { total } => {
                         self.state.started = true;
+                        if let Some(total) = total {
+                            self.state.total = total;
+                        }
+                        if total == Some(0) {
+                            // Nothing is left to work on, such as when every selected file was
+                            // filtered out, so no progress will ever be reported to wait on.
+                            self.state.summary =
+                                Some(format!("{}: nothing to do", self.task_name()));
+                            self.state.finished = true;
+                            break;
+                        }
                     }
                     IOProgress::Advanced {
                         completed,
@@ -355,6 +540,9 @@ impl BackendTask for IOTask {
                         self.state.total = total;
                         self.state.label = label;
                     }
+                    IOProgress::Summary(summary) => {
+                        self.state.summary = Some(summary);
+                    }
                     IOProgress::Finished => {
                         self.state.finished = true;
                     }
@@ -376,6 +564,9 @@ impl BackendTask for IOTask {
     fn error(&self) -> Option<String> {
         self.state.error.clone()
     }
+    fn summary(&self) -> Option<String> {
+        self.state.summary.clone()
+    }
 
     fn completed_count(&self) -> Option<usize> {
         Some(self.state.completed_operations)
@@ -420,6 +611,13 @@ impl BackendTask for IOTask {
             Some(std::mem::take(&mut self.after_messages))
         }
     }
+
+    fn add_on_finished(&mut self, msg: AppMessage) {
+        self.finished_messages.push(msg);
+    }
+    fn take_finished_messages(&mut self) -> Vec<AppMessage> {
+        std::mem::take(&mut self.finished_messages)
+    }
 }
 
 /// Builder for creating a sequence of backend tasks.
@@ -460,3 +658,105 @@ impl<T: BackendTask + 'static> TaskBuilder<T> {
         Some(current_task)
     }
 }
+
+/// Run a task chain to its end on the current thread, such as from within another task.
+///
+/// The chain is polled every `poll_interval`, and the summary of the last task to report one is
+/// returned. `on_poll` is called with the running task after each poll, and the chain is cancelled once it
+/// returns an error, such as when the progress channel of the calling task has been closed.
+/// # Errors
+/// Returns the first error of the chain, or an error if a task fails to start or is cancelled.
+pub fn run_chain_blocking(
+    mut task: BackendTaskPtr,
+    poll_interval: Duration,
+    mut on_poll: impl FnMut(&dyn BackendTask) -> AnyResult<()>,
+) -> AnyResult<Option<String>> {
+    let mut summary = None;
+    loop {
+        if !task.run() {
+            return Err(format!("Failed to start task `{}`", task.task_name()).into());
+        }
+        while !task.finished() {
+            std::thread::sleep(poll_interval);
+            task.poll();
+            if let Err(e) = on_poll(task.as_ref()) {
+                task.cancel();
+                return Err(e);
+            }
+        }
+
+        if let Some(error) = task.error() {
+            return Err(error.into());
+        }
+        if task.cancelled() {
+            return Err(format!("Task `{}` was cancelled", task.task_name()).into());
+        }
+        summary = task.summary().or(summary);
+        match task.next_task() {
+            Some(next) => task = next,
+            None => return Ok(summary),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_task_with_zero_files_finishes_without_waiting_on_progress() {
+        // The task never reports more progress until released, as when every file is filtered out.
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+        let release_rx = Mutex::new(release_rx);
+        let mut task = IOTask::new(move |tx| {
+            tx.send(IOProgress::Started { total: Some(0) })?;
+            let _ = release_rx.lock().map(|rx| rx.recv());
+            Ok(())
+        })
+        .name("Backing up");
+        assert!(task.run());
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !task.finished() && Instant::now() < deadline {
+            task.poll();
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        drop(release_tx);
+
+        assert!(task.finished());
+        assert_eq!(task.summary().as_deref(), Some("Backing up: nothing to do"));
+        assert_eq!(task.error(), None);
+    }
+
+    #[test]
+    fn blocking_chains_stop_at_the_first_error() {
+        let mut builder = TaskBuilder::new();
+        builder
+            .add_task(IOTask::new(|tx| {
+                tx.send(IOProgress::Summary("Backed up".to_string()))?;
+                Ok(())
+            }))
+            .add_task(IOTask::new(|_| Err("Disk full".into())))
+            .add_task(IOTask::new(|_| {
+                panic!("Tasks after a failure should not run")
+            }));
+        let task = builder.build().expect("Chain should have tasks").boxed();
+
+        let outcome = run_chain_blocking(task, Duration::from_millis(1), |_| Ok(()));
+        assert!(
+            outcome
+                .expect_err("Chain should fail")
+                .to_string()
+                .contains("Disk full")
+        );
+
+        let task = IOTask::new(|tx| {
+            tx.send(IOProgress::Summary("Pasted 2 files".to_string()))?;
+            Ok(())
+        })
+        .boxed();
+        let outcome = run_chain_blocking(task, Duration::from_millis(1), |_| Ok(()))
+            .expect("Chain should succeed");
+        assert_eq!(outcome.as_deref(), Some("Pasted 2 files"));
+    }
+}
@@ -0,0 +1,527 @@
+#[cfg(test)]
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Debug;
+use std::fs as filesystem;
+#[cfg(test)]
+use std::io::SeekFrom;
+use std::io::{self, Cursor, Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+#[cfg(test)]
+use std::sync::{Mutex, MutexGuard};
+
+use crate::{files::AnyResult, tui_log::mock_prefix};
+
+/// A readable and seekable file handle, as returned by [`FileSystem::open`].
+pub trait ReadSeek: Read + Seek + Send + Debug {}
+impl<T: Read + Seek + Send + Debug> ReadSeek for T {}
+
+/// A writable and seekable file handle, as returned by [`FileSystem::create`].
+pub trait WriteSeek: Write + Seek + Send + Debug {}
+impl<T: Write + Seek + Send + Debug> WriteSeek for T {}
+
+/// Shared pointer to a filesystem implementation, able to be moved across task threads.
+pub type FileSystemPtr = Arc<dyn FileSystem>;
+
+/// Interface over the filesystem operations performed by the backend, allowing backend
+/// operations to be run against the real disk, in mock mode, or entirely in memory.
+pub trait FileSystem: Debug + Send + Sync {
+    /// Open a file for reading.
+    /// # Errors
+    /// Returns an error if the file cannot be opened.
+    fn open(&self, path: &Path) -> io::Result<Box<dyn ReadSeek>>;
+    /// Create (or truncate) a file for writing.
+    /// # Errors
+    /// Returns an error if the file cannot be created.
+    fn create(&self, path: &Path) -> io::Result<Box<dyn WriteSeek>>;
+    /// Copy a file, returning the number of bytes copied.
+    /// # Errors
+    /// Returns an error if the file cannot be copied.
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<u64>;
+    /// Rename (move) a file.
+    /// # Errors
+    /// Returns an error if the file cannot be renamed.
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()>;
+    /// Remove a file.
+    /// # Errors
+    /// Returns an error if the file cannot be removed.
+    fn remove_file(&self, path: &Path) -> io::Result<()>;
+    /// Recursively create a directory and all of its missing parents.
+    /// # Errors
+    /// Returns an error if the directory cannot be created.
+    fn create_dir_all(&self, path: &Path) -> io::Result<()>;
+    /// Get the paths of all entries directly within a directory.
+    /// # Errors
+    /// Returns an error if the directory cannot be read.
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>>;
+    /// Clear the read-only attribute of a file, if set.
+    /// # Errors
+    /// Returns an error if the file's permissions cannot be changed.
+    fn clear_read_only(&self, path: &Path) -> io::Result<()>;
+    /// Get the size of a file in bytes.
+    /// # Errors
+    /// Returns an error if the file's metadata cannot be read.
+    fn file_size(&self, path: &Path) -> io::Result<u64>;
+    /// Get the space available for new files on the volume containing the path, in bytes.
+    /// # Errors
+    /// Returns an error if the free space cannot be determined.
+    fn available_space(&self, path: &Path) -> io::Result<u64>;
+    /// Returns `true` if the path exists and is a file.
+    fn is_file(&self, path: &Path) -> bool;
+    /// Returns `true` if the path exists and is a directory.
+    fn is_dir(&self, path: &Path) -> bool;
+
+    /// Returns `true` if writes to this filesystem are discarded, I.e. mock mode.
+    fn is_mock(&self) -> bool {
+        false
+    }
+
+    // Default implementations..
+
+    /// Returns `true` if the path exists.
+    fn exists(&self, path: &Path) -> bool {
+        self.is_file(path) || self.is_dir(path)
+    }
+
+    /// Ensure that a directory exists at the given path, creating it if necessary.
+    /// # Errors
+    /// Returns an error if the directory cannot be created if it does not exist.
+    fn ensure_directory(&self, path: &Path) -> AnyResult<()> {
+        if !self.exists(path) {
+            self.create_dir_all(path)?;
+            log::info!(
+                "{}Created directory: {}",
+                mock_prefix(self.is_mock()),
+                path.display()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Lazy, depth-first iterator over all files within a directory tree of a [`FileSystem`].
+/// Only the entries of the directories in the current descent are held in memory.
+#[derive(Debug)]
+pub struct FileSystemWalk<'a> {
+    /// The filesystem being walked.
+    fs: &'a dyn FileSystem,
+    /// Entries of the directories of the current descent, the last being the deepest.
+    stack: Vec<std::vec::IntoIter<PathBuf>>,
+    /// Fully qualified paths excluded from the walk.
+    excluded_paths: Vec<PathBuf>,
+}
+
+impl<'a> FileSystemWalk<'a> {
+    /// Create a new walk over `base_path` within the given filesystem.
+    /// # Errors
+    /// Returns an error if `base_path` cannot be read.
+    pub fn new(
+        fs: &'a dyn FileSystem,
+        base_path: &Path,
+        excluded_dirs: &[&str],
+    ) -> io::Result<Self> {
+        Ok(Self {
+            fs,
+            stack: vec![fs.read_dir(base_path)?.into_iter()],
+            excluded_paths: excluded_dirs.iter().map(|p| base_path.join(p)).collect(),
+        })
+    }
+}
+
+impl Iterator for FileSystemWalk<'_> {
+    type Item = io::Result<PathBuf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let current_dir = self.stack.last_mut()?;
+            let Some(path) = current_dir.next() else {
+                self.stack.pop();
+                continue;
+            };
+            if self.excluded_paths.contains(&path) {
+                continue;
+            }
+
+            if self.fs.is_file(&path) {
+                return Some(Ok(path));
+            } else if self.fs.is_dir(&path) {
+                match self.fs.read_dir(&path) {
+                    Ok(entries) => self.stack.push(entries.into_iter()),
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+        }
+    }
+}
+
+/// Get the filesystem implementation to use for the given mock mode state.
+#[inline]
+#[must_use]
+pub fn filesystem_for(mock_mode: bool) -> FileSystemPtr {
+    if mock_mode {
+        Arc::new(MockFileSystem)
+    } else {
+        Arc::new(RealFileSystem)
+    }
+}
+
+// Real filesystem..
+
+/// Filesystem implementation operating on the real disk.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RealFileSystem;
+
+impl FileSystem for RealFileSystem {
+    fn open(&self, path: &Path) -> io::Result<Box<dyn ReadSeek>> {
+        Ok(Box::new(filesystem::File::open(path)?))
+    }
+    fn create(&self, path: &Path) -> io::Result<Box<dyn WriteSeek>> {
+        Ok(Box::new(filesystem::File::create(path)?))
+    }
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<u64> {
+        filesystem::copy(from, to)
+    }
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        filesystem::rename(from, to)
+    }
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        filesystem::remove_file(path)
+    }
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        filesystem::create_dir_all(path)
+    }
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        filesystem::read_dir(path)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect()
+    }
+    fn clear_read_only(&self, path: &Path) -> io::Result<()> {
+        crate::files::clear_read_only(path)
+    }
+    fn file_size(&self, path: &Path) -> io::Result<u64> {
+        Ok(filesystem::metadata(path)?.len())
+    }
+    fn available_space(&self, path: &Path) -> io::Result<u64> {
+        // The path may not exist yet, so query the volume of its nearest existing ancestor.
+        let existing = path
+            .ancestors()
+            .find(|p| p.exists())
+            .ok_or_else(|| not_found(path))?;
+        fs4::available_space(existing)
+    }
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+}
+
+// Mock filesystem..
+
+/// Filesystem implementation for mock mode, reads from the real disk,
+/// but discards all writes.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MockFileSystem;
+
+impl FileSystem for MockFileSystem {
+    fn open(&self, path: &Path) -> io::Result<Box<dyn ReadSeek>> {
+        RealFileSystem.open(path)
+    }
+    fn create(&self, _path: &Path) -> io::Result<Box<dyn WriteSeek>> {
+        Ok(Box::new(Cursor::new(Vec::new())))
+    }
+    fn copy(&self, from: &Path, _to: &Path) -> io::Result<u64> {
+        Ok(filesystem::metadata(from)?.len())
+    }
+    fn rename(&self, _from: &Path, _to: &Path) -> io::Result<()> {
+        Ok(())
+    }
+    fn remove_file(&self, _path: &Path) -> io::Result<()> {
+        Ok(())
+    }
+    fn create_dir_all(&self, _path: &Path) -> io::Result<()> {
+        Ok(())
+    }
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        RealFileSystem.read_dir(path)
+    }
+    fn clear_read_only(&self, _path: &Path) -> io::Result<()> {
+        Ok(())
+    }
+    fn file_size(&self, path: &Path) -> io::Result<u64> {
+        RealFileSystem.file_size(path)
+    }
+    fn available_space(&self, _path: &Path) -> io::Result<u64> {
+        // Nothing is written in mock mode, so space never runs out.
+        Ok(u64::MAX)
+    }
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+    fn is_mock(&self) -> bool {
+        true
+    }
+}
+
+// In-memory filesystem..
+
+/// Shared storage of file contents by path for the in-memory filesystem.
+#[cfg(test)]
+type MemoryFiles = Arc<Mutex<BTreeMap<PathBuf, Vec<u8>>>>;
+
+/// Filesystem implementation held entirely in memory, for exercising backend
+/// operations without touching the disk. Cloning shares the same underlying storage.
+#[cfg(test)]
+#[derive(Debug, Default, Clone)]
+pub struct MemoryFileSystem {
+    /// File contents by path.
+    files: MemoryFiles,
+    /// Explicitly created directories.
+    dirs: Arc<Mutex<BTreeSet<PathBuf>>>,
+    /// Files which fail to open, to simulate files which can't be read.
+    unreadable: Arc<Mutex<BTreeSet<PathBuf>>>,
+    /// Space reported as available for new files, unlimited if `None`.
+    available_space: Option<u64>,
+}
+
+#[cfg(test)]
+impl MemoryFileSystem {
+    /// Create a new, empty in-memory filesystem.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report the given number of bytes as available for new files, to simulate a nearly full disk.
+    #[must_use]
+    pub const fn with_available_space(mut self, bytes: u64) -> Self {
+        self.available_space = Some(bytes);
+        self
+    }
+
+    /// Insert a file with the given contents, replacing any existing file.
+    pub fn insert_file<P: Into<PathBuf>, C: Into<Vec<u8>>>(&self, path: P, contents: C) {
+        if let Ok(mut files) = self.files.lock() {
+            files.insert(path.into(), contents.into());
+        }
+    }
+
+    /// Make opening the file at the given path fail, as if it couldn't be read.
+    pub fn mark_unreadable<P: Into<PathBuf>>(&self, path: P) {
+        if let Ok(mut unreadable) = self.unreadable.lock() {
+            unreadable.insert(path.into());
+        }
+    }
+
+    /// Get a copy of the contents of the file at the given path.
+    #[must_use]
+    pub fn read_file(&self, path: &Path) -> Option<Vec<u8>> {
+        self.files.lock().ok()?.get(path).cloned()
+    }
+
+    /// Get the paths of all files in the filesystem.
+    #[must_use]
+    pub fn file_paths(&self) -> Vec<PathBuf> {
+        self.files
+            .lock()
+            .map(|files| files.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Lock the file storage, converting a poisoned lock into an I/O error.
+    fn lock_files(&self) -> io::Result<MutexGuard<'_, BTreeMap<PathBuf, Vec<u8>>>> {
+        lock_storage(&self.files)
+    }
+}
+
+#[cfg(test)]
+impl FileSystem for MemoryFileSystem {
+    fn open(&self, path: &Path) -> io::Result<Box<dyn ReadSeek>> {
+        if lock_storage(&self.unreadable)?.contains(path) {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("`{}` cannot be read", path.display()),
+            ));
+        }
+        let contents = self
+            .lock_files()?
+            .get(path)
+            .cloned()
+            .ok_or_else(|| not_found(path))?;
+        Ok(Box::new(Cursor::new(contents)))
+    }
+    fn create(&self, path: &Path) -> io::Result<Box<dyn WriteSeek>> {
+        self.lock_files()?.insert(path.to_path_buf(), Vec::new());
+        Ok(Box::new(MemoryFileWriter {
+            path: path.to_path_buf(),
+            buffer: Cursor::new(Vec::new()),
+            files: self.files.clone(),
+        }))
+    }
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<u64> {
+        let mut files = self.lock_files()?;
+        let contents = files.get(from).cloned().ok_or_else(|| not_found(from))?;
+        let len = contents.len() as u64;
+        files.insert(to.to_path_buf(), contents);
+        drop(files);
+        Ok(len)
+    }
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let mut files = self.lock_files()?;
+        let contents = files.remove(from).ok_or_else(|| not_found(from))?;
+        files.insert(to.to_path_buf(), contents);
+        drop(files);
+        Ok(())
+    }
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        self.lock_files()?
+            .remove(path)
+            .map(|_| ())
+            .ok_or_else(|| not_found(path))
+    }
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        lock_storage(&self.dirs)?.extend(path.ancestors().map(Path::to_path_buf));
+        Ok(())
+    }
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        if !self.is_dir(path) {
+            return Err(not_found(path));
+        }
+
+        let child_of = |p: &PathBuf| {
+            let first = p.strip_prefix(path).ok()?.components().next()?;
+            Some(path.join(first))
+        };
+        let mut entries = self
+            .lock_files()?
+            .keys()
+            .filter_map(child_of)
+            .collect::<BTreeSet<_>>();
+        entries.extend(lock_storage(&self.dirs)?.iter().filter_map(child_of));
+        Ok(entries.into_iter().collect())
+    }
+    fn clear_read_only(&self, path: &Path) -> io::Result<()> {
+        if self.is_file(path) {
+            Ok(())
+        } else {
+            Err(not_found(path))
+        }
+    }
+    fn file_size(&self, path: &Path) -> io::Result<u64> {
+        self.lock_files()?
+            .get(path)
+            .map(|contents| contents.len() as u64)
+            .ok_or_else(|| not_found(path))
+    }
+    fn available_space(&self, _path: &Path) -> io::Result<u64> {
+        Ok(self.available_space.unwrap_or(u64::MAX))
+    }
+    fn is_file(&self, path: &Path) -> bool {
+        self.files
+            .lock()
+            .is_ok_and(|files| files.contains_key(path))
+    }
+    fn is_dir(&self, path: &Path) -> bool {
+        let is_parent_of = |p: &PathBuf| p != path && p.starts_with(path);
+        self.dirs.lock().is_ok_and(|dirs| dirs.contains(path))
+            || self
+                .files
+                .lock()
+                .is_ok_and(|files| files.keys().any(is_parent_of))
+    }
+}
+
+/// Writable handle to a file in a [`MemoryFileSystem`],
+/// contents are committed to the filesystem on flush and drop.
+#[cfg(test)]
+#[derive(Debug)]
+struct MemoryFileWriter {
+    /// Path of the file being written.
+    path: PathBuf,
+    /// Contents written so far.
+    buffer: Cursor<Vec<u8>>,
+    /// Storage to commit the contents to.
+    files: MemoryFiles,
+}
+
+#[cfg(test)]
+impl Write for MemoryFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.write(buf)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        lock_storage(&self.files)?.insert(self.path.clone(), self.buffer.get_ref().clone());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+impl Seek for MemoryFileWriter {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.buffer.seek(pos)
+    }
+}
+
+#[cfg(test)]
+impl Drop for MemoryFileWriter {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush() {
+            log::error!(
+                "Failed to commit in-memory file `{}`: {e}",
+                self.path.display()
+            );
+        }
+    }
+}
+
+/// Lock shared in-memory storage, converting a poisoned lock into an I/O error.
+#[cfg(test)]
+#[inline]
+fn lock_storage<T>(storage: &Mutex<T>) -> io::Result<MutexGuard<'_, T>> {
+    storage
+        .lock()
+        .map_err(|_| io::Error::other("In-memory filesystem lock poisoned"))
+}
+
+/// Create a not found error for the given path.
+#[inline]
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("`{}` not found", path.display()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_files_are_committed_and_walked() {
+        let fs = MemoryFileSystem::new();
+        fs.insert_file("/wow/WTF/config.wtf", "SET gxApi \"D3D12\"");
+        {
+            let mut file = fs
+                .create(Path::new("/wow/WTF/Account/ACC/bindings-cache.wtf"))
+                .expect("File should be created");
+            file.write_all(b"bind W MOVEFORWARD")
+                .expect("File should be written");
+        }
+
+        assert_eq!(
+            fs.read_file(Path::new("/wow/WTF/Account/ACC/bindings-cache.wtf"))
+                .as_deref(),
+            Some(b"bind W MOVEFORWARD".as_slice())
+        );
+        let walked = FileSystemWalk::new(&fs, Path::new("/wow/WTF"), &["Account"])
+            .expect("Walk should start")
+            .collect::<io::Result<Vec<_>>>()
+            .expect("Walk should succeed");
+        assert_eq!(walked, [PathBuf::from("/wow/WTF/config.wtf")]);
+        assert_eq!(fs.file_paths().len(), 2);
+    }
+}
@@ -1,20 +1,24 @@
-use filesystem::File;
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
 use std::sync::Mutex;
-use std::{fs as filesystem, sync::Arc};
 use zip::ZipArchive;
 use zip::read::ZipFile;
 
-use zip::{ZipWriter, write::FileOptions};
+use zip::{AesMode, ZipWriter, write::FileOptions};
 
+use crate::backend::archive_manifest::is_archive_metadata;
+use crate::backend::vfs::{
+    FileSystem, FileSystemPtr, ReadSeek, RealFileSystem, WriteSeek, filesystem_for,
+};
 use crate::files::AnyResult;
 
 /// A simple ZIP writer wrapper/interface for creating backups.
 #[derive(Debug)]
 #[must_use]
 pub struct ChronoZipWriter<'a> {
-    zip: Option<ZipWriter<File>>,
+    zip: Option<ZipWriter<Box<dyn WriteSeek>>>,
     options: FileOptions<'a, ()>,
+    fs: FileSystemPtr,
 }
 
 impl ChronoZipWriter<'_> {
@@ -27,19 +31,26 @@ impl<'a> ChronoZipWriter<'a> {
     /// # Errors
     /// Returns an error if the file cannot be created.
     pub fn new(path: &Path, mock_mode: bool) -> AnyResult<Self> {
-        let zip = if mock_mode {
+        Self::new_with_fs(path, filesystem_for(mock_mode))
+    }
+
+    /// Create a new `ChronoZipWriter` writing to the specified file within the given filesystem.
+    /// # Errors
+    /// Returns an error if the file cannot be created.
+    pub fn new_with_fs(path: &Path, fs: FileSystemPtr) -> AnyResult<Self> {
+        let zip = if fs.is_mock() {
             log::debug!(
                 "Mock mode enabled, skipping creation of zip file at `{}`",
                 path.display()
             );
             None
         } else {
-            let file = filesystem::File::create(path)?;
-            Some(ZipWriter::new(file))
+            Some(ZipWriter::new(fs.create(path)?))
         };
         Ok(Self {
             zip,
             options: Self::DEFAULT_ZIP_OPTIONS,
+            fs,
         })
     }
 
@@ -82,7 +93,7 @@ impl ChronoZipWriter<'_> {
     /// Returns an error if the operation fails.
     pub fn add_directory<S: Into<String>>(&mut self, name: S) -> AnyResult<()> {
         if let Some(zip) = self.zip.as_mut() {
-            zip.add_directory(name.into(), self.options)?;
+            zip.add_directory(normalize_entry_name(&name.into()), self.options)?;
         }
         Ok(())
     }
@@ -102,7 +113,7 @@ impl ChronoZipWriter<'_> {
     /// Returns an error if the operation fails.
     pub fn start_file<S: Into<String>>(&mut self, name: S) -> AnyResult<()> {
         if let Some(zip) = self.zip.as_mut() {
-            zip.start_file(name.into(), self.options)?;
+            zip.start_file(normalize_entry_name(&name.into()), self.options)?;
         }
         Ok(())
     }
@@ -117,12 +128,44 @@ impl ChronoZipWriter<'_> {
     ) -> AnyResult<()> {
         self.start_file(name)?;
         if let Some(zip) = self.zip.as_mut() {
-            let mut f = filesystem::File::open(source_path.as_ref())?;
+            let mut f = self.fs.open(source_path.as_ref())?;
             std::io::copy(&mut f, zip)?;
         }
         Ok(())
     }
 
+    /// Copy a file into the ZIP archive, encrypting its contents with AES-256 using the given
+    /// passphrase. Other entries of the archive are unaffected. Does nothing if in mock mode.
+    /// # Errors
+    /// Returns an error if the operation fails.
+    pub fn copy_file_encrypted<S: Into<String>, P: AsRef<Path>>(
+        &mut self,
+        name: S,
+        source_path: P,
+        passphrase: &str,
+    ) -> AnyResult<()> {
+        if let Some(zip) = self.zip.as_mut() {
+            let options = self
+                .options
+                .with_aes_encryption(AesMode::Aes256, passphrase);
+            zip.start_file(normalize_entry_name(&name.into()), options)?;
+            let mut f = self.fs.open(source_path.as_ref())?;
+            std::io::copy(&mut f, zip)?;
+        }
+        Ok(())
+    }
+
+    /// Write a file with the given contents to the ZIP archive, does nothing if in mock mode.
+    /// # Errors
+    /// Returns an error if the operation fails.
+    pub fn write_file<S: Into<String>>(&mut self, name: S, contents: &[u8]) -> AnyResult<()> {
+        self.start_file(name)?;
+        if let Some(zip) = self.zip.as_mut() {
+            std::io::Write::write_all(zip, contents)?;
+        }
+        Ok(())
+    }
+
     /// Finish writing the ZIP archive, does nothing if in mock mode.
     /// # Errors
     /// Returns an error if the operation fails.
@@ -153,7 +196,7 @@ impl Drop for ChronoZipWriter<'_> {
 #[derive(Debug)]
 #[must_use]
 pub struct ChronoZipReader<'a> {
-    archive: ZipArchive<File>,
+    archive: ZipArchive<Box<dyn ReadSeek>>,
     options: FileOptions<'a, ()>,
 }
 
@@ -167,8 +210,14 @@ impl<'a> ChronoZipReader<'a> {
     /// # Errors
     /// Returns an error if the file cannot be created.
     pub fn new(path: &Path) -> AnyResult<Self> {
-        let file = filesystem::File::open(path)?;
-        let archive = ZipArchive::new(file)?;
+        Self::new_with_fs(path, &RealFileSystem)
+    }
+
+    /// Create a new `ChronoZipReader` reading the specified file from the given filesystem.
+    /// # Errors
+    /// Returns an error if the file cannot be opened or is not a valid ZIP archive.
+    pub fn new_with_fs(path: &Path, fs: &dyn FileSystem) -> AnyResult<Self> {
+        let archive = ZipArchive::new(fs.open(path)?)?;
         Ok(Self {
             archive,
             options: Self::DEFAULT_ZIP_OPTIONS,
@@ -205,14 +254,59 @@ impl ChronoZipReader<'_> {
     /// # Errors
     /// Returns an error if the ZIP read or access fails.
     #[inline]
-    pub fn by_index(&mut self, index: usize) -> AnyResult<ZipFile<'_, filesystem::File>> {
+    pub fn by_index(&mut self, index: usize) -> AnyResult<ZipFile<'_, Box<dyn ReadSeek>>> {
         Ok(self.archive.by_index(index)?)
     }
 
+    /// Get an encrypted file by its index in the ZIP archive, decrypting it with the passphrase.
+    /// # Errors
+    /// Returns an error if the ZIP read or access fails, or the passphrase is wrong.
+    #[inline]
+    pub fn by_index_decrypt(
+        &mut self,
+        index: usize,
+        passphrase: &str,
+    ) -> AnyResult<ZipFile<'_, Box<dyn ReadSeek>>> {
+        Ok(self
+            .archive
+            .by_index_decrypt(index, passphrase.as_bytes())?)
+    }
+
+    /// Get a file by its index in the ZIP archive, decrypting it with the passphrase if it is encrypted.
+    /// # Errors
+    /// Returns an error if the ZIP read or access fails, or the file is encrypted and
+    /// the passphrase is missing or wrong.
+    pub fn by_index_with_passphrase(
+        &mut self,
+        index: usize,
+        passphrase: Option<&str>,
+    ) -> AnyResult<ZipFile<'_, Box<dyn ReadSeek>>> {
+        if !self.is_encrypted(index)? {
+            return self.by_index(index);
+        }
+        let passphrase = passphrase.ok_or("the file is encrypted and no passphrase was given")?;
+        self.by_index_decrypt(index, passphrase)
+    }
+
+    /// Returns `true` if the file at the given index in the ZIP archive is encrypted.
+    /// # Errors
+    /// Returns an error if the ZIP read or access fails.
+    #[inline]
+    pub fn is_encrypted(&mut self, index: usize) -> AnyResult<bool> {
+        Ok(self.archive.by_index_raw(index)?.encrypted())
+    }
+
+    /// Get the name of the file at the given index in the ZIP archive.
+    #[inline]
+    #[must_use]
+    pub fn name_for_index(&self, index: usize) -> Option<&str> {
+        self.archive.name_for_index(index)
+    }
+
     /// Search for a file entry by name
     /// # Errors
     /// Returns an error if the ZIP read or access fails.
-    pub fn by_name(&mut self, name: &str) -> AnyResult<ZipFile<'_, filesystem::File>> {
+    pub fn by_name(&mut self, name: &str) -> AnyResult<ZipFile<'_, Box<dyn ReadSeek>>> {
         Ok(self.archive.by_name(name)?)
     }
 
@@ -223,12 +317,20 @@ impl ChronoZipReader<'_> {
     }
 
     /// Get an iterator over the directories in the root directory of the ZIP archive.
+    /// Files stored directly in the root, and `ChronoBind`'s own metadata, are skipped.
     #[inline]
     pub fn directories_in_root(&mut self) -> Vec<String> {
         let mut dirs = std::collections::HashSet::new();
 
         for name in self.archive.file_names() {
-            if let Some(first_component) = Path::new(name).components().next() {
+            if is_archive_metadata(name) {
+                continue;
+            }
+            let name = normalize_entry_name(name);
+            let mut components = Path::new(&name).components();
+            if let Some(first_component) = components.next()
+                && (components.next().is_some() || name.ends_with('/'))
+            {
                 dirs.insert(first_component.as_os_str().to_string_lossy().to_string());
             }
         }
@@ -241,7 +343,7 @@ impl ChronoZipReader<'_> {
         let mut files = Vec::new();
 
         for file_name in self.archive.file_names() {
-            if logical_is_path_inside(&dir, file_name) {
+            if logical_is_path_inside(&dir, normalize_entry_name(file_name)) {
                 files.push(file_name.to_string());
             }
         }
@@ -282,3 +384,73 @@ fn logical_is_path_inside<P: AsRef<Path>, Q: AsRef<Path>>(parent: P, child: Q) -
 
     true
 }
+
+/// Normalise the separators of a ZIP entry name to forward slashes, as the ZIP format specifies.
+/// Archives made on Windows by other tools may use backslashes instead.
+#[must_use]
+pub fn normalize_entry_name(name: &str) -> String {
+    name.replace('\\', "/")
+}
+
+/// Returns `true` if the ZIP entry name is a directory entry, with either separator.
+#[must_use]
+pub fn is_directory_entry(name: &str) -> bool {
+    name.ends_with(['/', '\\'])
+}
+
+/// Get the path of a ZIP entry relative to the directory it is extracted into, treating
+/// both separators as such.
+/// Returns `None` if the entry would escape that directory, like `ZipFile::enclosed_name`.
+#[must_use]
+pub fn entry_relative_path(name: &str) -> Option<PathBuf> {
+    let name = normalize_entry_name(name);
+    if name.contains('\0') || name.starts_with('/') {
+        return None;
+    }
+    let mut path = PathBuf::new();
+    for component in name.split('/') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                if !path.pop() {
+                    return None;
+                }
+            }
+            component => {
+                let mut components = Path::new(component).components();
+                if !matches!(
+                    (components.next(), components.next()),
+                    (Some(Component::Normal(_)), None)
+                ) {
+                    return None;
+                }
+                path.push(component);
+            }
+        }
+    }
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backslash_entry_names_are_nested_paths() {
+        assert_eq!(
+            entry_relative_path(r"SavedVariables\WeakAuras.lua"),
+            Some(PathBuf::from("SavedVariables").join("WeakAuras.lua"))
+        );
+        assert_eq!(
+            entry_relative_path(r"SavedVariables\Nested/Mixed.lua"),
+            Some(
+                PathBuf::from("SavedVariables")
+                    .join("Nested")
+                    .join("Mixed.lua")
+            )
+        );
+        assert_eq!(entry_relative_path(r"..\escape.lua"), None);
+        assert_eq!(entry_relative_path(r"\rooted.lua"), None);
+        assert!(is_directory_entry(r"SavedVariables\"));
+    }
+}
@@ -1,8 +1,16 @@
+use std::io::{IsTerminal, Write};
 use std::path::PathBuf;
 
 use clap::{Parser, ValueEnum};
+use ratatui::crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    terminal,
+};
 use terminal_relaunch::TerminalType;
 
+use crate::backend::encryption::{PASSPHRASE_ENV_VAR, Passphrase};
+use crate::wow::{self, WoWInstall};
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, ValueEnum)]
 pub enum TargetTerminal {
     /// `Windows Terminal`. (terminal app from Microsoft Store `wt.exe`)
@@ -43,6 +51,7 @@ impl TargetTerminal {
 /// CLI arguments for `ChronoBind` application.
 #[derive(Parser, Debug, Default, Clone, PartialEq, Eq, Hash)]
 #[command(name="ChronoBind", version, about, long_about = None)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ChronoCLIArgs {
     /// Optional file path to open in the import window on startup.
     pub file_to_import: Option<PathBuf>,
@@ -59,6 +68,29 @@ pub struct ChronoCLIArgs {
     #[arg(long, short, default_value_t = false)]
     pub preferred_only: bool,
 
+    /// Load `WoW` installations from a fixture directory instead of the system `Battle.net` install.
+    /// The directory should mirror a `WoW` install root, containing branch folders such as `_retail_`.
+    #[arg(long, env = "CHRONOBIND_FIXTURES")]
+    pub fixtures: Option<PathBuf>,
+
+    /// Run without the terminal UI, performing the requested operation and exiting.
+    #[arg(long, default_value_t = false)]
+    pub no_ui: bool,
+    /// Back up all characters without the terminal UI, scoped by `--branch`,
+    /// `--character` and `--realm`. Requires `--no-ui`.
+    #[arg(long, default_value_t = false, requires = "no_ui")]
+    pub backup_all: bool,
+    /// `WoW` branch to operate on in headless mode (I.e. `retail`, `classic_era`).
+    /// Defaults to the preferred branch from the configuration.
+    #[arg(long, requires = "no_ui")]
+    pub branch: Option<String>,
+    /// Only operate on characters with this name in headless mode.
+    #[arg(long, requires = "no_ui")]
+    pub character: Option<String>,
+    /// Only operate on characters on this realm in headless mode.
+    #[arg(long, requires = "no_ui")]
+    pub realm: Option<String>,
+
     /// Flag to signal if the terminal has been relaunched.
     #[arg(long = "relaunched-term", default_value_t = false, hide = true)]
     relaunched: bool,
@@ -72,6 +104,27 @@ impl ChronoCLIArgs {
         self.relaunched
     }
 
+    /// Locate the `WoW` installations to use, from the fixture directory if provided,
+    /// otherwise from the system `Battle.net` install, resolving paths within them through
+    /// symlinks if `resolve_symlinks` is set.
+    /// # Errors
+    /// Returns an error if the installations cannot be located.
+    pub fn locate_wow_installs(
+        &self,
+        resolve_symlinks: bool,
+    ) -> Result<Vec<WoWInstall>, Box<dyn std::error::Error>> {
+        self.fixtures.as_ref().map_or_else(
+            || wow::locate_wow_installs(resolve_symlinks),
+            |dir| {
+                log::info!(
+                    "Loading WoW installations from fixtures `{}`",
+                    dir.display()
+                );
+                wow::locate_fixture_wow_installs(dir, resolve_symlinks)
+            },
+        )
+    }
+
     /// Check if the current terminal does not match the preferred terminal and should relaunch.
     #[inline]
     #[must_use]
@@ -81,3 +134,82 @@ impl ChronoCLIArgs {
         })
     }
 }
+
+/// Read the passphrase the `encrypted_files` are encrypted and decrypted with.
+///
+/// Taken from the `CHRONOBIND_PASSPHRASE` environment variable, otherwise prompted for on the
+/// terminal. No prompt is shown without any `encrypted_files`, or when not run from a terminal.
+#[must_use]
+pub fn read_passphrase(encrypted_files: &[String]) -> Option<Passphrase> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV_VAR) {
+        return Passphrase::new(passphrase);
+    }
+    if encrypted_files.is_empty() || !std::io::stdin().is_terminal() {
+        return None;
+    }
+    prompt_passphrase()
+        .inspect_err(|e| log::error!("Failed to read the passphrase: {e}"))
+        .ok()
+        .flatten()
+}
+
+/// Prompt for the passphrase on the terminal without echoing it, `None` if skipped with an
+/// empty passphrase or `Esc`.
+///
+/// The passphrase is asked for twice and must match, as a mistyped passphrase would leave every
+/// file encrypted with it unrecoverable.
+fn prompt_passphrase() -> std::io::Result<Option<Passphrase>> {
+    loop {
+        let Some(passphrase) =
+            read_hidden("Passphrase for encrypted backup files (leave empty to skip them): ")?
+                .and_then(Passphrase::new)
+        else {
+            return Ok(None);
+        };
+        let Some(confirmation) = read_hidden("Enter the passphrase again to confirm it: ")? else {
+            return Ok(None);
+        };
+        if confirmation == passphrase.as_str() {
+            return Ok(Some(passphrase));
+        }
+        writeln!(std::io::stderr(), "The passphrases don't match, try again.")?;
+    }
+}
+
+/// Read a line from the terminal after showing the prompt, without echoing it.
+/// `None` if cancelled with `Esc` or `Ctrl+C`.
+fn read_hidden(prompt: &str) -> std::io::Result<Option<String>> {
+    let mut stderr = std::io::stderr();
+    write!(stderr, "{prompt}")?;
+    stderr.flush()?;
+
+    terminal::enable_raw_mode()?;
+    let mut input = String::new();
+    let result = loop {
+        let event = match event::read() {
+            Ok(event) => event,
+            Err(e) => break Err(e),
+        };
+        let Event::Key(key) = event else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Enter => break Ok(Some(input)),
+            KeyCode::Esc => break Ok(None),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                break Ok(None);
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+    };
+    terminal::disable_raw_mode()?;
+    writeln!(stderr)?;
+    result
+}
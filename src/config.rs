@@ -1,12 +1,30 @@
-use std::path::PathBuf;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+    time::Duration,
+};
 
 use directories::ProjectDirs;
 use ron::ser::PrettyConfig;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::{
+    backend::{
+        backup_name::BackupNameTemplate,
+        encryption::Passphrase,
+        hashing::{HashAlgorithm, HashSettings},
+        lua_sanitize::SanitizePattern,
+    },
     files::{AnyResult, ensure_directory},
-    wow,
+    tui_log,
+    ui::{messages::ConfirmRisk, recent_characters::RecentCharacter},
+    widgets::{
+        character_list::{ActivateAction, CharacterListColumn, SingleCharacterRealms},
+        toast::ToastPosition,
+    },
+    wow::{
+        self, BACKUPS_DIR_NAME, BackupLayout, CHRONOBIND_DIR, WoWCharacter, WoWInstall, WoWInstalls,
+    },
 };
 
 /// Application configuration options.
@@ -19,15 +37,438 @@ pub struct ChronoBindAppConfig {
     mock_mode: bool,
     /// Maximum automatic backups to keep per character.
     pub maximum_auto_backups: Option<usize>,
-    /// Whether to display character levels in the UI, if available.
-    pub display_character_levels: bool,
+    /// Number of backups of any kind above which a character is flagged, `None` to never flag.
+    #[serde(default = "ChronoBindAppConfig::default_backup_warning_threshold")]
+    pub backup_warning_threshold: Option<usize>,
+    /// Columns shown alongside character names in the character list.
+    #[serde(default = "CharacterListColumn::default_columns")]
+    pub character_list_columns: BTreeSet<CharacterListColumn>,
+    /// Whether character levels were shown, from configurations before the character list
+    /// columns could be chosen. Only read to hide the level column if it was `false`, and
+    /// dropped once migrated.
+    #[serde(
+        default,
+        skip_serializing,
+        deserialize_with = "deserialize_legacy_bool"
+    )]
+    display_character_levels: Option<bool>,
     /// Preferred branch.
     pub preferred_branch: Option<String>,
+    /// Branches tried in order when the preferred branch isn't installed, before falling back to
+    /// the first installed branch, I.e. `["retail", "classic", "classic_era"]`.
+    #[serde(default = "ChronoBindAppConfig::default_branch_fallback_order")]
+    pub branch_fallback_order: Vec<String>,
+    /// Character rename aliases, mapping the settings key of a previous character name,
+    /// I.e. `account/realm/name`, to its new name, so backups made under the old name remain
+    /// associated with the character.
+    #[serde(default)]
+    pub character_aliases: BTreeMap<String, String>,
+    /// Realm display names, mapping a realm's folder name to the name it is shown with.
+    #[serde(default)]
+    pub realm_aliases: BTreeMap<String, String>,
+    /// Favorite characters, keyed by `account/realm/name`.
+    #[serde(default)]
+    pub favorite_characters: Vec<String>,
+    /// Characters skipped by operations on many characters at once, such as pasting to a realm
+    /// or removing old backups of every character, keyed by `account/realm/name`.
+    #[serde(default)]
+    pub excluded_characters: Vec<String>,
+    /// Whether favorite characters, and the realms holding them, are listed first.
+    #[serde(default)]
+    pub favorites_first: bool,
+    /// Whether the addon section of each character's file list was left collapsed, keyed by
+    /// `account/realm/name`, for characters whose addon section has been collapsed or expanded.
+    #[serde(default)]
+    pub collapsed_addon_sections: BTreeMap<String, bool>,
+    /// Whether the addon section of a character's file list starts collapsed, for characters
+    /// whose addon section hasn't been collapsed or expanded yet.
+    #[serde(default)]
+    pub collapse_addon_sections_by_default: bool,
+    /// Which actions require confirmation before being performed.
+    #[serde(default)]
+    pub confirmations: ConfirmLevel,
+    /// Whether the user has been asked to disable mock mode after their first action in it.
+    #[serde(default)]
+    pub mock_mode_prompted: bool,
+    /// Whether the user has been asked to relaunch in a terminal with better symbol support.
+    #[serde(default)]
+    pub terminal_relaunch_prompted: bool,
+    /// How realms holding a single character are displayed in the character list.
+    #[serde(default)]
+    pub single_character_realms: SingleCharacterRealms,
+    /// What activating a character in the character list does.
+    #[serde(default)]
+    pub character_activate_action: ActivateAction,
+    /// Whether to always render the file list compactly, not only on short terminals.
+    #[serde(default)]
+    pub compact_file_list: bool,
+    /// Whether addon files are grouped by the addon they belong to in the file list.
+    #[serde(default)]
+    pub group_addon_files: bool,
+    /// Whether to mirror log messages to a log file in the data directory.
+    #[serde(default)]
+    pub log_to_file: bool,
+    /// Whether to resolve symlinks in install, branch and `WTF` paths before scanning,
+    /// so files are read from and written to their real location.
+    #[serde(default)]
+    pub resolve_symlinks: bool,
+    /// Whether the recently used characters are remembered between sessions.
+    #[serde(default)]
+    pub remember_recent_characters: bool,
+    /// The recently used characters, most recent first, if they are remembered between sessions.
+    #[serde(default)]
+    pub recent_characters: Vec<RecentCharacter>,
+    /// Unix time, in seconds, of the last full export of each branch, by branch identifier.
+    /// Exports of only the changed files export the files modified since this time.
+    #[serde(default)]
+    pub last_export_times: BTreeMap<String, u64>,
+    /// Free space, in MiB, to leave on the backup volume beyond a backup's estimated size.
+    /// Backups which would not leave this much space are refused.
+    #[serde(default = "ChronoBindAppConfig::default_backup_space_margin_mib")]
+    pub backup_space_margin_mib: u64,
+    /// Template for backup file names, I.e. `{name}-{realm}-{timestamp}`.
+    /// Must contain the `{name}` and `{timestamp}` placeholders.
+    #[serde(default = "ChronoBindAppConfig::default_backup_name_template")]
+    pub backup_name_template: String,
+    /// Whether backups are stored in per-character subfolders, or all together in the realm's folder.
+    #[serde(default)]
+    pub backup_layout: BackupLayout,
+    /// Algorithm used to hash file contents when comparing files.
+    /// Kept in the configuration so hashes are reproducible between runs and machines.
+    #[serde(default)]
+    pub hash_algorithm: HashAlgorithm,
+    /// Maximum number of files hashed at once, `0` to use every available core.
+    #[serde(default)]
+    pub hash_threads: usize,
+    /// Minimum time between the progress updates of a task, in milliseconds, `0` to update
+    /// after every file. Keeps tasks over many small files from flooding the interface.
+    #[serde(default = "ChronoBindAppConfig::default_progress_interval_ms")]
+    pub progress_interval_ms: u64,
+    /// Whether a snapshot of the character's `AddOns.txt` and the names of its enabled addons are
+    /// included in backups, to warn about addons which aren't installed when restoring.
+    #[serde(default)]
+    pub include_addon_manifest: bool,
+    /// Whether file selections are kept when the character list is refreshed, for files which
+    /// still exist.
+    #[serde(default = "ChronoBindAppConfig::default_keep_selections_on_refresh")]
+    pub keep_selections_on_refresh: bool,
+    /// Whether the selected character's folder is watched, refreshing its files when they change.
+    #[serde(default)]
+    pub watch_character_files: bool,
+    /// Files whose contents are encrypted with the passphrase within otherwise plain backups,
+    /// such as files identifying the account. Entries are paths relative to the character's
+    /// directory or file names matching in any directory.
+    #[serde(default)]
+    pub encrypted_files: Vec<String>,
+    /// Whether backups within a folder synced by cloud storage, such as `OneDrive` or Dropbox, are
+    /// warned about on startup and marked in the backup managers.
+    #[serde(default = "ChronoBindAppConfig::default_warn_cloud_synced_backups")]
+    pub warn_cloud_synced_backups: bool,
+    /// Names of the folders detected as synced by cloud storage, matched case-insensitively
+    /// against the start of each folder name in the path to the backups.
+    #[serde(default = "ChronoBindAppConfig::default_cloud_sync_folders")]
+    pub cloud_sync_folders: Vec<String>,
+    /// Whether a note is asked for before backing up all of a character's files from the backup
+    /// menu, stored within the backup.
+    #[serde(default)]
+    pub prompt_note_on_backup: bool,
+    /// Whether personal data, such as email addresses, `BattleTags` and account names, is removed
+    /// from `SavedVariables` and config files and account folder names when exporting, so exports
+    /// can be shared.
+    #[serde(default)]
+    pub sanitize_exports: bool,
+    /// Patterns of the personal data removed from `SavedVariables` and config files when
+    /// sanitising exports.
+    #[serde(default = "SanitizePattern::defaults")]
+    pub sanitize_patterns: Vec<SanitizePattern>,
+    /// How many folders deep character files are listed, below the character's folder for
+    /// config files and below its `SavedVariables` folder for addon files. `0` lists only the
+    /// files directly within them.
+    #[serde(default)]
+    pub character_scan_depth: usize,
+    /// Whether launching the game is offered once a backup has been restored, to check the
+    /// restored settings.
+    #[serde(default)]
+    pub offer_launch_after_restore: bool,
+    /// How long notifications are shown before they are dismissed, in seconds.
+    #[serde(default = "ChronoBindAppConfig::default_toast_duration_secs")]
+    pub toast_duration_secs: u64,
+    /// Corner of the screen notifications are shown in.
+    #[serde(default)]
+    pub toast_position: ToastPosition,
+    /// Whether `ChronoBind` and character backup folders found within the `WTF` folder are
+    /// left out of install exports, so existing backups aren't exported again within them.
+    #[serde(default = "ChronoBindAppConfig::default_exclude_backups_from_exports")]
+    pub exclude_backups_from_exports: bool,
+}
+
+/// Which actions ask for confirmation before being performed.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum ConfirmLevel {
+    /// Confirm every action, including low-risk ones such as pinning.
+    All,
+    /// Confirm only actions that delete or overwrite files.
+    #[default]
+    Destructive,
+    /// Only confirm critical actions, which are always confirmed.
+    None,
+}
+
+impl ConfirmLevel {
+    /// Every confirmation level, in cycling order.
+    pub const ALL: [Self; 3] = [Self::All, Self::Destructive, Self::None];
+
+    /// Returns `true` if an action of the given risk should be confirmed at this level.
+    #[inline]
+    #[must_use]
+    pub fn requires_confirmation(self, risk: ConfirmRisk) -> bool {
+        match self {
+            Self::All => true,
+            Self::Destructive => risk >= ConfirmRisk::Destructive,
+            Self::None => risk == ConfirmRisk::Critical,
+        }
+    }
+
+    /// Get the display name of the confirmation level.
+    #[inline]
+    #[must_use]
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::All => "All actions",
+            Self::Destructive => "Destructive actions",
+            Self::None => "None",
+        }
+    }
+
+    /// Get the next confirmation level, wrapping around.
+    #[inline]
+    #[must_use]
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|l| *l == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    /// Get the previous confirmation level, wrapping around.
+    #[inline]
+    #[must_use]
+    pub fn previous(self) -> Self {
+        let idx = Self::ALL.iter().position(|l| *l == self).unwrap_or(0);
+        Self::ALL[(idx + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
 }
 
 impl ChronoBindAppConfig {
     /// Default maximum automatic backups to keep per character.
     pub const DEFAULT_MAXIMUM_AUTO_BACKUPS: usize = 10;
+    /// Default number of backups above which a character is flagged.
+    pub const DEFAULT_BACKUP_WARNING_THRESHOLD: usize = 50;
+    /// Get the minimum time between the progress updates of a task.
+    #[inline]
+    #[must_use]
+    pub const fn progress_interval(&self) -> Duration {
+        Duration::from_millis(self.progress_interval_ms)
+    }
+
+    /// Default time notifications are shown for, in seconds.
+    pub const DEFAULT_TOAST_DURATION_SECS: u64 = 4;
+    /// Longest time notifications can be shown for, in seconds.
+    pub const MAX_TOAST_DURATION_SECS: u64 = 30;
+
+    /// Default number of backups above which a character is flagged.
+    #[must_use]
+    pub const fn default_backup_warning_threshold() -> Option<usize> {
+        Some(Self::DEFAULT_BACKUP_WARNING_THRESHOLD)
+    }
+
+    /// Default free space margin for backups, in MiB.
+    #[must_use]
+    pub const fn default_backup_space_margin_mib() -> u64 {
+        crate::backend::DEFAULT_BACKUP_SPACE_MARGIN / (1024 * 1024)
+    }
+
+    /// Default minimum time between the progress updates of a task, in milliseconds.
+    #[must_use]
+    pub const fn default_progress_interval_ms() -> u64 {
+        crate::backend::task::DEFAULT_PROGRESS_INTERVAL_MS
+    }
+
+    /// Default time notifications are shown for, in seconds.
+    #[must_use]
+    pub const fn default_toast_duration_secs() -> u64 {
+        Self::DEFAULT_TOAST_DURATION_SECS
+    }
+
+    /// Get how long notifications are shown before they are dismissed, within the allowed range.
+    #[inline]
+    #[must_use]
+    pub fn toast_duration(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(
+            self.toast_duration_secs
+                .clamp(1, Self::MAX_TOAST_DURATION_SECS),
+        )
+    }
+
+    /// Default for keeping file selections when the character list is refreshed.
+    #[must_use]
+    pub const fn default_keep_selections_on_refresh() -> bool {
+        true
+    }
+
+    /// Default for warning about backups within folders synced by cloud storage.
+    #[must_use]
+    pub const fn default_warn_cloud_synced_backups() -> bool {
+        true
+    }
+
+    /// Default for leaving backup folders within the `WTF` folder out of install exports.
+    #[must_use]
+    pub const fn default_exclude_backups_from_exports() -> bool {
+        true
+    }
+
+    /// Default branches tried when the preferred branch isn't installed.
+    #[must_use]
+    pub fn default_branch_fallback_order() -> Vec<String> {
+        vec![wow::WOW_RETAIL_IDENT.to_string()]
+    }
+
+    /// Choose the installed branch to load: the preferred branch, then the first installed
+    /// branch of the fallback order, then the first installed branch, logging which was chosen
+    /// and why. Returns `None` if no branches are installed.
+    #[must_use]
+    pub fn choose_branch<'a>(&self, installs: &'a WoWInstalls) -> Option<&'a WoWInstall> {
+        if let Some(preferred) = &self.preferred_branch {
+            if let Some(install) = installs.find_branch(preferred) {
+                log::debug!("Loading the preferred branch {preferred}");
+                return Some(install);
+            }
+            log::info!("Preferred branch {preferred} is not installed");
+        }
+        if let Some(install) = self
+            .branch_fallback_order
+            .iter()
+            .find_map(|branch| installs.find_branch(branch))
+        {
+            log::info!(
+                "Loading branch {}, the first installed branch of the fallback order",
+                install.branch_ident
+            );
+            return Some(install);
+        }
+        let install = installs.iter().next()?;
+        log::info!(
+            "No branch of the fallback order is installed, loading the first installed branch {}",
+            install.branch_ident
+        );
+        Some(install)
+    }
+
+    /// Default names of the folders detected as synced by cloud storage.
+    #[must_use]
+    pub fn default_cloud_sync_folders() -> Vec<String> {
+        wow::DEFAULT_CLOUD_SYNC_FOLDERS
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    /// Default template for backup file names.
+    #[must_use]
+    pub fn default_backup_name_template() -> String {
+        crate::backend::backup_name::DEFAULT_BACKUP_NAME_TEMPLATE.to_string()
+    }
+
+    /// Get the settings used by file operations and scans, passed to them as they are performed.
+    /// An invalid backup name template is logged, and the default template used in its place.
+    #[must_use]
+    pub fn runtime_settings(&self) -> RuntimeSettings {
+        let backup_name_template = BackupNameTemplate::parse(&self.backup_name_template)
+            .unwrap_or_else(|e| {
+                log::error!("Invalid backup name template, using the default template: {e}");
+                BackupNameTemplate::default()
+            });
+        let cloud_sync_folders = if self.warn_cloud_synced_backups {
+            self.cloud_sync_folders
+                .iter()
+                .map(|folder| folder.trim().to_lowercase())
+                .filter(|folder| !folder.is_empty())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        RuntimeSettings {
+            resolve_symlinks: self.resolve_symlinks,
+            backup_layout: self.backup_layout,
+            backup_name_template,
+            backup_space_margin: self.backup_space_margin_mib.saturating_mul(1024 * 1024),
+            character_scan_depth: self.character_scan_depth,
+            realm_aliases: self.realm_aliases.clone(),
+            include_addon_manifest: self.include_addon_manifest,
+            encrypted_files: self.encrypted_files.clone(),
+            cloud_sync_folders,
+            hash: HashSettings {
+                algorithm: self.hash_algorithm,
+                threads: self.hash_threads,
+            },
+            progress_interval: self.progress_interval(),
+            passphrase: None,
+        }
+    }
+}
+
+/// Settings used by file operations and scans, built from the configuration with
+/// [`ChronoBindAppConfig::runtime_settings`] and passed to the operations which use them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeSettings {
+    /// Whether paths and directory entries are resolved through symlinks before use.
+    pub resolve_symlinks: bool,
+    /// The layout new backups are stored with.
+    pub backup_layout: BackupLayout,
+    /// The template used for new backup file names.
+    pub backup_name_template: BackupNameTemplate,
+    /// Free space, in bytes, to leave on the backup volume beyond a backup's estimated size.
+    pub backup_space_margin: u64,
+    /// How many folders deep character files are listed.
+    pub character_scan_depth: usize,
+    /// Display names for realms, keyed by the realm's folder name.
+    pub realm_aliases: BTreeMap<String, String>,
+    /// Whether the enabled addons of a character are included in its backups.
+    pub include_addon_manifest: bool,
+    /// Files whose contents are encrypted within backups.
+    pub encrypted_files: Vec<String>,
+    /// Lowercase names of the folders detected as synced by cloud storage, empty to detect none.
+    pub cloud_sync_folders: Vec<String>,
+    /// Settings used when hashing file contents.
+    pub hash: HashSettings,
+    /// Minimum time between the progress updates sent by a task.
+    pub progress_interval: Duration,
+    /// Passphrase the `encrypted_files` are encrypted and decrypted with, if given.
+    pub passphrase: Option<Passphrase>,
+}
+
+impl RuntimeSettings {
+    /// Set the passphrase the `encrypted_files` are encrypted and decrypted with.
+    #[inline]
+    #[must_use]
+    pub fn with_passphrase(mut self, passphrase: Option<Passphrase>) -> Self {
+        self.passphrase = passphrase;
+        self
+    }
+
+    /// Get the name a realm is displayed with, its alias if one is set, otherwise its folder name.
+    #[inline]
+    #[must_use]
+    pub fn realm_display_name(&self, realm: &str) -> String {
+        wow::realm_display_name(&self.realm_aliases, realm)
+    }
+}
+
+impl Default for RuntimeSettings {
+    fn default() -> Self {
+        ChronoBindAppConfig::default().runtime_settings()
+    }
 }
 
 impl Default for ChronoBindAppConfig {
@@ -38,8 +479,50 @@ impl Default for ChronoBindAppConfig {
             show_friendly_names: true,
             mock_mode,
             preferred_branch: Some(wow::WOW_RETAIL_IDENT.to_string()),
-            display_character_levels: true,
+            branch_fallback_order: Self::default_branch_fallback_order(),
+            character_list_columns: CharacterListColumn::default_columns(),
+            display_character_levels: None,
             maximum_auto_backups: Some(Self::DEFAULT_MAXIMUM_AUTO_BACKUPS),
+            backup_warning_threshold: Self::default_backup_warning_threshold(),
+            character_aliases: BTreeMap::new(),
+            realm_aliases: BTreeMap::new(),
+            favorite_characters: Vec::new(),
+            collapsed_addon_sections: BTreeMap::new(),
+            collapse_addon_sections_by_default: false,
+            excluded_characters: Vec::new(),
+            favorites_first: false,
+            confirmations: ConfirmLevel::default(),
+            mock_mode_prompted: false,
+            terminal_relaunch_prompted: false,
+            compact_file_list: false,
+            group_addon_files: false,
+            single_character_realms: SingleCharacterRealms::default(),
+            character_activate_action: ActivateAction::default(),
+            log_to_file: false,
+            resolve_symlinks: false,
+            remember_recent_characters: false,
+            recent_characters: Vec::new(),
+            last_export_times: BTreeMap::new(),
+            backup_space_margin_mib: Self::default_backup_space_margin_mib(),
+            backup_name_template: Self::default_backup_name_template(),
+            backup_layout: BackupLayout::default(),
+            hash_algorithm: HashAlgorithm::default(),
+            hash_threads: 0,
+            progress_interval_ms: Self::default_progress_interval_ms(),
+            watch_character_files: false,
+            include_addon_manifest: false,
+            keep_selections_on_refresh: Self::default_keep_selections_on_refresh(),
+            encrypted_files: Vec::new(),
+            warn_cloud_synced_backups: Self::default_warn_cloud_synced_backups(),
+            cloud_sync_folders: Self::default_cloud_sync_folders(),
+            prompt_note_on_backup: false,
+            sanitize_exports: false,
+            sanitize_patterns: SanitizePattern::defaults(),
+            character_scan_depth: 0,
+            offer_launch_after_restore: false,
+            toast_duration_secs: Self::default_toast_duration_secs(),
+            toast_position: ToastPosition::default(),
+            exclude_backups_from_exports: Self::default_exclude_backups_from_exports(),
         }
     }
 }
@@ -57,6 +540,43 @@ impl ChronoBindAppConfig {
     pub const fn set_mock_mode(&mut self, new_value: bool) {
         self.mock_mode = new_value;
     }
+
+    /// Returns `true` if the character is skipped by operations on many characters at once.
+    #[inline]
+    #[must_use]
+    pub fn is_character_excluded(&self, character: &WoWCharacter) -> bool {
+        self.excluded_characters.contains(&character.settings_key())
+    }
+
+    /// Returns `true` if the addon section of the character's file list starts collapsed.
+    #[inline]
+    #[must_use]
+    pub fn is_addon_section_collapsed(&self, character: &WoWCharacter) -> bool {
+        self.collapsed_addon_sections
+            .get(&character.settings_key())
+            .copied()
+            .unwrap_or(self.collapse_addon_sections_by_default)
+    }
+
+    /// Get the patterns of personal data removed from exported `SavedVariables` and config files,
+    /// `None` if exports aren't sanitised.
+    #[inline]
+    #[must_use]
+    pub fn export_sanitize_patterns(&self) -> Option<&[SanitizePattern]> {
+        self.sanitize_exports
+            .then_some(self.sanitize_patterns.as_slice())
+    }
+
+    /// Get the names of the folders left out of the `WTF` folder when exporting an install.
+    #[inline]
+    #[must_use]
+    pub const fn export_excluded_dir_names(&self) -> &'static [&'static str] {
+        if self.exclude_backups_from_exports {
+            &[BACKUPS_DIR_NAME, CHRONOBIND_DIR]
+        } else {
+            &[]
+        }
+    }
 }
 
 impl ChronoBindAppConfig {
@@ -80,7 +600,7 @@ impl ChronoBindAppConfig {
         let config_src_str = std::fs::read_to_string(&config_file_path)?;
         log::debug!("Successfully read configuration file.. Parsing..");
 
-        let parsed = ron::from_str::<Self>(&config_src_str)?;
+        let parsed = ron::from_str::<Self>(&config_src_str)?.migrated();
         log::info!("Successfully parsed configuration file");
 
         Ok(Some(parsed))
@@ -126,6 +646,136 @@ impl ChronoBindAppConfig {
     }
 }
 
+impl ChronoBindAppConfig {
+    /// Serialise the configuration for embedding in an install export.
+    /// Recently used characters and export times are left out,
+    /// as they only make sense on this machine.
+    /// # Errors
+    /// Errors if serialising the configuration fails.
+    pub fn to_export_string(&self) -> AnyResult<String> {
+        let exported = Self {
+            recent_characters: Vec::new(),
+            last_export_times: BTreeMap::new(),
+            ..self.clone()
+        };
+        Ok(ron::ser::to_string_pretty(
+            &exported,
+            PrettyConfig::default(),
+        )?)
+    }
+
+    /// Parse a configuration embedded in an install export.
+    /// # Errors
+    /// Errors if parsing the configuration fails.
+    pub fn from_export_str(settings: &str) -> AnyResult<Self> {
+        Ok(ron::from_str::<Self>(settings)?.migrated())
+    }
+
+    /// Carry settings from configurations saved by earlier versions over to the settings
+    /// replacing them.
+    #[must_use]
+    fn migrated(mut self) -> Self {
+        if self.display_character_levels.take() == Some(false) {
+            self.character_list_columns
+                .remove(&CharacterListColumn::Level);
+        }
+        self
+    }
+
+    /// Get a copy of this configuration with the settings imported from an install export applied.
+    ///
+    /// Portable settings, listed in the struct literal below, are always taken. Settings specific
+    /// to this machine, such as the preferred branch or file logging, are only taken if
+    /// `include_machine_specific` is set, and are listed in the block below it.
+    /// Mock mode is never imported, so an import cannot change whether files are modified.
+    #[must_use]
+    pub fn with_imported_settings(&self, imported: &Self, include_machine_specific: bool) -> Self {
+        let mut merged = Self {
+            show_friendly_names: imported.show_friendly_names,
+            maximum_auto_backups: imported.maximum_auto_backups,
+            backup_warning_threshold: imported.backup_warning_threshold,
+            character_list_columns: imported.character_list_columns.clone(),
+            character_aliases: imported.character_aliases.clone(),
+            realm_aliases: imported.realm_aliases.clone(),
+            favorite_characters: imported.favorite_characters.clone(),
+            collapsed_addon_sections: imported.collapsed_addon_sections.clone(),
+            collapse_addon_sections_by_default: imported.collapse_addon_sections_by_default,
+            excluded_characters: imported.excluded_characters.clone(),
+            favorites_first: imported.favorites_first,
+            confirmations: imported.confirmations,
+            single_character_realms: imported.single_character_realms,
+            character_activate_action: imported.character_activate_action,
+            compact_file_list: imported.compact_file_list,
+            group_addon_files: imported.group_addon_files,
+            backup_name_template: imported.backup_name_template.clone(),
+            backup_layout: imported.backup_layout,
+            hash_algorithm: imported.hash_algorithm,
+            include_addon_manifest: imported.include_addon_manifest,
+            keep_selections_on_refresh: imported.keep_selections_on_refresh,
+            encrypted_files: imported.encrypted_files.clone(),
+            warn_cloud_synced_backups: imported.warn_cloud_synced_backups,
+            cloud_sync_folders: imported.cloud_sync_folders.clone(),
+            prompt_note_on_backup: imported.prompt_note_on_backup,
+            sanitize_exports: imported.sanitize_exports,
+            sanitize_patterns: imported.sanitize_patterns.clone(),
+            character_scan_depth: imported.character_scan_depth,
+            offer_launch_after_restore: imported.offer_launch_after_restore,
+            branch_fallback_order: imported.branch_fallback_order.clone(),
+            toast_duration_secs: imported.toast_duration_secs,
+            toast_position: imported.toast_position,
+            exclude_backups_from_exports: imported.exclude_backups_from_exports,
+            ..self.clone()
+        };
+        if include_machine_specific {
+            merged
+                .preferred_branch
+                .clone_from(&imported.preferred_branch);
+            merged.log_to_file = imported.log_to_file;
+            merged.resolve_symlinks = imported.resolve_symlinks;
+            merged.remember_recent_characters = imported.remember_recent_characters;
+            merged.backup_space_margin_mib = imported.backup_space_margin_mib;
+            merged.hash_threads = imported.hash_threads;
+            merged.progress_interval_ms = imported.progress_interval_ms;
+            merged.watch_character_files = imported.watch_character_files;
+        }
+        merged
+    }
+}
+
+/// Deserialise a setting saved as a plain `bool` by earlier versions, `None` if it is missing.
+fn deserialize_legacy_bool<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<bool>, D::Error> {
+    bool::deserialize(deserializer).map(Some)
+}
+
+/// Log file name, within the data directory.
+const LOG_FILE_NAME: &str = "chronobind.log";
+
+/// Get the path of the log file written when logging to a file is enabled.
+/// # Panics
+/// Panics if the project directories cannot be determined.
+#[must_use]
+pub fn get_log_file_path() -> PathBuf {
+    get_project_dirs().data_dir().join(LOG_FILE_NAME)
+}
+
+/// Start or stop mirroring log messages to the log file, to match the configuration.
+pub fn apply_file_logging(config: &ChronoBindAppConfig) {
+    if config.log_to_file == tui_log::file_logging_enabled() {
+        return;
+    }
+    if config.log_to_file {
+        let log_path = get_log_file_path();
+        match tui_log::enable_file_logging(&log_path) {
+            Ok(()) => log::info!("Logging to `{}`", log_path.display()),
+            Err(e) => log::error!("Failed to open log file `{}`: {e}", log_path.display()),
+        }
+    } else {
+        tui_log::disable_file_logging();
+    }
+}
+
 /// Project qualifier for application directories.
 const PROJ_QUALIFIER: &str = "dev";
 /// Project organisation for application directories.
@@ -148,3 +798,39 @@ fn get_config_dir() -> PathBuf {
     let proj_dirs = get_project_dirs();
     proj_dirs.config_dir().to_path_buf()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A saved configuration, as written before the character list columns could be chosen.
+    fn with_character_levels(shown: bool) -> ChronoBindAppConfig {
+        let saved = ChronoBindAppConfig::default()
+            .to_export_string()
+            .expect("Configuration should serialise")
+            .replacen('(', &format!("(display_character_levels: {shown},"), 1);
+        ChronoBindAppConfig::from_export_str(&saved).expect("Configuration should parse")
+    }
+
+    #[test]
+    fn hidden_character_levels_are_migrated_to_the_list_columns() {
+        let hidden = with_character_levels(false);
+        assert!(
+            !hidden
+                .character_list_columns
+                .contains(&CharacterListColumn::Level)
+        );
+        assert_eq!(hidden.display_character_levels, None);
+        assert!(
+            !hidden
+                .to_export_string()
+                .expect("Configuration should serialise")
+                .contains("display_character_levels")
+        );
+
+        assert_eq!(
+            with_character_levels(true).character_list_columns,
+            CharacterListColumn::default_columns()
+        );
+    }
+}
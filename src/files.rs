@@ -1,43 +1,147 @@
 use filesystem::DirEntry;
 use std::error::Error;
 use std::ffi::OsString;
-use std::fs as filesystem;
+use std::fs::{self as filesystem, FileType};
 use std::path::{Path, PathBuf};
 
 use crate::tui_log::mock_prefix;
 
 pub type AnyResult<T> = Result<T, Box<dyn Error>>;
 
-/// Reads a directory and returns an iterator over all folders within it.
+/// Get the file type of a directory entry, following symlinks to their target if
+/// `resolve_symlinks` is set.
+#[must_use]
+pub fn entry_file_type(entry: &DirEntry, resolve_symlinks: bool) -> Option<FileType> {
+    if resolve_symlinks {
+        filesystem::metadata(entry.path())
+            .ok()
+            .map(|m| m.file_type())
+    } else {
+        entry.file_type().ok()
+    }
+}
+
+/// Reads a directory and returns an iterator over all folders within it, including symlinks to
+/// folders if `resolve_symlinks` is set.
 /// # Errors
 /// Returns an error if there are any I/O issues.
-pub fn read_folders(dir: impl AsRef<Path>) -> AnyResult<impl Iterator<Item = DirEntry>> {
+pub fn read_folders(
+    dir: impl AsRef<Path>,
+    resolve_symlinks: bool,
+) -> AnyResult<impl Iterator<Item = DirEntry>> {
     Ok(std::fs::read_dir(dir.as_ref())?
         .filter_map(Result::ok)
-        .filter(|d| d.file_type().ok().is_some_and(|ft| ft.is_dir())))
+        .filter(move |d| entry_file_type(d, resolve_symlinks).is_some_and(|ft| ft.is_dir())))
 }
 
 /// Reads all folders in the given path and returns their names as a vector of strings.
 /// # Errors
 /// Returns an error if there are any I/O issues.
-pub fn read_folders_to_string(dir: impl AsRef<Path>) -> AnyResult<impl Iterator<Item = String>> {
-    Ok(read_folders(dir)?.filter_map(|d| Some(d.file_name().to_str()?.to_string())))
+pub fn read_folders_to_string(
+    dir: impl AsRef<Path>,
+    resolve_symlinks: bool,
+) -> AnyResult<impl Iterator<Item = String>> {
+    Ok(read_folders(dir, resolve_symlinks)?
+        .filter_map(|d| Some(d.file_name().to_str()?.to_string())))
 }
 
-/// Reads a directory and returns an iterator over all files within it.
+/// Reads a directory and returns an iterator over all files within it, including symlinks to
+/// files if `resolve_symlinks` is set.
 /// # Errors
 /// Returns an error if there are any I/O issues.
-pub fn read_files(dir: impl AsRef<Path>) -> AnyResult<impl Iterator<Item = DirEntry>> {
+pub fn read_files(
+    dir: impl AsRef<Path>,
+    resolve_symlinks: bool,
+) -> AnyResult<impl Iterator<Item = DirEntry>> {
     Ok(std::fs::read_dir(dir.as_ref())?
         .filter_map(Result::ok)
-        .filter(|d| d.file_type().ok().is_some_and(|ft| ft.is_file())))
+        .filter(move |d| entry_file_type(d, resolve_symlinks).is_some_and(|ft| ft.is_file())))
 }
 
 /// Reads a directory and returns an iterator over all files within it.
 /// # Errors
 /// Returns an error if there are any I/O issues.
-pub fn read_files_to_string(dir: impl AsRef<Path>) -> AnyResult<impl Iterator<Item = String>> {
-    Ok(read_files(dir)?.filter_map(|d| Some(d.file_name().to_str()?.to_string())))
+pub fn read_files_to_string(
+    dir: impl AsRef<Path>,
+    resolve_symlinks: bool,
+) -> AnyResult<impl Iterator<Item = String>> {
+    Ok(
+        read_files(dir, resolve_symlinks)?
+            .filter_map(|d| Some(d.file_name().to_str()?.to_string())),
+    )
+}
+
+/// Reads a directory and returns all files within it, and within its subfolders.
+///
+/// Subfolders are descended into up to `max_depth` levels deep, so a depth of `0` reads only
+/// the directory itself. Folders named in `excluded_dirs` are skipped at any depth, and symlinks
+/// are followed if `resolve_symlinks` is set.
+/// # Errors
+/// Returns an error if `dir` cannot be read, unreadable subfolders are skipped.
+pub fn read_files_to_depth(
+    dir: impl AsRef<Path>,
+    max_depth: usize,
+    excluded_dirs: &[&str],
+    resolve_symlinks: bool,
+) -> AnyResult<Vec<DirEntry>> {
+    let mut files = Vec::new();
+    let mut pending = vec![(std::fs::read_dir(dir.as_ref())?, 0)];
+    while let Some((entries, depth)) = pending.pop() {
+        for entry in entries.filter_map(Result::ok) {
+            let Some(file_type) = entry_file_type(&entry, resolve_symlinks) else {
+                continue;
+            };
+            if file_type.is_file() {
+                files.push(entry);
+            } else if file_type.is_dir()
+                && depth < max_depth
+                && !excluded_dirs
+                    .iter()
+                    .any(|excluded| entry.file_name() == *excluded)
+                && let Ok(sub_entries) = std::fs::read_dir(entry.path())
+            {
+                pending.push((sub_entries, depth + 1));
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Open a file with the system's default application for it.
+/// # Errors
+/// Returns an error if the opener cannot be launched.
+pub fn open_with_default_app(path: &Path) -> std::io::Result<()> {
+    let opener = if cfg!(target_os = "windows") {
+        "explorer"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    std::process::Command::new(opener).arg(path).spawn()?;
+    Ok(())
+}
+
+/// Clear the read-only attribute of a file, making it writable by its owner.
+/// # Errors
+/// Returns an error if the file's permissions cannot be read or changed.
+pub fn clear_read_only(path: &Path) -> std::io::Result<()> {
+    let mut permissions = filesystem::metadata(path)?.permissions();
+    if !permissions.readonly() {
+        return Ok(());
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        permissions.set_mode(permissions.mode() | 0o200);
+    }
+    #[cfg(not(unix))]
+    {
+        permissions.set_readonly(false);
+    }
+
+    filesystem::set_permissions(path, permissions)
 }
 
 /// Ensure that a directory exists at the given path, creating it if necessary.
@@ -65,35 +169,86 @@ pub fn walk_dir_recursive<T: AsRef<Path>>(
     base_path: &Path,
     excluded_dirs: &[T],
 ) -> AnyResult<Vec<PathBuf>> {
-    fn walk_dir_impl(
-        path: &Path,
-        excluded_dirs: &[PathBuf],
-        entries: &mut Vec<PathBuf>,
-    ) -> AnyResult<()> {
-        for entry in filesystem::read_dir(path)? {
-            let entry = entry?;
-            let path = entry.path();
-            if excluded_dirs.contains(&path) {
+    Ok(walk_dir_iter(base_path, excluded_dirs)?.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Returns a lazy iterator over all file paths recursively within `base_path`.
+///
+/// Paths are not collected up front, only the directory handles of the current descent are held.
+/// # Errors
+/// Returns an error if `base_path` cannot be read.
+pub fn walk_dir_iter<T: AsRef<Path>>(
+    base_path: &Path,
+    excluded_dirs: &[T],
+) -> AnyResult<WalkDirIter> {
+    let excluded_paths = excluded_dirs
+        .iter()
+        .map(|p| base_path.join(p.as_ref()))
+        .collect::<Vec<_>>();
+
+    Ok(WalkDirIter {
+        stack: vec![filesystem::read_dir(base_path)?],
+        excluded_paths,
+        excluded_names: Vec::new(),
+    })
+}
+
+/// Lazy, depth-first iterator over all files within a directory tree.
+/// Created by [`walk_dir_iter`].
+#[derive(Debug)]
+pub struct WalkDirIter {
+    /// Directory handles of the current descent, the last being the deepest.
+    stack: Vec<filesystem::ReadDir>,
+    /// Fully qualified paths excluded from the walk.
+    excluded_paths: Vec<PathBuf>,
+    /// Names of directories excluded from the walk at any depth.
+    excluded_names: Vec<String>,
+}
+
+impl WalkDirIter {
+    /// Exclude directories with any of the given names from the walk, at any depth.
+    #[must_use]
+    pub fn excluding_names<T: AsRef<str>>(mut self, names: &[T]) -> Self {
+        self.excluded_names = names.iter().map(|n| n.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Returns `true` if the directory has one of the excluded names.
+    fn is_excluded_name(&self, dir: &Path) -> bool {
+        dir.file_name()
+            .is_some_and(|name| self.excluded_names.iter().any(|n| name == n.as_str()))
+    }
+}
+
+impl Iterator for WalkDirIter {
+    type Item = std::io::Result<PathBuf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let current_dir = self.stack.last_mut()?;
+            let Some(entry) = current_dir.next() else {
+                self.stack.pop();
+                continue;
+            };
+
+            let path = match entry {
+                Ok(entry) => entry.path(),
+                Err(e) => return Some(Err(e)),
+            };
+            if self.excluded_paths.contains(&path) {
                 continue;
             }
 
             if path.is_file() {
-                entries.push(path);
-            } else if path.is_dir() {
-                walk_dir_impl(&path, excluded_dirs, entries)?;
+                return Some(Ok(path));
+            } else if path.is_dir() && !self.is_excluded_name(&path) {
+                match filesystem::read_dir(&path) {
+                    Ok(read_dir) => self.stack.push(read_dir),
+                    Err(e) => return Some(Err(e)),
+                }
             }
         }
-        Ok(())
     }
-
-    let excluded_paths = excluded_dirs
-        .iter()
-        .map(|p| base_path.join(p.as_ref()))
-        .collect::<Vec<_>>();
-
-    let mut entries = Vec::new();
-    walk_dir_impl(base_path, &excluded_paths, &mut entries)?;
-    Ok(entries)
 }
 
 /// Utility trait for converting `OsStr` and related types to `String`.
@@ -173,3 +328,27 @@ pub fn file_extension<P: AsRef<Path>>(path: P) -> String {
 pub fn cmp_extension<P: AsRef<Path>, S: AsRef<str>>(path: P, extension: S) -> bool {
     file_extension(path.as_ref()) == extension.as_ref().to_lowercase()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn clear_read_only_makes_a_read_only_file_writable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path =
+            std::env::temp_dir().join(format!("chronobind-read-only-{}.txt", std::process::id()));
+        filesystem::write(&path, "locked").expect("Temporary file should be written");
+        filesystem::set_permissions(&path, filesystem::Permissions::from_mode(0o444))
+            .expect("Permissions should be set");
+        assert!(filesystem::metadata(&path).is_ok_and(|m| m.permissions().readonly()));
+
+        let cleared = clear_read_only(&path);
+        let readonly = filesystem::metadata(&path).map(|m| m.permissions().readonly());
+        let _ = filesystem::remove_file(&path);
+        assert!(cleared.is_ok());
+        assert!(!readonly.expect("File should exist"));
+    }
+}
@@ -0,0 +1,199 @@
+use std::io::Write;
+use std::time::Duration;
+
+use color_eyre::Result;
+use color_eyre::eyre::{bail, eyre};
+
+use crate::backend::{self, CharWithInstallLocal, task::BackendTaskPtr};
+use crate::cli::{ChronoCLIArgs, read_passphrase};
+use crate::config::{ChronoBindAppConfig, RuntimeSettings, apply_file_logging};
+use crate::tui_log::mock_prefix;
+use crate::wow::{WoWCharacter, WoWInstall, WoWInstalls};
+
+/// Interval between polls of a running task's progress.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Run `ChronoBind` without the terminal UI, performing the operation requested by the CLI arguments.
+/// # Errors
+/// Returns an error if the operation cannot be performed, or any part of it fails.
+pub fn run(args: &ChronoCLIArgs) -> Result<()> {
+    if !args.backup_all {
+        bail!("Nothing to do, `--no-ui` requires an operation such as `--backup-all`");
+    }
+
+    let config = ChronoBindAppConfig::load_config_or_default()
+        .map_err(|e| eyre!("Failed to load configuration file: {e}"))?;
+    apply_file_logging(&config);
+    let settings = config
+        .runtime_settings()
+        .with_passphrase(read_passphrase(&config.encrypted_files));
+    let installs = WoWInstalls::new_from_installs(
+        args.locate_wow_installs(settings.resolve_symlinks)
+            .map_err(|e| eyre!("Failed to locate WoW installations: {e}"))?,
+    );
+
+    let install = select_install(args, &config, &installs)?;
+    if !install.has_account_dir() {
+        bail!(
+            "No `WTF/Account` directory in `{}`, have you logged into the game on it at least once?",
+            install.install_path
+        );
+    }
+    let characters = install
+        .find_all_characters_and_files(&settings, &config.character_aliases)
+        .ok_or_else(|| eyre!("Failed to find characters in `{}`", install.install_path))?
+        .into_iter()
+        .filter(|c| matches_scope(c, args))
+        .filter(|c| !is_skipped_as_excluded(c, args, &config))
+        .collect::<Vec<_>>();
+
+    if characters.is_empty() {
+        bail!(
+            "No characters matched on branch `{}`",
+            install.display_branch_name()
+        );
+    }
+
+    backup_all(&characters, install, &settings, config.mock_mode())
+}
+
+/// Select the installation to operate on, from `--branch`, otherwise the preferred branch or
+/// the configured fallbacks.
+fn select_install<'a>(
+    args: &ChronoCLIArgs,
+    config: &ChronoBindAppConfig,
+    installs: &'a WoWInstalls,
+) -> Result<&'a WoWInstall> {
+    let Some(branch) = &args.branch else {
+        return config
+            .choose_branch(installs)
+            .ok_or_else(|| eyre!("No WoW installations found"));
+    };
+
+    installs.find_branch(branch).ok_or_else(|| {
+        let available = installs
+            .iter()
+            .map(|install| install.branch_ident.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        eyre!("No WoW installation found for branch `{branch}` (available: {available})")
+    })
+}
+
+/// Returns `true` if the character is within the `--character` and `--realm` scope.
+fn matches_scope(character: &WoWCharacter, args: &ChronoCLIArgs) -> bool {
+    let matches =
+        |filter: Option<&String>, value: &str| filter.is_none_or(|f| f.eq_ignore_ascii_case(value));
+    matches(args.character.as_ref(), &character.name)
+        && matches(args.realm.as_ref(), &character.realm)
+}
+
+/// Returns `true` if the character is excluded from bulk operations, and isn't named by
+/// `--character`, so an excluded character can still be backed up on its own.
+fn is_skipped_as_excluded(
+    character: &WoWCharacter,
+    args: &ChronoCLIArgs,
+    config: &ChronoBindAppConfig,
+) -> bool {
+    let skipped = args.character.is_none() && config.is_character_excluded(character);
+    if skipped {
+        println!(
+            "Skipping {} - {}, it is excluded from bulk operations",
+            character.name, character.realm
+        );
+    }
+    skipped
+}
+
+/// Back up every given character, reporting progress to `stdout`.
+fn backup_all(
+    characters: &[WoWCharacter],
+    install: &WoWInstall,
+    settings: &RuntimeSettings,
+    mock_mode: bool,
+) -> Result<()> {
+    let total = characters.len();
+    let mut failures = Vec::new();
+
+    let mut skipped = 0;
+    for (i, character) in characters.iter().enumerate() {
+        if !character.has_files() {
+            println!(
+                "{}[{}/{total}] Skipping {} - {}, it has no files",
+                mock_prefix(mock_mode),
+                i + 1,
+                character.name,
+                character.realm
+            );
+            skipped += 1;
+            continue;
+        }
+        println!(
+            "{}[{}/{total}] Backing up {} - {}",
+            mock_prefix(mock_mode),
+            i + 1,
+            character.name,
+            character.realm
+        );
+
+        let task = backend::backup_character_all_async(
+            CharWithInstallLocal {
+                character: character.clone(),
+                install: install.clone(),
+            },
+            false,
+            false,
+            settings,
+            mock_mode,
+        );
+        if let Err(e) = run_task_blocking(Box::new(task)) {
+            eprintln!("Failed to back up {}: {e}", character.name);
+            failures.push(character.character_identifier_string());
+        }
+    }
+
+    if failures.is_empty() {
+        println!("Backed up {} characters", total - skipped);
+        Ok(())
+    } else {
+        Err(eyre!(
+            "{} of {total} backups failed: {}",
+            failures.len(),
+            failures.join(", ")
+        ))
+    }
+}
+
+/// Run a task chain to completion on the current thread, printing its progress.
+fn run_task_blocking(mut task: BackendTaskPtr) -> Result<()> {
+    loop {
+        if !task.run() {
+            bail!("Failed to start task `{}`", task.task_name());
+        }
+
+        let mut last_progress = String::new();
+        while !task.finished() {
+            std::thread::sleep(POLL_INTERVAL);
+            task.poll();
+
+            let progress = task.progress_formatted(false);
+            if progress != last_progress {
+                print!("\r  {}: {progress}", task.task_name());
+                std::io::stdout().flush().ok();
+                last_progress = progress;
+            }
+        }
+        println!();
+
+        if let Some(error) = task.error() {
+            bail!("{error}");
+        }
+        if let Some(summary) = task.summary() {
+            println!("  {summary}");
+        }
+        match task.next_task() {
+            Some(next) => task = next,
+            None => return Ok(()),
+        }
+    }
+}
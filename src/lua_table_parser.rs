@@ -1,4 +1,13 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+/// Represents a key of a Lua table.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LuaKey {
+    /// An integer key, such as the index of an array entry.
+    Integer(i64),
+    /// A string key.
+    String(String),
+}
 
 /// Represents a Lua value.
 #[derive(Debug, Clone, PartialEq)]
@@ -7,10 +16,18 @@ pub enum LuaValue {
     Boolean(bool),
     Number(f64),
     String(String),
-    Table(HashMap<String, Self>),
+    /// A string which isn't valid UTF-8, such as a serialized binary blob, kept byte-for-byte.
+    Bytes(Vec<u8>),
+    Table(BTreeMap<LuaKey, Self>),
 }
 
 impl LuaValue {
+    /// Returns the value of the given string key, if the value is a table containing it.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Self> {
+        self.as_table()?.get(&LuaKey::String(key.to_string()))
+    }
+
     /// Returns the value as a string slice, if it is a string.
     #[must_use]
     pub fn as_string(&self) -> Option<&str> {
@@ -44,7 +61,7 @@ impl LuaValue {
 
     /// Returns the value as a table, if it is a table.
     #[must_use]
-    pub const fn as_table(&self) -> Option<&HashMap<String, Self>> {
+    pub const fn as_table(&self) -> Option<&BTreeMap<LuaKey, Self>> {
         if let Self::Table(t) = self {
             Some(t)
         } else {
@@ -83,13 +100,26 @@ impl<'a> LuaTableParser<'a> {
         self.lookahead = self.chars.next();
     }
 
-    /// Keep advancing the parser until a non-whitespace character is found.
+    /// Peeks at the character after the next one without consuming either.
+    #[inline]
+    #[must_use]
+    fn peek_second(&self) -> Option<char> {
+        self.chars.clone().next()
+    }
+
+    /// Keep advancing the parser until a character outside of whitespace and `--` comments is found.
     #[inline]
     fn skip_whitespace(&mut self) {
-        while let Some(c) = self.peek()
-            && c.is_whitespace()
-        {
-            self.bump();
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => self.bump(),
+                Some('-') if self.peek_second() == Some('-') => {
+                    while self.peek().is_some_and(|c| c != '\n') {
+                        self.bump();
+                    }
+                }
+                _ => break,
+            }
         }
     }
 
@@ -126,23 +156,123 @@ impl<'a> LuaTableParser<'a> {
         Some(s)
     }
 
-    /// Parse a Lua string from the current position.
+    /// Parse a quoted Lua string from the current position, resolving escape sequences.
+    /// Escapes stand for bytes, so the string is returned as bytes, which may not be valid UTF-8.
+    #[inline]
+    fn parse_string(&mut self) -> Option<Vec<u8>> {
+        self.skip_whitespace();
+        let quote = self.peek().filter(|c| matches!(c, '"' | '\''))?;
+        self.bump();
+
+        let mut bytes = Vec::new();
+        loop {
+            let c = self.peek()?;
+            self.bump();
+            match c {
+                c if c == quote => return Some(bytes),
+                '\\' => self.parse_escape(&mut bytes)?,
+                // Unescaped line breaks end the string early in Lua, so the string is malformed.
+                '\n' => return None,
+                c => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+            }
+        }
+    }
+
+    /// Parse a quoted Lua string value, kept as bytes if it isn't valid UTF-8 so it is written
+    /// back unchanged.
     #[inline]
-    fn parse_string(&mut self) -> Option<String> {
-        if !self.expect('"') {
+    fn parse_quoted_value(&mut self) -> Option<LuaValue> {
+        Some(match String::from_utf8(self.parse_string()?) {
+            Ok(s) => LuaValue::String(s),
+            Err(e) => LuaValue::Bytes(e.into_bytes()),
+        })
+    }
+
+    /// Parse the escape sequence following a backslash within a string, pushing the bytes it stands for.
+    #[inline]
+    fn parse_escape(&mut self, bytes: &mut Vec<u8>) -> Option<()> {
+        let c = self.peek()?;
+        self.bump();
+        match c {
+            'n' => bytes.push(b'\n'),
+            'r' => bytes.push(b'\r'),
+            't' => bytes.push(b'\t'),
+            'a' => bytes.push(0x07),
+            'b' => bytes.push(0x08),
+            'f' => bytes.push(0x0c),
+            'v' => bytes.push(0x0b),
+            // Up to three decimal digits, I.e. `\9` or `\195\169`.
+            '0'..='9' => {
+                let mut code = c.to_digit(10)?;
+                for _ in 0..2 {
+                    let Some(digit) = self.peek().and_then(|c| c.to_digit(10)) else {
+                        break;
+                    };
+                    code = code * 10 + digit;
+                    self.bump();
+                }
+                bytes.push(u8::try_from(code).ok()?);
+            }
+            // Exactly two hexadecimal digits, I.e. `\x7F`.
+            'x' => {
+                let mut code = 0;
+                for _ in 0..2 {
+                    code = code * 16 + self.peek()?.to_digit(16)?;
+                    self.bump();
+                }
+                bytes.push(u8::try_from(code).ok()?);
+            }
+            // Skips the following whitespace, including line breaks.
+            'z' => {
+                while self.peek().is_some_and(char::is_whitespace) {
+                    self.bump();
+                }
+            }
+            // Quotes, backslashes and escaped line breaks stand for themselves.
+            c => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+        }
+        Some(())
+    }
+
+    /// Parse a long bracket Lua string from the current position, I.e. `[[text]]` or `[==[text]==]`.
+    /// Long strings have no escapes, and a line break directly after the opening bracket is skipped.
+    #[inline]
+    fn parse_long_string(&mut self) -> Option<String> {
+        if !self.expect('[') {
             return None;
         }
-        let mut out = String::new();
-        while let Some(c) = self.peek()
-            && c != '"'
-        {
-            out.push(c);
+        let mut level = 0;
+        while self.peek() == Some('=') {
+            level += 1;
             self.bump();
         }
-        if !self.expect('"') {
+        if self.peek() != Some('[') {
             return None;
         }
-        Some(out)
+        self.bump();
+        if self.peek() == Some('\r') {
+            self.bump();
+        }
+        if self.peek() == Some('\n') {
+            self.bump();
+        }
+
+        let closing = format!("]{}]", "=".repeat(level));
+        let mut out = String::new();
+        loop {
+            out.push(self.peek()?);
+            self.bump();
+            if out.ends_with(&closing) {
+                out.truncate(out.len() - closing.len());
+                return Some(out);
+            }
+        }
+    }
+
+    /// Returns `true` if the parser is at the opening bracket of a long string, I.e. `[[` or `[=[`.
+    #[inline]
+    fn at_long_string(&self) -> bool {
+        self.peek() == Some('[') && matches!(self.peek_second(), Some('[' | '='))
     }
 
     /// Parse a Lua number from the current position.
@@ -150,7 +280,7 @@ impl<'a> LuaTableParser<'a> {
     fn parse_number(&mut self) -> Option<f64> {
         self.skip_whitespace();
         let mut s = String::new();
-        while matches!(self.peek(), Some(c) if c.is_ascii_digit() || c == '.' || c == '-' || c == '+')
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E'))
         {
             s.push(self.peek()?);
             self.bump();
@@ -163,9 +293,25 @@ impl<'a> LuaTableParser<'a> {
     fn parse_value(&mut self) -> Option<LuaValue> {
         self.skip_whitespace();
         match self.peek() {
-            Some('"') => Some(LuaValue::String(self.parse_string()?)),
+            Some('"' | '\'') => self.parse_quoted_value(),
+            Some('[') if self.at_long_string() => Some(LuaValue::String(self.parse_long_string()?)),
             Some('{') => Some(LuaValue::Table(self.parse_table()?)),
-            Some(c) if c.is_ascii_digit() => Some(LuaValue::Number(self.parse_number()?)),
+            Some('(') => Some(LuaValue::Number(self.parse_nan()?)),
+            Some('-') if self.peek_second().is_some_and(|c| c.is_ascii_alphabetic()) => {
+                self.bump();
+                let identifier = self.parse_identifier()?;
+                match self.identifier_value(&identifier)? {
+                    LuaValue::Number(n) => Some(LuaValue::Number(-n)),
+                    _ => None,
+                }
+            }
+            Some(c) if c.is_ascii_digit() || matches!(c, '-' | '.') => {
+                Some(LuaValue::Number(self.parse_number()?))
+            }
+            Some(c) if c.is_ascii_alphabetic() => {
+                let identifier = self.parse_identifier()?;
+                self.identifier_value(&identifier)
+            }
             _ => {
                 log::warn!(
                     "Unexpected character while parsing value: {:?}",
@@ -176,44 +322,139 @@ impl<'a> LuaTableParser<'a> {
         }
     }
 
-    /// Parse a key into a Lua table from the current position.
+    /// Parse the not-a-number value `(0/0)` from the current position, as written for `NaN`.
+    #[inline]
+    fn parse_nan(&mut self) -> Option<f64> {
+        if !self.expect('(') {
+            return None;
+        }
+        let dividend = self.parse_number()?;
+        if !self.expect('/') {
+            return None;
+        }
+        let divisor = self.parse_number()?;
+        if !self.expect(')') {
+            return None;
+        }
+        (dividend == 0.0 && divisor == 0.0).then_some(f64::NAN)
+    }
+
+    /// Get the value of an identifier which was just parsed, reading the rest of `math.huge`
+    /// if the identifier is `math`. `None` if the identifier isn't a value.
+    #[inline]
+    fn identifier_value(&mut self, identifier: &str) -> Option<LuaValue> {
+        if identifier != "math" {
+            return Self::keyword_value(identifier);
+        }
+        if self.peek() != Some('.') {
+            log::warn!("Unexpected identifier while parsing value: {identifier}");
+            return None;
+        }
+        self.bump();
+        let field = self.parse_identifier()?;
+        if field == "huge" {
+            Some(LuaValue::Number(f64::INFINITY))
+        } else {
+            log::warn!("Unexpected identifier while parsing value: math.{field}");
+            None
+        }
+    }
+
+    /// Get the value of a keyword, `None` if the identifier isn't a value keyword.
+    #[inline]
+    fn keyword_value(identifier: &str) -> Option<LuaValue> {
+        match identifier {
+            "true" => Some(LuaValue::Boolean(true)),
+            "false" => Some(LuaValue::Boolean(false)),
+            "nil" => Some(LuaValue::Nil),
+            _ => {
+                log::warn!("Unexpected identifier while parsing value: {identifier}");
+                None
+            }
+        }
+    }
+
+    /// Parse a bracketed key into a Lua table from the current position, I.e. `["name"]`, `['name']` or `[1]`.
     #[inline]
-    fn parse_key(&mut self) -> Option<String> {
+    fn parse_bracketed_key(&mut self) -> Option<LuaKey> {
+        if !self.expect('[') {
+            return None;
+        }
         self.skip_whitespace();
-        self.expect('[');
-        let key = self.parse_string()?;
-        self.expect(']');
-        Some(key)
+        let key = if matches!(self.peek(), Some('"' | '\'')) {
+            // Keys which aren't valid UTF-8 aren't supported, so the file is left unparsed.
+            LuaKey::String(String::from_utf8(self.parse_string()?).ok()?)
+        } else if self.at_long_string() {
+            LuaKey::String(self.parse_long_string()?)
+        } else {
+            let number = self.parse_number()?;
+            #[allow(clippy::cast_possible_truncation)]
+            let integer = number as i64;
+            // Only integer keys are supported, as float keys can't be compared exactly.
+            #[allow(clippy::cast_precision_loss, clippy::float_cmp)]
+            if integer as f64 != number {
+                return None;
+            }
+            LuaKey::Integer(integer)
+        };
+        self.expect(']').then_some(key)
     }
 
     /// Parse a Lua table from the current position.
+    /// Entries without a key are positional, keyed by their index from 1 as in Lua.
     #[inline]
-    fn parse_table(&mut self) -> Option<HashMap<String, LuaValue>> {
-        pub const SAFETY_LIMIT: usize = 10_000;
+    fn parse_table(&mut self) -> Option<BTreeMap<LuaKey, LuaValue>> {
+        /// Bail out rather than return a partial table if there are more entries than this.
+        pub const SAFETY_LIMIT: usize = 1_000_000;
 
-        let mut map = HashMap::new();
-        self.expect('{');
+        let mut map = BTreeMap::new();
+        let mut next_index = 1;
+        if !self.expect('{') {
+            return None;
+        }
 
         for _ in 0..SAFETY_LIMIT {
             self.skip_whitespace();
             if self.peek() == Some('}') {
                 self.bump();
-                break;
+                return Some(map);
             }
 
-            let key = self.parse_key()?;
-            self.skip_whitespace();
-            self.expect('=');
-            let value = self.parse_value()?;
+            let (key, value) = match self.peek()? {
+                '[' if !self.at_long_string() => {
+                    let key = self.parse_bracketed_key()?;
+                    if !self.expect('=') {
+                        return None;
+                    }
+                    (key, self.parse_value()?)
+                }
+                c if c.is_ascii_alphabetic() || c == '_' => {
+                    let identifier = self.parse_identifier()?;
+                    if self.expect('=') {
+                        (LuaKey::String(identifier), self.parse_value()?)
+                    } else {
+                        next_index += 1;
+                        (
+                            LuaKey::Integer(next_index - 1),
+                            self.identifier_value(&identifier)?,
+                        )
+                    }
+                }
+                _ => {
+                    next_index += 1;
+                    (LuaKey::Integer(next_index - 1), self.parse_value()?)
+                }
+            };
             map.insert(key, value);
 
             self.skip_whitespace();
-            if self.peek() == Some(',') {
+            if matches!(self.peek(), Some(',' | ';')) {
                 self.bump();
             }
         }
 
-        Some(map)
+        log::warn!("Lua table has more than {SAFETY_LIMIT} entries, not parsing it");
+        None
     }
 
     /// Parse global variables from the Lua script.
@@ -228,8 +469,9 @@ impl<'a> LuaTableParser<'a> {
             }
 
             let name = self.parse_identifier()?;
-            self.skip_whitespace();
-            self.expect('=');
+            if !self.expect('=') {
+                return None;
+            }
             let value = self.parse_value()?;
 
             globals.insert(name, value);
@@ -240,3 +482,126 @@ impl<'a> LuaTableParser<'a> {
         Some(globals)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_string_value(literal: &str) -> Option<String> {
+        let mut globals = LuaTableParser::new(&format!("Value = {literal}")).parse_globals()?;
+        match globals.remove("Value")? {
+            LuaValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn parsed(literal: &str) -> String {
+        parse_string_value(literal).unwrap_or_else(|| panic!("`{literal}` should parse"))
+    }
+
+    #[test]
+    fn simple_escapes_are_resolved() {
+        assert_eq!(parsed(r#""a\nb""#), "a\nb");
+        assert_eq!(parsed(r#""tab\there\r""#), "tab\there\r");
+        assert_eq!(parsed(r#""back\\slash""#), "back\\slash");
+        assert_eq!(parsed(r#""say \"hi\"""#), "say \"hi\"");
+        assert_eq!(parsed(r"'it\'s'"), "it's");
+        assert_eq!(parsed(r#""\a\b\f\v""#), "\x07\x08\x0c\x0b");
+    }
+
+    #[test]
+    fn decimal_escapes_take_up_to_three_digits() {
+        assert_eq!(parsed(r#""\65\066\0671""#), "ABC1");
+        assert_eq!(parsed(r#""\195\169""#), "é");
+        assert_eq!(parse_string_value(r#""\256""#), None);
+    }
+
+    #[test]
+    fn hex_escapes_take_exactly_two_digits() {
+        assert_eq!(parsed(r#""\x41\x7a""#), "Az");
+        assert_eq!(parsed(r#""\xC3\xA9""#), "é");
+        assert_eq!(parse_string_value(r#""\x4""#), None);
+        assert_eq!(parse_string_value(r#""\xZZ""#), None);
+    }
+
+    #[test]
+    fn z_escape_skips_following_whitespace() {
+        assert_eq!(parsed("\"one\\z\n    two\""), "onetwo");
+    }
+
+    #[test]
+    fn escaped_newline_stands_for_a_newline() {
+        assert_eq!(parsed("\"one\\\ntwo\""), "one\ntwo");
+    }
+
+    #[test]
+    fn unescaped_newline_is_malformed() {
+        assert_eq!(parse_string_value("\"one\ntwo\""), None);
+    }
+
+    #[test]
+    fn invalid_utf8_is_kept_as_bytes() {
+        let bytes = |literal: &str| {
+            LuaTableParser::new(&format!("Value = {literal}"))
+                .parse_globals()
+                .and_then(|mut globals| globals.remove("Value"))
+        };
+        assert_eq!(
+            bytes(r#""ok\255\xC3""#),
+            Some(LuaValue::Bytes(b"ok\xFF\xC3".to_vec()))
+        );
+        assert_eq!(
+            bytes(r#""\xC3\xA9\xFF""#),
+            Some(LuaValue::Bytes(b"\xC3\xA9\xFF".to_vec()))
+        );
+        assert_eq!(bytes(r#"{ ["\255"] = 1 }"#), None);
+    }
+
+    #[test]
+    fn single_quoted_values_allow_double_quotes() {
+        assert_eq!(parsed(r#"'say "hi"'"#), "say \"hi\"");
+    }
+
+    #[test]
+    fn long_bracket_strings_have_no_escapes() {
+        assert_eq!(parsed(r"[[raw \n text]]"), r"raw \n text");
+        assert_eq!(parsed("[[multi\nline]]"), "multi\nline");
+    }
+
+    #[test]
+    fn leveled_long_brackets_only_close_at_their_level() {
+        assert_eq!(
+            parsed("[==[has ]] and ]=] inside]==]"),
+            "has ]] and ]=] inside"
+        );
+        assert_eq!(parse_string_value("[=[unclosed]]"), None);
+    }
+
+    #[test]
+    fn newline_after_opening_long_bracket_is_skipped() {
+        assert_eq!(parsed("[[\nfirst line\n]]"), "first line\n");
+        assert_eq!(parsed("[==[\r\nwindows]==]"), "windows");
+        assert_eq!(parsed("[[\n\nsecond]]"), "\nsecond");
+    }
+
+    #[test]
+    fn long_bracket_keys_are_string_keys() {
+        let globals =
+            LuaTableParser::new("T = { [ [[a key]] ] = 1, [ [=[other]=] ] = 2, [[positional]] }")
+                .parse_globals()
+                .expect("Table parses");
+        let table = globals["T"].as_table().expect("T is a table");
+        assert_eq!(
+            table.get(&LuaKey::String("a key".to_string())),
+            Some(&LuaValue::Number(1.0))
+        );
+        assert_eq!(
+            table.get(&LuaKey::String("other".to_string())),
+            Some(&LuaValue::Number(2.0))
+        );
+        assert_eq!(
+            table.get(&LuaKey::Integer(1)),
+            Some(&LuaValue::String("positional".to_string()))
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::hash::BuildHasher;
+
+use crate::lua_table_parser::{LuaKey, LuaValue};
+
+/// Write global variables as a Lua script, in the layout the game writes `SavedVariables` in.
+///
+/// The result can be read back by `LuaTableParser` and by the game.
+/// Globals are written in order of their names, so the output is stable.
+#[must_use]
+pub fn write_globals<S: BuildHasher>(globals: &HashMap<String, LuaValue, S>) -> String {
+    let mut names = globals.keys().collect::<Vec<_>>();
+    names.sort();
+
+    let mut out = String::new();
+    for name in names {
+        out.push_str(name);
+        out.push_str(" = ");
+        out.push_str(&globals[name].serialize());
+        out.push('\n');
+    }
+    out
+}
+
+impl LuaValue {
+    /// Serialize the value as Lua, readable by `LuaTableParser` and by the game.
+    /// Table entries are written in key order, so the output is stable.
+    #[must_use]
+    pub fn serialize(&self) -> String {
+        let mut out = String::new();
+        write_value(&mut out, self, 0);
+        out
+    }
+}
+
+/// Write a Lua value, indenting nested table entries by one tab per level.
+fn write_value(out: &mut String, value: &LuaValue, depth: usize) {
+    match value {
+        LuaValue::Nil => out.push_str("nil"),
+        LuaValue::Boolean(b) => out.push_str(if *b { "true" } else { "false" }),
+        LuaValue::Number(n) => write_number(out, *n),
+        LuaValue::String(s) => write_string(out, s),
+        LuaValue::Bytes(bytes) => write_bytes(out, bytes),
+        LuaValue::Table(table) => {
+            out.push_str("{\n");
+            for (key, value) in table {
+                out.push_str(&"\t".repeat(depth + 1));
+                match key {
+                    LuaKey::Integer(index) => {
+                        let _ = write!(out, "[{index}]");
+                    }
+                    LuaKey::String(key) => {
+                        out.push('[');
+                        write_string(out, key);
+                        out.push(']');
+                    }
+                }
+                out.push_str(" = ");
+                write_value(out, value, depth + 1);
+                out.push_str(",\n");
+            }
+            out.push_str(&"\t".repeat(depth));
+            out.push('}');
+        }
+    }
+}
+
+/// Write a Lua number, without a fractional part for whole numbers.
+fn write_number(out: &mut String, n: f64) {
+    if n.is_nan() {
+        out.push_str("(0/0)");
+    } else if n.is_infinite() {
+        out.push_str(if n > 0.0 { "math.huge" } else { "-math.huge" });
+    } else {
+        let _ = write!(out, "{n}");
+    }
+}
+
+/// Write a double quoted Lua string, escaping quotes, backslashes and control characters.
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' || c == '\x7f' => {
+                let _ = write!(out, "\\{:03}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Write a double quoted Lua string of bytes which aren't valid UTF-8, escaping every byte
+/// outside of printable ASCII so the bytes are read back unchanged.
+fn write_bytes(out: &mut String, bytes: &[u8]) {
+    out.push('"');
+    for &b in bytes {
+        match b {
+            b'"' => out.push_str("\\\""),
+            b'\\' => out.push_str("\\\\"),
+            b' '..=b'~' => out.push(char::from(b)),
+            b => {
+                let _ = write!(out, "\\{b:03}");
+            }
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lua_table_parser::LuaTableParser;
+
+    fn parse(input: &str) -> HashMap<String, LuaValue> {
+        LuaTableParser::new(input)
+            .parse_globals()
+            .expect("Input parses")
+    }
+
+    #[test]
+    fn parse_serialize_parse_round_trips() {
+        let input = r#"
+            Settings = {
+                ["name"] = "Quote \" and \\ backslash\nnew line",
+                ['single'] = 'it''s',
+                scale = 1.25,
+                whole = 3,
+                negative = -42,
+                enabled = true,
+                disabled = false,
+                list = { "a", "b", { nested = "c" } },
+                [10] = "sparse",
+                [ [[long key]] ] = [==[long ]] value]==],
+                control = "\1\127",
+            }
+            Count = 7
+            Empty = {}
+        "#;
+        let first = parse(input);
+        let written = write_globals(&first);
+        assert_eq!(parse(&written), first);
+        assert_eq!(write_globals(&parse(&written)), written);
+    }
+
+    #[test]
+    fn non_finite_numbers_round_trip() {
+        let written = write_globals(&HashMap::from([
+            ("NotANumber".to_string(), LuaValue::Number(f64::NAN)),
+            ("Huge".to_string(), LuaValue::Number(f64::INFINITY)),
+            (
+                "NegativeHuge".to_string(),
+                LuaValue::Number(f64::NEG_INFINITY),
+            ),
+            (
+                "InTable".to_string(),
+                LuaValue::Table([(LuaKey::Integer(1), LuaValue::Number(f64::INFINITY))].into()),
+            ),
+        ]));
+        let parsed = parse(&written);
+
+        assert!(
+            parsed["NotANumber"]
+                .number_as_f64()
+                .is_some_and(f64::is_nan)
+        );
+        assert_eq!(parsed["Huge"], LuaValue::Number(f64::INFINITY));
+        assert_eq!(parsed["NegativeHuge"], LuaValue::Number(f64::NEG_INFINITY));
+        assert_eq!(
+            parsed["InTable"],
+            LuaValue::Table([(LuaKey::Integer(1), LuaValue::Number(f64::INFINITY))].into())
+        );
+    }
+
+    #[test]
+    fn positional_math_huge_is_parsed() {
+        let parsed = parse("List = { math.huge, -math.huge, (0/0) }");
+        let list = parsed["List"].as_table().expect("List is a table");
+        assert_eq!(list[&LuaKey::Integer(1)], LuaValue::Number(f64::INFINITY));
+        assert_eq!(
+            list[&LuaKey::Integer(2)],
+            LuaValue::Number(f64::NEG_INFINITY)
+        );
+        assert!(
+            list[&LuaKey::Integer(3)]
+                .number_as_f64()
+                .is_some_and(f64::is_nan)
+        );
+    }
+
+    #[test]
+    fn unknown_math_fields_are_rejected() {
+        assert!(
+            LuaTableParser::new("Pi = math.pi")
+                .parse_globals()
+                .is_none()
+        );
+    }
+}
@@ -5,7 +5,9 @@ pub mod backend;
 pub mod cli;
 pub mod config;
 pub mod files;
+pub mod headless;
 pub mod lua_table_parser;
+pub mod lua_table_writer;
 pub mod palette;
 pub mod popups;
 pub mod tui_log;
@@ -17,42 +19,71 @@ use clap::Parser;
 use itertools::Itertools;
 use ratatui::buffer::Buffer;
 use ratatui::widgets::Widget;
-use widgets::character_list::NavigationAction;
+use widgets::character_list::{EmptyListReason, NavigationAction};
 use widgets::console::ConsoleWidget;
 use widgets::file_list::FileSelectionAction;
 
-use std::time::Duration;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use color_eyre::Result;
 use color_eyre::eyre::Context;
-use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::Stylize;
 use ratatui::text::{Line, Span};
 use ratatui::{DefaultTerminal, Frame};
 
-use crate::backend::task::{BackendTask, IOTask};
+use crate::backend::addon_profile;
+use crate::backend::archive_cache::{ArchiveCache, SharedArchiveCache};
+use crate::backend::archive_manifest::BackupManifest;
+use crate::backend::character_scan::{CharacterScan, FoundCharacter};
+use crate::backend::character_watcher::CharacterWatcher;
+use crate::backend::task::{BackendTask, IOProgress, IOTask, IOTaskKind, run_chain_blocking};
+use crate::backend::{CharWithInstallLocal, RestoreScope};
 use crate::cli::ChronoCLIArgs;
-use crate::config::ChronoBindAppConfig;
+use crate::config::{ChronoBindAppConfig, RuntimeSettings, apply_file_logging};
 use crate::palette::{ENTER_SYMBOL, PALETTE};
+use crate::popups::alias_popup::{AliasPopup, AliasPopupCommand};
 use crate::popups::backup_manager_popup::{BackupManagerPopup, BackupManagerPopupCommand};
+use crate::popups::backup_note_popup::BackupNotePopup;
 use crate::popups::backup_popup::{BackupPopup, BackupPopupCommand};
 use crate::popups::branch_popup::{BranchPopup, BranchPopupCommand};
+use crate::popups::character_info_popup::CharacterInfoPopup;
+use crate::popups::columns_popup::ColumnsPopup;
 use crate::popups::confirm_popup::ConfirmationPopup;
 use crate::popups::export_manager_popup::{ExportManagerMessage, ExportManagerPopup, ImportDialog};
+use crate::popups::file_preview_popup::FilePreviewPopup;
+use crate::popups::global_backup_manager_popup::{
+    GlobalBackupManagerPopup, GlobalBackupManagerPopupCommand,
+};
 use crate::popups::options_popup::{OptionsPopup, OptionsPopupCommand};
-use crate::popups::progress_popup::ProgressPopup;
+use crate::popups::paste_preview_popup::PastePreviewPopup;
+use crate::popups::progress_popup::{ProgressPopup, quit_during_task_message};
+use crate::popups::realm_alias_popup::{RealmAliasPopup, RealmAliasPopupCommand};
+use crate::popups::realm_summary_popup::{RealmSummaryPopup, RealmSummaryPopupCommand};
+use crate::popups::recent_characters_popup::{RecentCharactersPopup, RecentCharactersPopupCommand};
+use crate::popups::restore_file_popup::RestoreFilePopup;
 use crate::popups::restore_popup::{RestorePopup, RestorePopupCommand};
-use crate::ui::messages::{AppMessage, ConfirmActionText, PopupMessage};
+use crate::ui::messages::{AppMessage, ConfirmActionText, ConfirmRisk, PopupMessage};
 use crate::ui::{
     Character, CharacterWithIndex, CharacterWithInstall, KeyCodeExt,
+    character::{apply_realm_aliases, mark_duplicate_characters},
     main_character_ui::MainCharacterUI,
+    recent_characters::RecentCharacters,
 };
 use crate::widgets::popup::{Popup, PopupPtr};
+use crate::widgets::toast::{ToastLevel, ToastQueue};
 use crate::wow::{WoWCharacterBackup, WoWInstall, WoWInstalls};
 
 /// Whether to relaunch the terminal in debug mode on Windows Terminal if better symbols are not supported.
 const RELAUNCH_IN_DEBUG: bool = true;
+/// How long the mock mode banner is highlighted after mock mode is toggled.
+const MOCK_MODE_FLASH_DURATION: Duration = Duration::from_secs(2);
+/// How often events are polled while characters are being scanned, to show found characters promptly.
+const SCAN_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 /// Entry point..
 fn main() -> Result<()> {
@@ -72,6 +103,10 @@ fn main() -> Result<()> {
     );
 
     let cli_args = cli::ChronoCLIArgs::parse();
+    if cli_args.no_ui {
+        tui_log::set_stderr_echo_level(Some(log::Level::Warn));
+        return headless::run(&cli_args);
+    }
     args_entry_setup(&cli_args);
 
     let mut app = ChronoBindApp::new(&cli_args);
@@ -118,6 +153,14 @@ fn set_console_window_title(title: &str) -> crate::files::AnyResult<()> {
     Ok(())
 }
 
+/// Copy text to the clipboard through the terminal, which works over SSH too.
+/// # Errors
+/// Returns an error if writing to stdout fails.
+fn copy_to_clipboard(text: &str) -> std::io::Result<()> {
+    use ratatui::crossterm::{clipboard::CopyToClipboard, execute};
+    execute!(std::io::stdout(), CopyToClipboard::to_clipboard_from(text))
+}
+
 /// Different input modes for the application.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum InputMode {
@@ -130,11 +173,25 @@ pub enum InputMode {
     Popup,
 }
 
+/// The panes of the main screen that can be focused.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pane {
+    /// The character list.
+    #[default]
+    Characters,
+    /// The selected character's file list.
+    Files,
+    /// The console output, while it is visible.
+    Console,
+}
+
 /// Main application.
 #[derive(Debug, Default)]
 pub struct ChronoBindApp {
     /// Application configuration settings.
     config: ChronoBindAppConfig,
+    /// Settings of the backend operations, derived from the configuration.
+    settings: RuntimeSettings,
     /// CLI arguments provided on startup.
     cli_args: ChronoCLIArgs,
 
@@ -147,11 +204,34 @@ pub struct ChronoBindApp {
     wow_installations: WoWInstalls,
     /// List of characters across the selected branch.
     characters: Vec<Character>,
+    /// Background scan for the selected branch's characters, while it is running.
+    character_scan: Option<CharacterScan>,
+    /// Characters loaded before the running scan started, whose file selections are carried
+    /// over to the matching characters as they're found again.
+    characters_before_scan: Vec<Character>,
+    /// Characters with a backup, restore or paste task running, by their branch and settings key,
+    /// so a second task isn't started for a character mid-operation. Each is released once its
+    /// task ends, whether it completed, failed or was cancelled.
+    busy_characters: HashSet<String>,
+    /// Details read from backup archives, shared with the popups showing them.
+    archive_cache: SharedArchiveCache,
+    /// Watcher for changes to the selected character's files, if enabled.
+    character_watcher: Option<CharacterWatcher>,
+    /// The directory the character watcher was last pointed at, even if watching it failed.
+    watched_character_path: Option<PathBuf>,
     /// Index of the character from which files were copied.
     copied_char: Option<usize>,
+    /// Characters most recently selected, backed up or restored, across all branches.
+    recent_characters: RecentCharacters,
+    /// When mock mode was last toggled, to briefly highlight the change in the title bar.
+    mock_mode_toggled_at: Option<Instant>,
+    /// Notifications briefly shown over the interface.
+    toasts: ToastQueue,
 
     /// Current input mode of the application.
     input_mode: InputMode,
+    /// Whether the console has focus while it is visible, rather than the pane of the input mode.
+    console_focused: bool,
 
     /// Main UI drawing and widgets.
     main_ui: MainCharacterUI,
@@ -166,15 +246,6 @@ pub struct ChronoBindApp {
 impl ChronoBindApp {
     #[must_use]
     pub fn new(args: &ChronoCLIArgs) -> Self {
-        // Sample WoW characters with their associated files
-        let wow_installs = match wow::locate_wow_installs() {
-            Ok(installs) => installs,
-            Err(e) => {
-                log::error!("Failed to locate WoW installations: {e}");
-                Vec::new()
-            }
-        };
-
         let config = match ChronoBindAppConfig::load_config_or_default() {
             Ok(cfg) => cfg,
             Err(e) => {
@@ -182,9 +253,27 @@ impl ChronoBindApp {
                 ChronoBindAppConfig::default()
             }
         };
+        apply_file_logging(&config);
+        let settings = config
+            .runtime_settings()
+            .with_passphrase(cli::read_passphrase(&config.encrypted_files));
+
+        let wow_installs = match args.locate_wow_installs(config.resolve_symlinks) {
+            Ok(installs) => installs,
+            Err(e) => {
+                log::error!("Failed to locate WoW installations: {e}");
+                Vec::new()
+            }
+        };
+        let recent_characters = if config.remember_recent_characters {
+            RecentCharacters::from_entries(config.recent_characters.clone())
+        } else {
+            RecentCharacters::default()
+        };
 
         let mut app = Self {
             config,
+            settings,
             cli_args: args.clone(),
 
             should_exit: false,
@@ -192,9 +281,19 @@ impl ChronoBindApp {
             selected_branch: None,
             wow_installations: WoWInstalls::new_from_installs(wow_installs),
             characters: Vec::new(),
+            character_scan: None,
+            characters_before_scan: Vec::new(),
+            busy_characters: HashSet::new(),
+            archive_cache: ArchiveCache::new_shared(),
+            character_watcher: None,
+            watched_character_path: None,
             copied_char: None,
+            recent_characters,
+            mock_mode_toggled_at: None,
+            toasts: ToastQueue::default(),
 
             input_mode: InputMode::Navigation,
+            console_focused: false,
 
             main_ui: MainCharacterUI::new(),
             console_widget: ConsoleWidget::new(),
@@ -202,22 +301,113 @@ impl ChronoBindApp {
             popup_stack: Vec::new(),
         };
 
+        app.main_ui.character_list_widget.single_character_realms =
+            app.config.single_character_realms;
+        app.main_ui.character_list_widget.activate_action = app.config.character_activate_action;
+        app.main_ui
+            .character_list_widget
+            .favorite_characters
+            .clone_from(&app.config.favorite_characters);
+        app.main_ui
+            .character_list_widget
+            .excluded_characters
+            .clone_from(&app.config.excluded_characters);
+        app.main_ui.character_list_widget.favorites_first = app.config.favorites_first;
+        app.main_ui
+            .character_list_widget
+            .realm_aliases
+            .clone_from(&app.config.realm_aliases);
+
         let branch_to_load = app
             .config
-            .preferred_branch
-            .clone()
-            .unwrap_or_else(|| wow::WOW_RETAIL_IDENT.to_string());
-        app.set_selected_branch(&branch_to_load);
+            .choose_branch(&app.wow_installations)
+            .map(|install| install.branch_ident.clone());
+        if let Some(branch) = branch_to_load
+            && app.set_selected_branch(&branch)
+            && let Some(scan) = &app.character_scan
+        {
+            app.open_popup(ProgressPopup::new(scan.progress_task()));
+        }
+        if app.wow_installations.is_empty() {
+            app.main_ui.character_list_widget.empty_reason = Some(EmptyListReason::NoInstalls);
+        }
+        app.warn_cloud_synced_backups();
 
         if let Some(provided_file) = &app.cli_args.file_to_import {
             app.open_popup(ImportDialog::new_with_path(provided_file));
         }
+        app.prompt_terminal_relaunch();
 
         app
     }
 }
 
 impl ChronoBindApp {
+    /// Warn about installs whose backups are within a folder synced by cloud storage, as a
+    /// backup caught part way through syncing may be incomplete when restored.
+    fn warn_cloud_synced_backups(&mut self) {
+        let mut any_synced = false;
+        for install in self.wow_installations.iter() {
+            let chronobind_dir = install.get_chronobind_dir();
+            if let Some(folder) =
+                wow::cloud_sync_folder(&self.settings.cloud_sync_folders, &chronobind_dir)
+            {
+                log::warn!(
+                    "{} backups are in `{}`, within the cloud synced folder `{folder}`. \
+                    Backups may be incomplete while they sync; pause syncing while backing up \
+                    or restoring, or exclude the `{}` folder from syncing.",
+                    install.display_branch_name(),
+                    chronobind_dir.display(),
+                    wow::CHRONOBIND_DIR,
+                );
+                any_synced = true;
+            }
+        }
+        if any_synced {
+            self.toasts.push(
+                ToastLevel::Warning,
+                "Backups are in a cloud synced folder, see the console (`)",
+            );
+        }
+    }
+
+    /// Locate the `WoW` installations again, such as when none were found because Battle.net
+    /// was still starting, and load the preferred branch, or a fallback, if any are found.
+    fn retry_install_detection(&mut self) {
+        log::info!("Retrying WoW installation detection..");
+        let installs = self
+            .cli_args
+            .locate_wow_installs(self.config.resolve_symlinks)
+            .unwrap_or_else(|e| {
+                log::error!("Failed to locate WoW installations: {e}");
+                Vec::new()
+            });
+        let install_count = installs.len();
+        let plural = if install_count == 1 { "" } else { "s" };
+        self.toasts.push(
+            if install_count == 0 {
+                ToastLevel::Warning
+            } else {
+                ToastLevel::Info
+            },
+            format!("Found {install_count} WoW installation{plural}"),
+        );
+        self.wow_installations = WoWInstalls::new_from_installs(installs);
+
+        let branch_to_load = self
+            .config
+            .choose_branch(&self.wow_installations)
+            .map(|install| install.branch_ident.clone());
+        if let Some(branch) = branch_to_load {
+            log::info!("Found {install_count} WoW installation{plural}");
+            self.set_selected_branch(&branch);
+            self.warn_cloud_synced_backups();
+        } else {
+            log::warn!("Still no WoW installations found");
+            self.main_ui.character_list_widget.empty_reason = Some(EmptyListReason::NoInstalls);
+        }
+    }
+
     /// Find a `WoW` installation by its branch identifier.
     #[inline]
     #[must_use]
@@ -269,7 +459,9 @@ impl ChronoBindApp {
     #[must_use]
     pub fn character_with_install(&self, index: usize) -> Option<CharacterWithInstall<'_>> {
         let character = self.characters.get(index)?;
-        let install = self.find_wow_branch(&character.character.branch)?;
+        let install = self
+            .wow_installations
+            .find_character_install(&character.character)?;
         Some((character, install))
     }
 
@@ -278,7 +470,8 @@ impl ChronoBindApp {
     #[must_use]
     pub fn get_wow_branch_for_character(&self, index: usize) -> Option<&WoWInstall> {
         let character = self.characters.get(index)?;
-        self.find_wow_branch(&character.character.branch)
+        self.wow_installations
+            .find_character_install(&character.character)
     }
 
     /// Refresh the backups for the character at the given index.
@@ -289,10 +482,32 @@ impl ChronoBindApp {
         let Some(character) = self.characters.get_mut(index) else {
             return false;
         };
-        character.character.refresh_backups(&install)
+        character
+            .character
+            .refresh_backups(&install, &self.settings.backup_name_template)
     }
 
-    /// Refresh the backups for the character at the given index.
+    /// Refresh the backups of every character on the selected branch.
+    pub fn refresh_all_character_backups(&mut self) {
+        for index in 0..self.characters.len() {
+            if !self.refresh_character_backups(index) {
+                log::warn!("Failed to refresh backups of character at index {index}");
+            }
+        }
+    }
+
+    /// Get every character on the selected branch with its associated index for use in popups.
+    #[must_use]
+    pub fn characters_with_indices(&self) -> Vec<CharacterWithIndex> {
+        self.characters
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(index, character)| CharacterWithIndex(character, index))
+            .collect()
+    }
+
+    /// Refresh the files and backups for the character at the given index.
     pub fn refresh_character(&mut self, index: usize) -> bool {
         let Some(install) = self.get_wow_branch_for_character(index).cloned() else {
             return false;
@@ -300,7 +515,179 @@ impl ChronoBindApp {
         let Some(character) = self.characters.get_mut(index) else {
             return false;
         };
-        character.character.refresh_character_info(&install)
+        character.refresh(&install, &self.settings)
+    }
+
+    /// Find names on the character's realm that have backups but no matching character,
+    /// which may be previous names of the character at the given index.
+    #[must_use]
+    pub fn find_rename_candidates(&self, index: usize) -> Vec<String> {
+        let Some((character, install)) = self.character_with_install(index) else {
+            return vec![];
+        };
+        let realm_characters = self
+            .characters
+            .iter()
+            .filter(|c| c.realm() == character.realm() && c.account() == character.account())
+            .map(|c| c.name().to_string())
+            .collect::<Vec<_>>();
+        character.character.find_orphaned_backup_names(
+            install,
+            &self.settings.backup_name_template,
+            &realm_characters,
+        )
+    }
+
+    /// Pair each of the given rename candidates of the character at the given index with the
+    /// number of backups made under it.
+    #[must_use]
+    pub fn rename_candidate_backup_counts(
+        &self,
+        index: usize,
+        candidates: Vec<String>,
+    ) -> Vec<(String, usize)> {
+        let Some((character, install)) = self.character_with_install(index) else {
+            return vec![];
+        };
+        candidates
+            .into_iter()
+            .map(|name| {
+                let count = character
+                    .character
+                    .backups_for_name(install, &self.settings.backup_name_template, &name)
+                    .len();
+                (name, count)
+            })
+            .collect()
+    }
+
+    /// Get the indices of every other character on the same realm and branch as the character
+    /// at `index`, leaving out characters excluded from bulk operations.
+    #[must_use]
+    pub fn realm_paste_targets(&self, index: usize) -> Vec<usize> {
+        let Some(source) = self.characters.get(index) else {
+            return vec![];
+        };
+        self.characters
+            .iter()
+            .enumerate()
+            .filter(|(i, c)| {
+                *i != index
+                    && c.realm() == source.realm()
+                    && !c.is_cross_branch(source)
+                    && !self.config.is_character_excluded(&c.character)
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Paste the selected files of the character at `src_idx` to every other character on its realm.
+    /// Each paste runs in turn with its own safety backup, and a failed paste doesn't stop the pastes
+    /// to the other characters. The outcome for each character is reported once all have run.
+    fn paste_to_realm(&mut self, src_idx: usize) {
+        let Some(src_char) = self.character_with_install(src_idx) else {
+            log::error!("Invalid source character index for realm paste: {src_idx}");
+            return;
+        };
+        let files_to_paste = src_char.0.get_all_selected_files();
+        if files_to_paste.is_empty() {
+            log::warn!("No files selected to paste!");
+            return;
+        }
+        let src_local: CharWithInstallLocal = src_char.into();
+
+        let mut pastes = Vec::new();
+        let mut touched_characters = vec![src_idx];
+        let mut completion_messages = Vec::new();
+        for dest_idx in self.realm_paste_targets(src_idx) {
+            let Some(dest_char) = self.character_with_install(dest_idx) else {
+                continue;
+            };
+            let name = dest_char.0.display_name(false);
+            pastes.push((
+                name,
+                backend::paste_character_files_async(
+                    dest_char.into(),
+                    src_local.clone(),
+                    &files_to_paste,
+                    &self.settings,
+                    self.config.mock_mode(),
+                ),
+            ));
+            touched_characters.push(dest_idx);
+            completion_messages.push(AppMessage::PerformBackupManagement(dest_idx));
+        }
+        if pastes.is_empty() {
+            log::warn!("No other characters on the realm to paste to!");
+            return;
+        }
+
+        log::info!(
+            "Pasting {} files from {} to {} characters: {}",
+            files_to_paste.len(),
+            src_local.character.name,
+            pastes.len(),
+            pastes.iter().map(|(name, _)| name.as_str()).join(", ")
+        );
+        // Every character pasted to is locked, along with the source, whose files are read.
+        let Some(keys) = self.begin_characters_operation(&touched_characters) else {
+            return;
+        };
+        let src_name = src_local.character.name;
+        let poll_interval = self.settings.progress_interval;
+        let mut task = IOTask::new(move |tx| {
+            let total = pastes.len();
+            tx.send(IOProgress::Started { total: Some(total) })?;
+            let mut failed = Vec::new();
+            for (completed, (name, paste)) in pastes.into_iter().enumerate() {
+                let outcome = run_chain_blocking(paste.boxed(), poll_interval, |task| {
+                    tx.send(IOProgress::Advanced {
+                        completed,
+                        total,
+                        label: Some(format!("{name}: {}", task.progress_formatted(false))),
+                    })?;
+                    Ok(())
+                });
+                match outcome {
+                    Ok(summary) => log::info!(
+                        "Pasted files from {src_name} to {name}: {}",
+                        summary.as_deref().unwrap_or("done")
+                    ),
+                    Err(e) => {
+                        log::error!("Failed to paste files from {src_name} to {name}: {e}");
+                        failed.push(name);
+                    }
+                }
+                tx.send(IOProgress::Advanced {
+                    completed: completed + 1,
+                    total,
+                    label: None,
+                })?;
+            }
+
+            if !failed.is_empty() {
+                return Err(format!(
+                    "Failed to paste files from {src_name} to {} of {total} characters: {}",
+                    failed.len(),
+                    failed.join(", ")
+                )
+                .into());
+            }
+            tx.send(IOProgress::Summary(format!(
+                "Pasted files from {src_name} to {total} characters"
+            )))?;
+            Ok(())
+        })
+        .name("Pasting to realm")
+        .kind(IOTaskKind::Destructive);
+        // The characters are released first, as managing their backups starts a task for each.
+        for key in keys {
+            task.add_on_finished(AppMessage::ReleaseCharacter(key));
+        }
+        for message in completion_messages {
+            task.add_on_finished(message);
+        }
+        self.handle_task(task);
     }
 
     /// Set the currently selected branch identifier, and load the appropriate characters.
@@ -308,38 +695,210 @@ impl ChronoBindApp {
         let Some(install) = self.find_wow_branch(branch).cloned() else {
             return false;
         };
-        self.main_ui.character_list_widget.branch_display = Some(install.display_branch_name());
+        let install_count = self.wow_installations.find_branch_installs(branch).count();
+        self.main_ui.character_list_widget.branch_display = Some(if install_count > 1 {
+            format!(
+                "{} ({install_count} installs)",
+                install.display_branch_name()
+            )
+        } else {
+            install.display_branch_name()
+        });
         self.selected_branch = Some(branch.to_string());
-        let Some(characters) = self.load_branch_characters(branch) else {
-            return false;
-        };
-        self.characters = characters;
-        true
+        self.start_character_scan(branch)
     }
 
-    /// Load the characters from a given `WoW` branch identifier.
-    pub fn load_branch_characters(&mut self, branch: &str) -> Option<Vec<Character>> {
-        self.characters.clear();
+    /// Start scanning for the characters of a given `WoW` branch identifier in the background,
+    /// replacing the loaded characters and any scan already running.
+    pub fn start_character_scan(&mut self, branch: &str) -> bool {
+        let mut previous_characters = std::mem::take(&mut self.characters);
+        if self.character_scan.is_some() {
+            // Characters not found again yet by an interrupted scan keep their selection too.
+            previous_characters.append(&mut self.characters_before_scan);
+        }
+        self.characters_before_scan = if self.config.keep_selections_on_refresh {
+            previous_characters
+                .into_iter()
+                .filter(|character| character.branch() == branch && character.any_file_selected())
+                .collect()
+        } else {
+            Vec::new()
+        };
         self.main_ui.character_list_widget.state.select(Some(0));
         self.copied_char = None;
-
-        let Some(install) = self.find_wow_branch(branch) else {
+        self.character_scan = None;
+        self.main_ui.character_list_widget.scanning = false;
+        self.main_ui.character_list_widget.empty_reason = None;
+
+        let installs = self
+            .wow_installations
+            .find_branch_installs(branch)
+            .cloned()
+            .collect::<Vec<_>>();
+        if installs.is_empty() {
             log::error!("No WoW installation found for branch: {branch}");
-            return None;
+            return false;
+        }
+
+        log::debug!("Scanning for characters on branch {branch}..");
+        self.character_scan = Some(CharacterScan::start(
+            branch,
+            installs,
+            self.config.character_aliases.clone(),
+            self.settings.clone(),
+        ));
+        self.main_ui.character_list_widget.scanning = true;
+        true
+    }
+
+    /// Merge the characters found by the background scan since the last poll into the list,
+    /// keeping the selected realm or character selected, and finish the scan once it is done.
+    fn poll_character_scan(&mut self) {
+        let Some(scan) = self.character_scan.as_mut() else {
+            return;
         };
+        let found = scan.poll();
+        let finished = scan.is_finished();
+        if !found.is_empty() {
+            let widget = &mut self.main_ui.character_list_widget;
+            let previous_items = widget.get_character_list_items(&self.characters);
+            self.characters.extend(
+                found.iter().map(|found| {
+                    scanned_character(found, &self.config, &self.characters_before_scan)
+                }),
+            );
+            widget.reselect_after_update(&previous_items, &self.characters);
+        }
+        if finished && let Some(scan) = self.character_scan.take() {
+            self.finish_character_scan(&scan);
+        }
+    }
 
-        let Some(characters) = install
-            .find_all_characters_and_files()
-            .map(|chars| chars.iter().map(Character::new).collect::<Vec<_>>())
-        else {
-            log::error!(
-                "Failed to find characters in installation at {}",
-                install.install_path
+    /// Finish loading the characters found by a completed background scan.
+    fn finish_character_scan(&mut self, scan: &CharacterScan) {
+        self.main_ui.character_list_widget.scanning = false;
+        self.characters_before_scan.clear();
+        self.main_ui.character_list_widget.empty_reason = if !self.characters.is_empty() {
+            None
+        } else if scan.installs_without_accounts() > 0 {
+            Some(EmptyListReason::NoAccountDir)
+        } else if scan.loaded_installs() == 0 {
+            Some(EmptyListReason::ScanFailed)
+        } else {
+            Some(EmptyListReason::NoCharacters)
+        };
+        let branch = scan.branch();
+        if scan.loaded_installs() == 0 {
+            if scan.installs_without_accounts() == 0 {
+                log::error!("Failed to load characters for branch: {branch}");
+            }
+            return;
+        }
+        if scan.install_count() > 1 {
+            log::info!(
+                "Merged {} characters from {} installations of branch {branch}",
+                scan.found(),
+                scan.install_count()
             );
-            return None;
+        }
+        for character in &self.characters {
+            log::debug!(
+                " - Character: {} - {} / {}",
+                character.name(),
+                character.realm(),
+                character.account()
+            );
+        }
+
+        let duplicates = mark_duplicate_characters(&mut self.characters);
+        if duplicates > 0 {
+            log::warn!(
+                "Found {duplicates} characters sharing a name and realm across accounts or \
+                installations, their account (and installation) is shown to tell them apart"
+            );
+        }
+
+        let pruned = self
+            .recent_characters
+            .prune_branch(branch, &self.characters);
+        if pruned > 0 {
+            log::debug!("Removed {pruned} characters which no longer exist from recent characters");
+            self.save_recent_characters();
+        }
+    }
+
+    /// Load the files and backups of the selected character if the scan which found it was
+    /// cancelled before loading them.
+    fn map_selected_character_files(&mut self) {
+        let Some(char_idx) = self.get_selected_character_index() else {
+            return;
+        };
+        if self
+            .characters
+            .get(char_idx)
+            .is_none_or(|character| character.files_mapped)
+        {
+            return;
+        }
+        self.refresh_character(char_idx);
+        if let Some(character) = self.characters.get_mut(char_idx) {
+            character.files_mapped = true;
+            log::debug!("Loaded the files of {}", character.display_name(true));
+        }
+    }
+
+    /// Point the character watcher at the selected character's directory, and refresh the
+    /// character once changes to its files, such as those made by the game, have settled.
+    fn update_character_watcher(&mut self) {
+        let target = self
+            .config
+            .watch_character_files
+            .then(|| self.get_selected_character_index())
+            .flatten()
+            .and_then(|char_idx| {
+                let (character, install) = self.character_with_install(char_idx)?;
+                Some((char_idx, character.character.get_character_path(install)))
+            });
+        let Some((char_idx, path)) = target else {
+            if self.character_watcher.take().is_some() {
+                log::debug!("Stopped watching character files");
+            }
+            self.watched_character_path = None;
+            return;
         };
 
-        Some(characters)
+        if self.watched_character_path.as_ref() != Some(&path) {
+            self.character_watcher = CharacterWatcher::watch(&path)
+                .inspect_err(|e| {
+                    log::warn!("Failed to watch `{}` for changes: {e}", path.display());
+                })
+                .ok();
+            self.watched_character_path = Some(path);
+        }
+
+        if self
+            .character_watcher
+            .as_mut()
+            .is_some_and(CharacterWatcher::poll)
+        {
+            if let Some(character) = self.characters.get(char_idx) {
+                log::info!(
+                    "Files of {} changed, refreshing",
+                    character.display_name(true)
+                );
+            }
+            self.refresh_character(char_idx);
+        }
+    }
+
+    /// Returns `true`, logging a warning, if the characters are still being scanned, for actions
+    /// which need every character of the branch to be loaded.
+    fn warn_if_scanning(&self, action: &str) -> bool {
+        if self.character_scan.is_some() {
+            log::warn!("Still scanning for characters, wait for the scan to finish to {action}");
+            return true;
+        }
+        false
     }
 }
 
@@ -358,18 +917,12 @@ impl ChronoBindApp {
                 install.display_branch_name(),
                 install.install_path
             );
-
-            for character in self.find_characters_on_branch(&install.branch_ident) {
-                log::debug!(
-                    " - Character: {} - {} / {}",
-                    character.name(),
-                    character.realm(),
-                    character.account()
-                );
-            }
         }
 
         while !self.should_exit {
+            self.poll_character_scan();
+            self.map_selected_character_files();
+            self.update_character_watcher();
             terminal.draw(|frame| self.draw(frame))?;
             self.handle_events()?;
         }
@@ -378,7 +931,23 @@ impl ChronoBindApp {
 
     /// Handle key down events.
     fn on_key_down(&mut self, key: &KeyEvent) {
+        let type_ahead = self.input_mode == InputMode::Navigation
+            && self.focused_pane() != Pane::Console
+            && self.main_ui.character_list_widget.is_type_ahead_key(key);
+
         match key.keycode_lower() {
+            _ if type_ahead => {}
+            KeyCode::Tab => {
+                self.cycle_focus(true);
+                return;
+            }
+            KeyCode::BackTab => {
+                self.cycle_focus(false);
+                return;
+            }
+            KeyCode::Char('r') if self.wow_installations.is_empty() => {
+                self.retry_install_detection();
+            }
             KeyCode::Char('r') => {
                 log::debug!("Refreshing character list..");
                 if let Some(branch) = self.selected_branch.clone() {
@@ -390,6 +959,7 @@ impl ChronoBindApp {
             }
             KeyCode::Char('`' | '¬' | '~') => {
                 self.console_widget.toggle_show();
+                self.console_focused = self.console_widget.is_visible();
             }
             KeyCode::Char('t') => {
                 self.show_branch_select_popup();
@@ -397,17 +967,30 @@ impl ChronoBindApp {
             KeyCode::Char('o') => {
                 self.show_options_popup();
             }
+            KeyCode::Char('l') => {
+                self.open_popup(ColumnsPopup::new(self.config.clone()));
+            }
             KeyCode::Char('q') => {
                 log::debug!("Quit requested");
-                self.should_exit = true;
+                self.request_quit();
             }
             KeyCode::Char('u') => {
                 self.open_popup(ExportManagerPopup::new(self.selected_branch.clone()));
             }
+            KeyCode::Char('m') => {
+                self.show_recent_characters_popup();
+            }
+            KeyCode::Char('g') if !self.warn_if_scanning("manage every backup") => {
+                self.refresh_all_character_backups();
+                self.open_popup(GlobalBackupManagerPopup::new(
+                    self.characters_with_indices(),
+                    self.settings.cloud_sync_folders.clone(),
+                ));
+            }
             _ => {}
         }
 
-        if self.console_widget.is_visible() {
+        if self.focused_pane() == Pane::Console {
             self.console_widget.handle_input(key);
         } else {
             match self.input_mode {
@@ -428,14 +1011,32 @@ impl ChronoBindApp {
         match action {
             NavigationAction::None => {}
             NavigationAction::EnterFileSelection => {
-                self.input_mode = InputMode::FileSelection;
-                self.main_ui.file_list_widget.state.select(Some(0));
+                self.enter_file_selection();
             }
             NavigationAction::ShowBackup(char_idx) => {
                 self.show_backup_popup(char_idx);
             }
+            NavigationAction::ShowInfo(char_idx) => {
+                self.show_character_info_popup(char_idx);
+            }
+            NavigationAction::ShowRealmInfo(realm, account) => {
+                self.show_realm_summary_popup(&realm, account.as_deref());
+            }
+            NavigationAction::ToggleFavorite(char_idx) => {
+                self.toggle_favorite(char_idx);
+            }
+            NavigationAction::ToggleExcluded(char_idx) => {
+                self.toggle_excluded(char_idx);
+            }
+            NavigationAction::SetRealmAlias(realm) => {
+                self.open_popup(RealmAliasPopup::new(realm, &self.config.realm_aliases));
+            }
             NavigationAction::Copy(char_idx) => {
                 if let Some(character) = self.characters.get(char_idx) {
+                    if !character.has_files() {
+                        log::warn!("{} has no files to copy", character.display_name(true));
+                        return;
+                    }
                     self.copied_char = Some(char_idx);
                     let copied_files = character.total_selected_count();
                     log::info!(
@@ -445,32 +1046,135 @@ impl ChronoBindApp {
                 }
             }
             NavigationAction::Paste(target_char_idx) => {
-                if let Some(source_char_idx) = self.copied_char {
-                    if source_char_idx == target_char_idx {
-                        log::warn!(
-                            "Cannot paste files onto the same character they were copied from"
-                        );
-                    } else {
-                        let Some(dest_char) = self.characters.get(target_char_idx) else {
-                            log::error!("Failed to get target character for paste operation!");
-                            return;
-                        };
-                        let files_to_paste = self.characters[source_char_idx]
-                            .get_all_selected_files()
-                            .len();
-                        let plural = if files_to_paste == 1 { "" } else { "s" };
-                        let prompt = Span::from(format!("Paste {files_to_paste} file{plural} to "));
-                        let char_name = dest_char.display_span(true).bold();
-                        self.handle_popup_message(
-                            &AppMessage::Paste(target_char_idx)
-                                .with_confirm_and_line(Line::from(vec![prompt, char_name])),
-                        );
-                    }
-                } else {
-                    log::warn!("No files copied to paste");
+                self.show_paste_preview_popup(target_char_idx);
+            }
+        }
+    }
+
+    /// Preview pasting the copied character's selected files onto the target character, listing
+    /// where each file is pasted to and whether it is new, overwritten or identical.
+    fn show_paste_preview_popup(&mut self, target_char_idx: usize) {
+        let Some(source_char_idx) = self.copied_char else {
+            log::warn!("No files copied to paste");
+            return;
+        };
+        if source_char_idx == target_char_idx {
+            log::warn!("Cannot paste files onto the same character they were copied from");
+            return;
+        }
+        let (Some((src_char, src_install)), Some((dest_char, dest_install))) = (
+            self.character_with_install(source_char_idx),
+            self.character_with_install(target_char_idx),
+        ) else {
+            log::error!("Failed to get characters for paste operation!");
+            return;
+        };
+
+        let warning = dest_char.is_cross_branch(src_char).then(|| {
+            log::warn!(
+                "Pasting across branches, from `{}` to `{}`",
+                src_char.branch(),
+                dest_char.branch()
+            );
+            ConfirmActionText::from(vec![
+                src_char.display_span(true),
+                Span::from(" is on "),
+                Span::from(src_char.branch().to_string()).bold(),
+                Span::from(", but "),
+                dest_char.display_span(true),
+                Span::from(" is on "),
+                Span::from(dest_char.branch().to_string()).bold(),
+                Span::from(". Their files may be incompatible, paste anyway?")
+                    .fg(PALETTE.log_warn_fg),
+            ])
+        });
+        let popup = PastePreviewPopup::new(
+            target_char_idx,
+            dest_char,
+            src_char.get_all_selected_files(),
+            (
+                &src_char.character.get_character_path(src_install),
+                &dest_char.character.get_character_path(dest_install),
+            ),
+            warning,
+            self.settings.hash,
+        );
+        self.open_popup(popup);
+    }
+
+    /// Start selecting the selected character's files.
+    /// Returns `false` if the character has no files to select.
+    fn enter_file_selection(&mut self) -> bool {
+        if let Some(char_idx) = self.get_selected_character_index() {
+            if let Some(character) = self.characters.get(char_idx)
+                && !character.has_files()
+            {
+                log::warn!("{} has no files to select", character.display_name(true));
+                return false;
+            }
+            self.touch_recent_character(char_idx);
+        }
+        self.input_mode = InputMode::FileSelection;
+        self.main_ui.file_list_widget.state.select(Some(0));
+        true
+    }
+
+    /// Get the pane key presses go to, or that a popup was opened from.
+    fn focused_pane(&self) -> Pane {
+        if self.console_focused && self.console_widget.is_visible() {
+            return Pane::Console;
+        }
+        let input_mode = match self.input_mode {
+            InputMode::Popup => self
+                .popup_stack
+                .first()
+                .and_then(|(_, previous_input)| *previous_input)
+                .unwrap_or(InputMode::Navigation),
+            input_mode => input_mode,
+        };
+        if input_mode == InputMode::FileSelection {
+            Pane::Files
+        } else {
+            Pane::Characters
+        }
+    }
+
+    /// Move the focus to the next or previous pane, skipping the console while it is hidden
+    /// and the file list while the selected character has no files.
+    fn cycle_focus(&mut self, forward: bool) {
+        let mut panes = vec![Pane::Characters, Pane::Files];
+        if self.console_widget.is_visible() {
+            panes.push(Pane::Console);
+        }
+        if !forward {
+            panes.reverse();
+        }
+        let current = self.focused_pane();
+        let start = panes.iter().position(|pane| *pane == current).unwrap_or(0);
+        for pane in panes.iter().cycle().skip(start + 1).take(panes.len() - 1) {
+            if self.focus_pane(*pane) {
+                return;
+            }
+        }
+    }
+
+    /// Focus the given pane, returning `false` if it can't be focused.
+    fn focus_pane(&mut self, pane: Pane) -> bool {
+        match pane {
+            Pane::Console => {
+                self.console_focused = true;
+                return true;
+            }
+            Pane::Characters => self.input_mode = InputMode::Navigation,
+            Pane::Files if self.input_mode != InputMode::FileSelection => {
+                if !self.enter_file_selection() {
+                    return false;
                 }
             }
+            Pane::Files => {}
         }
+        self.console_focused = false;
+        true
     }
 
     /// Handle commands from the file selection widget.
@@ -479,10 +1183,11 @@ impl ChronoBindApp {
         let character = char_index.and_then(|idx| self.characters.get_mut(idx));
 
         if let Some(character) = character {
-            let action = self
-                .main_ui
-                .file_list_widget
-                .handle_file_selection_input(key, character);
+            let action = self.main_ui.file_list_widget.handle_file_selection_input(
+                key,
+                character,
+                self.config.group_addon_files,
+            );
 
             match action {
                 FileSelectionAction::None => {}
@@ -505,6 +1210,10 @@ impl ChronoBindApp {
                         log::warn!("No files selected to copy");
                     }
                 }
+                FileSelectionAction::Preview => self.show_file_preview_popup(),
+                FileSelectionAction::CopyPath => self.copy_hovered_path(),
+                FileSelectionAction::ExportAddonProfile => self.export_hovered_addon_profile(),
+                FileSelectionAction::AddonSectionToggled => self.save_addon_section_collapsed(),
             }
         }
     }
@@ -520,10 +1229,22 @@ impl ChronoBindApp {
 
     /// Handle input events.
     fn handle_events(&mut self) -> Result<()> {
-        if event::poll(Duration::from_millis(250)).context("Event poll failed")? {
+        // Poll more often while scanning, so found characters show up promptly.
+        let poll_timeout = if self.character_scan.is_some() {
+            SCAN_POLL_INTERVAL
+        } else {
+            Duration::from_millis(250)
+        };
+        if event::poll(poll_timeout).context("Event poll failed")? {
             let ev = event::read().context("Event read failed")?;
 
-            if !self.dispatch_popup_events(&ev)
+            if let Event::Key(k) = ev
+                && k.kind == KeyEventKind::Press
+                && k.modifiers.contains(KeyModifiers::CONTROL)
+                && k.keycode_lower() == KeyCode::Char('k')
+            {
+                self.toggle_mock_mode();
+            } else if !self.dispatch_popup_events(&ev)
                 && let Event::Key(k) = ev
                 && k.kind == KeyEventKind::Press
             {
@@ -541,49 +1262,25 @@ impl ChronoBindApp {
         match command {
             AppMessage::PerformBackupManagement(char_idx) => {
                 if let Some(task) = get_manage_auto_backup_task(self, *char_idx) {
-                    self.handle_task(task);
+                    self.handle_character_task(*char_idx, task);
                 }
             }
             AppMessage::Backup(char_idx, backup_command) => {
                 self.handle_backup_message(backup_command, *char_idx);
             }
-            AppMessage::Restore(char_idx, restore_command) => match restore_command {
-                RestorePopupCommand::RestoreBackup(backup) => {
-                    if let Some(task) = character_restore_task(self, *char_idx, backup) {
-                        self.handle_task(task);
-                    }
-                }
-            },
-            AppMessage::Paste(char_idx) => {
-                let Some(source_char_idx) = &self.copied_char else {
-                    log::error!("No character found for paste operation!");
-                    return;
-                };
-                let Some(dest_char) = self.character_with_install(*char_idx) else {
-                    log::error!("Invalid character index for paste popup: {char_idx}");
-                    return;
-                };
-                let Some(src_char) = self.character_with_install(*source_char_idx) else {
-                    log::error!(
-                        "Invalid source character index for paste operation: {source_char_idx}"
-                    );
-                    return;
-                };
-                let files_to_paste = src_char.0.get_all_selected_files();
-                let task = backend::paste_character_files_async(
-                    dest_char.into(),
-                    src_char.into(),
-                    &files_to_paste,
-                    self.config.mock_mode(),
-                )
-                .on_all_complete(AppMessage::PerformBackupManagement(*char_idx));
-                self.handle_task(task);
+            AppMessage::Restore(char_idx, restore_command) => {
+                self.touch_recent_character(*char_idx);
+                self.handle_restore_message(restore_command, *char_idx);
+            }
+            AppMessage::Paste(char_idx, files_to_paste) => {
+                self.paste_copied_files(*char_idx, files_to_paste);
             }
             AppMessage::Branch(BranchPopupCommand::SelectBranch(chosen_branch)) => {
                 log::info!("Switching to branch: {chosen_branch}");
                 self.set_selected_branch(chosen_branch);
             }
             AppMessage::Options(cmd) => self.handle_options_message(cmd),
+            AppMessage::GlobalBackupManager(cmd) => self.handle_global_backup_message(cmd),
             AppMessage::BackupManager(char_idx, cmd) => {
                 match cmd {
                     BackupManagerPopupCommand::DeleteBackup(backup_index) => {
@@ -592,36 +1289,411 @@ impl ChronoBindApp {
                     BackupManagerPopupCommand::ToggleBackupPin(backup_index) => {
                         perform_backup_pin_toggle(self, *char_idx, *backup_index);
                     }
+                    BackupManagerPopupCommand::ToggleBackupProtection(backup_index) => {
+                        perform_backup_protection_toggle(self, *char_idx, *backup_index);
+                    }
                 }
                 self.refresh_character_backups(*char_idx);
                 if let Some(character) = self.character_with_index(*char_idx) {
-                    self.send_popup_message(&PopupMessage::UpdateCharacter(character));
+                    self.send_popup_message(&PopupMessage::UpdateCharacter(Box::new(character)));
                 }
             }
-            AppMessage::ConfirmAction(action, action_line) => {
-                log::debug!("Showing confirmation popup for action.");
-                self.show_confirmation_popup(*action.clone(), action_line.clone());
+            AppMessage::ConfirmAction(action, action_line, risk) => {
+                if self.config.confirmations.requires_confirmation(*risk) {
+                    log::debug!("Showing confirmation popup for action.");
+                    self.show_confirmation_popup(*action.clone(), action_line.clone());
+                } else {
+                    log::debug!("Skipping confirmation for {risk:?} action.");
+                    self.handle_popup_message(action);
+                }
             }
             AppMessage::ExportManager(cmd) => {
                 self.handle_export_manager_message(cmd);
             }
+            AppMessage::Alias(char_idx, AliasPopupCommand::SetAlias(previous_name)) => {
+                self.set_character_alias(*char_idx, previous_name);
+            }
+            AppMessage::Alias(char_idx, AliasPopupCommand::MigrateBackups(previous_name)) => {
+                self.migrate_backups(*char_idx, previous_name);
+            }
+            AppMessage::RealmAlias(realm, RealmAliasPopupCommand::SetAlias(alias)) => {
+                self.set_realm_alias(realm, alias);
+            }
+            AppMessage::RecentCharacters(RecentCharactersPopupCommand::JumpToCharacter(
+                char_idx,
+            )) => self.jump_to_character(*char_idx),
+            AppMessage::RealmSummary(msg) => self.handle_realm_summary_message(msg),
+            AppMessage::LaunchGame(branch) => self.launch_game(branch),
+            AppMessage::Toast(level, text) => self.toasts.push(*level, text.clone()),
+            AppMessage::CancelTask => {
+                self.send_popup_message(&PopupMessage::CancelTask);
+            }
+            AppMessage::ReleaseCharacter(key) => {
+                self.busy_characters.remove(key);
+            }
+            AppMessage::Quit => {
+                for (popup, _) in &mut self.popup_stack {
+                    if popup.is_running_task() {
+                        popup.process_message(&PopupMessage::CancelTask);
+                    }
+                }
+                self.should_exit = true;
+            }
+        }
+    }
+
+    /// Returns `true` if any background task is still running.
+    #[must_use]
+    fn task_running(&self) -> bool {
+        self.popup_stack
+            .iter()
+            .any(|(popup, _)| popup.is_running_task())
+    }
+
+    /// Paste the given files from the copied character to the character at the given index.
+    fn paste_copied_files(&mut self, char_idx: usize, files_to_paste: &[PathBuf]) {
+        let Some(source_char_idx) = self.copied_char else {
+            log::error!("No character found for paste operation!");
+            return;
+        };
+        let Some(dest_char) = self.character_with_install(char_idx) else {
+            log::error!("Invalid character index for paste popup: {char_idx}");
+            return;
+        };
+        let Some(src_char) = self.character_with_install(source_char_idx) else {
+            log::error!("Invalid source character index for paste operation: {source_char_idx}");
+            return;
+        };
+        let task = backend::paste_character_files_async(
+            dest_char.into(),
+            src_char.into(),
+            files_to_paste,
+            &self.settings,
+            self.config.mock_mode(),
+        )
+        .on_all_complete(AppMessage::PerformBackupManagement(char_idx));
+        self.handle_character_task(char_idx, task);
+    }
+
+    /// Mark the character at the given index as mid-operation, before starting a task for it,
+    /// returning the key to release it with once the task ends.
+    /// Returns `None`, showing a notice, if a task is already running for the character.
+    fn begin_character_operation(&mut self, char_idx: usize) -> Option<String> {
+        let character = self.characters.get(char_idx)?;
+        let key = format!(
+            "{}/{}",
+            character.branch(),
+            character.character.settings_key()
+        );
+        if self.busy_characters.insert(key.clone()) {
+            return Some(key);
+        }
+        let notice = format!(
+            "Operation already in progress for {}",
+            character.display_name(true)
+        );
+        log::warn!("{notice}");
+        self.toasts.push(ToastLevel::Warning, notice);
+        None
+    }
+
+    /// Mark every character at the given indices as mid-operation, before starting a task which
+    /// touches them all, returning the keys to release them with once the task ends.
+    /// Returns `None`, marking none of them, if a task is already running for any of them.
+    fn begin_characters_operation(&mut self, char_indices: &[usize]) -> Option<Vec<String>> {
+        let mut keys = Vec::with_capacity(char_indices.len());
+        for &char_idx in char_indices.iter().unique() {
+            let Some(key) = self.begin_character_operation(char_idx) else {
+                for key in &keys {
+                    self.busy_characters.remove(key);
+                }
+                return None;
+            };
+            keys.push(key);
+        }
+        Some(keys)
+    }
+
+    /// Run a task for the character at the given index, keeping a second task from starting for
+    /// the character until it ends. The task is dropped if one is already running for it.
+    fn handle_character_task<T: BackendTask + 'static>(&mut self, char_idx: usize, task: T) {
+        self.handle_characters_task(&[char_idx], task);
+    }
+
+    /// Run a task touching every character at the given indices, keeping a second task from
+    /// starting for any of them until it ends. The task is dropped if one is already running for
+    /// any of them.
+    fn handle_characters_task<T: BackendTask + 'static>(
+        &mut self,
+        char_indices: &[usize],
+        mut task: T,
+    ) {
+        let Some(keys) = self.begin_characters_operation(char_indices) else {
+            return;
+        };
+        for key in keys {
+            task.add_on_finished(AppMessage::ReleaseCharacter(key));
+        }
+        self.handle_task(task);
+    }
+
+    /// Quit immediately when idle, or ask for confirmation first while a task is running.
+    fn request_quit(&mut self) {
+        if self.task_running() {
+            self.handle_popup_message(&quit_during_task_message());
+        } else {
+            self.should_exit = true;
+        }
+    }
+
+    /// Select the given character in the character list, returning to navigation.
+    fn jump_to_character(&mut self, char_idx: usize) {
+        self.main_ui
+            .character_list_widget
+            .select_character(&self.characters, char_idx);
+        self.input_mode = InputMode::Navigation;
+    }
+
+    /// Handle a realm summary popup message.
+    fn handle_realm_summary_message(&mut self, msg: &RealmSummaryPopupCommand) {
+        match msg {
+            RealmSummaryPopupCommand::JumpToCharacter(char_idx) => {
+                self.jump_to_character(*char_idx);
+            }
+            RealmSummaryPopupCommand::ShowBackup(char_idx) => {
+                self.jump_to_character(*char_idx);
+                self.show_backup_popup(*char_idx);
+            }
+        }
+    }
+
+    /// Mark a character as recently used, saving the recent characters if they are remembered.
+    fn touch_recent_character(&mut self, char_idx: usize) {
+        let Some(character) = self.characters.get(char_idx) else {
+            return;
+        };
+        if self.recent_characters.touch(character) {
+            self.save_recent_characters();
+        }
+    }
+
+    /// Save the recent characters to the configuration file, if they are remembered between
+    /// sessions.
+    fn save_recent_characters(&mut self) {
+        if !self.config.remember_recent_characters {
+            return;
+        }
+        self.config.recent_characters = self.recent_characters.to_vec();
+        self.config.save_to_file().unwrap_or_else(|e| {
+            log::error!("Failed to save configuration file: {e}");
+        });
+    }
+
+    /// Associate a previous character name with the character at the given index,
+    /// persisting the alias to the configuration and refreshing the character's backups.
+    fn set_character_alias(&mut self, char_idx: usize, previous_name: &str) {
+        let Some(character) = self.characters.get(char_idx) else {
+            log::error!("Invalid character index for alias: {char_idx}");
+            return;
+        };
+        let new_name = character.name().to_string();
+        let alias_key = character.character.settings_key_for_name(previous_name);
+
+        self.config
+            .character_aliases
+            .insert(alias_key, new_name.clone());
+        self.config.save_to_file().unwrap_or_else(|e| {
+            log::error!("Failed to save configuration file: {e}");
+        });
+
+        if let Some(character) = self.characters.get_mut(char_idx) {
+            character
+                .character
+                .set_previous_names_from_aliases(&self.config.character_aliases);
+        }
+        self.refresh_character_backups(char_idx);
+
+        log::info!("Linked backups of `{previous_name}` to renamed character `{new_name}`");
+    }
+
+    /// Rename the backups made under a previous name of the character at the given index to
+    /// its current name, moving them into its backups directory.
+    fn migrate_backups(&mut self, char_idx: usize, previous_name: &str) {
+        let Some(character) = self.character_with_install(char_idx) else {
+            log::error!("Invalid character index for backup migration: {char_idx}");
+            return;
+        };
+        match backend::migrate_backups_async(
+            previous_name,
+            character.into(),
+            &self.settings,
+            self.config.mock_mode(),
+        ) {
+            Ok(task) => {
+                self.handle_character_task(
+                    char_idx,
+                    task.on_all_complete(AppMessage::PerformBackupManagement(char_idx)),
+                );
+            }
+            Err(e) => log::error!("Failed to migrate backups: {e}"),
+        }
+    }
+
+    /// Remember whether the selected character's addon section is collapsed, so it stays so
+    /// across refreshes and restarts. Characters without addon files are left to the default,
+    /// so their addon section starts as configured once they have some.
+    fn save_addon_section_collapsed(&mut self) {
+        let Some(character) = self
+            .get_selected_character_index()
+            .and_then(|idx| self.characters.get(idx))
+        else {
+            return;
+        };
+        if character.addon_files().is_empty() {
+            return;
+        }
+        self.config.collapsed_addon_sections.insert(
+            character.character.settings_key(),
+            character.addon_options_collapsed,
+        );
+        self.config.save_to_file().unwrap_or_else(|e| {
+            log::error!("Failed to save configuration file: {e}");
+        });
+    }
+
+    /// Exclude or include the given character in operations on many characters at once,
+    /// and save the configuration.
+    fn toggle_excluded(&mut self, char_idx: usize) {
+        let Some(character) = self.characters.get(char_idx) else {
+            return;
+        };
+        let key = character.character.settings_key();
+        let excluded = &mut self.config.excluded_characters;
+        if let Some(position) = excluded.iter().position(|excluded| *excluded == key) {
+            excluded.remove(position);
+            log::info!(
+                "Included {} in bulk operations",
+                character.display_name(true)
+            );
+        } else {
+            excluded.push(key);
+            log::info!(
+                "Excluded {} from bulk operations",
+                character.display_name(true)
+            );
+        }
+
+        self.main_ui
+            .character_list_widget
+            .excluded_characters
+            .clone_from(&self.config.excluded_characters);
+        self.config.save_to_file().unwrap_or_else(|e| {
+            log::error!("Failed to save configuration file: {e}");
+        });
+    }
+
+    /// Star or unstar the given character as a favorite, keeping it selected as the list
+    /// may be reordered, and save the configuration.
+    fn toggle_favorite(&mut self, char_idx: usize) {
+        let Some(character) = self.characters.get(char_idx) else {
+            return;
+        };
+        let key = character.favorite_key();
+        let favorites = &mut self.config.favorite_characters;
+        if let Some(position) = favorites.iter().position(|favorite| *favorite == key) {
+            favorites.remove(position);
+            log::info!("Removed {} from favorites", character.display_name(true));
+        } else {
+            favorites.push(key);
+            log::info!("Added {} to favorites", character.display_name(true));
+        }
+
+        let widget = &mut self.main_ui.character_list_widget;
+        widget
+            .favorite_characters
+            .clone_from(&self.config.favorite_characters);
+        widget.select_character(&self.characters, char_idx);
+        self.config.save_to_file().unwrap_or_else(|e| {
+            log::error!("Failed to save configuration file: {e}");
+        });
+    }
+
+    /// Update the realm aliases of the settings, character list and characters from the
+    /// configuration.
+    fn sync_realm_aliases(&mut self) {
+        self.settings
+            .realm_aliases
+            .clone_from(&self.config.realm_aliases);
+        self.main_ui
+            .character_list_widget
+            .realm_aliases
+            .clone_from(&self.config.realm_aliases);
+        apply_realm_aliases(&mut self.characters, &self.config.realm_aliases);
+    }
+
+    /// Set the name the realm with the given folder name is displayed with, persisting it to the
+    /// configuration. An empty alias displays the realm with its folder name again.
+    fn set_realm_alias(&mut self, realm: &str, alias: &str) {
+        if alias.is_empty() || alias == realm {
+            if self.config.realm_aliases.remove(realm).is_none() {
+                return;
+            }
+            log::info!("Cleared the display name of realm `{realm}`");
+        } else {
+            self.config
+                .realm_aliases
+                .insert(realm.to_string(), alias.to_string());
+            log::info!("Realm `{realm}` is now displayed as `{alias}`");
         }
+        self.sync_realm_aliases();
+        self.config.save_to_file().unwrap_or_else(|e| {
+            log::error!("Failed to save configuration file: {e}");
+        });
     }
 
     /// Handle a backup popup message.
+    /// Back up all of the character's files, with the note stored in the backup if given.
+    /// If `prompt_note` is set, the note is asked for first and the backup made once entered.
+    fn backup_all_files(&mut self, char_idx: usize, note: Option<String>, prompt_note: bool) {
+        self.touch_recent_character(char_idx);
+        let Some(character) = self.character_with_install(char_idx) else {
+            log::error!("Invalid character index for backup popup: {char_idx}");
+            return;
+        };
+        if !character.0.has_files() {
+            log::warn!("{} has no files to back up", character.0.display_name(true));
+            return;
+        }
+        if prompt_note {
+            let char_name = character.0.display_span(true);
+            self.open_popup(BackupNotePopup::new(char_idx, char_name));
+            return;
+        }
+        let task = backend::backup_character_all_with_note_async(
+            character.into(),
+            note,
+            &self.settings,
+            self.config.mock_mode(),
+        );
+        self.handle_character_task(char_idx, task);
+    }
+
     fn handle_backup_message(&mut self, msg: &BackupPopupCommand, char_idx: usize) {
         match msg {
             BackupPopupCommand::ManageBackups => {
                 self.show_manage_backups_popup(char_idx, 0);
             }
             BackupPopupCommand::BackupSelectedFiles => {
+                self.touch_recent_character(char_idx);
                 let Some(character) = self.character_with_install(char_idx) else {
                     log::error!("Invalid character index for backup popup: {char_idx}");
                     return;
                 };
                 let selected_files = character.0.get_all_selected_files();
                 if selected_files.is_empty() {
-                    log::warn!("No files selected for backup!");
+                    log::warn!(
+                        "No files selected to back up, select files first or back up all files."
+                    );
                     return;
                 }
                 let task = backend::backup_character_selected_async(
@@ -629,22 +1701,16 @@ impl ChronoBindApp {
                     &selected_files,
                     false,
                     false,
+                    &self.settings,
                     self.config.mock_mode(),
                 );
-                self.handle_task(task);
+                self.handle_character_task(char_idx, task);
             }
             BackupPopupCommand::BackupAllFiles => {
-                let Some(character) = self.character_with_install(char_idx) else {
-                    log::error!("Invalid character index for backup popup: {char_idx}");
-                    return;
-                };
-                let task = backend::backup_character_all_async(
-                    character.into(),
-                    false,
-                    false,
-                    self.config.mock_mode(),
-                );
-                self.handle_task(task);
+                self.backup_all_files(char_idx, None, self.config.prompt_note_on_backup);
+            }
+            BackupPopupCommand::BackupAllFilesWithNote(note) => {
+                self.backup_all_files(char_idx, Some(note.clone()), false);
             }
             BackupPopupCommand::RestoreFromBackup => {
                 if !self.refresh_character_backups(char_idx) {
@@ -657,6 +1723,20 @@ impl ChronoBindApp {
                 };
                 self.open_popup(RestorePopup::new(character, None));
             }
+            BackupPopupCommand::RestoreFromFile => {
+                let Some(character) = self.character_with_index(char_idx) else {
+                    log::error!("Failed to get character for restore from file popup!");
+                    return;
+                };
+                self.open_popup(RestoreFilePopup::new(character));
+            }
+            BackupPopupCommand::ImportAddonProfile => {
+                let Some(character) = self.character_with_index(char_idx) else {
+                    log::error!("Failed to get character for addon profile import popup!");
+                    return;
+                };
+                self.open_popup(RestoreFilePopup::new(character).with_addon_profile());
+            }
             BackupPopupCommand::RestoreFromCopiedBackups => {
                 let Some(source_char_idx) = self.copied_char else {
                     log::error!("No character found for restore operation!");
@@ -676,6 +1756,190 @@ impl ChronoBindApp {
                 };
                 self.open_popup(RestorePopup::new(dest_char, Some(source_char)));
             }
+            BackupPopupCommand::PasteToRealm => {
+                if !self.warn_if_scanning("paste to the whole realm") {
+                    self.paste_to_realm(char_idx);
+                }
+            }
+            BackupPopupCommand::LinkRenamedBackups => {
+                let candidates = self.find_rename_candidates(char_idx);
+                if candidates.is_empty() {
+                    log::warn!("No orphaned backups found to link to this character.");
+                    return;
+                }
+                let candidates = self.rename_candidate_backup_counts(char_idx, candidates);
+                self.open_popup(AliasPopup::new(char_idx, candidates));
+            }
+        }
+    }
+
+    fn handle_restore_message(&mut self, msg: &RestorePopupCommand, char_idx: usize) {
+        match msg {
+            RestorePopupCommand::RestoreBackup(backup, scope) => {
+                if let Some(task) = character_restore_task(self, char_idx, backup, *scope) {
+                    self.handle_restore_task(char_idx, task);
+                }
+            }
+            RestorePopupCommand::RestoreFromFile(archive_path) => {
+                if let Some(confirmation) =
+                    archive_branch_confirmation(self, char_idx, archive_path)
+                {
+                    self.handle_popup_message(&confirmation);
+                } else {
+                    self.restore_from_file(char_idx, archive_path);
+                }
+            }
+            RestorePopupCommand::RestoreFromFileAcrossBranches(archive_path) => {
+                self.restore_from_file(char_idx, archive_path);
+            }
+            RestorePopupCommand::ImportAddonProfile(archive_path) => {
+                self.import_addon_profile(char_idx, archive_path);
+            }
+        }
+    }
+
+    /// Import an addon profile archive onto the character at the given index and its account.
+    fn import_addon_profile(&mut self, char_idx: usize, archive_path: &Path) {
+        let Some(character) = self.character_with_install(char_idx) else {
+            log::error!("Invalid character index for addon profile import: {char_idx}");
+            return;
+        };
+        match addon_profile::import_addon_profile_async(
+            character.into(),
+            archive_path.to_path_buf(),
+            &self.settings,
+            self.config.mock_mode(),
+        ) {
+            Ok(task) => self.handle_restore_task(
+                char_idx,
+                task.on_all_complete(AppMessage::PerformBackupManagement(char_idx)),
+            ),
+            Err(e) => log::error!("Failed to import addon profile: {e}"),
+        }
+    }
+
+    /// Restore an archive from an arbitrary path to the character at the given index.
+    fn restore_from_file(&mut self, char_idx: usize, archive_path: &Path) {
+        if let Some(task) = character_restore_from_file_task(self, char_idx, archive_path) {
+            self.handle_restore_task(
+                char_idx,
+                task.on_all_complete(AppMessage::PerformBackupManagement(char_idx)),
+            );
+        }
+    }
+
+    /// Run a restore task for the character at the given index, offering to launch the game
+    /// once it completes if enabled.
+    fn handle_restore_task(&mut self, char_idx: usize, task: IOTask) {
+        let launch_offer = self
+            .config
+            .offer_launch_after_restore
+            .then(|| self.character_with_install(char_idx))
+            .flatten()
+            .map(|(_, install)| {
+                AppMessage::LaunchGame(install.branch_ident.clone()).with_risk_confirm_and_line(
+                    ConfirmRisk::Critical,
+                    vec![
+                        Span::from("Restore complete, launch "),
+                        Span::from(install.display_branch_name()).bold(),
+                        Span::from("?"),
+                    ],
+                )
+            });
+        let task = match launch_offer {
+            Some(launch_offer) => task.on_all_complete(launch_offer),
+            None => task,
+        };
+        self.handle_character_task(char_idx, task);
+    }
+
+    /// Launch the game of the given branch, such as to check restored settings.
+    /// In mock mode the launch is only logged.
+    fn launch_game(&self, branch: &str) {
+        let Some(install) = self.find_wow_branch(branch) else {
+            log::error!("No WoW installation found for branch: {branch}");
+            return;
+        };
+        let Some(executable) = install.find_game_executable() else {
+            log::warn!(
+                "Could not find the game executable for {} in `{}`",
+                install.display_branch_name(),
+                install.get_branch_path().display()
+            );
+            return;
+        };
+        if self.config.mock_mode() {
+            log::info!(
+                "Mock mode enabled, skipping launching `{}`",
+                executable.display()
+            );
+            return;
+        }
+        match files::open_with_default_app(&executable) {
+            Ok(()) => log::info!("Launched {}", install.display_branch_name()),
+            Err(e) => log::error!("Failed to launch `{}`: {e}", executable.display()),
+        }
+    }
+
+    fn handle_global_backup_message(&mut self, msg: &GlobalBackupManagerPopupCommand) {
+        match msg {
+            GlobalBackupManagerPopupCommand::DeleteBackups(paths) => {
+                let touched_characters = self
+                    .characters
+                    .iter()
+                    .positions(|c| c.backups().iter().any(|b| paths.contains(&b.path)))
+                    .collect_vec();
+                let backups = self
+                    .characters
+                    .iter()
+                    .flat_map(Character::backups)
+                    .filter(|backup| paths.contains(&backup.path))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                let task = backend::delete_backups_async(backups, self.config.mock_mode())
+                    .on_all_complete(AppMessage::GlobalBackupManager(
+                        GlobalBackupManagerPopupCommand::Refresh,
+                    ));
+                self.handle_characters_task(&touched_characters, task);
+            }
+            GlobalBackupManagerPopupCommand::PruneAll => {
+                let Some(max_backups) = self.config.maximum_auto_backups else {
+                    log::warn!(
+                        "Automatic backup cleanup is disabled, set a maximum number of \
+                        automatic backups in the options first."
+                    );
+                    return;
+                };
+                self.refresh_all_character_backups();
+                let touched_characters = self
+                    .characters
+                    .iter()
+                    .positions(|c| !self.config.is_character_excluded(&c.character))
+                    .collect_vec();
+                let Some(task) = backend::manage_all_character_backups(
+                    touched_characters
+                        .iter()
+                        .map(|&i| &self.characters[i].character),
+                    max_backups,
+                    self.config.mock_mode(),
+                ) else {
+                    log::info!(
+                        "No character has more than {max_backups} unpinned automatic backups."
+                    );
+                    return;
+                };
+                self.handle_characters_task(
+                    &touched_characters,
+                    task.on_all_complete(AppMessage::GlobalBackupManager(
+                        GlobalBackupManagerPopupCommand::Refresh,
+                    )),
+                );
+            }
+            GlobalBackupManagerPopupCommand::Refresh => {
+                self.refresh_all_character_backups();
+                let characters = self.characters_with_indices();
+                self.send_popup_message(&PopupMessage::UpdateCharacters(characters));
+            }
         }
     }
 
@@ -683,12 +1947,186 @@ impl ChronoBindApp {
         match msg {
             OptionsPopupCommand::UpdateConfiguration(new_config) => {
                 log::debug!("Updating application configuration.");
-                self.config = new_config.clone();
+                let addon_default_changed = self.config.collapse_addon_sections_by_default
+                    != new_config.collapse_addon_sections_by_default;
+                let rescan_needed = self.config.resolve_symlinks != new_config.resolve_symlinks
+                    || self.config.backup_layout != new_config.backup_layout
+                    || self.config.character_scan_depth != new_config.character_scan_depth;
+                if self.config.mock_mode() != new_config.mock_mode() {
+                    self.mock_mode_changed(new_config.mock_mode());
+                }
+                self.config = new_config.as_ref().clone();
+                self.config.recent_characters = if self.config.remember_recent_characters {
+                    self.recent_characters.to_vec()
+                } else {
+                    Vec::new()
+                };
+                apply_file_logging(&self.config);
+                self.settings = self
+                    .config
+                    .runtime_settings()
+                    .with_passphrase(self.settings.passphrase.take());
+                self.wow_installations
+                    .set_resolve_symlinks(self.config.resolve_symlinks);
+                self.sync_realm_aliases();
+                if addon_default_changed {
+                    for character in &mut self.characters {
+                        character.addon_options_collapsed =
+                            self.config.is_addon_section_collapsed(&character.character);
+                    }
+                }
+                if rescan_needed && let Some(branch) = self.selected_branch.clone() {
+                    // Character and backup paths depend on how symlinks are resolved and how
+                    // backups are laid out, so rescan them.
+                    self.set_selected_branch(&branch);
+                }
+                self.main_ui.character_list_widget.single_character_realms =
+                    self.config.single_character_realms;
+                self.main_ui.character_list_widget.activate_action =
+                    self.config.character_activate_action;
+                self.main_ui
+                    .character_list_widget
+                    .favorite_characters
+                    .clone_from(&self.config.favorite_characters);
+                self.main_ui
+                    .character_list_widget
+                    .excluded_characters
+                    .clone_from(&self.config.excluded_characters);
+                self.main_ui.character_list_widget.favorites_first = self.config.favorites_first;
                 self.config.save_to_file().unwrap_or_else(|e| {
                     log::error!("Failed to save configuration file: {e}");
                 });
             }
+            OptionsPopupCommand::OpenLogFile => {
+                let log_path = config::get_log_file_path();
+                if !log_path.exists() {
+                    log::warn!(
+                        "No log file at `{}`, enable logging to a file first",
+                        log_path.display()
+                    );
+                    return;
+                }
+                if let Err(e) = files::open_with_default_app(&log_path) {
+                    log::error!("Failed to open log file `{}`: {e}", log_path.display());
+                }
+            }
+            OptionsPopupCommand::SetMockMode(mock_mode) => {
+                self.set_mock_mode(*mock_mode);
+            }
+            OptionsPopupCommand::RelaunchInTerminal => self.relaunch_in_terminal(),
+        }
+    }
+
+    /// Ask once whether to relaunch in a terminal with better symbol support, when the current
+    /// terminal lacks it and wasn't relaunched on startup, such as with `--no-relaunch`.
+    fn prompt_terminal_relaunch(&mut self) {
+        if self.config.terminal_relaunch_prompted
+            || *terminal_relaunch::SUPPORTS_FULL_UNICODE
+            || terminal_relaunch::has_been_relaunched()
+        {
+            return;
+        }
+        let Some(provider) = terminal_relaunch::find_alternative_terminal() else {
+            return;
+        };
+
+        self.config.terminal_relaunch_prompted = true;
+        self.config.save_to_file().unwrap_or_else(|e| {
+            log::error!("Failed to save configuration file: {e}");
+        });
+
+        let prompt = Line::from(vec![
+            Span::from("Symbols are limited in this terminal. ").fg(PALETTE.log_warn_fg),
+            Span::from(format!("Relaunch in {}?", provider.terminal_type().name())).bold(),
+        ]);
+        self.handle_popup_message(
+            &AppMessage::Options(OptionsPopupCommand::RelaunchInTerminal)
+                .with_risk_confirm_and_line(ConfirmRisk::Critical, prompt),
+        );
+    }
+
+    /// Relaunch in a terminal with better symbol support, exiting this instance once it has started.
+    fn relaunch_in_terminal(&mut self) {
+        let Some(provider) = terminal_relaunch::find_alternative_terminal() else {
+            log::warn!("No terminal with better symbol support is installed");
+            return;
+        };
+        let name = provider.terminal_type().name();
+        match provider.relaunch_in_terminal() {
+            Ok(()) => {
+                log::info!("Relaunched in {name}, exiting");
+                self.request_quit();
+            }
+            Err(e) => log::error!("Failed to relaunch in {name}: {e:?}"),
+        }
+    }
+
+    /// Log and show a change of mock mode, stating whether files will now be changed,
+    /// and highlight the change in the title bar.
+    fn mock_mode_changed(&mut self, mock_mode: bool) {
+        if mock_mode {
+            log::info!("Mock mode enabled, no files will be changed");
+            self.toasts.push(ToastLevel::Info, "Mock mode on");
+        } else {
+            log::warn!("Mock mode disabled, file operations will now change files");
+            self.toasts
+                .push(ToastLevel::Warning, "Mock mode off, files will be changed");
+        }
+        self.mock_mode_toggled_at = Some(Instant::now());
+    }
+
+    /// Toggle mock mode from anywhere, including while a popup is open.
+    fn toggle_mock_mode(&mut self) {
+        self.set_mock_mode(!self.config.mock_mode());
+        let message = PopupMessage::UpdateConfiguration(Box::new(self.config.clone()));
+        for (popup, _) in &mut self.popup_stack {
+            popup.process_message(&message);
+        }
+    }
+
+    /// Set and save the mock mode state, highlighting the change in the title bar.
+    fn set_mock_mode(&mut self, mock_mode: bool) {
+        self.mock_mode_changed(mock_mode);
+        self.config.set_mock_mode(mock_mode);
+        self.config.save_to_file().unwrap_or_else(|e| {
+            log::error!("Failed to save configuration file: {e}");
+        });
+    }
+
+    /// Estimate the size of an export of every branch, and confirm the export showing the estimate,
+    /// refusing it if it may not fit on the disk.
+    fn confirm_all_branches_export(
+        &mut self,
+        backup_options: backend::InstallBackupOptions,
+        export_name: &str,
+    ) {
+        let Some(root_path) = self.wow_installations.root_path.clone() else {
+            log::error!("No WoW installations found for exporting all backups!");
+            return;
+        };
+        let estimate =
+            backend::estimate_export(self.wow_installations.iter(), backup_options, &self.config);
+        if let Err(e) = backend::ensure_space_for_export(
+            &root_path.join(export_name),
+            estimate,
+            &self.settings,
+            self.config.mock_mode(),
+        ) {
+            log::error!("Not exporting every branch: {e}");
+            return;
         }
+
+        let branches = self.wow_installations.iter().count();
+        let command =
+            AppMessage::ExportManager(ExportManagerMessage::ExportAllBranches(backup_options))
+                .with_confirm_and_line(Line::from(vec![
+                    Span::from(format!(
+                        "Export {branches} branches, about {} files totalling ",
+                        estimate.files
+                    )),
+                    Span::from(backend::format_size(estimate.size)).bold(),
+                ]));
+        self.handle_popup_message(&command);
     }
 
     fn handle_export_manager_message(&mut self, msg: &ExportManagerMessage) {
@@ -700,7 +2138,9 @@ impl ChronoBindApp {
                     if let Some(task) = backend::export_install(
                         selected_install,
                         backend::InstallBackupOptions::character_backups(),
+                        &self.config,
                         &final_export_path,
+                        None,
                         self.config.mock_mode(),
                     ) {
                         self.handle_task(task);
@@ -708,34 +2148,25 @@ impl ChronoBindApp {
                 }
             }
             ExportManagerMessage::ExportAllBranchesChronoBind => {
-                let Some(root_path) = self.wow_installations.root_path.clone() else {
-                    log::error!("No WoW installations found for exporting all backups!");
-                    return;
-                };
-                let final_export_path = root_path.join(&export_name);
-                if let Some(task) = backend::export_all_installs(
-                    &self.wow_installations,
+                self.confirm_all_branches_export(
                     backend::InstallBackupOptions::character_backups(),
-                    &final_export_path,
-                    self.config.mock_mode(),
-                ) {
-                    self.handle_task(task);
-                }
+                    &export_name,
+                );
             }
             ExportManagerMessage::ExportFullCurrentBranch => {
-                if let Some(selected_install) = self.get_selected_branch_install() {
-                    let final_export_path = selected_install.install_path_join(&export_name);
-                    if let Some(task) = backend::export_install(
-                        selected_install,
-                        backend::InstallBackupOptions::all(),
-                        &final_export_path,
-                        self.config.mock_mode(),
-                    ) {
-                        self.handle_task(task);
-                    }
-                }
+                self.export_full_current_branch(&export_name, false);
+            }
+            ExportManagerMessage::ExportChangedCurrentBranch => {
+                let export_name = backend::get_export_filename("Chronobind-changes");
+                self.export_full_current_branch(&export_name, true);
             }
             ExportManagerMessage::ExportFullAllBranches => {
+                self.confirm_all_branches_export(
+                    backend::InstallBackupOptions::all(),
+                    &export_name,
+                );
+            }
+            ExportManagerMessage::ExportAllBranches(backup_options) => {
                 let Some(root_path) = self.wow_installations.root_path.clone() else {
                     log::error!("No WoW installations found for exporting all backups!");
                     return;
@@ -743,7 +2174,8 @@ impl ChronoBindApp {
                 let final_export_path = root_path.join(&export_name);
                 if let Some(task) = backend::export_all_installs(
                     &self.wow_installations,
-                    backend::InstallBackupOptions::all(),
+                    *backup_options,
+                    &self.config,
                     &final_export_path,
                     self.config.mock_mode(),
                 ) {
@@ -753,17 +2185,122 @@ impl ChronoBindApp {
             ExportManagerMessage::OpenImportDialog => {
                 self.open_popup(ImportDialog::new());
             }
-            ExportManagerMessage::ImportChronoBindBackup(import_path, settings) => {
-                if let Some(task) = backend::import_chronobind_backup(
+            ExportManagerMessage::ImportChronoBindBackup(
+                import_path,
+                settings,
+                include_machine_settings,
+            ) => {
+                let apply_settings = if settings.include_settings {
+                    self.imported_settings_message(import_path, *include_machine_settings)
+                } else {
+                    None
+                };
+                let task = backend::import_chronobind_backup(
                     import_path.clone(),
                     &self.wow_installations,
                     *settings,
+                    self.config.progress_interval(),
                     self.config.mock_mode(),
-                ) {
-                    self.handle_task(task);
+                );
+                match (task, apply_settings) {
+                    (Some(task), Some(apply_settings)) => {
+                        self.handle_task(task.on_all_complete(apply_settings));
+                    }
+                    (Some(task), None) => self.handle_task(task),
+                    (None, Some(apply_settings)) => self.handle_popup_message(&apply_settings),
+                    (None, None) => {}
                 }
             }
+            ExportManagerMessage::RecordExportTime(branch, started) => {
+                self.config
+                    .last_export_times
+                    .insert(branch.clone(), *started);
+                self.config.save_to_file().unwrap_or_else(|e| {
+                    log::error!("Failed to save configuration file: {e}");
+                });
+            }
+        }
+    }
+
+    /// Export everything from the selected branch, recording when the export was started once
+    /// it completes. If `changed_only`, only the files changed since the branch's last export
+    /// are exported, or everything if the branch has not been exported before.
+    fn export_full_current_branch(&mut self, export_name: &str, changed_only: bool) {
+        let Some(selected_install) = self.get_selected_branch_install() else {
+            return;
+        };
+        let branch = selected_install.branch_ident.clone();
+        let modified_since = if changed_only {
+            let since = self.config.last_export_times.get(&branch).copied();
+            match since {
+                Some(since) => log::info!(
+                    "Exporting files changed in {} since {}",
+                    selected_install.display_branch_name(),
+                    backend::format_unix_time(since)
+                ),
+                None => log::info!(
+                    "{} has not been exported before, exporting everything",
+                    selected_install.display_branch_name()
+                ),
+            }
+            since
+        } else {
+            None
+        };
+
+        let started = backend::unix_time_now();
+        let final_export_path = selected_install.install_path_join(export_name);
+        if let Some(task) = backend::export_install(
+            selected_install,
+            backend::InstallBackupOptions::all(),
+            &self.config,
+            &final_export_path,
+            modified_since,
+            self.config.mock_mode(),
+        ) {
+            self.handle_task(task.on_all_complete(AppMessage::ExportManager(
+                ExportManagerMessage::RecordExportTime(branch, started),
+            )));
+        }
+    }
+
+    /// Get the message to apply the settings embedded in an import, wrapped in a confirmation.
+    /// Returns `None` if the import has no settings or they cannot be read.
+    fn imported_settings_message(
+        &self,
+        import_path: &Path,
+        include_machine_settings: bool,
+    ) -> Option<AppMessage> {
+        let imported = match backend::read_exported_settings(import_path) {
+            Ok(Some(settings)) => ChronoBindAppConfig::from_export_str(&settings),
+            Ok(None) => {
+                log::info!(
+                    "Import `{}` contains no ChronoBind settings",
+                    import_path.display()
+                );
+                return None;
+            }
+            Err(e) => Err(e),
+        };
+        let imported = imported
+            .map_err(|e| log::error!("Failed to read settings from import: {e}"))
+            .ok()?;
+
+        let merged = self
+            .config
+            .with_imported_settings(&imported, include_machine_settings);
+        if merged == self.config {
+            log::info!("Imported ChronoBind settings match the current settings");
+            return None;
         }
+        Some(
+            AppMessage::Options(OptionsPopupCommand::UpdateConfiguration(Box::new(merged)))
+                .with_confirm_and_line(vec![
+                    Span::from("Replace your settings with the "),
+                    Span::from("imported settings").bold(),
+                    Span::from("?"),
+                ]),
+        )
     }
 
     /// Dispatch message to the popup, if any, also handling closing the popup.
@@ -804,6 +2341,13 @@ impl ChronoBindApp {
             popup.render(frame);
         }
 
+        self.toasts.expire(self.config.toast_duration());
+        self.toasts.render(
+            main_layout_chunks[1],
+            frame.buffer_mut(),
+            self.config.toast_position,
+        );
+
         self.dispatch_popup_messages();
     }
 
@@ -814,16 +2358,19 @@ impl ChronoBindApp {
                 .direction(Direction::Vertical)
                 .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
                 .split(area);
-            self.console_widget.render(chunks[1], buf);
+            let focused = self.focused_pane() == Pane::Console;
+            self.console_widget.render(chunks[1], buf, focused);
             chunks[0]
         } else {
             area
         };
+        let focused_pane = self.focused_pane();
         self.main_ui.draw(
             main_area,
             buf,
             &self.characters,
             self.input_mode,
+            focused_pane,
             &self.config,
         );
     }
@@ -831,12 +2378,30 @@ impl ChronoBindApp {
     /// Render the top title bar.
     #[allow(clippy::cast_possible_truncation)]
     fn top_bar(&self, area: Rect, buf: &mut Buffer) {
-        let mock = if self.config.mock_mode() {
-            "[Safe] "
-        } else {
-            Default::default()
-        };
-        let title_span = Span::from(format!(" ChronoBind {mock}")).fg(PALETTE.std_fg);
+        let mut title_spans = vec![Span::from(" ChronoBind ").fg(PALETTE.std_fg)];
+        let flash = self
+            .mock_mode_toggled_at
+            .is_some_and(|toggled_at| toggled_at.elapsed() < MOCK_MODE_FLASH_DURATION);
+        if self.config.mock_mode() {
+            title_spans.push(
+                Span::from(" MOCK MODE - no files will be changed ")
+                    .bold()
+                    .fg(PALETTE.std_fg_invert)
+                    .bg(if flash {
+                        PALETTE.log_info_fg
+                    } else {
+                        PALETTE.log_warn_fg
+                    }),
+            );
+        } else if flash {
+            title_spans.push(
+                Span::from(" MOCK MODE OFF - files will be changed ")
+                    .bold()
+                    .fg(PALETTE.std_fg_invert)
+                    .bg(PALETTE.log_error_fg),
+            );
+        }
+        let title_span = Line::from(title_spans);
 
         let copy_display = if let Some(char_idx) = &self.copied_char
             && let Some(copied_char) = self.characters.get(*char_idx)
@@ -873,43 +2438,68 @@ impl ChronoBindApp {
             "(O)ptions".to_string(),
             "(Q)uit".to_string(),
         ];
-        let status_elements: Vec<String> = if self.console_widget.is_visible() {
-            ["↑/↓", "PgUp/PgDn: Fast Scroll", "Home/End: Jump"]
-                .iter()
-                .map(std::string::ToString::to_string)
-                .collect()
+        let console_focused = self.focused_pane() == Pane::Console;
+        let status_elements: Vec<String> = if console_focused {
+            [
+                "↑/↓",
+                "PgUp/PgDn: Fast Scroll",
+                "Home/End: Jump",
+                "Tab: Switch Pane",
+            ]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect()
         } else {
             match self.input_mode {
                 InputMode::Navigation => {
-                    let mut items = vec![
+                    let mut items = vec![];
+                    if let Some(buffer) = self.main_ui.character_list_widget.type_ahead_buffer() {
+                        items.push(format!("Jump: {buffer}"));
+                    }
+                    if self.wow_installations.is_empty() {
+                        items.push("R: Retry Detection".to_string());
+                    }
+                    items.extend([
                         "↑/↓".to_string(),
-                        format!("{}/→/Space: Select", ENTER_SYMBOL),
+                        format!("{ENTER_SYMBOL}/→/Space: Select"),
+                        "[/]: Collapse/Expand All".to_string(),
+                        "Tab: Switch Pane".to_string(),
                         "(B)ackup".to_string(),
                         "U: Import/Export".to_string(),
                         "(C)opy".to_string(),
-                    ];
+                        "(I)nfo".to_string(),
+                        "F: Favorite".to_string(),
+                        "X: Exclude".to_string(),
+                        "N: Realm Name".to_string(),
+                        "M: Recent".to_string(),
+                        "G: All Backups".to_string(),
+                    ]);
                     if self.copied_char.is_some() {
                         items.push("V: Paste".to_string());
                     }
+                    items.push("L: Columns".to_string());
+                    items
+                }
+                InputMode::FileSelection => {
+                    let mut items = self
+                        .get_selected_character_index()
+                        .and_then(|idx| self.characters.get(idx))
+                        .map(|character| {
+                            self.main_ui
+                                .file_list_widget
+                                .bottom_bar_options(character, self.config.group_addon_files)
+                        })
+                        .unwrap_or_default();
+                    items.extend(["U: Import/Export".to_string(), "M: Recent".to_string()]);
                     items
                 }
-                InputMode::FileSelection => vec![
-                    "↑/↓".to_string(),
-                    "←: Back".to_string(),
-                    format!("{} /Space/→: Toggle", ENTER_SYMBOL),
-                    "Ctrl+A: Select All".to_string(),
-                    "(B)ackup".to_string(),
-                    "U: Import/Export".to_string(),
-                    "(C)opy".to_string(),
-                ],
                 InputMode::Popup => self.active_popup().map_or_else(Vec::new, |popup| {
                     popup.bottom_bar_options().unwrap_or_default()
                 }),
             }
         };
 
-        let final_text = if self.input_mode == InputMode::Popup || self.console_widget.is_visible()
-        {
+        let final_text = if self.input_mode == InputMode::Popup || console_focused {
             status_elements
                 .iter()
                 .map(std::string::ToString::to_string)
@@ -983,6 +2573,27 @@ impl ChronoBindApp {
     #[inline]
     pub fn handle_task<T: BackendTask + 'static>(&mut self, task: T) {
         self.open_popup(ProgressPopup::new(Box::new(task)));
+        if self.config.mock_mode() && !self.config.mock_mode_prompted {
+            self.prompt_disable_mock_mode();
+        }
+    }
+
+    /// Ask once whether to disable mock mode, as a user's first action in mock mode
+    /// silently changes nothing, which is easily mistaken for a real backup.
+    fn prompt_disable_mock_mode(&mut self) {
+        self.config.mock_mode_prompted = true;
+        self.config.save_to_file().unwrap_or_else(|e| {
+            log::error!("Failed to save configuration file: {e}");
+        });
+
+        let warning = Line::from(vec![
+            Span::from("Mock mode is on, no files were changed. ").fg(PALETTE.log_warn_fg),
+            Span::from("Disable mock mode?").bold(),
+        ]);
+        self.handle_popup_message(
+            &AppMessage::Options(OptionsPopupCommand::SetMockMode(false))
+                .with_risk_confirm_and_line(ConfirmRisk::Critical, warning),
+        );
     }
 
     /// Send a message to the current popup.
@@ -1002,6 +2613,85 @@ impl ChronoBindApp {
         self.open_popup(ConfirmationPopup::new(action, action_line));
     }
 
+    /// Show a preview of the file hovered in the file list of the selected character.
+    fn show_file_preview_popup(&mut self) {
+        let Some(character) = self
+            .get_selected_character_index()
+            .and_then(|idx| self.characters.get(idx))
+        else {
+            return;
+        };
+        let Some(file) = self
+            .main_ui
+            .file_list_widget
+            .hovered_file(character, self.config.group_addon_files)
+        else {
+            log::warn!("Hover a file to preview it");
+            return;
+        };
+        let popup = FilePreviewPopup::new(file);
+        self.open_popup(popup);
+    }
+
+    /// Copy the path of the hovered file to the clipboard, or the character's directory
+    /// if a header is hovered.
+    fn copy_hovered_path(&mut self) {
+        let Some(char_idx) = self.get_selected_character_index() else {
+            return;
+        };
+        let Some((character, install)) = self.character_with_install(char_idx) else {
+            return;
+        };
+        let path = self
+            .main_ui
+            .file_list_widget
+            .hovered_file(character, self.config.group_addon_files)
+            .map_or_else(
+                || character.character.get_character_path(install),
+                |file| file.path.clone(),
+            );
+
+        match copy_to_clipboard(&path.display().to_string()) {
+            Ok(()) => {
+                log::info!("Copied `{}` to the clipboard", path.display());
+                self.toasts.push(ToastLevel::Success, "Copied path");
+            }
+            Err(e) => log::error!("Failed to copy `{}` to the clipboard: {e}", path.display()),
+        }
+    }
+
+    /// Export the `SavedVariables` of the hovered addon of the selected character to an addon
+    /// profile archive in the install directory.
+    fn export_hovered_addon_profile(&mut self) {
+        let Some(char_idx) = self.get_selected_character_index() else {
+            return;
+        };
+        let Some(character) = self.character_with_install(char_idx) else {
+            return;
+        };
+        let Some(addon) = self
+            .main_ui
+            .file_list_widget
+            .hovered_addon(character.0, self.config.group_addon_files)
+        else {
+            log::warn!("Hover an addon in the grouped addon files to export its profile");
+            return;
+        };
+        let export_path = character
+            .1
+            .install_path_join(backend::get_export_filename(&format!("{addon}-profile")));
+        match addon_profile::export_addon_profile_async(
+            character.into(),
+            addon,
+            export_path,
+            &self.settings,
+            self.config.mock_mode(),
+        ) {
+            Ok(task) => self.handle_task(task),
+            Err(e) => log::error!("Failed to export addon profile: {e}"),
+        }
+    }
+
     /// Show the backup options popup for the given character index.
     pub fn show_backup_popup(&mut self, char_idx: usize) {
         let Some(character) = self.character_with_index(char_idx) else {
@@ -1013,7 +2703,90 @@ impl ChronoBindApp {
             .copied_char
             .and_then(|idx| self.character_with_index(idx));
 
-        self.open_popup(BackupPopup::new(character, copied_char));
+        let has_rename_candidates =
+            character.0.backups().is_empty() && !self.find_rename_candidates(char_idx).is_empty();
+        let realm_paste_targets = self.realm_paste_targets(char_idx).len();
+
+        // Check the backups can be written now, rather than failing after a backup has started.
+        let backup_location_error = self
+            .character_with_install(char_idx)
+            .map(|(character, install)| {
+                character
+                    .character
+                    .get_backups_dir(install, self.settings.backup_layout)
+            })
+            .and_then(|backups_dir| {
+                backend::probe_backup_location(&backups_dir, self.config.mock_mode()).err()
+            })
+            .map(|e| e.to_string());
+        if let Some(error) = &backup_location_error {
+            log::warn!("{error}");
+        }
+
+        self.open_popup(
+            BackupPopup::new(character, copied_char)
+                .with_rename_candidates(has_rename_candidates)
+                .with_realm_paste_targets(realm_paste_targets)
+                .with_backup_location_error(backup_location_error),
+        );
+    }
+
+    /// Show the detail popup for the given character index.
+    pub fn show_character_info_popup(&mut self, char_idx: usize) {
+        if !self.refresh_character_backups(char_idx) {
+            log::warn!("Failed to refresh backups before showing character info!");
+        }
+        let Some(character) = self.character_with_install(char_idx) else {
+            log::error!("Invalid character index for character info popup: {char_idx}");
+            return;
+        };
+        let popup = CharacterInfoPopup::new(character, self.config.backup_warning_threshold);
+        self.open_popup(popup);
+    }
+
+    /// Show the summary of a realm's characters and their backups, of the given account only
+    /// if the realm spans accounts.
+    pub fn show_realm_summary_popup(&mut self, realm: &str, account: Option<&str>) {
+        let char_indices = self
+            .characters
+            .iter()
+            .enumerate()
+            .filter(|(_, character)| {
+                character.realm() == realm
+                    && account.is_none_or(|account| character.account() == account)
+            })
+            .map(|(char_idx, _)| char_idx)
+            .collect::<Vec<_>>();
+        for &char_idx in &char_indices {
+            if !self.refresh_character_backups(char_idx) {
+                log::warn!("Failed to refresh backups before showing the realm summary!");
+            }
+        }
+
+        let characters = char_indices
+            .iter()
+            .filter_map(|&char_idx| Some((char_idx, self.characters.get(char_idx)?)))
+            .collect::<Vec<_>>();
+        let popup = RealmSummaryPopup::new(realm, account, &characters, &self.config.realm_aliases);
+        self.open_popup(popup);
+    }
+
+    /// Show the recently used characters of the selected branch, to jump back to one of them.
+    pub fn show_recent_characters_popup(&mut self) {
+        let characters = self
+            .recent_characters
+            .character_indices(&self.characters)
+            .into_iter()
+            .filter_map(|char_idx| {
+                let character = self.characters.get(char_idx)?;
+                Some((char_idx, character.display_span(true)))
+            })
+            .collect::<Vec<_>>();
+        if characters.is_empty() {
+            log::info!("No recently used characters on this branch yet");
+            return;
+        }
+        self.open_popup(RecentCharactersPopup::new(characters));
     }
 
     /// Show the backup manager popup for the given character index, and selected backup index.
@@ -1027,7 +2800,12 @@ impl ChronoBindApp {
             return;
         };
 
-        self.open_popup(BackupManagerPopup::new(character, selected_index));
+        self.open_popup(BackupManagerPopup::new(
+            character,
+            selected_index,
+            Arc::clone(&self.archive_cache),
+            self.settings.cloud_sync_folders.clone(),
+        ));
     }
 
     /// Show the branch selection popup.
@@ -1053,6 +2831,7 @@ fn character_restore_task(
     app: &ChronoBindApp,
     dest_char_index: usize,
     backup: &WoWCharacterBackup,
+    scope: RestoreScope,
 ) -> Option<IOTask> {
     let Some(dest_char) = app.character_with_install(dest_char_index) else {
         log::error!("Invalid destination character index for backup popup: {dest_char_index}");
@@ -1060,15 +2839,189 @@ fn character_restore_task(
     };
 
     log::info!(
-        "Restoring backup `{}` for character {} on branch {}",
+        "Restoring {} of backup `{}` for character {} on branch {}",
+        scope.display_name(),
         backup.formatted_name(),
         dest_char.0.name(),
         dest_char.1.branch_ident
     );
 
-    Some(backend::restore_backup_async(
+    if scope == RestoreScope::All {
+        return Some(backend::restore_backup_async(
+            dest_char.into(),
+            backup.path.clone(),
+            &app.settings,
+            app.config.mock_mode(),
+        ));
+    }
+
+    match backend::restore_backup_scoped_async(
         dest_char.into(),
         backup.path.clone(),
+        scope,
+        &app.settings,
+        app.config.mock_mode(),
+    ) {
+        Ok(task) => {
+            Some(task.on_all_complete(AppMessage::PerformBackupManagement(dest_char_index)))
+        }
+        Err(e) => {
+            log::error!("Failed to restore backup: {e}");
+            None
+        }
+    }
+}
+
+/// Check that an archive chosen to restore from a path was backed up on the destination
+/// character's branch, as settings from another branch may not be compatible.
+/// Returns the confirmation to restore it anyway on a mismatch, archives without a manifest
+/// aren't checked.
+fn archive_branch_confirmation(
+    app: &ChronoBindApp,
+    dest_char_index: usize,
+    archive_path: &Path,
+) -> Option<AppMessage> {
+    let dest_char = app.characters.get(dest_char_index)?;
+    // Unreadable archives are reported when restoring.
+    let details = app.archive_cache.lock().ok()?.details(archive_path)?;
+    branch_mismatch_confirmation(
+        dest_char,
+        dest_char_index,
+        archive_path,
+        details.manifest.clone(),
+    )
+}
+
+/// Build the list entry of a character found by a scan, applying its configured display
+/// settings and keeping its file selection from before the scan.
+fn scanned_character(
+    found: &FoundCharacter,
+    config: &ChronoBindAppConfig,
+    characters_before_scan: &[Character],
+) -> Character {
+    let mut character = Character::new(&found.character);
+    character.files_mapped = found.files_mapped;
+    character.addon_options_collapsed = config.is_addon_section_collapsed(&found.character);
+    character.realm_alias = config.realm_aliases.get(character.realm()).cloned();
+    if let Some(previous) = characters_before_scan
+        .iter()
+        .find(|previous| previous.character.is_same_character(&found.character))
+    {
+        character.merge_selection_from(previous);
+    }
+    character
+}
+
+/// Get the confirmation to restore an archive with the given manifest to the destination
+/// character anyway, if it was backed up on another branch.
+fn branch_mismatch_confirmation(
+    dest_char: &Character,
+    dest_char_index: usize,
+    archive_path: &Path,
+    manifest: Option<BackupManifest>,
+) -> Option<AppMessage> {
+    let Some(manifest) = manifest else {
+        log::info!(
+            "`{}` has no ChronoBind manifest, skipping the branch check",
+            archive_path.display()
+        );
+        return None;
+    };
+    if manifest.branch.eq_ignore_ascii_case(dest_char.branch()) {
+        return None;
+    }
+
+    log::warn!(
+        "Archive was backed up on the {} branch, restoring it to {} on the {} branch",
+        manifest.branch,
+        dest_char.name(),
+        dest_char.branch()
+    );
+    Some(
+        AppMessage::Restore(
+            dest_char_index,
+            RestorePopupCommand::RestoreFromFileAcrossBranches(archive_path.to_path_buf()),
+        )
+        .with_risk_confirm_and_line(
+            ConfirmRisk::Critical,
+            Line::from(vec![
+                Span::from("Archive is from "),
+                Span::from(manifest.branch).bold(),
+                Span::from(", restore to "),
+                dest_char.display_span(true).bold(),
+                Span::from(" on "),
+                Span::from(dest_char.branch().to_string()).bold(),
+                Span::from(" anyway?"),
+            ]),
+        ),
+    )
+}
+
+/// Create the task to restore an archive from an arbitrary path to the destination character,
+/// warning if the archive belongs to another character or was not created by `ChronoBind`.
+fn character_restore_from_file_task(
+    app: &ChronoBindApp,
+    dest_char_index: usize,
+    archive_path: &Path,
+) -> Option<IOTask> {
+    let Some(dest_char) = app.character_with_install(dest_char_index) else {
+        log::error!("Invalid destination character index for restore from file: {dest_char_index}");
+        return None;
+    };
+
+    if !archive_path.is_file() {
+        log::error!("Archive `{}` does not exist", archive_path.display());
+        return None;
+    }
+
+    let info =
+        match backend::inspect_external_archive(&app.settings.backup_name_template, archive_path) {
+            Ok(info) => info,
+            Err(e) => {
+                log::error!("Failed to read archive `{}`: {e}", archive_path.display());
+                return None;
+            }
+        };
+
+    if !info.is_character_backup {
+        log::warn!(
+            "`{}` does not look like a ChronoBind character backup, attempting a best-effort extraction of {} files",
+            archive_path.display(),
+            info.file_count
+        );
+    }
+    if !info.encrypted_files.is_empty() {
+        let note = if app.settings.passphrase.is_some() {
+            "decrypting with the given passphrase"
+        } else {
+            "no passphrase was given, so they will be skipped"
+        };
+        log::warn!(
+            "{} encrypted files in the archive, {note}: {}",
+            info.encrypted_files.len(),
+            info.encrypted_files.join(", ")
+        );
+    }
+    if let Some(backup_char_name) = &info.char_name
+        && !backup_char_name.eq_ignore_ascii_case(dest_char.0.name())
+    {
+        log::warn!(
+            "Archive was backed up from {backup_char_name}, restoring it to {}",
+            dest_char.0.name()
+        );
+    }
+
+    log::info!(
+        "Restoring `{}` for character {} on branch {}",
+        archive_path.display(),
+        dest_char.0.name(),
+        dest_char.1.branch_ident
+    );
+
+    Some(backend::restore_backup_from_file_async(
+        dest_char.into(),
+        archive_path.to_path_buf(),
+        &app.settings,
         app.config.mock_mode(),
     ))
 }
@@ -1093,7 +3046,11 @@ fn perform_backup_pin_toggle(app: &ChronoBindApp, char_idx: usize, backup_index:
         character.name()
     );
 
-    match backend::toggle_backup_pin(&backup, app.config.mock_mode()) {
+    match backend::toggle_backup_pin(
+        &backup,
+        &app.settings.backup_name_template,
+        app.config.mock_mode(),
+    ) {
         Ok(()) => {
             log::info!(
                 "Backup pin state toggled successfully for backup `{}` of character {}",
@@ -1114,6 +3071,54 @@ fn perform_backup_pin_toggle(app: &ChronoBindApp, char_idx: usize, backup_index:
     }
 }
 
+/// Perform the backup protection toggle operation.
+fn perform_backup_protection_toggle(
+    app: &ChronoBindApp,
+    char_idx: usize,
+    backup_index: usize,
+) -> bool {
+    let Some(character) = app.characters.get(char_idx) else {
+        log::error!("Invalid character index for backup protection toggle: {char_idx}");
+        return false;
+    };
+    let Some(backup) = character.backups().get(backup_index).cloned() else {
+        log::error!(
+            "Invalid backup selection index: {backup_index} for character {}",
+            character.name()
+        );
+        return false;
+    };
+
+    match backend::toggle_backup_protection(
+        &backup,
+        &app.settings.backup_name_template,
+        app.config.mock_mode(),
+    ) {
+        Ok(()) => {
+            log::info!(
+                "Backup `{}` of character {} is {} protected",
+                backup.formatted_name(),
+                character.name(),
+                if backup.is_protected() {
+                    "no longer"
+                } else {
+                    "now"
+                }
+            );
+            true
+        }
+        Err(e) => {
+            log::error!(
+                "Failed to toggle protection for backup `{}` of character {}: {}",
+                backup.formatted_name(),
+                character.name(),
+                e
+            );
+            false
+        }
+    }
+}
+
 fn perform_backup_deletion(app: &ChronoBindApp, char_idx: usize, backup_index: usize) -> bool {
     let Some(character) = app.character_with_install(char_idx) else {
         log::error!("Invalid character index for backup deletion: {char_idx}");
@@ -1178,3 +3183,91 @@ fn get_manage_auto_backup_task(app: &mut ChronoBindApp, char_idx: usize) -> Opti
 
     backend::manage_character_backups(character, max_backups, app.config.mock_mode())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::test_harness::character;
+
+    fn retail_alpha() -> Character {
+        let mut alpha = character("ACC", "Draenor", "Alpha", &[], &[]);
+        alpha.character.branch = "retail".to_string();
+        alpha
+    }
+
+    fn manifest_on(branch: &str) -> BackupManifest {
+        let mut character = retail_alpha().character;
+        character.branch = branch.to_string();
+        BackupManifest::for_character(&character)
+    }
+
+    #[test]
+    fn restoring_an_archive_from_another_branch_is_confirmed() {
+        let archive_path = Path::new("/exports/Alpha.zip");
+        let confirmation = branch_mismatch_confirmation(
+            &retail_alpha(),
+            3,
+            archive_path,
+            Some(manifest_on("classic")),
+        );
+        let Some(AppMessage::ConfirmAction(action, Some(_), ConfirmRisk::Critical)) = confirmation
+        else {
+            panic!("Mismatched branch should be confirmed, got {confirmation:?}");
+        };
+        assert_eq!(
+            *action,
+            AppMessage::Restore(
+                3,
+                RestorePopupCommand::RestoreFromFileAcrossBranches(archive_path.to_path_buf())
+            )
+        );
+    }
+
+    #[test]
+    fn archives_on_the_same_branch_or_without_a_manifest_are_not_confirmed() {
+        let archive_path = Path::new("/exports/Alpha.zip");
+        let alpha = retail_alpha();
+        assert_eq!(
+            branch_mismatch_confirmation(&alpha, 3, archive_path, Some(manifest_on("Retail"))),
+            None
+        );
+        assert_eq!(
+            branch_mismatch_confirmation(&alpha, 3, archive_path, None),
+            None
+        );
+    }
+
+    fn found(name: &str) -> FoundCharacter {
+        FoundCharacter {
+            character: character("ACC", "Draenor", name, &[], &["Details.lua"]).character,
+            files_mapped: true,
+        }
+    }
+
+    #[test]
+    fn addon_section_collapse_state_survives_a_rescan() {
+        let (alpha, beta) = (found("Alpha"), found("Beta"));
+        let mut config = ChronoBindAppConfig::default();
+        config
+            .collapsed_addon_sections
+            .insert(alpha.character.settings_key(), true);
+
+        let before_scan = [
+            scanned_character(&alpha, &config, &[]),
+            scanned_character(&beta, &config, &[]),
+        ];
+        assert!(before_scan[0].addon_options_collapsed);
+        assert!(!before_scan[1].addon_options_collapsed);
+
+        let rescanned_alpha = scanned_character(&alpha, &config, &before_scan);
+        assert!(rescanned_alpha.addon_options_collapsed);
+
+        // A remembered state wins over the default, which applies to characters without one.
+        config.collapse_addon_sections_by_default = true;
+        config
+            .collapsed_addon_sections
+            .insert(alpha.character.settings_key(), false);
+        assert!(!scanned_character(&alpha, &config, &before_scan).addon_options_collapsed);
+        assert!(scanned_character(&beta, &config, &before_scan).addon_options_collapsed);
+    }
+}
@@ -224,6 +224,26 @@ pub const INDENTATION_STR: &str = " ";
 /// Symbol used to indicate pinned items.
 pub const PINNED_SYMBOL: DualSymbols = DualSymbols("☆", "**");
 
+/// Symbol used to indicate favorite characters.
+pub const FAVORITE_SYMBOL: DualSymbols = DualSymbols("★", "*");
+
+/// Symbol used to indicate protected items.
+pub const PROTECTED_SYMBOL: DualSymbols = DualSymbols("🔒", "!!");
+
+/// Symbol used to indicate items needing attention.
+pub const WARNING_SYMBOL: DualSymbols = DualSymbols("⚠", "!");
+
+/// Symbol used to indicate files changed since the last backup.
+pub const CHANGED_SINCE_BACKUP_SYMBOL: DualSymbols = DualSymbols("●", "*");
+
+/// Frames of the spinner shown while the progress of a task is not yet known.
+pub const SPINNER_FRAMES: [DualSymbols; 4] = [
+    DualSymbols("◐", "|"),
+    DualSymbols("◓", "/"),
+    DualSymbols("◑", "-"),
+    DualSymbols("◒", "\\"),
+];
+
 /// Symbol used to indicate unlimited values.
 pub const UNLIMITED_SYMBOL: DualSymbols = DualSymbols("∞", "inf");
 
@@ -250,6 +270,40 @@ pub fn pinned_string(pinned: bool) -> &'static str {
     }
 }
 
+/// Get a string indicating the protection tier of a backup, followed by a space if not `Normal`.
+#[inline]
+#[must_use]
+pub fn backup_tier_string(tier: crate::wow::BackupTier) -> &'static str {
+    const PROTECTED_BETTER: &str = concatcp!(PROTECTED_SYMBOL.0, " ");
+    const PROTECTED_STANDARD: &str = concatcp!(PROTECTED_SYMBOL.1, " ");
+
+    match tier {
+        crate::wow::BackupTier::Normal => "",
+        crate::wow::BackupTier::Pinned => pinned_string(true),
+        crate::wow::BackupTier::Protected => {
+            if *BETTER_SYMBOLS {
+                PROTECTED_BETTER
+            } else {
+                PROTECTED_STANDARD
+            }
+        }
+    }
+}
+
+/// Get a string marking a backup within a folder synced by cloud storage as possibly still
+/// syncing, preceded by a space, or an empty string if the backup isn't in one of the
+/// `cloud_sync_folders`.
+#[must_use]
+pub fn cloud_sync_marker(
+    cloud_sync_folders: &[String],
+    backup: &crate::wow::WoWCharacterBackup,
+) -> String {
+    crate::wow::cloud_sync_folder(cloud_sync_folders, &backup.path)
+        .map_or_else(String::new, |folder| {
+            format!(" {WARNING_SYMBOL} {folder}, may be syncing")
+        })
+}
+
 /// Get a checkbox string based on whether the item is selected.
 #[inline]
 #[must_use]
@@ -332,6 +386,27 @@ pub fn display_backup_time(dt: &chrono::DateTime<chrono::Local>) -> String {
     dt.format(crate::backend::DISPLAY_TIME_FORMAT).to_string()
 }
 
+/// Format a time relative to now, such as "2h ago", falling back to the absolute time
+/// for times more than a week ago or in the future.
+#[must_use]
+pub fn display_relative_time(time: std::time::SystemTime) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+    const WEEK: u64 = 7 * DAY;
+
+    let Ok(elapsed) = time.elapsed() else {
+        return display_backup_time(&time.into());
+    };
+    match elapsed.as_secs() {
+        secs if secs < MINUTE => "just now".to_string(),
+        secs if secs < HOUR => format!("{}m ago", secs / MINUTE),
+        secs if secs < DAY => format!("{}h ago", secs / HOUR),
+        secs if secs < WEEK => format!("{}d ago", secs / DAY),
+        _ => display_backup_time(&time.into()),
+    }
+}
+
 /// Convert an (r, g, b) tuple into a `Color::Rgb`
 #[inline]
 #[must_use]
@@ -0,0 +1,170 @@
+#[allow(clippy::wildcard_imports)]
+use crate::palette::*;
+use crate::{
+    popups::clamp_selection,
+    ui::{CharacterIndex, KeyCodeExt, messages::AppMessage},
+    widgets::popup::{Popup, popup_block, popup_list},
+};
+
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Rect,
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{ListItem, ListState, StatefulWidget},
+};
+
+/// Different commands that can be issued from a character alias popup.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AliasPopupCommand {
+    /// Associate the backups of the given previous character name with the character.
+    SetAlias(String),
+    /// Rename the backups of the given previous character name to the character's name.
+    MigrateBackups(String),
+}
+
+/// Popup for linking the backups of a renamed character to its new name.
+#[derive(Debug, Clone)]
+pub struct AliasPopup {
+    /// The index of the character the alias will be added to.
+    pub char_idx: CharacterIndex,
+    /// Names on the same realm which have backups, but no matching character,
+    /// along with how many backups each has.
+    pub candidate_names: Vec<(String, usize)>,
+
+    /// Whether the popup should close.
+    pub close: bool,
+    /// The state of the list within the popup.
+    pub state: ListState,
+
+    /// Commands issued by the popup.
+    pub commands: Vec<AppMessage>,
+}
+
+impl AliasPopup {
+    #[must_use]
+    pub fn new(char_idx: CharacterIndex, candidate_names: Vec<(String, usize)>) -> Self {
+        let mut list_state = ListState::default();
+        list_state.select(Some(0));
+        Self {
+            char_idx,
+            candidate_names,
+
+            close: false,
+            state: list_state,
+
+            commands: vec![],
+        }
+    }
+
+    /// Push a command to the popup's command list.
+    #[inline]
+    pub fn push_command(&mut self, command: AliasPopupCommand) {
+        self.commands
+            .push(AppMessage::Alias(self.char_idx, command));
+    }
+
+    /// Push a command to the popup's command list and close the popup.
+    #[inline]
+    pub fn push_command_close(&mut self, command: AliasPopupCommand) {
+        self.push_command(command);
+        self.close = true;
+    }
+
+    /// Get the selected previous name and its number of backups.
+    #[inline]
+    #[must_use]
+    pub fn selected_candidate(&self) -> Option<(String, usize)> {
+        self.candidate_names.get(self.state.selected()?).cloned()
+    }
+
+    /// Ask to rename the backups of the selected previous name to the character's name,
+    /// closing the popup.
+    fn push_migrate_command(&mut self) {
+        let Some((name, count)) = self.selected_candidate() else {
+            return;
+        };
+        let line = vec![
+            Span::from("Rename "),
+            Span::from(format!(
+                "{count} backup{}",
+                if count == 1 { "" } else { "s" }
+            ))
+            .bold(),
+            Span::from(" of "),
+            Span::from(name.clone()).bold(),
+            Span::from(" to this character?"),
+        ];
+        self.commands.push(
+            AppMessage::Alias(self.char_idx, AliasPopupCommand::MigrateBackups(name))
+                .with_confirm_and_line(line),
+        );
+        self.close = true;
+    }
+}
+
+impl Popup for AliasPopup {
+    fn on_key_down(&mut self, key: &KeyEvent) {
+        match key.keycode_lower() {
+            KeyCode::Up | KeyCode::Char('w') => {
+                self.state.select_previous();
+            }
+            KeyCode::Down | KeyCode::Char('s') => {
+                self.state.select_next();
+                clamp_selection(&mut self.state, self.candidate_names.len());
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                if let Some((name, _)) = self.selected_candidate() {
+                    self.push_command_close(AliasPopupCommand::SetAlias(name));
+                }
+            }
+            KeyCode::Char('m') => self.push_migrate_command(),
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.close = true;
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, area: Rect, frame: &mut Frame<'_>) {
+        let block = popup_block(" Previous Character Name ");
+
+        let selected_index = self.state.selected().unwrap_or(0);
+        let items = self
+            .candidate_names
+            .iter()
+            .enumerate()
+            .map(|(i, (name, count))| {
+                let plural = if *count == 1 { "" } else { "s" };
+                let text = format!("{name} ({count} backup{plural})");
+                let line = Line::from(dual_highlight_str(&text, i == selected_index)).centered();
+                ListItem::new(line)
+            })
+            .collect::<Vec<ListItem>>();
+
+        let list_view = popup_list(block, items);
+        StatefulWidget::render(list_view, area, frame.buffer_mut(), &mut self.state);
+    }
+
+    fn should_close(&self) -> bool {
+        self.close
+    }
+    fn close(&mut self) {
+        self.close = true;
+    }
+    fn popup_identifier(&self) -> &'static str {
+        "alias_popup"
+    }
+    fn bottom_bar_options(&self) -> Option<Vec<String>> {
+        Some(vec![
+            "↑/↓".to_string(),
+            format!("{}/Space: Link Backups", ENTER_SYMBOL),
+            "M: Migrate Backups".to_string(),
+            "Esc: Close".to_string(),
+        ])
+    }
+    fn internal_commands_mut(&mut self) -> Option<&mut Vec<AppMessage>> {
+        Some(&mut self.commands)
+    }
+}
@@ -1,10 +1,23 @@
 #[allow(clippy::wildcard_imports)]
 use crate::palette::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
 use crate::{
     CharacterWithIndex,
-    popups::list_with_scrollbar,
-    ui::{KeyCodeExt, messages::AppMessage},
+    backend::{
+        RestoreScope,
+        archive_cache::{ArchiveDetails, SharedArchiveCache},
+        format_size,
+    },
+    popups::{clamp_selection, list_with_scrollbar, restore_popup::restore_command},
+    ui::{
+        KeyCodeExt,
+        messages::{AppMessage, ConfirmRisk},
+    },
     widgets::popup::{Popup, PopupMessage, popup_block, popup_list},
+    wow::{BackupTier, WoWCharacterBackup},
 };
 
 use itertools::Itertools;
@@ -22,6 +35,8 @@ use ratatui::{
 pub enum BackupManagerPopupCommand {
     /// Toggle the pinned state of a backup at a specified index on the associated character index.
     ToggleBackupPin(usize),
+    /// Toggle the protected state of a backup at a specified index on the associated character index.
+    ToggleBackupProtection(usize),
     /// Delete the backup at a specified index on the associated character index.
     DeleteBackup(usize),
 }
@@ -31,6 +46,12 @@ pub enum BackupManagerPopupCommand {
 pub struct BackupManagerPopup {
     /// The character associated with the backup manager popup.
     pub character: CharacterWithIndex,
+    /// Cache of backup archive details, shared with the app.
+    archive_cache: SharedArchiveCache,
+    /// Details of the character's backups, by path, for backups which could be read.
+    details: HashMap<PathBuf, Arc<ArchiveDetails>>,
+    /// Lowercase names of the folders synced by cloud storage, whose backups are marked.
+    cloud_sync_folders: Vec<String>,
 
     /// Whether the popup should close.
     pub close: bool,
@@ -43,17 +64,42 @@ pub struct BackupManagerPopup {
 
 impl BackupManagerPopup {
     #[must_use]
-    pub fn new(character: CharacterWithIndex, selected_index: usize) -> Self {
+    pub fn new(
+        character: CharacterWithIndex,
+        selected_index: usize,
+        archive_cache: SharedArchiveCache,
+        cloud_sync_folders: Vec<String>,
+    ) -> Self {
         let mut list_state = ListState::default();
         list_state.select(Some(selected_index));
-        Self {
+        let mut popup = Self {
             character,
+            archive_cache,
+            details: HashMap::new(),
+            cloud_sync_folders,
 
             close: false,
             state: list_state,
 
             commands: vec![],
-        }
+        };
+        popup.refresh_details();
+        popup
+    }
+
+    /// Refresh the details of the character's backups from the archive cache, only reading
+    /// archives which are new or changed since they were last read.
+    fn refresh_details(&mut self) {
+        let Ok(mut cache) = self.archive_cache.lock() else {
+            return;
+        };
+        self.details = cache.details_for(
+            self.character
+                .0
+                .backups()
+                .iter()
+                .map(|backup| backup.path.as_path()),
+        );
     }
 
     /// Push a command to the popup's command list.
@@ -63,6 +109,19 @@ impl BackupManagerPopup {
             .push(AppMessage::BackupManager(self.character.1, command));
     }
 
+    /// Push a low-risk command, confirmed only when the configuration asks for all confirmations.
+    #[inline]
+    fn push_low_risk_command(
+        &mut self,
+        command: BackupManagerPopupCommand,
+        action_line: Vec<Span<'static>>,
+    ) {
+        self.commands.push(
+            AppMessage::BackupManager(self.character.1, command)
+                .with_risk_confirm_and_line(ConfirmRisk::Low, action_line),
+        );
+    }
+
     /// Push a command to the popup's command list and close the popup.
     #[inline]
     pub fn push_command_close(&mut self, command: BackupManagerPopupCommand) {
@@ -73,9 +132,28 @@ impl BackupManagerPopup {
     /// Get the backup at a specified index from the source character.
     #[inline]
     #[must_use]
-    pub fn get_backup(&self, index: usize) -> Option<&crate::wow::WoWCharacterBackup> {
+    pub fn get_backup(&self, index: usize) -> Option<&WoWCharacterBackup> {
         self.character.0.backups().get(index)
     }
+
+    /// Flip the pinned state of the backup at the given index straight away, rather than once
+    /// the backend has renamed it. The character sent back once the toggle is done replaces the
+    /// optimistic state, reverting it if the toggle failed.
+    fn toggle_pin_optimistically(&mut self, index: usize) {
+        if let Some(backup) = self.character.0.character.backups.get_mut(index) {
+            backup.tier = BackupTier::from_pinned(!backup.is_pinned());
+        }
+    }
+
+    /// Push the command to restore only the files within the scope of the selected backup.
+    fn restore_selected(&mut self, scope: RestoreScope) {
+        if let Some(selected) = self.state.selected()
+            && let Some(backup) = self.get_backup(selected).cloned()
+        {
+            let command = restore_command(&self.character, backup, scope);
+            self.commands.push(command);
+        }
+    }
 }
 
 impl Popup for BackupManagerPopup {
@@ -86,12 +164,37 @@ impl Popup for BackupManagerPopup {
             }
             KeyCode::Down | KeyCode::Char('s') => {
                 self.state.select_next();
+                clamp_selection(&mut self.state, self.character.0.backups().len());
             }
             KeyCode::Char('e') => {
                 if let Some(selected) = self.state.selected()
-                    && self.character.0.backups().len() > selected
+                    && let Some(backup) = self.get_backup(selected)
                 {
-                    self.push_command(BackupManagerPopupCommand::ToggleBackupPin(selected));
+                    if backup.is_protected() {
+                        log::warn!(
+                            "Backup `{}` is protected, press P to remove its protection first.",
+                            backup.formatted_name()
+                        );
+                    } else {
+                        self.toggle_pin_optimistically(selected);
+                        self.push_command(BackupManagerPopupCommand::ToggleBackupPin(selected));
+                    }
+                }
+            }
+            KeyCode::Char('p') => {
+                if let Some(selected) = self.state.selected()
+                    && let Some(backup) = self.get_backup(selected)
+                {
+                    let verb = if backup.is_protected() {
+                        "Unprotect"
+                    } else {
+                        "Protect"
+                    };
+                    let line = toggle_line(verb, &backup.formatted_name());
+                    self.push_low_risk_command(
+                        BackupManagerPopupCommand::ToggleBackupProtection(selected),
+                        line,
+                    );
                 }
             }
             KeyCode::Char('d') => {
@@ -102,13 +205,37 @@ impl Popup for BackupManagerPopup {
                         self.character.1,
                         BackupManagerPopupCommand::DeleteBackup(selected),
                     );
-                    self.commands.push(command.with_confirm_and_line(vec![
+                    let delete_line = vec![
                         Span::from("Delete `"),
                         backup.formatted_name().bold(),
                         Span::from("`"),
-                    ]));
+                    ];
+                    let command = if backup.is_protected() {
+                        command.with_strong_confirm_and_line(
+                            delete_line,
+                            vec![
+                                Span::from("`"),
+                                backup.formatted_name().bold(),
+                                Span::from("` is "),
+                                Span::from("PROTECTED").bold().fg(PALETTE.log_warn_fg),
+                                Span::from(", really delete it?"),
+                            ],
+                        )
+                    } else {
+                        command.with_confirm_and_line(delete_line)
+                    };
+                    self.commands.push(command);
                 }
             }
+            KeyCode::Char('c') => {
+                self.restore_selected(RestoreScope::Config);
+            }
+            KeyCode::Char('a') => {
+                self.restore_selected(RestoreScope::Addons);
+            }
+            KeyCode::Char('m') => {
+                self.restore_selected(RestoreScope::MergeAddons);
+            }
             KeyCode::Esc | KeyCode::Char('q') => {
                 self.close = true;
             }
@@ -132,11 +259,16 @@ impl Popup for BackupManagerPopup {
             .enumerate()
             .map(|(i, backup)| {
                 let content = format!(
-                    "{}{} {}{}",
-                    pinned_string(backup.is_pinned),
+                    "{}{} {}{}{}{}",
+                    backup_tier_string(backup.tier),
                     backup.char_name,
                     display_backup_time(&backup.timestamp),
                     if backup.is_paste { " (Auto)" } else { "" },
+                    cloud_sync_marker(&self.cloud_sync_folders, backup),
+                    self.details
+                        .get(&backup.path)
+                        .map(|details| details_suffix(details))
+                        .unwrap_or_default(),
                 );
                 let line = Line::from(dual_highlight_str(content, i == selected_index)).centered();
                 ListItem::new(line)
@@ -151,10 +283,15 @@ impl Popup for BackupManagerPopup {
         match message {
             PopupMessage::UpdateCharacter(updated_char) => {
                 if updated_char.0.is_same_character(&self.character.0) {
-                    self.character = updated_char.clone();
+                    self.character = updated_char.as_ref().clone();
+                    self.refresh_details();
+                    clamp_selection(&mut self.state, self.character.0.backups().len());
                     log::debug!("Updated backup manager popup character info");
                 }
             }
+            PopupMessage::UpdateCharacters(_)
+            | PopupMessage::UpdateConfiguration(_)
+            | PopupMessage::CancelTask => {}
         }
     }
 
@@ -169,18 +306,25 @@ impl Popup for BackupManagerPopup {
     }
     fn bottom_bar_options(&self) -> Option<Vec<String>> {
         let selected_backup_index = self.state.selected().unwrap_or(0);
-        let pin_backup_opt = if let Some(backup) = self.get_backup(selected_backup_index)
-            && backup.is_pinned
-        {
+        let selected_backup = self.get_backup(selected_backup_index);
+        let pin_backup_opt = if selected_backup.is_some_and(WoWCharacterBackup::is_pinned) {
             "E: Unpin Backup"
         } else {
             "E: Pin Backup"
         };
+        let protect_backup_opt = if selected_backup.is_some_and(WoWCharacterBackup::is_protected) {
+            "P: Unprotect Backup"
+        } else {
+            "P: Protect Backup"
+        };
         Some(vec![
             "↑/↓".to_string(),
             "Esc: Close".to_string(),
             "D: Delete Backup".to_string(),
             pin_backup_opt.to_string(),
+            protect_backup_opt.to_string(),
+            "C/A: Restore Config/Addons".to_string(),
+            "M: Merge Addons".to_string(),
         ])
     }
     fn internal_commands_mut(&mut self) -> Option<&mut Vec<AppMessage>> {
@@ -193,3 +337,54 @@ impl Popup for BackupManagerPopup {
         16
     }
 }
+
+/// Create the confirmation line for toggling a flag on a backup.
+fn toggle_line(verb: &str, backup_name: &str) -> Vec<Span<'static>> {
+    vec![
+        Span::from(format!("{verb} `")),
+        Span::from(backup_name.to_string()).bold(),
+        Span::from("`"),
+    ]
+}
+
+/// Describe a backup's file count, size and note, shown after its name.
+fn details_suffix(details: &ArchiveDetails) -> String {
+    let file_count = details.files.len();
+    let note = details
+        .note()
+        .map(|note| format!(" - \"{note}\""))
+        .unwrap_or_default();
+    format!(
+        " - {file_count} file{}, {}{note}",
+        if file_count == 1 { "" } else { "s" },
+        format_size(details.size)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{backend::archive_cache::ArchiveCache, widgets::test_harness::character};
+
+    fn with_backups(count: usize) -> CharacterWithIndex {
+        let mut alpha = character("ACC", "Draenor", "Alpha", &[], &[]);
+        alpha.character.backups = (0..count)
+            .map(|i| WoWCharacterBackup {
+                path: PathBuf::from(format!("/missing/Alpha-{i}.zip")),
+                ..WoWCharacterBackup::default()
+            })
+            .collect();
+        CharacterWithIndex(alpha, 0)
+    }
+
+    #[test]
+    fn deleting_the_selected_last_backup_selects_the_new_last_backup() {
+        let mut popup =
+            BackupManagerPopup::new(with_backups(3), 2, ArchiveCache::new_shared(), Vec::new());
+        popup.process_message(&PopupMessage::UpdateCharacter(Box::new(with_backups(2))));
+        assert_eq!(popup.state.selected(), Some(1));
+
+        popup.process_message(&PopupMessage::UpdateCharacter(Box::new(with_backups(0))));
+        assert_eq!(popup.state.selected(), None);
+    }
+}
@@ -0,0 +1,133 @@
+#[allow(clippy::wildcard_imports)]
+use crate::palette::*;
+use crate::{
+    popups::backup_popup::BackupPopupCommand,
+    ui::{CharacterIndex, messages::AppMessage},
+    widgets::{
+        popup::{Popup, popup_block},
+        text_input::{TextInput, TextInputMode},
+    },
+};
+
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::{Margin, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+/// Popup for entering a note to store with a backup before it is made.
+#[derive(Debug, Clone)]
+pub struct BackupNotePopup {
+    /// Index of the character being backed up.
+    pub char_idx: CharacterIndex,
+    /// The coloured name of the character being backed up.
+    pub char_name: Span<'static>,
+    /// Note input.
+    pub note_input: TextInput,
+
+    /// Whether the popup should close.
+    pub close: bool,
+
+    /// Commands issued by the popup.
+    pub commands: Vec<AppMessage>,
+}
+
+impl BackupNotePopup {
+    #[must_use]
+    pub fn new(char_idx: CharacterIndex, char_name: Span<'static>) -> Self {
+        let mut note_input = TextInput::new_with_placeholder(
+            "Enter a note for the backup, or leave empty to back up without one...",
+        );
+        note_input.mode = TextInputMode::Editing;
+
+        Self {
+            char_idx,
+            char_name,
+            note_input,
+
+            close: false,
+
+            commands: vec![],
+        }
+    }
+
+    /// Push the command backing up all files with the entered note, and close the popup.
+    fn submit(&mut self) {
+        let note = self.note_input.input.trim().to_string();
+        let command = if note.is_empty() {
+            BackupPopupCommand::BackupAllFiles
+        } else {
+            BackupPopupCommand::BackupAllFilesWithNote(note)
+        };
+        self.commands
+            .push(AppMessage::Backup(self.char_idx, command));
+        self.close = true;
+    }
+}
+
+impl Popup for BackupNotePopup {
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if let Event::Key(key_event) = event
+            && key_event.kind == KeyEventKind::Press
+        {
+            match key_event.code {
+                KeyCode::Enter => {
+                    self.submit();
+                    return true;
+                }
+                KeyCode::Esc => {
+                    self.close = true;
+                    return true;
+                }
+                _ => {}
+            }
+        }
+        self.note_input.mode = TextInputMode::Editing;
+        self.note_input.handle_event(event);
+        true
+    }
+
+    fn draw(&mut self, area: Rect, frame: &mut Frame<'_>) {
+        let block = popup_block(Line::from(vec![
+            Span::from(" Backup Note - "),
+            self.char_name.clone(),
+            Span::from(" "),
+        ]))
+        .border_style(Style::default().fg(PALETTE.log_info_fg));
+
+        Widget::render(block, area, frame.buffer_mut());
+        self.note_input.render(area.inner(Margin::new(1, 1)), frame);
+    }
+
+    fn should_close(&self) -> bool {
+        self.close
+    }
+    fn close(&mut self) {
+        self.close = true;
+    }
+    fn popup_identifier(&self) -> &'static str {
+        "backup_note_popup"
+    }
+    fn bottom_bar_options(&self) -> Option<Vec<String>> {
+        Some(vec![
+            format!("{ENTER_SYMBOL}: Back Up"),
+            "Esc: Cancel".to_string(),
+        ])
+    }
+    fn internal_commands_mut(&mut self) -> Option<&mut Vec<AppMessage>> {
+        Some(&mut self.commands)
+    }
+
+    fn popup_width_percent(&self) -> u16 {
+        70
+    }
+    fn popup_height_percent(&self) -> u16 {
+        0
+    }
+    fn popup_min_height(&self) -> u16 {
+        3
+    }
+}
@@ -2,7 +2,7 @@
 use crate::palette::*;
 use crate::{
     CharacterWithIndex,
-    popups::wrap_selection,
+    popups::{clamp_selection, wrap_selection},
     ui::{KeyCodeExt, messages::AppMessage},
     widgets::popup::{Popup, popup_block, popup_list},
 };
@@ -11,6 +11,7 @@ use ratatui::{
     Frame,
     crossterm::event::{KeyCode, KeyEvent},
     layout::Rect,
+    style::Stylize,
     text::{Line, Span},
     widgets::{ListItem, ListState, StatefulWidget},
 };
@@ -24,10 +25,20 @@ pub enum BackupPopupCommand {
     BackupSelectedFiles,
     /// Command to backup all files.
     BackupAllFiles,
+    /// Command to backup all files, with the given note stored in the backup.
+    BackupAllFilesWithNote(String),
     /// Command to restore from backup.
     RestoreFromBackup,
+    /// Command to restore from an archive outside the character's backups directory.
+    RestoreFromFile,
+    /// Command to import a single addon's settings from an addon profile archive.
+    ImportAddonProfile,
     /// Command to restore from copied character's backups.
     RestoreFromCopiedBackups,
+    /// Command to link the backups of a previous (renamed) character name.
+    LinkRenamedBackups,
+    /// Command to paste the selected files to every other character on the realm.
+    PasteToRealm,
 }
 
 /// Popup for backup options for a character.
@@ -37,6 +48,12 @@ pub struct BackupPopup {
     pub character: CharacterWithIndex,
     /// The copied character if applicable, for restoring from their backups.
     pub copied_character: Option<CharacterWithIndex>,
+    /// Whether the character has orphaned backups from a possible previous name.
+    pub has_rename_candidates: bool,
+    /// Number of other characters on the realm the selected files can be pasted to.
+    pub realm_paste_targets: usize,
+    /// Why the character's backups directory can't be written to, if it can't.
+    pub backup_location_error: Option<String>,
 
     /// Whether the popup should close.
     pub close: bool,
@@ -58,6 +75,9 @@ impl BackupPopup {
         Self {
             character,
             copied_character,
+            has_rename_candidates: false,
+            realm_paste_targets: 0,
+            backup_location_error: None,
 
             close: false,
             state: list_state,
@@ -66,6 +86,37 @@ impl BackupPopup {
         }
     }
 
+    /// Set whether to offer linking backups from a previous character name.
+    #[must_use]
+    pub const fn with_rename_candidates(mut self, has_rename_candidates: bool) -> Self {
+        self.has_rename_candidates = has_rename_candidates;
+        self
+    }
+
+    /// Set the number of other characters on the realm the selected files can be pasted to.
+    #[must_use]
+    pub const fn with_realm_paste_targets(mut self, realm_paste_targets: usize) -> Self {
+        self.realm_paste_targets = realm_paste_targets;
+        self
+    }
+
+    /// Set why the character's backups directory can't be written to, if it can't.
+    /// Options which write backups are unavailable while set.
+    #[must_use]
+    pub fn with_backup_location_error(mut self, backup_location_error: Option<String>) -> Self {
+        self.backup_location_error = backup_location_error;
+        self
+    }
+
+    /// Returns `true` if backups can be written, logging why not otherwise.
+    fn check_backup_location(&self) -> bool {
+        if let Some(error) = &self.backup_location_error {
+            log::error!("{error}");
+            return false;
+        }
+        true
+    }
+
     /// Push a command to the popup's command list.
     #[inline]
     pub fn push_command(&mut self, command: BackupPopupCommand) {
@@ -86,7 +137,64 @@ impl BackupPopup {
     pub const BACKUP_SELECTED_IDX: usize = 1;
     pub const BACKUP_ALL_IDX: usize = 2;
     pub const RESTORE_FROM_BACKUP_IDX: usize = 3;
-    pub const RESTORE_FROM_COPIED_IDX: usize = 4;
+    pub const RESTORE_FROM_FILE_IDX: usize = 4;
+    pub const IMPORT_ADDON_PROFILE_IDX: usize = 5;
+    pub const RESTORE_FROM_COPIED_IDX: usize = 6;
+
+    /// Get the list index of the link renamed backups option, if shown.
+    #[inline]
+    #[must_use]
+    pub const fn link_renamed_idx(&self) -> Option<usize> {
+        if !self.has_rename_candidates {
+            return None;
+        }
+        if self.copied_character.is_some() {
+            Some(Self::RESTORE_FROM_COPIED_IDX + 1)
+        } else {
+            Some(Self::RESTORE_FROM_COPIED_IDX)
+        }
+    }
+
+    /// Get the list index of the paste to realm option, if shown.
+    /// Only shown when files are selected and there are other characters on the realm.
+    #[inline]
+    #[must_use]
+    pub fn paste_to_realm_idx(&self) -> Option<usize> {
+        if self.realm_paste_targets == 0 || !self.character.0.any_file_selected() {
+            return None;
+        }
+        let preceding_optional =
+            usize::from(self.copied_character.is_some()) + usize::from(self.has_rename_candidates);
+        Some(Self::RESTORE_FROM_COPIED_IDX + preceding_optional)
+    }
+
+    /// Get the number of options shown, including the optional ones.
+    #[must_use]
+    pub fn item_count(&self) -> usize {
+        // The optional options follow the last of the options which are always shown.
+        Self::RESTORE_FROM_COPIED_IDX
+            + usize::from(self.copied_character.is_some())
+            + usize::from(self.link_renamed_idx().is_some())
+            + usize::from(self.paste_to_realm_idx().is_some())
+    }
+
+    /// Push the paste to realm command, wrapped in a confirmation showing the character count.
+    fn push_paste_to_realm_command(&mut self) {
+        let targets = self.realm_paste_targets;
+        let command = AppMessage::Backup(self.character.1, BackupPopupCommand::PasteToRealm)
+            .with_confirm_and_line(vec![
+                Span::from("Paste to "),
+                Span::from(format!(
+                    "{targets} character{}",
+                    if targets == 1 { "" } else { "s" }
+                ))
+                .bold(),
+                Span::from(" on "),
+                Span::from(self.character.0.display_realm()).bold(),
+            ]);
+        self.commands.push(command);
+        self.close = true;
+    }
 }
 
 impl Popup for BackupPopup {
@@ -96,23 +204,51 @@ impl Popup for BackupPopup {
                 self.state.select_previous();
             }
             KeyCode::Down | KeyCode::Char('s') => {
+                let len = self.item_count();
                 self.state.select_next();
+                clamp_selection(&mut self.state, len);
             }
             KeyCode::Enter | KeyCode::Char(' ') => {
                 if let Some(selected) = self.state.selected() {
+                    if Some(selected) == self.link_renamed_idx() {
+                        self.push_command_close(BackupPopupCommand::LinkRenamedBackups);
+                        return;
+                    }
+                    if Some(selected) == self.paste_to_realm_idx() {
+                        if self.check_backup_location() {
+                            self.push_paste_to_realm_command();
+                        }
+                        return;
+                    }
                     match selected {
                         Self::MANAGE_BACKUPS_IDX => {
                             self.push_command_close(BackupPopupCommand::ManageBackups);
                         }
                         Self::BACKUP_SELECTED_IDX => {
-                            self.push_command_close(BackupPopupCommand::BackupSelectedFiles);
+                            if self.character.0.total_selected_count() == 0 {
+                                log::warn!(
+                                    "No files selected to back up, select files first or back up all files."
+                                );
+                            } else if self.check_backup_location() {
+                                self.push_command_close(BackupPopupCommand::BackupSelectedFiles);
+                            }
                         }
                         Self::BACKUP_ALL_IDX => {
-                            self.push_command_close(BackupPopupCommand::BackupAllFiles);
+                            if !self.character.0.has_files() {
+                                log::warn!("This character has no files to back up.");
+                            } else if self.check_backup_location() {
+                                self.push_command_close(BackupPopupCommand::BackupAllFiles);
+                            }
                         }
                         Self::RESTORE_FROM_BACKUP_IDX => {
                             self.push_command_close(BackupPopupCommand::RestoreFromBackup);
                         }
+                        Self::RESTORE_FROM_FILE_IDX => {
+                            self.push_command_close(BackupPopupCommand::RestoreFromFile);
+                        }
+                        Self::IMPORT_ADDON_PROFILE_IDX => {
+                            self.push_command_close(BackupPopupCommand::ImportAddonProfile);
+                        }
                         Self::RESTORE_FROM_COPIED_IDX => {
                             if self.copied_character.is_some() {
                                 self.push_command_close(
@@ -134,12 +270,22 @@ impl Popup for BackupPopup {
     }
 
     fn draw(&mut self, area: Rect, frame: &mut Frame<'_>) {
-        let block = popup_block(" Backup Options ");
+        let mut block = popup_block(" Backup Options ");
+        if let Some(error) = &self.backup_location_error {
+            block = block.title_bottom(
+                Line::from(format!(" {error} "))
+                    .fg(PALETTE.log_error_fg)
+                    .centered(),
+            );
+        }
+        let backup_location_available = self.backup_location_error.is_none();
         let item_names = [
             "Manage backups",
             "Backup selected files",
             "Backup all files",
             "Restore from backup",
+            "Restore from file...",
+            "Import addon profile...",
         ];
 
         let selected_index = self.state.selected().unwrap_or(0);
@@ -149,7 +295,22 @@ impl Popup for BackupPopup {
             .map(|(i, item)| {
                 let content = dual_highlight_str(item, selected_index == i);
                 let line = Line::from(content).centered();
-                ListItem::new(line)
+                // Backing up a selection is unavailable until files are selected, and backing up
+                // anything is unavailable for characters without files or a writable location.
+                let unavailable = match i {
+                    Self::BACKUP_SELECTED_IDX => {
+                        !backup_location_available || self.character.0.total_selected_count() == 0
+                    }
+                    Self::BACKUP_ALL_IDX => {
+                        !backup_location_available || !self.character.0.has_files()
+                    }
+                    _ => false,
+                };
+                if unavailable {
+                    ListItem::new(line.dim())
+                } else {
+                    ListItem::new(line)
+                }
             })
             .collect::<Vec<ListItem>>();
 
@@ -163,6 +324,30 @@ impl Popup for BackupPopup {
             items.push(ListItem::new(line));
         }
 
+        if let Some(link_idx) = self.link_renamed_idx() {
+            let content = dual_highlight_str(
+                "Link backups from a previous name",
+                selected_index == link_idx,
+            );
+            items.push(ListItem::new(Line::from(content).centered()));
+        }
+
+        if let Some(paste_idx) = self.paste_to_realm_idx() {
+            let content = dual_highlight_str(
+                format!(
+                    "Paste selected files to all characters on {}",
+                    self.character.0.display_realm()
+                ),
+                selected_index == paste_idx,
+            );
+            let line = Line::from(content).centered();
+            if backup_location_available {
+                items.push(ListItem::new(line));
+            } else {
+                items.push(ListItem::new(line.dim()));
+            }
+        }
+
         let list_view = popup_list(block, items);
 
         StatefulWidget::render(list_view, area, frame.buffer_mut(), &mut self.state);
@@ -191,3 +376,55 @@ impl Popup for BackupPopup {
         64
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::test_harness::{character, render_popup, trimmed_rows};
+
+    fn alpha() -> CharacterWithIndex {
+        CharacterWithIndex(
+            character("ACC", "Draenor", "Alpha", &["config-cache.wtf"], &[]),
+            0,
+        )
+    }
+
+    #[test]
+    fn lists_the_options_always_shown() {
+        let mut popup = BackupPopup::new(alpha(), None);
+        assert_eq!(
+            trimmed_rows(&render_popup(&mut popup, 70, 14)),
+            [
+                "╭─────────────────────── Backup Options ───────────────────────╮",
+                "│                      > Manage backups <                      │",
+                "│                    Backup selected files                     │",
+                "│                       Backup all files                       │",
+                "│                     Restore from backup                      │",
+                "│                     Restore from file...                     │",
+                "│                   Import addon profile...                    │",
+                "│                                                              │",
+                "│                                                              │",
+                "╰──────────────────────────────────────────────────────────────╯",
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_restoring_from_the_copied_character() {
+        let copied = CharacterWithIndex(character("ACC", "Draenor", "Bravo", &[], &[]), 1);
+        let mut popup = BackupPopup::new(alpha(), Some(copied));
+        popup
+            .state
+            .select(Some(BackupPopup::RESTORE_FROM_COPIED_IDX));
+        let snapshot = render_popup(&mut popup, 70, 14);
+        let rows = trimmed_rows(&snapshot);
+        assert_eq!(
+            rows[7],
+            "│          > Restore from Bravo - Draenor's backups <          │"
+        );
+        assert_eq!(
+            rows[1],
+            "│                        Manage backups                        │"
+        );
+    }
+}
@@ -1,6 +1,7 @@
 #[allow(clippy::wildcard_imports)]
 use crate::palette::*;
 use crate::{
+    popups::clamp_selection,
     ui::{KeyCodeExt, messages::AppMessage},
     widgets::popup::{Popup, popup_block, popup_list},
     wow::WoWInstalls,
@@ -76,13 +77,15 @@ impl Popup for BranchPopup {
             }
             KeyCode::Down | KeyCode::Char('s') => {
                 self.state.select_next();
+                clamp_selection(&mut self.state, self.branches.distinct_branches().len());
             }
             KeyCode::Enter | KeyCode::Char(' ') => {
                 if let Some(selected) = self.state.selected()
-                    && selected < self.branches.len()
+                    && let Some((install, _)) =
+                        self.branches.distinct_branches().get(selected).copied()
                 {
                     self.push_command_close(BranchPopupCommand::SelectBranch(
-                        self.branches.installs[selected].branch_ident.clone(),
+                        install.branch_ident.clone(),
                     ));
                 }
             }
@@ -99,17 +102,19 @@ impl Popup for BranchPopup {
         let selected_index = self.state.selected().unwrap_or(0);
         let items = self
             .branches
-            .installs
-            .iter()
+            .distinct_branches()
+            .into_iter()
             .enumerate()
-            .map(|(i, item)| {
-                let content = if let Some(selected_branch) = &self.current_branch
+            .map(|(i, (item, install_count))| {
+                let mut content = item.display_branch_name();
+                if install_count > 1 {
+                    content = format!("{content} (all {install_count} installs)");
+                }
+                if let Some(selected_branch) = &self.current_branch
                     && item.branch_ident == *selected_branch
                 {
-                    format!("{} (current)", item.display_branch_name())
-                } else {
-                    item.display_branch_name()
-                };
+                    content = format!("{content} (current)");
+                }
                 let line = Line::from(dual_highlight_str(content, i == selected_index)).centered();
                 ListItem::new(line)
             })
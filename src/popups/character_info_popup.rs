@@ -0,0 +1,178 @@
+#[allow(clippy::wildcard_imports)]
+use crate::palette::*;
+use crate::{
+    backend::format_size,
+    ui::{Character, CharacterWithInstall, KeyCodeExt},
+    widgets::popup::{Popup, popup_block},
+};
+
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Rect,
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Paragraph, Widget, Wrap},
+};
+
+/// Read-only popup showing everything known about a character.
+#[derive(Debug, Clone)]
+pub struct CharacterInfoPopup {
+    /// The label and value rows to display.
+    rows: Vec<(&'static str, Vec<Span<'static>>)>,
+
+    /// Whether the popup should close.
+    pub close: bool,
+}
+
+impl CharacterInfoPopup {
+    /// Width of the label column, including the separator.
+    const LABEL_WIDTH: usize = 14;
+
+    /// Create the popup for the given character, flagging its backups if there are more than
+    /// the warning threshold.
+    #[must_use]
+    pub fn new(
+        (character, install): CharacterWithInstall<'_>,
+        backup_warning_threshold: Option<usize>,
+    ) -> Self {
+        let backups = character.backups();
+        let backups_size: u64 = backups
+            .iter()
+            .filter_map(|backup| std::fs::metadata(&backup.path).ok())
+            .map(|metadata| metadata.len())
+            .sum();
+        let plural = if backups.len() == 1 { "" } else { "s" };
+        let mut backups_span = Span::from(format!(
+            "{} backup{plural}, {}",
+            backups.len(),
+            format_size(backups_size)
+        ));
+        if character.exceeds_backup_threshold(backup_warning_threshold) {
+            backups_span = Span::from(format!(
+                "{} {WARNING_SYMBOL} consider removing old backups",
+                backups_span.content
+            ))
+            .fg(PALETTE.log_warn_fg);
+        }
+
+        let level = character.level().map_or_else(
+            || Span::from("Unknown, install the ChronoBind Companion addon").dim(),
+            |level| Span::from(level.to_string()),
+        );
+        let last_backup = backups
+            .iter()
+            .map(|backup| backup.timestamp)
+            .max()
+            .map_or_else(
+                || Span::from("Never").dim(),
+                |time| Span::from(display_relative_time(time.into())),
+            );
+
+        let rows = vec![
+            ("Name", vec![character.display_span(false).bold()]),
+            ("Realm", realm_spans(character)),
+            ("Account", vec![Span::from(character.account().to_string())]),
+            ("Branch", vec![Span::from(install.display_branch_name())]),
+            (
+                "Class",
+                vec![Span::from(character.class().class_name()).fg(character.class_colour())],
+            ),
+            ("Level", vec![level]),
+            (
+                "Config files",
+                vec![Span::from(character.config_files().len().to_string())],
+            ),
+            (
+                "Addon files",
+                vec![Span::from(character.addon_files().len().to_string())],
+            ),
+            ("Backups", vec![backups_span]),
+            ("Last backup", vec![last_backup]),
+            (
+                "Directory",
+                vec![Span::from(
+                    character
+                        .character
+                        .get_character_path(install)
+                        .display()
+                        .to_string(),
+                )],
+            ),
+        ];
+
+        Self { rows, close: false }
+    }
+}
+
+/// Get the spans showing the character's realm, with its folder name alongside if it has an alias.
+fn realm_spans(character: &Character) -> Vec<Span<'static>> {
+    let display_realm = character.display_realm();
+    if display_realm == character.realm() {
+        vec![Span::from(display_realm)]
+    } else {
+        vec![
+            Span::from(display_realm),
+            Span::from(format!(" ({})", character.realm())).dim(),
+        ]
+    }
+}
+
+impl Popup for CharacterInfoPopup {
+    fn on_key_down(&mut self, key: &KeyEvent) {
+        if matches!(
+            key.keycode_lower(),
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q' | 'i')
+        ) {
+            self.close = true;
+        }
+    }
+
+    fn draw(&mut self, area: Rect, frame: &mut Frame<'_>) {
+        let block = popup_block(" Character Info ");
+
+        let lines = self
+            .rows
+            .iter()
+            .map(|(label, value)| {
+                let label = format!("{:<width$}", format!("{label}:"), width = Self::LABEL_WIDTH);
+                let mut spans = vec![Span::from(label).bold()];
+                spans.extend(value.iter().cloned());
+                Line::from(spans)
+            })
+            .collect::<Vec<_>>();
+
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .render(area, frame.buffer_mut());
+    }
+
+    fn should_close(&self) -> bool {
+        self.close
+    }
+    fn close(&mut self) {
+        self.close = true;
+    }
+    fn popup_identifier(&self) -> &'static str {
+        "character_info_popup"
+    }
+    fn bottom_bar_options(&self) -> Option<Vec<String>> {
+        Some(vec!["Esc: Close".to_string()])
+    }
+
+    fn popup_width_percent(&self) -> u16 {
+        60
+    }
+    fn popup_height_percent(&self) -> u16 {
+        0
+    }
+    fn popup_min_width(&self) -> u16 {
+        50
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    fn popup_min_height(&self) -> u16 {
+        // One line per row, plus the borders and room for a long directory path to wrap.
+        self.rows.len() as u16 + 4
+    }
+}
@@ -0,0 +1,123 @@
+#[allow(clippy::wildcard_imports)]
+use crate::palette::*;
+use crate::{
+    ChronoBindAppConfig,
+    popups::{clamp_selection, options_popup::OptionsPopupCommand, toggle_option},
+    ui::{KeyCodeExt, messages::AppMessage},
+    widgets::{
+        character_list::CharacterListColumn,
+        popup::{Popup, popup_block, popup_list},
+    },
+};
+
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Rect,
+    widgets::{ListItem, ListState, StatefulWidget},
+};
+
+/// Popup for choosing which columns are shown in the character list.
+#[derive(Debug, Clone)]
+pub struct ColumnsPopup {
+    /// The current application configuration.
+    pub configuration: ChronoBindAppConfig,
+
+    /// Whether the popup should close.
+    pub close: bool,
+    /// The state of the list within the popup.
+    pub state: ListState,
+
+    /// Commands issued by the popup.
+    pub commands: Vec<AppMessage>,
+}
+
+impl ColumnsPopup {
+    #[must_use]
+    pub fn new(config: ChronoBindAppConfig) -> Self {
+        let mut list_state = ListState::default();
+        list_state.select(Some(0));
+        Self {
+            configuration: config,
+
+            close: false,
+            state: list_state,
+
+            commands: vec![],
+        }
+    }
+
+    /// Toggle the visibility of the column at the given index, and push the updated configuration.
+    fn toggle_column(&mut self, index: usize) {
+        let Some(column) = CharacterListColumn::ALL.get(index).copied() else {
+            return;
+        };
+        let columns = &mut self.configuration.character_list_columns;
+        if !columns.remove(&column) {
+            columns.insert(column);
+        }
+        self.commands.push(AppMessage::Options(
+            OptionsPopupCommand::UpdateConfiguration(Box::new(self.configuration.clone())),
+        ));
+    }
+}
+
+impl Popup for ColumnsPopup {
+    fn on_key_down(&mut self, key: &KeyEvent) {
+        match key.keycode_lower() {
+            KeyCode::Up | KeyCode::Char('w') => {
+                self.state.select_previous();
+            }
+            KeyCode::Down | KeyCode::Char('s') => {
+                self.state.select_next();
+                clamp_selection(&mut self.state, CharacterListColumn::ALL.len());
+            }
+            KeyCode::Enter | KeyCode::Char(' ' | 'd') | KeyCode::Right => {
+                if let Some(selected) = self.state.selected() {
+                    self.toggle_column(selected);
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q' | 'l') => {
+                self.close = true;
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, area: Rect, frame: &mut Frame<'_>) {
+        let block = popup_block(" Character List Columns ");
+
+        let selected_index = self.state.selected().unwrap_or(0);
+        let items = CharacterListColumn::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let shown = self.configuration.character_list_columns.contains(column);
+                ListItem::new(toggle_option(column.title(), shown, i == selected_index))
+            })
+            .collect::<Vec<ListItem>>();
+
+        let list_view = popup_list(block, items);
+        StatefulWidget::render(list_view, area, frame.buffer_mut(), &mut self.state);
+    }
+
+    fn should_close(&self) -> bool {
+        self.close
+    }
+    fn close(&mut self) {
+        self.close = true;
+    }
+    fn popup_identifier(&self) -> &'static str {
+        "columns_popup"
+    }
+    fn bottom_bar_options(&self) -> Option<Vec<String>> {
+        Some(vec![
+            "↑/↓".to_string(),
+            format!("{ENTER_SYMBOL}/→/Space: Toggle"),
+            "Esc: Close".to_string(),
+        ])
+    }
+    fn internal_commands_mut(&mut self) -> Option<&mut Vec<AppMessage>> {
+        Some(&mut self.commands)
+    }
+}
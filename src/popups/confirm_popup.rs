@@ -1,7 +1,7 @@
 #[allow(clippy::wildcard_imports)]
 use crate::palette::*;
 use crate::{
-    popups::wrap_selection_text,
+    popups::{clamp_selection, wrap_selection_text},
     ui::{
         KeyCodeExt,
         messages::{AppMessage, ConfirmActionText},
@@ -76,6 +76,7 @@ impl Popup for ConfirmationPopup {
             }
             KeyCode::Down | KeyCode::Char('s') => {
                 self.state.select_next();
+                clamp_selection(&mut self.state, Self::CONFIRM_IDX + 1);
             }
             KeyCode::Enter | KeyCode::Char(' ' | 'd') => {
                 if let Some(selected) = self.state.selected() {
@@ -180,3 +181,41 @@ impl Popup for ConfirmationPopup {
 //         .map(Line::into_owned)
 //         .collect()
 // }
+
+#[cfg(test)]
+mod tests {
+    use ratatui::text::Span;
+
+    use super::*;
+    use crate::widgets::test_harness::{render_popup, trimmed_rows};
+
+    #[test]
+    fn cancel_is_hovered_by_default() {
+        let mut popup = ConfirmationPopup::new(AppMessage::Quit, None);
+        assert_eq!(
+            trimmed_rows(&render_popup(&mut popup, 60, 10)),
+            [
+                "╭──────────────── Are you sure? ─────────────────╮",
+                "│                   > Cancel <                   │",
+                "│                    Confirm                     │",
+                "╰────────────────────────────────────────────────╯",
+            ]
+        );
+    }
+
+    #[test]
+    fn action_line_replaces_confirm_and_sizes_the_popup() {
+        let mut popup =
+            ConfirmationPopup::new(AppMessage::Quit, Some(Span::from("Quit now").into()));
+        popup.state.select(Some(ConfirmationPopup::CONFIRM_IDX));
+        assert_eq!(
+            trimmed_rows(&render_popup(&mut popup, 60, 10)),
+            [
+                "╭─ Are you sure? ─╮",
+                "│     Cancel      │",
+                "│  > Quit now <   │",
+                "╰─────────────────╯",
+            ]
+        );
+    }
+}
@@ -3,8 +3,8 @@ use std::path::{Path, PathBuf};
 #[allow(clippy::wildcard_imports)]
 use crate::palette::*;
 use crate::{
-    backend::InstallBackupOptions,
-    popups::{format_option, toggle_option},
+    backend::{InstallBackupOptions, zip_rw::ChronoZipReader},
+    popups::{clamp_selection, format_option, parse_path, toggle_option},
     ui::{KeyCodeExt, messages::AppMessage},
     widgets::{
         popup::{Popup, popup_block, popup_list, popup_list_no_block},
@@ -17,7 +17,7 @@ use ratatui::{
     buffer::Buffer,
     crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind},
     layout::{Constraint, Direction, Layout, Rect},
-    style::Style,
+    style::{Style, Stylize},
     text::Line,
     widgets::{Clear, ListItem, ListState, StatefulWidget, Widget},
 };
@@ -31,12 +31,21 @@ pub enum ExportManagerMessage {
     ExportAllBranchesChronoBind,
     /// Export all from the currently selected branch (Addons, WTF, `ChronoBind`).
     ExportFullCurrentBranch,
+    /// Export all from the currently selected branch (Addons, WTF, `ChronoBind`),
+    /// only the files changed since the branch was last exported.
+    ExportChangedCurrentBranch,
     /// Export all from all branches (Addons, WTF, `ChronoBind`).
     ExportFullAllBranches,
+    /// Export the given contents of all branches, after its estimate has been confirmed.
+    ExportAllBranches(InstallBackupOptions),
     /// Open the import dialog.
     OpenImportDialog,
-    /// Import a `ChronoBind` backup from the specified path with the given options.
-    ImportChronoBindBackup(PathBuf, InstallBackupOptions),
+    /// Import a `ChronoBind` backup from the specified path with the given options,
+    /// and whether to also apply machine-specific settings from the import.
+    ImportChronoBindBackup(PathBuf, InstallBackupOptions, bool),
+    /// Record the unix time, in seconds, an export of the given branch was started at,
+    /// once the export has completed.
+    RecordExportTime(String, u64),
 }
 
 /// Popup for managing import/export operations.
@@ -90,10 +99,12 @@ impl ExportManagerPopup {
     pub const EXPORT_ALL_BRANCHES_CHRONOBIND_IDX: usize = 1;
     /// Index of Export all from current branch option.
     pub const EXPORT_CURRENT_BRANCH_ALL_IDX: usize = 2;
+    /// Index of Export changes since the last export from current branch option.
+    pub const EXPORT_CURRENT_BRANCH_CHANGED_IDX: usize = 3;
     /// Index of Export all from all branches option.
-    pub const EXPORT_ALL_BRANCHES_ALL_IDX: usize = 3;
+    pub const EXPORT_ALL_BRANCHES_ALL_IDX: usize = 4;
     /// Index of Import backup option.
-    pub const IMPORT_BACKUP_IDX: usize = 4;
+    pub const IMPORT_BACKUP_IDX: usize = 5;
 }
 
 impl Popup for ExportManagerPopup {
@@ -104,6 +115,7 @@ impl Popup for ExportManagerPopup {
             }
             KeyCode::Down | KeyCode::Char('s') => {
                 self.state.select_next();
+                clamp_selection(&mut self.state, Self::IMPORT_BACKUP_IDX + 1);
             }
             KeyCode::Enter | KeyCode::Char(' ' | 'd') => {
                 if let Some(selected) = self.state.selected() {
@@ -117,6 +129,9 @@ impl Popup for ExportManagerPopup {
                         Self::EXPORT_CURRENT_BRANCH_ALL_IDX => {
                             self.push_command(ExportManagerMessage::ExportFullCurrentBranch);
                         }
+                        Self::EXPORT_CURRENT_BRANCH_CHANGED_IDX => {
+                            self.push_command(ExportManagerMessage::ExportChangedCurrentBranch);
+                        }
                         Self::EXPORT_ALL_BRANCHES_ALL_IDX => {
                             self.push_command(ExportManagerMessage::ExportFullAllBranches);
                         }
@@ -157,6 +172,13 @@ impl Popup for ExportManagerPopup {
                 ),
                 selected_idx == Self::EXPORT_CURRENT_BRANCH_ALL_IDX,
             )),
+            ListItem::new(highlight_str(
+                format!(
+                    "Export changes since the last export from currently selected branch: {}",
+                    format_option(self.selected_branch.as_ref())
+                ),
+                selected_idx == Self::EXPORT_CURRENT_BRANCH_CHANGED_IDX,
+            )),
             ListItem::new(highlight_str(
                 "Export all from all branches (Addons, WTF, ChronoBind)",
                 selected_idx == Self::EXPORT_ALL_BRANCHES_ALL_IDX,
@@ -206,8 +228,12 @@ impl Popup for ExportManagerPopup {
 pub struct ImportDialog {
     /// Options for importing backups.
     pub import_options: InstallBackupOptions,
+    /// Whether to also apply machine-specific settings, such as the preferred branch.
+    pub include_machine_settings: bool,
     /// Text import state.
     pub path_input: TextInput,
+    /// Why the entered path can't be imported, shown below it, if the last import attempt failed.
+    pub path_error: Option<String>,
 
     /// Whether the popup should close.
     pub close: bool,
@@ -249,8 +275,13 @@ impl ImportDialog {
         text_input.input = text.into();
 
         Self {
-            import_options: InstallBackupOptions::all(),
+            import_options: InstallBackupOptions {
+                include_settings: false,
+                ..InstallBackupOptions::all()
+            },
+            include_machine_settings: false,
             path_input: text_input,
+            path_error: None,
 
             close: false,
             state: list_state,
@@ -280,8 +311,12 @@ impl ImportDialog {
     pub const INCLUDE_INTERFACE_IDX: usize = 1;
     /// Index of the include characters (Character backups) option.
     pub const INCLUDE_CHARACTERS_IDX: usize = 2;
+    /// Index of the include settings (`ChronoBind` settings) option.
+    pub const INCLUDE_SETTINGS_IDX: usize = 3;
+    /// Index of the include machine-specific settings option.
+    pub const INCLUDE_MACHINE_SETTINGS_IDX: usize = 4;
     /// Index of the import button.
-    pub const IMPORT_IDX: usize = 3;
+    pub const IMPORT_IDX: usize = 5;
 
     /// Check if the given index is currently hovered
     #[inline]
@@ -293,6 +328,41 @@ impl ImportDialog {
         let selected_index = self.state.selected().unwrap_or(0);
         selected_index == index
     }
+
+    /// Import the backup at the entered path, closing the dialog, or show why it can't be
+    /// imported and keep the dialog open.
+    fn try_import(&mut self) {
+        let import_path = parse_path(&self.path_input.input);
+        if let Err(e) = validate_import_path(&import_path) {
+            log::warn!("Cannot import `{}`: {e}", import_path.display());
+            self.path_error = Some(e);
+            return;
+        }
+        self.path_error = None;
+        self.push_command_close(ExportManagerMessage::ImportChronoBindBackup(
+            import_path,
+            self.import_options,
+            self.import_options.include_settings && self.include_machine_settings,
+        ));
+    }
+}
+
+/// Check that the path points to a readable ZIP archive, so it can be imported.
+/// # Errors
+/// Returns a message describing why the path can't be imported.
+pub fn validate_import_path(path: &Path) -> Result<(), String> {
+    if path.as_os_str().is_empty() {
+        return Err("Enter the path of a backup to import".to_string());
+    }
+    if !path.exists() {
+        return Err("File not found".to_string());
+    }
+    if !path.is_file() {
+        return Err("Path is not a file".to_string());
+    }
+    ChronoZipReader::new(path)
+        .map(|_| ())
+        .map_err(|e| format!("Not a readable ZIP archive: {e}"))
 }
 
 impl Popup for ImportDialog {
@@ -303,9 +373,11 @@ impl Popup for ImportDialog {
             }
             KeyCode::Down | KeyCode::Char('s') => {
                 self.state.select_next();
+                clamp_selection(&mut self.state, Self::IMPORT_IDX + 1);
             }
             KeyCode::Char('t') => {
                 self.path_input.mode = TextInputMode::Editing;
+                self.path_error = None;
             }
             KeyCode::Enter | KeyCode::Char(' ' | 'd' | 'e') => {
                 match self.state.selected().unwrap_or_default() {
@@ -320,13 +392,14 @@ impl Popup for ImportDialog {
                         self.import_options.include_character_backups =
                             !self.import_options.include_character_backups;
                     }
-                    Self::IMPORT_IDX => {
-                        let import_path = parse_path(&self.path_input.input);
-                        self.push_command_close(ExportManagerMessage::ImportChronoBindBackup(
-                            import_path,
-                            self.import_options,
-                        ));
+                    Self::INCLUDE_SETTINGS_IDX => {
+                        self.import_options.include_settings =
+                            !self.import_options.include_settings;
                     }
+                    Self::INCLUDE_MACHINE_SETTINGS_IDX if self.import_options.include_settings => {
+                        self.include_machine_settings = !self.include_machine_settings;
+                    }
+                    Self::IMPORT_IDX => self.try_import(),
                     _ => {}
                 }
             }
@@ -358,9 +431,10 @@ impl Popup for ImportDialog {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(1), // Path display
-                Constraint::Length(1), // Separator
-                Constraint::Fill(1),   // Remaining
+                Constraint::Length(1),                                    // Path display
+                Constraint::Length(u16::from(self.path_error.is_some())), // Path error
+                Constraint::Length(1),                                    // Separator
+                Constraint::Fill(1),                                      // Remaining
             ])
             .split(inner_area);
 
@@ -380,6 +454,16 @@ impl Popup for ImportDialog {
                 self.import_options.include_character_backups,
                 self.is_hovered(Self::INCLUDE_CHARACTERS_IDX),
             ),
+            toggle_option(
+                "Apply ChronoBind settings",
+                self.import_options.include_settings,
+                self.is_hovered(Self::INCLUDE_SETTINGS_IDX),
+            ),
+            toggle_option(
+                "Include machine-specific settings (branch, log file, space margin)",
+                self.import_options.include_settings && self.include_machine_settings,
+                self.is_hovered(Self::INCLUDE_MACHINE_SETTINGS_IDX),
+            ),
             Line::from(dual_highlight_str(
                 "Import backup",
                 self.is_hovered(Self::IMPORT_IDX),
@@ -394,13 +478,20 @@ impl Popup for ImportDialog {
 
         Widget::render(block, area, frame.buffer_mut());
         self.path_input.render(chunks[0], frame);
+        if let Some(error) = &self.path_error {
+            Widget::render(
+                Line::from(error.as_str()).fg(PALETTE.log_error_fg),
+                chunks[1],
+                frame.buffer_mut(),
+            );
+        }
         draw_horizontal_separator(
-            chunks[1],
+            chunks[2],
             frame.buffer_mut(),
             1,
             Style::new().fg(PALETTE.log_info_fg).dim(),
         );
-        StatefulWidget::render(list_view, chunks[2], frame.buffer_mut(), &mut self.state);
+        StatefulWidget::render(list_view, chunks[3], frame.buffer_mut(), &mut self.state);
     }
 
     fn should_close(&self) -> bool {
@@ -435,7 +526,7 @@ impl Popup for ImportDialog {
         0
     }
     fn popup_min_height(&self) -> u16 {
-        8
+        10 + u16::from(self.path_error.is_some())
     }
 }
 
@@ -453,16 +544,56 @@ pub fn draw_horizontal_separator(area: Rect, buf: &mut Buffer, inset: u16, style
     }
 }
 
-/// Parse a path string, removing surrounding quotes if present.
-#[inline]
-#[must_use]
-fn parse_path(input: &str) -> PathBuf {
-    let s = input.trim();
-    let unquoted = s
-        .strip_prefix('"')
-        .and_then(|s| s.strip_suffix('"'))
-        .or_else(|| s.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')))
-        .unwrap_or(s);
-
-    PathBuf::from(unquoted)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("chronobind-import-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn importing_a_missing_file_keeps_the_dialog_open() {
+        let path = temp_path("missing.zip");
+        let mut dialog = ImportDialog::new_with_path(&path);
+        dialog.try_import();
+        assert_eq!(dialog.path_error.as_deref(), Some("File not found"));
+        assert!(dialog.commands.is_empty());
+        assert!(!dialog.should_close());
+    }
+
+    #[test]
+    fn importing_a_file_which_is_not_a_zip_keeps_the_dialog_open() {
+        let path = temp_path("notes.zip");
+        std::fs::write(&path, "Not an archive").expect("File should be written");
+        let mut dialog = ImportDialog::new_with_path(&path);
+        dialog.try_import();
+        let _ = std::fs::remove_file(&path);
+
+        let error = dialog.path_error.as_deref().expect("Import should fail");
+        assert!(error.starts_with("Not a readable ZIP archive"), "{error}");
+        assert!(dialog.commands.is_empty());
+        assert!(!dialog.should_close());
+    }
+
+    #[test]
+    fn importing_a_zip_closes_the_dialog() {
+        let path = temp_path("backup.zip");
+        let file = std::fs::File::create(&path).expect("Archive should be created");
+        zip::ZipWriter::new(file)
+            .finish()
+            .expect("Archive should be written");
+        let mut dialog = ImportDialog::new_with_path(&path);
+        dialog.try_import();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(dialog.path_error, None);
+        assert!(matches!(
+            dialog.commands[..],
+            [AppMessage::ExportManager(
+                ExportManagerMessage::ImportChronoBindBackup(..)
+            )]
+        ));
+        assert!(dialog.should_close());
+    }
 }
@@ -0,0 +1,244 @@
+#[allow(clippy::wildcard_imports)]
+use crate::palette::*;
+use crate::{
+    backend::format_size,
+    popups::render_optional_scrollbar,
+    ui::KeyCodeExt,
+    widgets::popup::{Popup, popup_block},
+    wow::WoWCharacterFile,
+};
+
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+/// Largest file shown in the preview, larger files are not read.
+const MAX_PREVIEW_SIZE: u64 = 512 * 1024;
+/// Number of lines scrolled by page up and page down.
+const PAGE_SCROLL_LINES: usize = 10;
+
+/// Read-only, scrollable popup showing the contents of a character file.
+#[derive(Debug, Clone)]
+pub struct FilePreviewPopup {
+    /// The name of the previewed file.
+    file_name: String,
+    /// The tinted lines of the file, empty if the file can't be previewed.
+    lines: Vec<Line<'static>>,
+    /// Why the file can't be previewed, if it can't.
+    unavailable: Option<String>,
+    /// Index of the first visible line.
+    scroll: usize,
+    /// Number of lines visible at once, as of the last draw.
+    visible_lines: usize,
+
+    /// Whether the popup should close.
+    pub close: bool,
+}
+
+impl FilePreviewPopup {
+    /// Create the popup for the given file, reading its contents if it is a small text file.
+    #[must_use]
+    pub fn new(file: &WoWCharacterFile) -> Self {
+        let mut popup = Self {
+            file_name: file.get_full_filename(),
+            lines: Vec::new(),
+            unavailable: None,
+            scroll: 0,
+            visible_lines: 0,
+            close: false,
+        };
+
+        match read_preview_text(file) {
+            Ok(text) => {
+                let lua = file.name.to_lowercase().ends_with(".lua");
+                popup.lines = text.lines().map(|line| tint_line(line, lua)).collect();
+            }
+            Err(reason) => {
+                log::debug!("Not previewing `{}`: {reason}", file.path.display());
+                popup.unavailable = Some(reason);
+            }
+        }
+        popup
+    }
+
+    /// Scroll by the given number of lines, keeping the last line within view.
+    fn scroll_by(&mut self, lines: isize) {
+        let max_scroll = self.lines.len().saturating_sub(self.visible_lines.max(1));
+        self.scroll = self.scroll.saturating_add_signed(lines).min(max_scroll);
+    }
+}
+
+/// Read the file's contents for previewing.
+/// # Errors
+/// Returns the reason the file can't be previewed, such as being too large or not text.
+fn read_preview_text(file: &WoWCharacterFile) -> Result<String, String> {
+    let size = std::fs::metadata(&file.path)
+        .map_err(|e| format!("Could not read the file: {e}"))?
+        .len();
+    if size > MAX_PREVIEW_SIZE {
+        return Err(format!(
+            "Too large to preview ({}, the limit is {})",
+            format_size(size),
+            format_size(MAX_PREVIEW_SIZE)
+        ));
+    }
+
+    let bytes = std::fs::read(&file.path).map_err(|e| format!("Could not read the file: {e}"))?;
+    if bytes.contains(&0) {
+        return Err("Binary file, not previewable".to_string());
+    }
+    String::from_utf8(bytes).map_err(|_| "Not a text file, not previewable".to_string())
+}
+
+/// Tint a line of a text file, colouring strings, numbers and keywords,
+/// and comments in Lua files.
+fn tint_line(line: &str, lua: bool) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut chars = line.char_indices().peekable();
+
+    let flush = |plain: &mut String, spans: &mut Vec<Span<'static>>| {
+        if !plain.is_empty() {
+            spans.push(Span::from(std::mem::take(plain)));
+        }
+    };
+
+    while let Some((start, c)) = chars.next() {
+        if lua && line[start..].starts_with("--") {
+            flush(&mut plain, &mut spans);
+            spans.push(Span::from(line[start..].to_string()).dim());
+            return Line::from(spans);
+        }
+        match c {
+            '"' | '\'' => {
+                flush(&mut plain, &mut spans);
+                let mut end = line.len();
+                let mut escaped = false;
+                for (idx, next) in chars.by_ref() {
+                    if !escaped && next == c {
+                        end = idx + next.len_utf8();
+                        break;
+                    }
+                    escaped = !escaped && next == '\\';
+                }
+                spans.push(Span::from(line[start..end].to_string()).fg(PALETTE.special_fg));
+            }
+            c if c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                let mut end = start + c.len_utf8();
+                while let Some(&(idx, next)) = chars.peek()
+                    && (next.is_ascii_alphanumeric() || next == '_' || next == '.')
+                {
+                    end = idx + next.len_utf8();
+                    chars.next();
+                }
+                let word = &line[start..end];
+                let style = if word.parse::<f64>().is_ok() {
+                    Some(Style::new().fg(PALETTE.log_info_fg))
+                } else if matches!(word, "true" | "false" | "nil" | "SET") {
+                    Some(Style::new().fg(PALETTE.selected_fg).bold())
+                } else {
+                    None
+                };
+                if let Some(style) = style {
+                    flush(&mut plain, &mut spans);
+                    spans.push(Span::from(word.to_string()).style(style));
+                } else {
+                    plain.push_str(word);
+                }
+            }
+            c => plain.push(c),
+        }
+    }
+    flush(&mut plain, &mut spans);
+    Line::from(spans)
+}
+
+impl Popup for FilePreviewPopup {
+    fn on_key_down(&mut self, key: &KeyEvent) {
+        #[allow(clippy::cast_possible_wrap)]
+        const PAGE: isize = PAGE_SCROLL_LINES as isize;
+
+        match key.keycode_lower() {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q' | 'p') => self.close = true,
+            KeyCode::Up | KeyCode::Char('w') => self.scroll_by(-1),
+            KeyCode::Down | KeyCode::Char('s') => self.scroll_by(1),
+            KeyCode::PageUp => self.scroll_by(-PAGE),
+            KeyCode::PageDown => self.scroll_by(PAGE),
+            KeyCode::Home => self.scroll = 0,
+            KeyCode::End => self.scroll_by(isize::MAX),
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, area: Rect, frame: &mut Frame<'_>) {
+        let mut block = popup_block(format!(" Preview - {} ", self.file_name));
+
+        if let Some(reason) = &self.unavailable {
+            Paragraph::new(reason.clone())
+                .fg(PALETTE.log_warn_fg)
+                .block(block)
+                .render(area, frame.buffer_mut());
+            return;
+        }
+
+        self.visible_lines = usize::from(area.height.saturating_sub(2));
+        // The popup may have grown since the last scroll, so keep the view filled.
+        self.scroll_by(0);
+        if !self.lines.is_empty() {
+            block = block.title_bottom(
+                Line::from(format!(" Line {}/{} ", self.scroll + 1, self.lines.len()))
+                    .right_aligned(),
+            );
+        }
+
+        let visible = self
+            .lines
+            .iter()
+            .skip(self.scroll)
+            .take(self.visible_lines)
+            .cloned()
+            .collect::<Vec<_>>();
+        Paragraph::new(visible)
+            .fg(PALETTE.std_fg)
+            .block(block)
+            .render(area, frame.buffer_mut());
+
+        let content_length = self.lines.len().saturating_sub(self.visible_lines);
+        render_optional_scrollbar(area, frame.buffer_mut(), content_length, self.scroll);
+    }
+
+    fn should_close(&self) -> bool {
+        self.close
+    }
+    fn close(&mut self) {
+        self.close = true;
+    }
+    fn popup_identifier(&self) -> &'static str {
+        "file_preview_popup"
+    }
+    fn bottom_bar_options(&self) -> Option<Vec<String>> {
+        Some(vec![
+            "↑/↓: Scroll".to_string(),
+            "PgUp/PgDn: Page".to_string(),
+            "Esc: Close".to_string(),
+        ])
+    }
+
+    fn popup_width_percent(&self) -> u16 {
+        80
+    }
+    fn popup_height_percent(&self) -> u16 {
+        80
+    }
+    fn popup_min_width(&self) -> u16 {
+        50
+    }
+    fn popup_min_height(&self) -> u16 {
+        12
+    }
+}
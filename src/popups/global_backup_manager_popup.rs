@@ -0,0 +1,358 @@
+#[allow(clippy::wildcard_imports)]
+use crate::palette::*;
+use crate::{
+    CharacterWithIndex,
+    backend::format_size,
+    popups::{clamp_selection, list_with_scrollbar},
+    ui::{KeyCodeExt, messages::AppMessage, truncate_with_ellipsis},
+    widgets::popup::{Popup, PopupMessage, popup_block, popup_list},
+    wow::WoWCharacterBackup,
+};
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use itertools::Itertools;
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Rect,
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{ListItem, ListState},
+};
+
+/// Different commands that can be issued from the global backup manager popup.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GlobalBackupManagerPopupCommand {
+    /// Delete the backups at the given paths.
+    DeleteBackups(Vec<PathBuf>),
+    /// Remove the oldest automatic backups beyond the maximum to keep from every character.
+    PruneAll,
+    /// Reload the listed backups after they have changed.
+    Refresh,
+}
+
+/// Order in which the global backup manager lists backups.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BackupSort {
+    /// Most recent backups first.
+    #[default]
+    Newest,
+    /// Oldest backups first.
+    Oldest,
+    /// Largest backups first.
+    Largest,
+}
+
+impl BackupSort {
+    /// Every sort order, in cycling order.
+    pub const ALL: [Self; 3] = [Self::Newest, Self::Oldest, Self::Largest];
+
+    /// Get the display name of the sort order.
+    #[inline]
+    #[must_use]
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::Newest => "Newest",
+            Self::Oldest => "Oldest",
+            Self::Largest => "Largest",
+        }
+    }
+
+    /// Get the next sort order, wrapping around.
+    #[inline]
+    #[must_use]
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|s| *s == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+}
+
+/// A backup listed in the global backup manager, along with the character it belongs to.
+#[derive(Debug, Clone)]
+struct GlobalBackupEntry {
+    /// Position of the backup's character within the popup's characters.
+    character: usize,
+    /// The backup itself.
+    backup: WoWCharacterBackup,
+    /// Size of the backup file in bytes.
+    size: u64,
+}
+
+/// Popup for managing the backups of every character on the branch at once.
+#[derive(Debug, Clone)]
+pub struct GlobalBackupManagerPopup {
+    /// The characters whose backups are listed.
+    characters: Vec<CharacterWithIndex>,
+    /// Every backup across the characters, in the current sort order.
+    entries: Vec<GlobalBackupEntry>,
+    /// Paths of the backups selected for deletion.
+    selected: BTreeSet<PathBuf>,
+    /// Order in which the backups are listed.
+    sort: BackupSort,
+    /// Lowercase names of the folders synced by cloud storage, whose backups are marked.
+    cloud_sync_folders: Vec<String>,
+
+    /// Whether the popup should close.
+    pub close: bool,
+    /// The state of the list within the popup.
+    pub state: ListState,
+
+    /// Commands issued by the popup.
+    pub commands: Vec<AppMessage>,
+}
+
+impl GlobalBackupManagerPopup {
+    #[must_use]
+    pub fn new(characters: Vec<CharacterWithIndex>, cloud_sync_folders: Vec<String>) -> Self {
+        let mut list_state = ListState::default();
+        list_state.select(Some(0));
+        let mut popup = Self {
+            characters: Vec::new(),
+            entries: Vec::new(),
+            selected: BTreeSet::new(),
+            sort: BackupSort::default(),
+            cloud_sync_folders,
+
+            close: false,
+            state: list_state,
+
+            commands: vec![],
+        };
+        popup.set_characters(characters);
+        popup
+    }
+
+    /// Replace the listed characters, keeping the selection of backups which still exist.
+    fn set_characters(&mut self, characters: Vec<CharacterWithIndex>) {
+        self.entries = characters
+            .iter()
+            .enumerate()
+            .flat_map(|(character, CharacterWithIndex(char, _))| {
+                char.backups().iter().map(move |backup| GlobalBackupEntry {
+                    character,
+                    backup: backup.clone(),
+                    size: std::fs::metadata(&backup.path).map_or(0, |m| m.len()),
+                })
+            })
+            .collect();
+        self.characters = characters;
+        self.selected
+            .retain(|path| self.entries.iter().any(|entry| entry.backup.path == *path));
+        self.sort_entries();
+    }
+
+    /// Sort the listed backups by the current sort order.
+    fn sort_entries(&mut self) {
+        match self.sort {
+            BackupSort::Newest => self
+                .entries
+                .sort_by_key(|entry| std::cmp::Reverse(entry.backup.timestamp)),
+            BackupSort::Oldest => self.entries.sort_by_key(|entry| entry.backup.timestamp),
+            BackupSort::Largest => self
+                .entries
+                .sort_by_key(|entry| std::cmp::Reverse(entry.size)),
+        }
+        clamp_selection(&mut self.state, self.entries.len());
+    }
+
+    /// Toggle whether the hovered backup is selected for deletion.
+    fn toggle_hovered(&mut self) {
+        let Some(entry) = self.state.selected().and_then(|i| self.entries.get(i)) else {
+            return;
+        };
+        if entry.backup.is_pinned() {
+            log::warn!(
+                "Backup `{}` is {}, it can only be deleted from its character's backup manager.",
+                entry.backup.formatted_name(),
+                entry.backup.tier.display_name().to_lowercase()
+            );
+            return;
+        }
+        let path = entry.backup.path.clone();
+        if !self.selected.remove(&path) {
+            self.selected.insert(path);
+        }
+    }
+
+    /// Push the command to delete the selected backups, or the hovered backup if none are
+    /// selected, after confirmation.
+    fn delete_selected(&mut self) {
+        let targets = if self.selected.is_empty() {
+            self.state
+                .selected()
+                .and_then(|i| self.entries.get(i))
+                .filter(|entry| !entry.backup.is_pinned())
+                .into_iter()
+                .collect_vec()
+        } else {
+            self.entries
+                .iter()
+                .filter(|entry| self.selected.contains(&entry.backup.path))
+                .collect_vec()
+        };
+        if targets.is_empty() {
+            log::warn!(
+                "No backups to delete, pinned and protected backups can only be deleted from \
+                their character's backup manager."
+            );
+            return;
+        }
+
+        let size = targets.iter().map(|entry| entry.size).sum();
+        let delete_line = vec![
+            Span::from("Delete "),
+            Span::from(format!(
+                "{} backup{}",
+                targets.len(),
+                if targets.len() == 1 { "" } else { "s" }
+            ))
+            .bold(),
+            Span::from(format!(" ({})", format_size(size))),
+        ];
+        let paths = targets
+            .into_iter()
+            .map(|entry| entry.backup.path.clone())
+            .collect();
+        self.commands.push(
+            AppMessage::GlobalBackupManager(GlobalBackupManagerPopupCommand::DeleteBackups(paths))
+                .with_confirm_and_line(delete_line),
+        );
+    }
+
+    /// Create the list line for a backup.
+    fn entry_line(&self, entry: &GlobalBackupEntry, hovered: bool) -> Line<'static> {
+        const NAME_WIDTH: usize = 16;
+        const REALM_WIDTH: usize = 16;
+
+        let Some(CharacterWithIndex(character, _)) = self.characters.get(entry.character) else {
+            return Line::default();
+        };
+        let selected = self.selected.contains(&entry.backup.path);
+        let name = truncate_with_ellipsis(character.display_name(false), NAME_WIDTH);
+        let realm = truncate_with_ellipsis(character.display_realm(), REALM_WIDTH);
+        let spans = vec![
+            Span::from(format!(
+                "{}{} {}",
+                highlight_symbol(hovered),
+                checkbox(selected),
+                backup_tier_string(entry.backup.tier)
+            )),
+            Span::from(format!("{name:<NAME_WIDTH$} ")).fg(character.class_colour()),
+            Span::from(format!("{realm:<REALM_WIDTH$} ")),
+            Span::from(format!(
+                "{}{} ",
+                display_backup_time(&entry.backup.timestamp),
+                if entry.backup.is_paste { " (Auto)" } else { "" }
+            )),
+            Span::from(format!("{:>10}", format_size(entry.size))).dim(),
+            Span::from(cloud_sync_marker(&self.cloud_sync_folders, &entry.backup))
+                .fg(PALETTE.log_warn_fg),
+        ];
+        Line::from(spans).fg(PALETTE.selection_fg(selected))
+    }
+}
+
+impl Popup for GlobalBackupManagerPopup {
+    fn on_key_down(&mut self, key: &KeyEvent) {
+        match key.keycode_lower() {
+            KeyCode::Up | KeyCode::Char('w') => {
+                self.state.select_previous();
+            }
+            KeyCode::Down | KeyCode::Char('s') => {
+                self.state.select_next();
+                clamp_selection(&mut self.state, self.entries.len());
+            }
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                self.toggle_hovered();
+            }
+            KeyCode::Char('o') => {
+                self.sort = self.sort.next();
+                self.sort_entries();
+            }
+            KeyCode::Char('d') => {
+                self.delete_selected();
+            }
+            KeyCode::Char('p') => {
+                self.commands.push(
+                    AppMessage::GlobalBackupManager(GlobalBackupManagerPopupCommand::PruneAll)
+                        .with_confirm_and_line(Span::from(
+                            "Remove old automatic backups beyond the maximum from every character",
+                        )),
+                );
+            }
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.close = true;
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, area: Rect, frame: &mut Frame<'_>) {
+        let total_size = self.entries.iter().map(|entry| entry.size).sum();
+        let block = popup_block(format!(
+            " All Backups ({}, {}) ",
+            self.entries.len(),
+            format_size(total_size)
+        ));
+
+        let selected_index = self.state.selected().unwrap_or(0);
+        let items = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| ListItem::new(self.entry_line(entry, i == selected_index)))
+            .collect_vec();
+
+        let list_view = popup_list(block, items);
+        list_with_scrollbar(list_view, area, frame.buffer_mut(), &mut self.state);
+    }
+
+    fn process_message(&mut self, message: &PopupMessage) {
+        if let PopupMessage::UpdateCharacters(characters) = message {
+            self.set_characters(characters.clone());
+            log::debug!("Updated global backup manager backups");
+        }
+    }
+
+    fn should_close(&self) -> bool {
+        self.close
+    }
+    fn close(&mut self) {
+        self.close = true;
+    }
+    fn popup_identifier(&self) -> &'static str {
+        "global_backup_manager_popup"
+    }
+    fn bottom_bar_options(&self) -> Option<Vec<String>> {
+        let delete_opt = if self.selected.is_empty() {
+            "D: Delete".to_string()
+        } else {
+            format!("D: Delete {} Selected", self.selected.len())
+        };
+        Some(vec![
+            "↑/↓".to_string(),
+            "Space: Select".to_string(),
+            delete_opt,
+            format!("O: Sort ({})", self.sort.display_name()),
+            "P: Clean Up All".to_string(),
+            "Esc: Close".to_string(),
+        ])
+    }
+    fn internal_commands_mut(&mut self) -> Option<&mut Vec<AppMessage>> {
+        Some(&mut self.commands)
+    }
+    fn popup_width_percent(&self) -> u16 {
+        60
+    }
+    fn popup_height_percent(&self) -> u16 {
+        60
+    }
+    fn popup_min_width(&self) -> u16 {
+        84
+    }
+    fn popup_min_height(&self) -> u16 {
+        16
+    }
+}
@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{fmt::Display, path::PathBuf};
 
 use ratatui::{
     buffer::Buffer,
@@ -13,13 +13,24 @@ use crate::palette::{
     highlight_symbol_rev,
 };
 
+pub mod alias_popup;
 pub mod backup_manager_popup;
+pub mod backup_note_popup;
 pub mod backup_popup;
 pub mod branch_popup;
+pub mod character_info_popup;
+pub mod columns_popup;
 pub mod confirm_popup;
 pub mod export_manager_popup;
+pub mod file_preview_popup;
+pub mod global_backup_manager_popup;
 pub mod options_popup;
+pub mod paste_preview_popup;
 pub mod progress_popup;
+pub mod realm_alias_popup;
+pub mod realm_summary_popup;
+pub mod recent_characters_popup;
+pub mod restore_file_popup;
 pub mod restore_popup;
 
 /// Create a line representing a toggle option.
@@ -61,6 +72,17 @@ pub fn with_optional_scrollbar<T: StatefulWidget>(
     offset: usize,
 ) {
     StatefulWidget::render(widget, area, buf, state);
+    render_optional_scrollbar(area, buf, content_length, offset);
+}
+
+/// Render a scrollbar over the right border of `area` if the content length exceeds the viewable area.
+#[inline]
+pub fn render_optional_scrollbar(
+    area: Rect,
+    buf: &mut Buffer,
+    content_length: usize,
+    offset: usize,
+) {
     if content_length > 1 {
         let mut scrollbar_state = ScrollbarState::new(content_length).position(offset);
         let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
@@ -89,9 +111,37 @@ pub fn list_with_scrollbar(list: List<'_>, area: Rect, buf: &mut Buffer, state:
     with_optional_scrollbar(list, area, buf, state, content_length, offset);
 }
 
+/// Keep a list's selection on a valid row after navigating or after the list changes, such as
+/// the selected last item being removed. Nothing is selected when the list is empty.
+#[inline]
+pub fn clamp_selection(state: &mut ListState, len: usize) {
+    let clamped = match (state.selected(), len) {
+        (_, 0) => None,
+        (None, _) => Some(0),
+        (Some(selected), len) => Some(selected.min(len - 1)),
+    };
+    if clamped != state.selected() {
+        state.select(clamped);
+    }
+}
+
 /// Format an option for display purposes.
 #[inline]
 #[must_use]
 pub fn format_option<T: Display>(opt: Option<&T>) -> String {
     opt.map_or_else(|| "None".to_string(), std::string::ToString::to_string)
 }
+
+/// Parse a path string, removing surrounding quotes if present.
+#[inline]
+#[must_use]
+pub fn parse_path(input: &str) -> PathBuf {
+    let s = input.trim();
+    let unquoted = s
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .or_else(|| s.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')))
+        .unwrap_or(s);
+
+    PathBuf::from(unquoted)
+}
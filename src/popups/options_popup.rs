@@ -2,9 +2,10 @@
 use crate::palette::*;
 use crate::{
     ChronoBindAppConfig,
-    popups::toggle_option,
+    config::get_log_file_path,
+    popups::{clamp_selection, toggle_option},
     ui::{KeyCodeExt, messages::AppMessage},
-    widgets::popup::{Popup, popup_block, popup_list_no_block},
+    widgets::popup::{Popup, PopupMessage, popup_block, popup_list_no_block},
     wow::WoWInstalls,
 };
 
@@ -18,20 +19,50 @@ use ratatui::{
     widgets::{ListState, StatefulWidget, Widget},
 };
 
+/// Amount the backup warning threshold changes by with each adjustment.
+const BACKUP_WARNING_THRESHOLD_STEP: usize = 5;
+
 /// Different commands that can be issued from a restore popup.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum OptionsPopupCommand {
     /// Command to update the app configuration with new settings.
-    UpdateConfiguration(ChronoBindAppConfig),
+    UpdateConfiguration(Box<ChronoBindAppConfig>),
+    /// Command to open the log file with the system's default application.
+    OpenLogFile,
+    /// Command to enable or disable mock mode, and save the configuration.
+    SetMockMode(bool),
+    /// Command to relaunch in a terminal with better symbol support, exiting this instance.
+    RelaunchInTerminal,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum OptionKind {
     ShowFriendlyNames,
+    CompactFileList,
+    GroupAddonFiles,
     MockMode,
-    DisplayCharacterLevels,
     MaximumAutoBackups,
+    BackupWarningThreshold,
     PreferredBranch,
+    Confirmations,
+    SingleCharacterRealms,
+    CharacterActivateAction,
+    FavoritesFirst,
+    LogToFile,
+    ResolveSymlinks,
+    RememberRecentCharacters,
+    KeepSelectionsOnRefresh,
+    BackupLayout,
+    IncludeAddonManifest,
+    WatchCharacterFiles,
+    WarnCloudSyncedBackups,
+    PromptNoteOnBackup,
+    SanitizeExports,
+    ExcludeBackupsFromExports,
+    CollapseAddonSectionsByDefault,
+    OfferLaunchAfterRestore,
+    ToastDuration,
+    ToastPosition,
 }
 
 impl OptionKind {
@@ -40,10 +71,31 @@ impl OptionKind {
     fn get_options_list() -> Vec<Self> {
         vec![
             Self::ShowFriendlyNames,
+            Self::CompactFileList,
+            Self::GroupAddonFiles,
             Self::MockMode,
-            Self::DisplayCharacterLevels,
             Self::MaximumAutoBackups,
+            Self::BackupWarningThreshold,
             Self::PreferredBranch,
+            Self::Confirmations,
+            Self::SingleCharacterRealms,
+            Self::CharacterActivateAction,
+            Self::FavoritesFirst,
+            Self::LogToFile,
+            Self::ResolveSymlinks,
+            Self::RememberRecentCharacters,
+            Self::KeepSelectionsOnRefresh,
+            Self::BackupLayout,
+            Self::IncludeAddonManifest,
+            Self::WatchCharacterFiles,
+            Self::WarnCloudSyncedBackups,
+            Self::PromptNoteOnBackup,
+            Self::SanitizeExports,
+            Self::ExcludeBackupsFromExports,
+            Self::CollapseAddonSectionsByDefault,
+            Self::OfferLaunchAfterRestore,
+            Self::ToastDuration,
+            Self::ToastPosition,
         ]
     }
 
@@ -53,10 +105,31 @@ impl OptionKind {
     pub const fn title(&self) -> &'static str {
         match self {
             Self::ShowFriendlyNames => "Show friendly file names",
+            Self::CompactFileList => "Always use compact file list",
+            Self::GroupAddonFiles => "Group addon files by addon",
             Self::MockMode => "Mock mode (Don't perform file operations)",
-            Self::DisplayCharacterLevels => "Display character levels in list (if available)",
             Self::MaximumAutoBackups => "Maximum allowed automatic backups",
+            Self::BackupWarningThreshold => "Warn when a character has more backups than",
             Self::PreferredBranch => "Preferred WoW branch",
+            Self::Confirmations => "Ask for confirmation on",
+            Self::SingleCharacterRealms => "Realms with one character",
+            Self::CharacterActivateAction => "Selecting a character",
+            Self::FavoritesFirst => "List favorite characters first",
+            Self::LogToFile => "Write logs to file",
+            Self::ResolveSymlinks => "Resolve symlinked WoW folders",
+            Self::RememberRecentCharacters => "Remember recent characters between sessions",
+            Self::KeepSelectionsOnRefresh => "Keep selected files when refreshing",
+            Self::BackupLayout => "Store backups in",
+            Self::IncludeAddonManifest => "Include enabled addons in backups",
+            Self::WatchCharacterFiles => "Watch selected character for changes",
+            Self::WarnCloudSyncedBackups => "Warn about backups in cloud synced folders",
+            Self::PromptNoteOnBackup => "Ask for a note when backing up all files",
+            Self::SanitizeExports => "Remove personal data from exported WTF folders",
+            Self::ExcludeBackupsFromExports => "Leave backup folders out of exported WTF folders",
+            Self::CollapseAddonSectionsByDefault => "Collapse addon files by default",
+            Self::OfferLaunchAfterRestore => "Offer to launch the game after restoring",
+            Self::ToastDuration => "Show notifications for",
+            Self::ToastPosition => "Show notifications in",
         }
     }
 
@@ -73,27 +146,87 @@ impl OptionKind {
             Self::ShowFriendlyNames => {
                 toggle_option(self.title(), config.show_friendly_names, hovered)
             }
+            Self::CompactFileList => toggle_option(self.title(), config.compact_file_list, hovered),
+            Self::GroupAddonFiles => toggle_option(self.title(), config.group_addon_files, hovered),
             Self::MockMode => toggle_option(self.title(), config.mock_mode(), hovered),
-            Self::DisplayCharacterLevels => {
-                toggle_option(self.title(), config.display_character_levels, hovered)
+            Self::ResolveSymlinks => toggle_option(self.title(), config.resolve_symlinks, hovered),
+            Self::RememberRecentCharacters => {
+                toggle_option(self.title(), config.remember_recent_characters, hovered)
+            }
+            Self::KeepSelectionsOnRefresh => {
+                toggle_option(self.title(), config.keep_selections_on_refresh, hovered)
+            }
+            Self::FavoritesFirst => toggle_option(self.title(), config.favorites_first, hovered),
+            Self::IncludeAddonManifest => {
+                toggle_option(self.title(), config.include_addon_manifest, hovered)
+            }
+            Self::WatchCharacterFiles => {
+                toggle_option(self.title(), config.watch_character_files, hovered)
+            }
+            Self::WarnCloudSyncedBackups => {
+                toggle_option(self.title(), config.warn_cloud_synced_backups, hovered)
+            }
+            Self::PromptNoteOnBackup => {
+                toggle_option(self.title(), config.prompt_note_on_backup, hovered)
+            }
+            Self::SanitizeExports => toggle_option(self.title(), config.sanitize_exports, hovered),
+            Self::ExcludeBackupsFromExports => {
+                toggle_option(self.title(), config.exclude_backups_from_exports, hovered)
+            }
+            Self::CollapseAddonSectionsByDefault => toggle_option(
+                self.title(),
+                config.collapse_addon_sections_by_default,
+                hovered,
+            ),
+            Self::OfferLaunchAfterRestore => {
+                toggle_option(self.title(), config.offer_launch_after_restore, hovered)
             }
             Self::MaximumAutoBackups => {
                 let displayed_text = config.maximum_auto_backups.map_or_else(
                     || UNLIMITED_SYMBOL.to_string(),
                     |max_backups| format!("{max_backups}"),
                 );
-                Line::from(highlight_str(
-                    format!("{}: {displayed_text}", self.title()),
-                    hovered,
-                ))
+                value_option(self.title(), displayed_text, hovered)
+            }
+            Self::BackupWarningThreshold => {
+                let displayed_text = config
+                    .backup_warning_threshold
+                    .map_or_else(|| "Never".to_string(), |threshold| format!("{threshold}"));
+                value_option(self.title(), displayed_text, hovered)
             }
             Self::PreferredBranch => {
                 let displayed_text =
                     preferred_branch_display(config.preferred_branch.as_ref(), installs);
-                Line::from(highlight_str(
-                    format!("{}: {displayed_text}", self.title()),
-                    hovered,
-                ))
+                value_option(self.title(), displayed_text, hovered)
+            }
+            Self::LogToFile => {
+                let mut line = toggle_option(self.title(), config.log_to_file, hovered);
+                line.push_span(Span::from(format!(" ({})", get_log_file_path().display())).dim());
+                line
+            }
+            Self::Confirmations => {
+                value_option(self.title(), config.confirmations.display_name(), hovered)
+            }
+            Self::SingleCharacterRealms => value_option(
+                self.title(),
+                config.single_character_realms.display_name(),
+                hovered,
+            ),
+            Self::CharacterActivateAction => value_option(
+                self.title(),
+                config.character_activate_action.display_name(),
+                hovered,
+            ),
+            Self::BackupLayout => {
+                value_option(self.title(), config.backup_layout.display_name(), hovered)
+            }
+            Self::ToastDuration => value_option(
+                self.title(),
+                format!("{}s", config.toast_duration().as_secs()),
+                hovered,
+            ),
+            Self::ToastPosition => {
+                value_option(self.title(), config.toast_position.display_name(), hovered)
             }
         }
     }
@@ -103,10 +236,40 @@ impl OptionKind {
     #[must_use]
     pub fn get_bottom_bar_segments(&self) -> Vec<String> {
         match self {
-            Self::ShowFriendlyNames | Self::MockMode | Self::DisplayCharacterLevels => {
+            Self::ShowFriendlyNames
+            | Self::CompactFileList
+            | Self::GroupAddonFiles
+            | Self::ResolveSymlinks
+            | Self::RememberRecentCharacters
+            | Self::KeepSelectionsOnRefresh
+            | Self::FavoritesFirst
+            | Self::IncludeAddonManifest
+            | Self::WatchCharacterFiles
+            | Self::WarnCloudSyncedBackups
+            | Self::PromptNoteOnBackup
+            | Self::SanitizeExports
+            | Self::ExcludeBackupsFromExports
+            | Self::CollapseAddonSectionsByDefault
+            | Self::OfferLaunchAfterRestore => {
                 vec![format!("{ENTER_SYMBOL}/→/Space: Toggle")]
             }
-            Self::MaximumAutoBackups | Self::PreferredBranch => {
+            Self::MockMode => vec![
+                format!("{ENTER_SYMBOL}/→/Space: Toggle"),
+                "Ctrl+K: Toggle from anywhere".to_string(),
+            ],
+            Self::LogToFile => vec![
+                format!("{ENTER_SYMBOL}/→/Space: Toggle"),
+                "F: Open log file".to_string(),
+            ],
+            Self::MaximumAutoBackups
+            | Self::BackupWarningThreshold
+            | Self::PreferredBranch
+            | Self::Confirmations
+            | Self::SingleCharacterRealms
+            | Self::CharacterActivateAction
+            | Self::BackupLayout
+            | Self::ToastDuration
+            | Self::ToastPosition => {
                 vec!["←/→: Adjust".to_string()]
             }
         }
@@ -169,9 +332,9 @@ impl OptionsPopup {
     /// Push an update configuration command to the popup's command list.
     #[inline]
     pub fn push_update_command(&mut self) {
-        self.push_command(OptionsPopupCommand::UpdateConfiguration(
+        self.push_command(OptionsPopupCommand::UpdateConfiguration(Box::new(
             self.configuration.clone(),
-        ));
+        )));
     }
 }
 
@@ -184,23 +347,87 @@ impl OptionsPopup {
                 self.configuration.show_friendly_names = !self.configuration.show_friendly_names;
                 config_changed = true;
             }
+            OptionKind::CompactFileList => {
+                self.configuration.compact_file_list = !self.configuration.compact_file_list;
+                config_changed = true;
+            }
+            OptionKind::GroupAddonFiles => {
+                self.configuration.group_addon_files = !self.configuration.group_addon_files;
+                config_changed = true;
+            }
             OptionKind::MockMode => {
                 self.configuration
                     .set_mock_mode(!self.configuration.mock_mode());
                 config_changed = true;
             }
-            OptionKind::DisplayCharacterLevels => {
-                self.configuration.display_character_levels =
-                    !self.configuration.display_character_levels;
+            OptionKind::LogToFile => {
+                self.configuration.log_to_file = !self.configuration.log_to_file;
+                config_changed = true;
+            }
+            OptionKind::ResolveSymlinks => {
+                self.configuration.resolve_symlinks = !self.configuration.resolve_symlinks;
+                config_changed = true;
+            }
+            OptionKind::RememberRecentCharacters => {
+                self.configuration.remember_recent_characters =
+                    !self.configuration.remember_recent_characters;
+                config_changed = true;
+            }
+            OptionKind::KeepSelectionsOnRefresh => {
+                self.configuration.keep_selections_on_refresh =
+                    !self.configuration.keep_selections_on_refresh;
+                config_changed = true;
+            }
+            OptionKind::FavoritesFirst => {
+                self.configuration.favorites_first = !self.configuration.favorites_first;
+                config_changed = true;
+            }
+            OptionKind::IncludeAddonManifest => {
+                self.configuration.include_addon_manifest =
+                    !self.configuration.include_addon_manifest;
+                config_changed = true;
+            }
+            OptionKind::WatchCharacterFiles => {
+                self.configuration.watch_character_files =
+                    !self.configuration.watch_character_files;
+                config_changed = true;
+            }
+            OptionKind::WarnCloudSyncedBackups => {
+                self.configuration.warn_cloud_synced_backups =
+                    !self.configuration.warn_cloud_synced_backups;
+                config_changed = true;
+            }
+            OptionKind::PromptNoteOnBackup => {
+                self.configuration.prompt_note_on_backup =
+                    !self.configuration.prompt_note_on_backup;
+                config_changed = true;
+            }
+            OptionKind::SanitizeExports => {
+                self.configuration.sanitize_exports = !self.configuration.sanitize_exports;
+                config_changed = true;
+            }
+            OptionKind::ExcludeBackupsFromExports => {
+                self.configuration.exclude_backups_from_exports =
+                    !self.configuration.exclude_backups_from_exports;
+                config_changed = true;
+            }
+            OptionKind::CollapseAddonSectionsByDefault => {
+                self.configuration.collapse_addon_sections_by_default =
+                    !self.configuration.collapse_addon_sections_by_default;
+                config_changed = true;
+            }
+            OptionKind::OfferLaunchAfterRestore => {
+                self.configuration.offer_launch_after_restore =
+                    !self.configuration.offer_launch_after_restore;
                 config_changed = true;
             }
             _ => {}
         }
 
         if config_changed {
-            self.push_command(OptionsPopupCommand::UpdateConfiguration(
+            self.push_command(OptionsPopupCommand::UpdateConfiguration(Box::new(
                 self.configuration.clone(),
-            ));
+            )));
         }
     }
 }
@@ -274,11 +501,142 @@ impl OptionsPopup {
         true
     }
 
+    /// Decrease the backup warning threshold, disabling the warning below the smallest step.
+    const fn decrement_backup_warning_threshold(&mut self) {
+        self.configuration.backup_warning_threshold =
+            match self.configuration.backup_warning_threshold {
+                Some(current) if current > BACKUP_WARNING_THRESHOLD_STEP => {
+                    Some(current - BACKUP_WARNING_THRESHOLD_STEP)
+                }
+                _ => None,
+            };
+    }
+
+    /// Increase the backup warning threshold, enabling the warning if it was disabled.
+    const fn increment_backup_warning_threshold(&mut self) {
+        self.configuration.backup_warning_threshold =
+            match self.configuration.backup_warning_threshold {
+                Some(current) => Some(current + BACKUP_WARNING_THRESHOLD_STEP),
+                None => Some(BACKUP_WARNING_THRESHOLD_STEP),
+            };
+    }
+
+    /// Decrement how long notifications are shown for, by a second.
+    fn decrement_toast_duration(&mut self) {
+        self.configuration.toast_duration_secs = self
+            .configuration
+            .toast_duration()
+            .as_secs()
+            .saturating_sub(1)
+            .max(1);
+    }
+
+    /// Increment how long notifications are shown for, by a second.
+    fn increment_toast_duration(&mut self) {
+        let secs = self.configuration.toast_duration().as_secs() + 1;
+        self.configuration.toast_duration_secs =
+            secs.min(ChronoBindAppConfig::MAX_TOAST_DURATION_SECS);
+    }
+
+    /// Adjust the given option to its previous value, saving the change.
+    fn adjust_option_previous(&mut self, option: Option<&OptionKind>) {
+        match option {
+            Some(OptionKind::PreferredBranch)
+                if !self.branches.is_empty() && self.select_previous_branch() =>
+            {
+                self.push_update_command();
+            }
+            Some(OptionKind::MaximumAutoBackups) if self.decrement_max_auto_backups() => {
+                self.push_update_command();
+            }
+            Some(OptionKind::BackupWarningThreshold) => {
+                self.decrement_backup_warning_threshold();
+                self.push_update_command();
+            }
+            Some(OptionKind::Confirmations) => {
+                self.configuration.confirmations = self.configuration.confirmations.previous();
+                self.push_update_command();
+            }
+            Some(OptionKind::SingleCharacterRealms) => {
+                self.configuration.single_character_realms =
+                    self.configuration.single_character_realms.previous();
+                self.push_update_command();
+            }
+            Some(OptionKind::CharacterActivateAction) => {
+                self.configuration.character_activate_action =
+                    self.configuration.character_activate_action.previous();
+                self.push_update_command();
+            }
+            Some(OptionKind::BackupLayout) => {
+                self.configuration.backup_layout = self.configuration.backup_layout.previous();
+                self.push_update_command();
+            }
+            Some(OptionKind::ToastDuration) => {
+                self.decrement_toast_duration();
+                self.push_update_command();
+            }
+            Some(OptionKind::ToastPosition) => {
+                self.configuration.toast_position = self.configuration.toast_position.previous();
+                self.push_update_command();
+            }
+            _ => {}
+        }
+    }
+
+    /// Adjust the given option to its next value, saving the change, or interact with it if
+    /// it has no values to cycle through.
+    fn adjust_option_next(&mut self, option: Option<&OptionKind>) {
+        match option {
+            Some(OptionKind::PreferredBranch)
+                if !self.branches.is_empty() && self.select_next_branch() =>
+            {
+                self.push_update_command();
+            }
+            Some(OptionKind::MaximumAutoBackups) if self.increment_max_auto_backups() => {
+                self.push_update_command();
+            }
+            Some(OptionKind::BackupWarningThreshold) => {
+                self.increment_backup_warning_threshold();
+                self.push_update_command();
+            }
+            Some(OptionKind::Confirmations) => {
+                self.configuration.confirmations = self.configuration.confirmations.next();
+                self.push_update_command();
+            }
+            Some(OptionKind::SingleCharacterRealms) => {
+                self.configuration.single_character_realms =
+                    self.configuration.single_character_realms.next();
+                self.push_update_command();
+            }
+            Some(OptionKind::CharacterActivateAction) => {
+                self.configuration.character_activate_action =
+                    self.configuration.character_activate_action.next();
+                self.push_update_command();
+            }
+            Some(OptionKind::BackupLayout) => {
+                self.configuration.backup_layout = self.configuration.backup_layout.next();
+                self.push_update_command();
+            }
+            Some(OptionKind::ToastDuration) => {
+                self.increment_toast_duration();
+                self.push_update_command();
+            }
+            Some(OptionKind::ToastPosition) => {
+                self.configuration.toast_position = self.configuration.toast_position.next();
+                self.push_update_command();
+            }
+            Some(OptionKind::PreferredBranch | OptionKind::MaximumAutoBackups) => {}
+            Some(opt) => self.interact_with_option(opt),
+            _ => {}
+        }
+    }
+
     /// Select the next `WoW` branch in the list.
     fn select_next_branch(&mut self) -> bool {
+        let branches = self.branches.distinct_branches();
         if let Some(current_index) = self.get_selected_branch_index() {
-            let next_index = (current_index + 1) % self.branches.len();
-            let next_branch_ident = self.branches.installs[next_index].branch_ident.clone();
+            let next_index = (current_index + 1) % branches.len();
+            let next_branch_ident = branches[next_index].0.branch_ident.clone();
             self.configuration.preferred_branch = Some(next_branch_ident);
             return true;
         }
@@ -287,32 +645,39 @@ impl OptionsPopup {
 
     /// Select the previous `WoW` branch in the list.
     fn select_previous_branch(&mut self) -> bool {
+        let branches = self.branches.distinct_branches();
         if let Some(current_index) = self.get_selected_branch_index() {
             let previous_index = if current_index == 0 {
-                self.branches.len() - 1
+                branches.len() - 1
             } else {
                 current_index - 1
             };
-            let previous_branch_ident = self.branches.installs[previous_index].branch_ident.clone();
+            let previous_branch_ident = branches[previous_index].0.branch_ident.clone();
             self.configuration.preferred_branch = Some(previous_branch_ident);
             return true;
         }
         false
     }
 
-    /// Find the selected index of the preferred `WoW` branch.
+    /// Find the selected index of the preferred `WoW` branch, among the distinct branches.
     #[inline]
     #[must_use]
     pub fn get_selected_branch_index(&self) -> Option<usize> {
         let selected_ident = self.configuration.preferred_branch.clone()?;
-        let (i, _) = self.branches.iter().enumerate().find(|(_, install)| {
-            install.branch_ident.to_lowercase() == selected_ident.to_lowercase()
-        })?;
-        Some(i)
+        self.branches
+            .distinct_branches()
+            .iter()
+            .position(|(install, _)| install.branch_ident.eq_ignore_ascii_case(&selected_ident))
     }
 }
 
 impl Popup for OptionsPopup {
+    fn process_message(&mut self, message: &PopupMessage) {
+        if let PopupMessage::UpdateConfiguration(config) = message {
+            self.configuration = config.as_ref().clone();
+        }
+    }
+
     fn on_key_down(&mut self, key: &KeyEvent) {
         let list = OptionKind::get_options_list();
         let selected_opt = list.get(self.selected_index());
@@ -322,39 +687,18 @@ impl Popup for OptionsPopup {
             }
             KeyCode::Down | KeyCode::Char('s') => {
                 self.state.select_next();
+                clamp_selection(&mut self.state, list.len());
             }
-            KeyCode::Left | KeyCode::Char('a') => match selected_opt {
-                Some(OptionKind::PreferredBranch) => {
-                    if !self.branches.is_empty() && self.select_previous_branch() {
-                        self.push_update_command();
-                    }
-                }
-                Some(OptionKind::MaximumAutoBackups) => {
-                    if self.decrement_max_auto_backups() {
-                        self.push_update_command();
-                    }
-                }
-                _ => {}
-            },
-            KeyCode::Right | KeyCode::Char('d') => match selected_opt {
-                Some(OptionKind::PreferredBranch) => {
-                    if !self.branches.is_empty() && self.select_next_branch() {
-                        self.push_update_command();
-                    }
-                }
-                Some(OptionKind::MaximumAutoBackups) => {
-                    if self.increment_max_auto_backups() {
-                        self.push_update_command();
-                    }
-                }
-                Some(opt) => self.interact_with_option(opt),
-                _ => {}
-            },
+            KeyCode::Left | KeyCode::Char('a') => self.adjust_option_previous(selected_opt),
+            KeyCode::Right | KeyCode::Char('d') => self.adjust_option_next(selected_opt),
             KeyCode::Enter | KeyCode::Char(' ') => {
                 if let Some(o) = selected_opt {
                     self.interact_with_option(o);
                 }
             }
+            KeyCode::Char('f') if selected_opt == Some(&OptionKind::LogToFile) => {
+                self.push_command(OptionsPopupCommand::OpenLogFile);
+            }
             KeyCode::Esc | KeyCode::Char('q' | 'o') => {
                 self.close();
             }
@@ -378,9 +722,9 @@ impl Popup for OptionsPopup {
         self.close
     }
     fn close(&mut self) {
-        self.push_command_close(OptionsPopupCommand::UpdateConfiguration(
+        self.push_command_close(OptionsPopupCommand::UpdateConfiguration(Box::new(
             self.configuration.clone(),
-        ));
+        )));
     }
     fn popup_identifier(&self) -> &'static str {
         "options_popup"
@@ -405,6 +749,12 @@ impl Popup for OptionsPopup {
     }
 }
 
+/// Create a line showing an option's title along with its current value.
+#[inline]
+fn value_option(title: &str, value: impl std::fmt::Display, hovered: bool) -> Line<'static> {
+    Line::from(highlight_str(format!("{title}: {value}"), hovered))
+}
+
 /// Get the display text for the preferred branch.
 fn preferred_branch_display(preferred_branch: Option<&String>, installs: &WoWInstalls) -> String {
     let Some(branch_ident) = preferred_branch else {
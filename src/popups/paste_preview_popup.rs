@@ -0,0 +1,314 @@
+#[allow(clippy::wildcard_imports)]
+use crate::palette::*;
+use crate::{
+    backend::{PasteFileStatus, hashing::HashSettings, paste_file_status},
+    popups::{clamp_selection, list_with_scrollbar},
+    ui::{
+        Character, CharacterIndex, KeyCodeExt,
+        messages::{AppMessage, ConfirmActionText, ConfirmRisk},
+    },
+    widgets::popup::{Popup, popup_block, popup_list},
+};
+
+use std::path::{Path, PathBuf};
+
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Clear, ListItem, ListState, Widget},
+};
+
+/// A selected file of the copied character, and where it would be pasted to.
+#[derive(Debug, Clone)]
+struct PastePreviewFile {
+    /// Path of the file relative to the character directories.
+    relative_path: PathBuf,
+    /// Full path of the copied character's file.
+    src_path: PathBuf,
+    /// Full path the file would be pasted to.
+    dest_path: PathBuf,
+    /// How pasting the file would change the destination's copy of it.
+    status: PasteFileStatus,
+    /// Whether the file is still to be pasted.
+    selected: bool,
+}
+
+/// Popup listing each file to be pasted with where it is pasted to and whether it is new,
+/// overwrites a differing file or is identical, to deselect files before pasting.
+#[derive(Debug, Clone)]
+pub struct PastePreviewPopup {
+    /// Index of the character the files are pasted to.
+    dest_char_idx: CharacterIndex,
+    /// The coloured name of the character the files are pasted to.
+    dest_name: Span<'static>,
+    /// The files to be pasted.
+    files: Vec<PastePreviewFile>,
+    /// Warning confirmed again before pasting, such as when pasting across branches.
+    warning: Option<ConfirmActionText>,
+
+    /// Whether the popup should close.
+    pub close: bool,
+    /// The state of the list within the popup.
+    pub state: ListState,
+
+    /// Commands issued by the popup.
+    pub commands: Vec<AppMessage>,
+}
+
+impl PastePreviewPopup {
+    /// Create the popup for pasting the given files, relative to the character directories,
+    /// from the source character's directory to the destination character's directory.
+    /// Files identical at the destination start deselected.
+    #[must_use]
+    pub fn new(
+        dest_char_idx: CharacterIndex,
+        dest_char: &Character,
+        relative_paths: Vec<PathBuf>,
+        (src_dir, dest_dir): (&Path, &Path),
+        warning: Option<ConfirmActionText>,
+        hash_settings: HashSettings,
+    ) -> Self {
+        let files = relative_paths
+            .into_iter()
+            .map(|relative_path| {
+                let src_path = src_dir.join(&relative_path);
+                let dest_path = dest_dir.join(&relative_path);
+                let status = paste_file_status(hash_settings, &src_path, &dest_path);
+                PastePreviewFile {
+                    relative_path,
+                    src_path,
+                    dest_path,
+                    status,
+                    selected: status != PasteFileStatus::Identical,
+                }
+            })
+            .collect();
+
+        let mut state = ListState::default();
+        state.select(Some(0));
+        Self {
+            dest_char_idx,
+            dest_name: dest_char.display_span(true),
+            files,
+            warning,
+
+            close: false,
+            state,
+
+            commands: vec![],
+        }
+    }
+
+    /// Get the files still selected to be pasted.
+    fn selected_files(&self) -> Vec<PathBuf> {
+        self.files
+            .iter()
+            .filter(|file| file.selected)
+            .map(|file| file.relative_path.clone())
+            .collect()
+    }
+
+    /// Toggle whether the hovered file is pasted.
+    fn toggle_hovered(&mut self) {
+        if let Some(file) = self
+            .state
+            .selected()
+            .and_then(|idx| self.files.get_mut(idx))
+        {
+            file.selected = !file.selected;
+        }
+    }
+
+    /// Select every file, or deselect every file if all are already selected.
+    fn toggle_all(&mut self) {
+        let select = !self.files.iter().all(|file| file.selected);
+        for file in &mut self.files {
+            file.selected = select;
+        }
+    }
+
+    /// Push the command pasting the selected files and close the popup, unless none are selected.
+    fn submit(&mut self) {
+        let files = self.selected_files();
+        if files.is_empty() {
+            log::warn!("No files selected to paste");
+            return;
+        }
+        let command = AppMessage::Paste(self.dest_char_idx, files);
+        self.commands.push(match self.warning.clone() {
+            Some(warning) => command.with_risk_confirm_and_line(ConfirmRisk::Critical, warning),
+            None => command,
+        });
+        self.close = true;
+    }
+
+    /// Create the list item for a file, with its status and where it is pasted from and to.
+    fn file_item(file: &PastePreviewFile, hovered: bool) -> ListItem<'static> {
+        let status_style = match file.status {
+            PasteFileStatus::New => Style::new().fg(PALETTE.log_info_fg),
+            PasteFileStatus::Overwrite => Style::new().fg(PALETTE.log_warn_fg),
+            PasteFileStatus::Identical => Style::new().dim(),
+        };
+        let title = Line::from(vec![
+            Span::from(format!(
+                "{}{} {} ",
+                highlight_symbol(hovered),
+                checkbox(file.selected),
+                file.relative_path.display()
+            ))
+            .fg(PALETTE.selection_fg(file.selected)),
+            Span::from(file.status.display_name()).style(status_style),
+        ]);
+        let paths = Line::from(format!(
+            "      {} → {}",
+            file.src_path.display(),
+            file.dest_path.display()
+        ))
+        .dim();
+        ListItem::new(Text::from(vec![title, paths]))
+    }
+
+    /// Get the line summarising the files to be pasted.
+    fn summary_line(&self) -> String {
+        let count = |status| {
+            self.files
+                .iter()
+                .filter(|file| file.selected && file.status == status)
+                .count()
+        };
+        format!(
+            " {} selected: {} new, {} overwritten, {} identical ",
+            self.files.iter().filter(|file| file.selected).count(),
+            count(PasteFileStatus::New),
+            count(PasteFileStatus::Overwrite),
+            count(PasteFileStatus::Identical),
+        )
+    }
+}
+
+impl Popup for PastePreviewPopup {
+    fn on_key_down(&mut self, key: &KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.keycode_lower() {
+            KeyCode::Up | KeyCode::Char('w') => {
+                self.state.select_previous();
+            }
+            KeyCode::Down | KeyCode::Char('s') => {
+                self.state.select_next();
+                clamp_selection(&mut self.state, self.files.len());
+            }
+            KeyCode::Char('a') if ctrl => self.toggle_all(),
+            KeyCode::Char(' ') => self.toggle_hovered(),
+            KeyCode::Enter => self.submit(),
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.close = true;
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, area: Rect, frame: &mut Frame<'_>) {
+        let block = popup_block(vec![
+            Span::from(" Paste to "),
+            self.dest_name.clone(),
+            Span::from(" "),
+        ])
+        .title_bottom(Line::from(self.summary_line()).right_aligned());
+
+        let selected_index = self.state.selected().unwrap_or(0);
+        let items = self
+            .files
+            .iter()
+            .enumerate()
+            .map(|(i, file)| Self::file_item(file, i == selected_index))
+            .collect::<Vec<_>>();
+
+        Widget::render(Clear, area, frame.buffer_mut());
+        list_with_scrollbar(
+            popup_list(block, items),
+            area,
+            frame.buffer_mut(),
+            &mut self.state,
+        );
+    }
+
+    fn should_close(&self) -> bool {
+        self.close
+    }
+    fn close(&mut self) {
+        self.close = true;
+    }
+    fn popup_identifier(&self) -> &'static str {
+        "paste_preview_popup"
+    }
+    fn bottom_bar_options(&self) -> Option<Vec<String>> {
+        Some(vec![
+            "↑/↓".to_string(),
+            "Space: Toggle".to_string(),
+            "Ctrl+A: Toggle All".to_string(),
+            format!("{ENTER_SYMBOL}: Paste Selected"),
+            "Esc: Cancel".to_string(),
+        ])
+    }
+    fn internal_commands_mut(&mut self) -> Option<&mut Vec<AppMessage>> {
+        Some(&mut self.commands)
+    }
+
+    fn popup_width_percent(&self) -> u16 {
+        90
+    }
+    fn popup_height_percent(&self) -> u16 {
+        70
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::test_harness::character;
+
+    fn on_branch(branch: &str) -> Character {
+        let mut alpha = character("ACC", "Draenor", "Alpha", &["config-cache.wtf"], &[]);
+        alpha.character.branch = branch.to_string();
+        alpha
+    }
+
+    /// Submit pasting the copied character's files onto the destination, as the main
+    /// application opens the preview, returning the command pushed.
+    fn submit_paste(src_char: &Character, dest_char: &Character) -> AppMessage {
+        let warning = dest_char
+            .is_cross_branch(src_char)
+            .then(|| ConfirmActionText::from(Span::from("Files may be incompatible")));
+        let mut popup = PastePreviewPopup::new(
+            1,
+            dest_char,
+            vec![PathBuf::from("config-cache.wtf")],
+            (Path::new("/missing/src"), Path::new("/missing/dest")),
+            warning,
+            HashSettings::default(),
+        );
+        popup.submit();
+        let [command] = &popup.commands[..] else {
+            panic!("Exactly one command should be pushed");
+        };
+        command.clone()
+    }
+
+    #[test]
+    fn pasting_across_branches_requires_a_critical_confirmation() {
+        let command = submit_paste(&on_branch("classic"), &on_branch("retail"));
+        let AppMessage::ConfirmAction(action, Some(_), ConfirmRisk::Critical) = command else {
+            panic!("Cross-branch paste should be confirmed, got {command:?}");
+        };
+        assert!(matches!(*action, AppMessage::Paste(1, _)));
+    }
+
+    #[test]
+    fn pasting_within_a_branch_is_not_confirmed_again() {
+        let command = submit_paste(&on_branch("retail"), &on_branch("retail"));
+        assert!(matches!(command, AppMessage::Paste(1, _)), "{command:?}");
+    }
+}
@@ -4,23 +4,53 @@ use std::fmt::Debug;
 use crate::palette::*;
 use crate::{
     backend::task::BackendTaskPtr,
-    ui::messages::AppMessage,
-    widgets::popup::{Popup, popup_block},
+    ui::{
+        KeyCodeExt,
+        messages::{AppMessage, ConfirmRisk, PopupMessage},
+    },
+    widgets::{
+        popup::{Popup, popup_block},
+        toast::ToastLevel,
+    },
 };
 
 use ratatui::{
     Frame,
+    crossterm::event::{KeyCode, KeyEvent},
     layout::{Margin, Rect},
-    style::Style,
+    style::{Style, Stylize},
+    text::Span,
     widgets::{Gauge, ListState, Widget},
 };
 
+/// Create the message to quit while a task is running, wrapped in a critical confirmation,
+/// as quitting part way through may leave a partial archive or half restored files.
+#[must_use]
+pub fn quit_during_task_message() -> AppMessage {
+    AppMessage::Quit.with_risk_confirm_and_line(
+        ConfirmRisk::Critical,
+        vec![
+            Span::from("A task is running")
+                .bold()
+                .fg(PALETTE.log_warn_fg),
+            Span::from(", quit anyway?"),
+        ],
+    )
+}
+
 /// Popup for paste confirmation.
 #[derive(Debug)]
 pub struct ProgressPopup {
     /// Backend task being tracked by the popup.
     pub task: BackendTaskPtr,
 
+    /// Frame of the spinner shown while the task's progress is not yet known,
+    /// advanced on each poll of the task.
+    spinner_frame: usize,
+
+    /// Messages of the task chain to be sent once it ends, however it ends.
+    finished_messages: Vec<AppMessage>,
+
     /// Whether the popup should close.
     pub close: bool,
 
@@ -35,6 +65,8 @@ impl ProgressPopup {
         list_state.select(Some(0));
         let mut popup = Self {
             task,
+            spinner_frame: 0,
+            finished_messages: vec![],
             close: false,
             commands: vec![],
         };
@@ -47,12 +79,45 @@ impl ProgressPopup {
     /// Start running the task.
     /// If it fails to start, log an error and close the popup.
     fn run_task(&mut self) {
+        self.finished_messages
+            .append(&mut self.task.take_finished_messages());
         if !self.task.run() {
             log::error!("Failed to start task `{}`", self.task.task_name());
             self.close();
         }
     }
 
+    /// Request cancellation of the task.
+    /// Destructive tasks require confirmation first, as stopping part way through
+    /// can leave live files half written.
+    fn request_cancel(&mut self) {
+        if self.task.finished() {
+            return;
+        }
+        if self.task.task_kind().is_destructive() {
+            self.commands
+                .push(AppMessage::CancelTask.with_risk_confirm_and_line(
+                    ConfirmRisk::Critical,
+                    vec![
+                        Span::from("Cancelling may leave files ").fg(PALETTE.log_warn_fg),
+                        Span::from("inconsistent").bold().fg(PALETTE.log_warn_fg),
+                        Span::from(", continue?"),
+                    ],
+                ));
+        } else {
+            self.cancel_task();
+        }
+    }
+
+    /// Cancel the task immediately.
+    fn cancel_task(&mut self) {
+        if self.task.finished() {
+            return;
+        }
+        self.task.cancel();
+        log::warn!("Cancelled task `{}`", self.task.task_name());
+    }
+
     /// Check if the task has finalised and handle closure and errors.
     fn check_finalise(&mut self) {
         if self.task.finished() {
@@ -60,14 +125,29 @@ impl ProgressPopup {
                 // self.commands
                 //     .push(AppMessage::ShowError("Task Error".to_string(), error));
                 log::error!("Task error: `{error}`");
+                self.commands.push(AppMessage::Toast(
+                    ToastLevel::Error,
+                    format!("{} failed, see the console (`)", self.task.task_name()),
+                ));
                 self.close();
                 return;
             }
 
+            if let Some(summary) = self.task.summary() {
+                log::info!("{summary}");
+                self.commands
+                    .push(AppMessage::Toast(ToastLevel::Success, summary));
+            }
+            let next = self.task.next_task();
+            if next.is_none() {
+                // Sent before the completion messages, as those may start further tasks
+                // depending on the chain having ended, such as for the same character.
+                self.commands.append(&mut self.finished_messages);
+            }
             if let Some(after_msg) = self.task.after_messages() {
                 self.commands.extend_from_slice(&after_msg);
             }
-            if let Some(next) = self.task.next_task() {
+            if let Some(next) = next {
                 self.task = next;
                 self.run_task();
             } else {
@@ -78,6 +158,16 @@ impl ProgressPopup {
 }
 
 impl Popup for ProgressPopup {
+    fn on_key_down(&mut self, key: &KeyEvent) {
+        match key.keycode_lower() {
+            KeyCode::Esc => self.request_cancel(),
+            KeyCode::Char('q') if !self.task.finished() => {
+                self.commands.push(quit_during_task_message());
+            }
+            _ => {}
+        }
+    }
+
     #[allow(
         clippy::cast_lossless,
         clippy::cast_possible_truncation,
@@ -91,7 +181,20 @@ impl Popup for ProgressPopup {
         let block = popup_block(format!(" {} ", self.task.task_name()))
             .border_style(Style::default().fg(PALETTE.log_info_fg));
 
-        let progress_label = self.task.progress_formatted(true);
+        // Totals aren't known until the task has counted what it works on, which can take a while
+        // for large backups, so show a spinner rather than a misleading 0%.
+        let progress_label = if self.task.progress().is_some() {
+            self.task.progress_formatted(true)
+        } else {
+            self.spinner_frame = (self.spinner_frame + 1) % SPINNER_FRAMES.len();
+            format!(
+                "{} {}",
+                SPINNER_FRAMES[self.spinner_frame],
+                self.task
+                    .task_label()
+                    .unwrap_or_else(|| "Preparing...".to_string())
+            )
+        };
         let percentage = self.task.progress_ui();
 
         let progress_bar = Gauge::default()
@@ -110,12 +213,21 @@ impl Popup for ProgressPopup {
     }
     fn close(&mut self) {
         self.close = true;
+        self.commands.append(&mut self.finished_messages);
     }
     fn popup_identifier(&self) -> &'static str {
         "progress_popup"
     }
     fn bottom_bar_options(&self) -> Option<Vec<String>> {
-        None
+        Some(vec!["Esc: Cancel".to_string(), "Q: Quit".to_string()])
+    }
+    fn process_message(&mut self, message: &PopupMessage) {
+        if message == &PopupMessage::CancelTask {
+            self.cancel_task();
+        }
+    }
+    fn is_running_task(&self) -> bool {
+        !self.task.finished()
     }
     fn internal_commands_mut(&mut self) -> Option<&mut Vec<AppMessage>> {
         Some(&mut self.commands)
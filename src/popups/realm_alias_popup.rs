@@ -0,0 +1,137 @@
+#[allow(clippy::wildcard_imports)]
+use crate::palette::*;
+use crate::{
+    ui::messages::AppMessage,
+    widgets::{
+        popup::{Popup, popup_block},
+        text_input::{TextInput, TextInputMode},
+    },
+    wow::realm_display_name,
+};
+
+use std::collections::BTreeMap;
+
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::{Margin, Rect},
+    style::Style,
+    widgets::Widget,
+};
+
+/// Different commands that can be issued from a realm alias popup.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RealmAliasPopupCommand {
+    /// Display the realm with the given name, or its folder name again if the name is empty.
+    SetAlias(String),
+}
+
+/// Popup for setting the name a realm is displayed with.
+#[derive(Debug, Clone)]
+pub struct RealmAliasPopup {
+    /// The folder name of the realm the alias is set for.
+    pub realm: String,
+    /// Display name input.
+    pub name_input: TextInput,
+
+    /// Whether the popup should close.
+    pub close: bool,
+
+    /// Commands issued by the popup.
+    pub commands: Vec<AppMessage>,
+}
+
+impl RealmAliasPopup {
+    /// Create the popup for the given realm, prefilled with its current alias, if any.
+    #[must_use]
+    pub fn new(realm: String, realm_aliases: &BTreeMap<String, String>) -> Self {
+        let mut name_input = TextInput::new_with_placeholder(format!(
+            "Enter a display name for {realm}, or leave empty to use the folder name..."
+        ));
+        name_input.mode = TextInputMode::Editing;
+        let display_name = realm_display_name(realm_aliases, &realm);
+        if display_name != realm {
+            name_input.input = display_name;
+            name_input.set_cursor_pos(name_input.character_count());
+        }
+
+        Self {
+            realm,
+            name_input,
+
+            close: false,
+
+            commands: vec![],
+        }
+    }
+
+    /// Push the command setting the entered alias, and close the popup.
+    fn submit(&mut self) {
+        let alias = self.name_input.input.trim().to_string();
+        self.commands.push(AppMessage::RealmAlias(
+            self.realm.clone(),
+            RealmAliasPopupCommand::SetAlias(alias),
+        ));
+        self.close = true;
+    }
+}
+
+impl Popup for RealmAliasPopup {
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if let Event::Key(key_event) = event
+            && key_event.kind == KeyEventKind::Press
+        {
+            match key_event.code {
+                KeyCode::Enter => {
+                    self.submit();
+                    return true;
+                }
+                KeyCode::Esc => {
+                    self.close = true;
+                    return true;
+                }
+                _ => {}
+            }
+        }
+        self.name_input.mode = TextInputMode::Editing;
+        self.name_input.handle_event(event);
+        true
+    }
+
+    fn draw(&mut self, area: Rect, frame: &mut Frame<'_>) {
+        let block = popup_block(format!(" Realm Name - {} ", self.realm))
+            .border_style(Style::default().fg(PALETTE.log_info_fg));
+
+        Widget::render(block, area, frame.buffer_mut());
+        self.name_input.render(area.inner(Margin::new(1, 1)), frame);
+    }
+
+    fn should_close(&self) -> bool {
+        self.close
+    }
+    fn close(&mut self) {
+        self.close = true;
+    }
+    fn popup_identifier(&self) -> &'static str {
+        "realm_alias_popup"
+    }
+    fn bottom_bar_options(&self) -> Option<Vec<String>> {
+        Some(vec![
+            format!("{ENTER_SYMBOL}: Save"),
+            "Esc: Close".to_string(),
+        ])
+    }
+    fn internal_commands_mut(&mut self) -> Option<&mut Vec<AppMessage>> {
+        Some(&mut self.commands)
+    }
+
+    fn popup_width_percent(&self) -> u16 {
+        70
+    }
+    fn popup_height_percent(&self) -> u16 {
+        0
+    }
+    fn popup_min_height(&self) -> u16 {
+        3
+    }
+}
@@ -0,0 +1,230 @@
+#[allow(clippy::wildcard_imports)]
+use crate::palette::*;
+use crate::{
+    backend::format_size,
+    popups::{clamp_selection, list_with_scrollbar, wrap_selection},
+    ui::{Character, CharacterIndex, KeyCodeExt, messages::AppMessage},
+    widgets::popup::{Popup, popup_block, popup_list},
+    wow::realm_display_name,
+};
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Local};
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Rect,
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Clear, ListItem, ListState, Widget},
+};
+
+/// Different commands that can be issued from a realm summary popup.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RealmSummaryPopupCommand {
+    /// Select the given character in the character list.
+    JumpToCharacter(CharacterIndex),
+    /// Select the given character in the character list, and open its backup popup.
+    ShowBackup(CharacterIndex),
+}
+
+/// Summary of one of the realm's characters.
+#[derive(Debug, Clone)]
+struct RealmCharacterRow {
+    /// Index of the character.
+    char_idx: CharacterIndex,
+    /// The class coloured display name of the character.
+    name: Span<'static>,
+    /// Number of backups of the character.
+    backups: usize,
+    /// Total size of the character's backups, in bytes.
+    backups_size: u64,
+    /// When the character was last backed up, if ever.
+    last_backup: Option<DateTime<Local>>,
+}
+
+/// Popup summarising the characters of a realm and their backups, with each character
+/// actionable to jump to or back up.
+#[derive(Debug, Clone)]
+pub struct RealmSummaryPopup {
+    /// The display name of the realm, with its account if the realm spans accounts.
+    title: String,
+    /// The realm's characters.
+    rows: Vec<RealmCharacterRow>,
+
+    /// Whether the popup should close.
+    pub close: bool,
+    /// The state of the list within the popup.
+    pub state: ListState,
+
+    /// Commands issued by the popup.
+    pub commands: Vec<AppMessage>,
+}
+
+impl RealmSummaryPopup {
+    /// Create the popup for the given characters of a realm, and the account they belong to
+    /// if the realm spans accounts.
+    #[must_use]
+    pub fn new(
+        realm: &str,
+        account: Option<&str>,
+        characters: &[(CharacterIndex, &Character)],
+        realm_aliases: &BTreeMap<String, String>,
+    ) -> Self {
+        let rows = characters
+            .iter()
+            .map(|(char_idx, character)| {
+                let backups = character.backups();
+                RealmCharacterRow {
+                    char_idx: *char_idx,
+                    name: character.display_span(false),
+                    backups: backups.len(),
+                    backups_size: backups
+                        .iter()
+                        .filter_map(|backup| std::fs::metadata(&backup.path).ok())
+                        .map(|metadata| metadata.len())
+                        .sum(),
+                    last_backup: backups.iter().map(|backup| backup.timestamp).max(),
+                }
+            })
+            .collect();
+
+        let title = account.map_or_else(
+            || realm_display_name(realm_aliases, realm),
+            |account| format!("{} - {account}", realm_display_name(realm_aliases, realm)),
+        );
+
+        let mut state = ListState::default();
+        state.select(Some(0));
+        Self {
+            title,
+            rows,
+
+            close: false,
+            state,
+
+            commands: vec![],
+        }
+    }
+
+    /// Push a command for the selected character to the popup's command list and close the popup.
+    fn push_selected_command_close(
+        &mut self,
+        command: impl FnOnce(CharacterIndex) -> RealmSummaryPopupCommand,
+    ) {
+        if let Some(row) = self.state.selected().and_then(|idx| self.rows.get(idx)) {
+            self.commands
+                .push(AppMessage::RealmSummary(command(row.char_idx)));
+            self.close = true;
+        }
+    }
+
+    /// Get the line summarising the whole realm.
+    fn summary_line(&self) -> String {
+        let characters = self.rows.len();
+        let backups = self.rows.iter().map(|row| row.backups).sum::<usize>();
+        let size = self.rows.iter().map(|row| row.backups_size).sum::<u64>();
+        format!(
+            " {characters} character{}, {backups} backup{}, {} ",
+            if characters == 1 { "" } else { "s" },
+            if backups == 1 { "" } else { "s" },
+            format_size(size)
+        )
+    }
+}
+
+impl Popup for RealmSummaryPopup {
+    fn on_key_down(&mut self, key: &KeyEvent) {
+        match key.keycode_lower() {
+            KeyCode::Up | KeyCode::Char('w') => {
+                self.state.select_previous();
+            }
+            KeyCode::Down | KeyCode::Char('s') => {
+                self.state.select_next();
+                clamp_selection(&mut self.state, self.rows.len());
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                self.push_selected_command_close(RealmSummaryPopupCommand::JumpToCharacter);
+            }
+            KeyCode::Char('b') => {
+                self.push_selected_command_close(RealmSummaryPopupCommand::ShowBackup);
+            }
+            KeyCode::Esc | KeyCode::Char('q' | 'i') => {
+                self.close = true;
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, area: Rect, frame: &mut Frame<'_>) {
+        let block = popup_block(format!(" Realm - {} ", self.title))
+            .title_bottom(Line::from(self.summary_line()).right_aligned());
+
+        let name_width = self
+            .rows
+            .iter()
+            .map(|row| row.name.content.chars().count())
+            .max()
+            .unwrap_or(0);
+        let selected_index = self.state.selected().unwrap_or(0);
+        let items = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let plural = if row.backups == 1 { "" } else { "s" };
+                let last_backup = row.last_backup.map_or_else(
+                    || "never backed up".to_string(),
+                    |time| format!("last backup {}", display_relative_time(time.into())),
+                );
+                let spans = vec![
+                    Span::from(format!("{:<name_width$}", row.name.content)).style(row.name.style),
+                    Span::from(format!(
+                        "  {} backup{plural}, {}, {last_backup}",
+                        row.backups,
+                        format_size(row.backups_size)
+                    ))
+                    .dim(),
+                ];
+                ListItem::new(wrap_selection(spans, i == selected_index))
+            })
+            .collect::<Vec<ListItem>>();
+
+        Widget::render(Clear, area, frame.buffer_mut());
+        list_with_scrollbar(
+            popup_list(block, items),
+            area,
+            frame.buffer_mut(),
+            &mut self.state,
+        );
+    }
+
+    fn should_close(&self) -> bool {
+        self.close
+    }
+    fn close(&mut self) {
+        self.close = true;
+    }
+    fn popup_identifier(&self) -> &'static str {
+        "realm_summary_popup"
+    }
+    fn bottom_bar_options(&self) -> Option<Vec<String>> {
+        Some(vec![
+            "↑/↓".to_string(),
+            format!("{ENTER_SYMBOL}/Space: Jump to"),
+            "(B)ackup".to_string(),
+            "Esc: Close".to_string(),
+        ])
+    }
+    fn internal_commands_mut(&mut self) -> Option<&mut Vec<AppMessage>> {
+        Some(&mut self.commands)
+    }
+
+    fn popup_width_percent(&self) -> u16 {
+        70
+    }
+    fn popup_height_percent(&self) -> u16 {
+        60
+    }
+}
@@ -0,0 +1,124 @@
+#[allow(clippy::wildcard_imports)]
+use crate::palette::*;
+use crate::{
+    popups::{clamp_selection, wrap_selection},
+    ui::{CharacterIndex, KeyCodeExt, messages::AppMessage},
+    widgets::popup::{Popup, popup_block, popup_list},
+};
+
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Rect,
+    text::Span,
+    widgets::{ListItem, ListState, StatefulWidget},
+};
+
+/// Different commands that can be issued from a recent characters popup.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RecentCharactersPopupCommand {
+    /// Select the given character in the character list.
+    JumpToCharacter(CharacterIndex),
+}
+
+/// Popup listing the most recently used characters, for quickly jumping back to them.
+#[derive(Debug, Clone)]
+pub struct RecentCharactersPopup {
+    /// The recently used characters, most recent first, with their styled display names.
+    pub characters: Vec<(CharacterIndex, Span<'static>)>,
+
+    /// Whether the popup should close.
+    pub close: bool,
+    /// The state of the list within the popup.
+    pub state: ListState,
+
+    /// Commands issued by the popup.
+    pub commands: Vec<AppMessage>,
+}
+
+impl RecentCharactersPopup {
+    #[must_use]
+    pub fn new(characters: Vec<(CharacterIndex, Span<'static>)>) -> Self {
+        let mut list_state = ListState::default();
+        list_state.select(Some(0));
+        Self {
+            characters,
+
+            close: false,
+            state: list_state,
+
+            commands: vec![],
+        }
+    }
+
+    /// Push a command to the popup's command list and close the popup.
+    #[inline]
+    pub fn push_command_close(&mut self, command: RecentCharactersPopupCommand) {
+        self.commands.push(AppMessage::RecentCharacters(command));
+        self.close = true;
+    }
+}
+
+impl Popup for RecentCharactersPopup {
+    fn on_key_down(&mut self, key: &KeyEvent) {
+        match key.keycode_lower() {
+            KeyCode::Up | KeyCode::Char('w') => {
+                self.state.select_previous();
+            }
+            KeyCode::Down | KeyCode::Char('s') => {
+                self.state.select_next();
+                clamp_selection(&mut self.state, self.characters.len());
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                if let Some(selected) = self.state.selected()
+                    && let Some((char_idx, _)) = self.characters.get(selected)
+                {
+                    self.push_command_close(RecentCharactersPopupCommand::JumpToCharacter(
+                        *char_idx,
+                    ));
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q' | 'm') => {
+                self.close = true;
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, area: Rect, frame: &mut Frame<'_>) {
+        let block = popup_block(" Recent Characters ");
+
+        let selected_index = self.state.selected().unwrap_or(0);
+        let items = self
+            .characters
+            .iter()
+            .enumerate()
+            .map(|(i, (_, name))| {
+                ListItem::new(wrap_selection(vec![name.clone()], i == selected_index))
+            })
+            .collect::<Vec<ListItem>>();
+
+        let list_view = popup_list(block, items);
+        StatefulWidget::render(list_view, area, frame.buffer_mut(), &mut self.state);
+    }
+
+    fn should_close(&self) -> bool {
+        self.close
+    }
+    fn close(&mut self) {
+        self.close = true;
+    }
+    fn popup_identifier(&self) -> &'static str {
+        "recent_characters_popup"
+    }
+    fn bottom_bar_options(&self) -> Option<Vec<String>> {
+        Some(vec![
+            "↑/↓".to_string(),
+            format!("{}/Space: Jump to", ENTER_SYMBOL),
+            "Esc: Close".to_string(),
+        ])
+    }
+    fn internal_commands_mut(&mut self) -> Option<&mut Vec<AppMessage>> {
+        Some(&mut self.commands)
+    }
+}
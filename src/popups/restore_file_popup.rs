@@ -0,0 +1,175 @@
+#[allow(clippy::wildcard_imports)]
+use crate::palette::*;
+use crate::{
+    CharacterWithIndex,
+    popups::{parse_path, restore_popup::RestorePopupCommand},
+    ui::messages::AppMessage,
+    widgets::{
+        popup::{Popup, popup_block},
+        text_input::{TextInput, TextInputMode},
+    },
+};
+
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::{Margin, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+/// Popup for restoring a character from an archive outside of its backups directory,
+/// or importing an addon profile archive onto it.
+#[derive(Debug, Clone)]
+pub struct RestoreFilePopup {
+    /// The character the archive is restored to.
+    pub dest_char: CharacterWithIndex,
+    /// Archive path input.
+    pub path_input: TextInput,
+    /// Whether the archive is an addon profile to import, rather than a backup to restore.
+    pub addon_profile: bool,
+
+    /// Whether the popup should close.
+    pub close: bool,
+
+    /// Commands issued by the popup.
+    pub commands: Vec<AppMessage>,
+}
+
+impl RestoreFilePopup {
+    #[must_use]
+    pub fn new(character: CharacterWithIndex) -> Self {
+        let mut path_input = TextInput::new_with_placeholder("Enter archive path here...");
+        path_input.mode = TextInputMode::Editing;
+
+        Self {
+            dest_char: character,
+            path_input,
+            addon_profile: false,
+
+            close: false,
+
+            commands: vec![],
+        }
+    }
+
+    /// Import the entered path as an addon profile rather than restoring it as a backup.
+    #[must_use]
+    pub const fn with_addon_profile(mut self) -> Self {
+        self.addon_profile = true;
+        self
+    }
+
+    /// Push the restore command for the entered path, wrapped in a confirmation, and close the popup.
+    fn submit(&mut self) {
+        if self.path_input.input.trim().is_empty() {
+            log::warn!("No archive path entered to restore from.");
+            return;
+        }
+
+        let archive_path = parse_path(&self.path_input.input);
+        let file_name = archive_path.file_name().map_or_else(
+            || archive_path.display().to_string(),
+            |name| name.to_string_lossy().to_string(),
+        );
+        let (command, action) = if self.addon_profile {
+            (
+                RestorePopupCommand::ImportAddonProfile(archive_path),
+                "Import addon profile",
+            )
+        } else {
+            (
+                RestorePopupCommand::RestoreFromFile(archive_path),
+                "Restore",
+            )
+        };
+        let command =
+            AppMessage::Restore(self.dest_char.1, command).with_confirm_and_line(Line::from(vec![
+                Span::from(format!("{action} `{file_name}` to ")),
+                self.dest_char.0.display_span(true).bold(),
+            ]));
+
+        self.commands.push(command);
+        self.close = true;
+    }
+}
+
+impl Popup for RestoreFilePopup {
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if let Event::Key(key_event) = event
+            && key_event.kind == KeyEventKind::Press
+        {
+            match key_event.code {
+                KeyCode::Enter => {
+                    self.submit();
+                    return true;
+                }
+                KeyCode::Esc => {
+                    self.close = true;
+                    return true;
+                }
+                _ => {}
+            }
+        }
+        self.path_input.mode = TextInputMode::Editing;
+        self.path_input.handle_event(event);
+        true
+    }
+
+    fn draw(&mut self, area: Rect, frame: &mut Frame<'_>) {
+        let title = if self.addon_profile {
+            vec![
+                Span::from(" Import addon profile to "),
+                self.dest_char.0.display_span(true),
+                Span::from(" "),
+            ]
+        } else {
+            vec![
+                Span::from(" Restore "),
+                self.dest_char.0.display_span(true),
+                Span::from(" from file "),
+            ]
+        };
+        let block = popup_block(title).border_style(Style::default().fg(PALETTE.log_info_fg));
+
+        Widget::render(block, area, frame.buffer_mut());
+        self.path_input.render(area.inner(Margin::new(1, 1)), frame);
+    }
+
+    fn should_close(&self) -> bool {
+        self.close
+    }
+    fn close(&mut self) {
+        self.close = true;
+    }
+    fn popup_identifier(&self) -> &'static str {
+        "restore_file_popup"
+    }
+    fn bottom_bar_options(&self) -> Option<Vec<String>> {
+        Some(vec![
+            format!(
+                "{ENTER_SYMBOL}: {}",
+                if self.addon_profile {
+                    "Import"
+                } else {
+                    "Restore"
+                }
+            ),
+            "Esc: Close".to_string(),
+        ])
+    }
+    fn internal_commands_mut(&mut self) -> Option<&mut Vec<AppMessage>> {
+        Some(&mut self.commands)
+    }
+
+    fn popup_width_percent(&self) -> u16 {
+        90
+    }
+    fn popup_height_percent(&self) -> u16 {
+        0
+    }
+    fn popup_min_height(&self) -> u16 {
+        3
+    }
+}
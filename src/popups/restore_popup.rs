@@ -1,8 +1,11 @@
+use std::path::PathBuf;
+
 #[allow(clippy::wildcard_imports)]
 use crate::palette::*;
 use crate::{
     CharacterWithIndex,
-    popups::list_with_scrollbar,
+    backend::RestoreScope,
+    popups::{clamp_selection, list_with_scrollbar},
     ui::{KeyCodeExt, messages::AppMessage},
     widgets::popup::{Popup, popup_block, popup_list},
     wow::WoWCharacterBackup,
@@ -21,8 +24,59 @@ use ratatui::{
 /// Different commands that can be issued from a restore popup.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum RestorePopupCommand {
-    /// Command to restore a backup to the associated destination character.
-    RestoreBackup(WoWCharacterBackup),
+    /// Command to restore the files within the scope of a backup to the associated
+    /// destination character.
+    RestoreBackup(WoWCharacterBackup, RestoreScope),
+    /// Command to restore an archive from an arbitrary path to the associated destination character.
+    RestoreFromFile(PathBuf),
+    /// Command to restore an archive from an arbitrary path to the associated destination character,
+    /// after confirming it was backed up on a different branch.
+    RestoreFromFileAcrossBranches(PathBuf),
+    /// Command to import an addon profile archive from an arbitrary path to the associated
+    /// destination character and its account.
+    ImportAddonProfile(PathBuf),
+}
+
+/// Create the command to restore the files within the scope of a backup to the destination
+/// character, wrapped in a confirmation.
+#[must_use]
+pub fn restore_command(
+    dest_char: &CharacterWithIndex,
+    backup: WoWCharacterBackup,
+    scope: RestoreScope,
+) -> AppMessage {
+    let start_span = match scope {
+        RestoreScope::All => format!("Restore backup `{}` to ", backup.formatted_name()),
+        RestoreScope::Config | RestoreScope::Addons => format!(
+            "Restore only {} from `{}` to ",
+            scope.display_name(),
+            backup.formatted_name()
+        ),
+        RestoreScope::MergeAddons => format!(
+            "Merge addon settings from `{}` into ",
+            backup.formatted_name()
+        ),
+    };
+    let action_line = Line::from(vec![
+        Span::from(start_span),
+        dest_char.0.display_span(true).bold(),
+    ]);
+    let command = AppMessage::Restore(
+        dest_char.1,
+        RestorePopupCommand::RestoreBackup(backup, scope),
+    );
+    if scope == RestoreScope::MergeAddons {
+        command.with_strong_confirm_and_line(
+            action_line,
+            vec![
+                Span::from("Merging is "),
+                Span::from("EXPERIMENTAL").bold().fg(PALETTE.log_warn_fg),
+                Span::from(", merged files are rewritten, continue?"),
+            ],
+        )
+    } else {
+        command.with_confirm_and_line(action_line)
+    }
 }
 
 /// Popup for restoring a backup for a character.
@@ -92,6 +146,16 @@ impl RestorePopup {
         self.source_char.as_ref().unwrap_or(&self.dest_char)
     }
 
+    /// Push the command to restore the files within the scope of the selected backup, and close.
+    fn restore_selected(&mut self, scope: RestoreScope) {
+        if let Some(selected) = self.state.selected()
+            && let Some(backup) = self.get_backup(selected).cloned()
+        {
+            let command = restore_command(&self.dest_char, backup, scope);
+            self.push_command_close(command);
+        }
+    }
+
     /// Get the backup at a specified index from the source character.
     #[inline]
     #[must_use]
@@ -107,21 +171,21 @@ impl Popup for RestorePopup {
                 self.state.select_previous();
             }
             KeyCode::Down | KeyCode::Char('s') => {
+                let len = self.source_char().0.backups().len();
                 self.state.select_next();
+                clamp_selection(&mut self.state, len);
             }
             KeyCode::Enter | KeyCode::Char(' ') => {
-                if let Some(selected) = self.state.selected()
-                    && let Some(backup) = self.get_backup(selected).cloned()
-                {
-                    let command =
-                        self.get_command(RestorePopupCommand::RestoreBackup(backup.clone()));
-                    let start_span =
-                        Span::from(format!("Restore backup `{}` to ", backup.formatted_name()));
-                    let dest_char_span = self.dest_char.0.display_span(true).bold();
-                    self.push_command_close(
-                        command.with_confirm_and_line(Line::from(vec![start_span, dest_char_span])),
-                    );
-                }
+                self.restore_selected(RestoreScope::All);
+            }
+            KeyCode::Char('c') => {
+                self.restore_selected(RestoreScope::Config);
+            }
+            KeyCode::Char('a') => {
+                self.restore_selected(RestoreScope::Addons);
+            }
+            KeyCode::Char('m') => {
+                self.restore_selected(RestoreScope::MergeAddons);
             }
             KeyCode::Esc | KeyCode::Char('q') => {
                 self.close = true;
@@ -148,7 +212,7 @@ impl Popup for RestorePopup {
             .map(|(i, backup)| {
                 let content = format!(
                     "{}{} {}{}",
-                    pinned_string(backup.is_pinned),
+                    backup_tier_string(backup.tier),
                     backup.char_name,
                     display_backup_time(&backup.timestamp),
                     if backup.is_paste { " (Auto)" } else { "" },
@@ -176,6 +240,9 @@ impl Popup for RestorePopup {
         Some(vec![
             "↑/↓".to_string(),
             format!("{}/Space: Select", ENTER_SYMBOL),
+            "C: Config Only".to_string(),
+            "A: Addons Only".to_string(),
+            "M: Merge Addons (Experimental)".to_string(),
             "Esc: Close".to_string(),
         ])
     }
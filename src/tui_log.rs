@@ -1,5 +1,10 @@
 use log::{Log, Metadata, Record};
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
 
 #[derive(Debug, Clone)]
 pub struct TuiLogLine {
@@ -43,6 +48,10 @@ impl Log for TuiLogger {
             && let Ok(mut logs) = self.logs.lock()
         {
             let message = format!("[{}] {}", record.level(), record.args());
+            if record.level() as usize <= ECHO_LEVEL.load(Ordering::Relaxed) {
+                eprintln!("{message}");
+            }
+            send_to_log_file(&message);
             for line in message.lines() {
                 logs.insert(
                     0,
@@ -62,6 +71,15 @@ impl Log for TuiLogger {
     fn flush(&self) {}
 }
 
+/// Most verbose log level echoed to `stderr`, as a `log::Level` discriminant, `0` disables echoing.
+static ECHO_LEVEL: AtomicUsize = AtomicUsize::new(0);
+
+/// Echo log messages at or above the given level to `stderr`, for running without the TUI.
+/// Messages are still recorded for the TUI debug window either way.
+pub fn set_stderr_echo_level(level: Option<log::Level>) {
+    ECHO_LEVEL.store(level.map_or(0, |l| l as usize), Ordering::Relaxed);
+}
+
 /// Global TUI logger instance.
 pub static TUI_LOGGER: TuiLogger = TuiLogger {
     logs: Mutex::new(Vec::new()),
@@ -82,6 +100,94 @@ pub fn init_tui_logger(max_level: log::LevelFilter) {
         .unwrap();
 }
 
+// On-disk logging..
+
+/// Size at which the log file is rotated, keeping a single previous log file.
+pub const MAX_LOG_FILE_SIZE: u64 = 2 * 1024 * 1024;
+
+/// Channel to the thread writing log lines to disk, if file logging is enabled.
+static LOG_FILE_SENDER: Mutex<Option<Sender<String>>> = Mutex::new(None);
+
+/// Queue a log line to be written to the log file, if file logging is enabled.
+/// Writing happens on a background thread, so logging never waits on the disk.
+fn send_to_log_file(message: &str) {
+    if let Ok(sender) = LOG_FILE_SENDER.lock()
+        && let Some(sender) = sender.as_ref()
+    {
+        let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f");
+        sender.send(format!("{timestamp} {message}")).ok();
+    }
+}
+
+/// Mirror all log records to the file at `path`, rotating it once it exceeds
+/// [`MAX_LOG_FILE_SIZE`]. Replaces any previously enabled log file.
+/// # Errors
+/// Returns an error if the log file or its directory cannot be created.
+pub fn enable_file_logging(path: &Path) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = open_log_file(path)?;
+
+    let (tx, rx) = mpsc::channel();
+    let path = path.to_path_buf();
+    std::thread::Builder::new()
+        .name("chronobind-log-file".to_string())
+        .spawn(move || write_log_file(&path, file, &rx))?;
+
+    if let Ok(mut sender) = LOG_FILE_SENDER.lock() {
+        *sender = Some(tx);
+    }
+    Ok(())
+}
+
+/// Stop mirroring log records to the log file, flushing any queued lines.
+pub fn disable_file_logging() {
+    if let Ok(mut sender) = LOG_FILE_SENDER.lock() {
+        *sender = None;
+    }
+}
+
+/// Returns `true` if log records are being mirrored to a log file.
+#[must_use]
+pub fn file_logging_enabled() -> bool {
+    LOG_FILE_SENDER.lock().is_ok_and(|sender| sender.is_some())
+}
+
+/// Open the log file for appending.
+fn open_log_file(path: &Path) -> std::io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// Write queued log lines to the log file until file logging is disabled, flushing after each
+/// batch of lines and rotating the file once it grows too large.
+fn write_log_file(path: &PathBuf, file: File, rx: &Receiver<String>) {
+    let mut written = file.metadata().map_or(0, |m| m.len());
+    let mut writer = BufWriter::new(file);
+
+    while let Ok(line) = rx.recv() {
+        let mut write_line = |line: String, writer: &mut BufWriter<File>| {
+            if writeln!(writer, "{line}").is_ok() {
+                written += line.len() as u64 + 1;
+            }
+        };
+        write_line(line, &mut writer);
+        while let Ok(line) = rx.try_recv() {
+            write_line(line, &mut writer);
+        }
+        writer.flush().ok();
+
+        if written > MAX_LOG_FILE_SIZE {
+            std::fs::rename(path, path.with_extension("log.1")).ok();
+            let Ok(file) = open_log_file(path) else {
+                return;
+            };
+            writer = BufWriter::new(file);
+            written = 0;
+        }
+    }
+}
+
 // File system operations logging..
 pub const MOCK_HEADER: &str = "[Mock] ";
 
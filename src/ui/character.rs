@@ -1,10 +1,13 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
 use std::path::PathBuf;
+use std::time::SystemTime;
 
 use ratatui::{
     style::{Color, Stylize},
     text::Span,
 };
 
+use crate::config::RuntimeSettings;
 use crate::wow::{
     SAVED_VARIABLES_DIR, WoWCharacter, WoWCharacterBackup, WoWCharacterFile, WoWClass, WoWInstall,
 };
@@ -12,20 +15,53 @@ use crate::wow::{
 /// Type alias for a character index.
 pub type CharacterIndex = usize;
 
+/// Maximum number of selection states kept for undo.
+pub const MAX_SELECTION_HISTORY: usize = 32;
+
+/// Snapshot of a character's config and addon file selections.
+type SelectionSnapshot = (Vec<bool>, Vec<bool>);
+
+/// The addon files of a character belonging to the same addon.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AddonGroup {
+    /// The name of the addon, derived from the file names.
+    pub name: String,
+    /// Indices of the addon's files within the character's addon files.
+    pub files: Vec<usize>,
+}
+
 /// Representation of a `WoW` character along with its selected files and
 /// options inside the app UI.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[allow(clippy::struct_field_names)]
+#[allow(clippy::struct_field_names, clippy::struct_excessive_bools)]
 pub struct Character {
     /// The underlying `WoW` character data.
     pub character: WoWCharacter,
     /// Whether the addon options section is collapsed.
     pub addon_options_collapsed: bool,
+    /// Names of the addon groups which are collapsed, when addon files are grouped by addon.
+    pub collapsed_addon_groups: BTreeSet<String>,
+    /// Whether another character with the same name and realm exists under a different
+    /// account, in which case the account is shown alongside the name.
+    pub duplicate_name: bool,
+    /// Whether the same character, on the same account, was also found in another installation
+    /// of the branch, in which case the installation path is shown as well.
+    pub duplicate_install: bool,
+    /// The name the character's realm is displayed with, if an alias is set for it.
+    pub realm_alias: Option<String>,
+    /// Whether the character's files and backups have been loaded, `false` if the scan which
+    /// found it was cancelled before reaching it, in which case they're loaded once selected.
+    pub files_mapped: bool,
 
     /// Which config files are selected.
     selected_config_files: Vec<bool>,
     /// Which addon files are selected.
     selected_addon_files: Vec<bool>,
+
+    /// Previous selection states, most recent last.
+    selection_undo: VecDeque<SelectionSnapshot>,
+    /// Undone selection states, most recent last.
+    selection_redo: Vec<SelectionSnapshot>,
 }
 
 impl Character {
@@ -37,20 +73,123 @@ impl Character {
             character: character.clone(),
             selected_config_files: vec![false; config_file_count],
             selected_addon_files: vec![false; addon_file_count],
+            selection_undo: VecDeque::new(),
+            selection_redo: Vec::new(),
             addon_options_collapsed: false,
+            collapsed_addon_groups: BTreeSet::new(),
+            duplicate_name: false,
+            duplicate_install: false,
+            realm_alias: None,
+            files_mapped: true,
         }
     }
+
+    /// Reload the character's files and backups from disk, keeping the selection of files
+    /// which still exist. Returns `false` if the character's directory could not be read.
+    pub fn refresh(&mut self, install: &WoWInstall, settings: &RuntimeSettings) -> bool {
+        let previous = self.clone();
+        let refreshed = self.character.refresh_character_info(install, settings);
+        self.merge_selection_from(&previous);
+        refreshed
+    }
+
+    /// Carry over the file selection of an earlier copy of this character, such as from before
+    /// a refresh, matching files by name. Files which no longer exist drop their selection, and
+    /// new files are left unselected.
+    pub fn merge_selection_from(&mut self, old: &Self) {
+        fn selected_names<'a>(
+            files: &'a [WoWCharacterFile],
+            selected: &[bool],
+        ) -> HashSet<&'a str> {
+            files
+                .iter()
+                .zip(selected)
+                .filter(|(_, selected)| **selected)
+                .map(|(file, _)| file.name.as_str())
+                .collect()
+        }
+
+        let config_names = selected_names(&old.character.config_files, &old.selected_config_files);
+        let addon_names = selected_names(&old.character.addon_files, &old.selected_addon_files);
+        self.selected_config_files = self
+            .character
+            .config_files
+            .iter()
+            .map(|file| config_names.contains(file.name.as_str()))
+            .collect();
+        self.selected_addon_files = self
+            .character
+            .addon_files
+            .iter()
+            .map(|file| addon_names.contains(file.name.as_str()))
+            .collect();
+        // Snapshots refer to files by index, which may no longer line up.
+        self.selection_undo.clear();
+        self.selection_redo.clear();
+    }
+}
+
+/// Flag characters sharing a name and realm with a character on another account or in another
+/// installation, returning the number of characters flagged.
+pub fn mark_duplicate_characters(characters: &mut [Character]) -> usize {
+    let mut counts: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+    let mut account_counts: BTreeMap<(&str, &str, &str), usize> = BTreeMap::new();
+    for character in characters.iter() {
+        *counts
+            .entry((character.name(), character.realm()))
+            .or_default() += 1;
+        *account_counts
+            .entry((character.name(), character.realm(), character.account()))
+            .or_default() += 1;
+    }
+    let duplicates = characters
+        .iter()
+        .map(|c| {
+            (
+                counts[&(c.name(), c.realm())] > 1,
+                account_counts[&(c.name(), c.realm(), c.account())] > 1,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    for (character, (duplicate_name, duplicate_install)) in characters.iter_mut().zip(&duplicates) {
+        character.duplicate_name = *duplicate_name;
+        character.duplicate_install = *duplicate_install;
+    }
+    duplicates.into_iter().filter(|(d, _)| *d).count()
+}
+
+/// Set the realm alias of each character from the configured realm aliases, keyed by the
+/// realm's folder name.
+pub fn apply_realm_aliases(characters: &mut [Character], realm_aliases: &BTreeMap<String, String>) {
+    for character in characters {
+        character.realm_alias = realm_aliases.get(character.realm()).cloned();
+    }
 }
 
 // Accessors..
 impl Character {
     /// Get the display name of the character, optionally including the realm.
+    /// Characters sharing their name and realm with another account also show the account,
+    /// and the installation path if found in more than one installation.
     #[must_use]
     pub fn display_name(&self, show_realm: bool) -> String {
-        if show_realm {
-            format!("{} - {}", self.name(), self.realm())
+        let name = if self.duplicate_install {
+            format!(
+                "{} [{}, {}]",
+                self.name(),
+                self.account(),
+                self.character.install_path
+            )
+        } else if self.duplicate_name {
+            format!("{} [{}]", self.name(), self.account())
         } else {
             self.name().to_string()
+        };
+        if show_realm {
+            format!("{name} - {}", self.display_realm())
+        } else {
+            name
         }
     }
 
@@ -61,6 +200,23 @@ impl Character {
         &self.character.realm
     }
 
+    /// Get the name the character's realm is displayed with, its alias if one is set.
+    #[inline]
+    #[must_use]
+    pub fn display_realm(&self) -> String {
+        self.realm_alias
+            .clone()
+            .unwrap_or_else(|| self.realm().to_string())
+    }
+
+    /// Get the key the character is stored under in the favorite characters,
+    /// I.e. `account/realm/name`.
+    #[inline]
+    #[must_use]
+    pub fn favorite_key(&self) -> String {
+        self.character.settings_key()
+    }
+
     /// Get the name of the character.
     #[inline]
     #[must_use]
@@ -124,6 +280,31 @@ impl Character {
         &self.character.addon_files
     }
 
+    /// Returns `true` if the character has any config or addon files.
+    #[inline]
+    #[must_use]
+    pub const fn has_files(&self) -> bool {
+        self.character.has_files()
+    }
+
+    /// Get the time of the character's most recent backup, if it has any.
+    #[must_use]
+    pub fn last_backup_time(&self) -> Option<SystemTime> {
+        self.backups()
+            .iter()
+            .map(|backup| SystemTime::from(backup.timestamp))
+            .max()
+    }
+
+    /// Returns `true` if the file was modified after the character's most recent backup.
+    /// Files of characters without backups are never considered modified.
+    #[must_use]
+    pub fn modified_since_last_backup(&self, file: &WoWCharacterFile) -> bool {
+        self.last_backup_time()
+            .zip(file.modified)
+            .is_some_and(|(last_backup, modified)| modified > last_backup)
+    }
+
     /// Get the backups of the character.
     #[inline]
     #[must_use]
@@ -131,13 +312,29 @@ impl Character {
         &self.character.backups
     }
 
+    /// Returns `true` if the character has more backups than the given threshold, counting
+    /// pinned and paste backups which are never removed automatically.
+    #[inline]
+    #[must_use]
+    pub fn exceeds_backup_threshold(&self, threshold: Option<usize>) -> bool {
+        threshold.is_some_and(|threshold| self.backups().len() > threshold)
+    }
+
     /// Returns `true` if the other character represents the same character
-    /// (same `name`, `realm`, `account`, and `branch`).
+    /// (same `name`, `realm`, `account`, `branch` and `install_path`).
     #[inline]
     #[must_use]
     pub fn is_same_character(&self, other: &Self) -> bool {
         self.character.is_same_character(&other.character)
     }
+
+    /// Returns `true` if the other character belongs to a different branch, whose files may use
+    /// an incompatible format and so should not be pasted onto this character without warning.
+    #[inline]
+    #[must_use]
+    pub fn is_cross_branch(&self, other: &Self) -> bool {
+        self.branch() != other.branch()
+    }
 }
 
 impl Character {
@@ -185,6 +382,67 @@ impl Character {
             })
     }
 
+    /// Get the character's addon files grouped by the addon they belong to, ordered by addon name.
+    #[must_use]
+    pub fn addon_groups(&self) -> Vec<AddonGroup> {
+        let mut groups = BTreeMap::<String, AddonGroup>::new();
+        for (idx, file) in self.addon_files().iter().enumerate() {
+            let name = file.addon_name();
+            groups
+                .entry(name.to_lowercase())
+                .or_insert_with(|| AddonGroup {
+                    name: name.to_string(),
+                    files: Vec::new(),
+                })
+                .files
+                .push(idx);
+        }
+        groups.into_values().collect()
+    }
+
+    /// Check if the addon group with the given name is collapsed.
+    #[inline]
+    #[must_use]
+    pub fn is_addon_group_collapsed(&self, name: &str) -> bool {
+        self.collapsed_addon_groups.contains(name)
+    }
+
+    /// Toggle whether the addon group with the given name is collapsed.
+    pub fn toggle_addon_group_collapsed(&mut self, name: &str) {
+        if !self.collapsed_addon_groups.remove(name) {
+            self.collapsed_addon_groups.insert(name.to_string());
+        }
+    }
+
+    /// Check if any file of the addon group is selected.
+    #[inline]
+    #[must_use]
+    pub fn any_addon_group_file_selected(&self, group: &AddonGroup) -> bool {
+        group
+            .files
+            .iter()
+            .any(|&idx| self.is_addon_file_selected(idx))
+    }
+
+    /// Check if all files of the addon group are selected.
+    #[inline]
+    #[must_use]
+    pub fn all_addon_group_files_selected(&self, group: &AddonGroup) -> bool {
+        group
+            .files
+            .iter()
+            .all(|&idx| self.is_addon_file_selected(idx))
+    }
+
+    /// Set the selected status of all files of the addon group.
+    pub fn set_addon_group_selected(&mut self, group: &AddonGroup, state: bool) {
+        for &idx in &group.files {
+            if let Some(selected) = self.selected_addon_files.get_mut(idx) {
+                *selected = state;
+            }
+        }
+    }
+
     /// Check if any config files are selected.
     #[inline]
     #[must_use]
@@ -239,6 +497,95 @@ impl Character {
         self.set_all_addon_selected(state);
     }
 
+    /// Select only the files that have a friendly name (the commonly important files),
+    /// deselecting all others.
+    #[inline]
+    pub fn set_only_friendly_selected(&mut self) {
+        for (selected, file) in self
+            .selected_config_files
+            .iter_mut()
+            .zip(&self.character.config_files)
+        {
+            *selected = file.has_friendly_name();
+        }
+        for (selected, file) in self
+            .selected_addon_files
+            .iter_mut()
+            .zip(&self.character.addon_files)
+        {
+            *selected = file.has_friendly_name();
+        }
+    }
+
+    /// Check if exactly the files with a friendly name are selected, and at least one is.
+    #[inline]
+    #[must_use]
+    pub fn only_friendly_selected(&self) -> bool {
+        let config_matches = self
+            .selected_config_files
+            .iter()
+            .zip(&self.character.config_files)
+            .all(|(selected, file)| *selected == file.has_friendly_name());
+        let addon_matches = self
+            .selected_addon_files
+            .iter()
+            .zip(&self.character.addon_files)
+            .all(|(selected, file)| *selected == file.has_friendly_name());
+        config_matches && addon_matches && self.any_file_selected()
+    }
+
+    /// Take a snapshot of the current file selections.
+    fn selection_snapshot(&self) -> SelectionSnapshot {
+        (
+            self.selected_config_files.clone(),
+            self.selected_addon_files.clone(),
+        )
+    }
+
+    /// Replace the current file selections with a snapshot.
+    fn restore_selection_snapshot(&mut self, (config, addon): SelectionSnapshot) {
+        self.selected_config_files = config;
+        self.selected_addon_files = addon;
+    }
+
+    /// Apply a bulk selection change, recording the previous selection so it can be undone.
+    /// Nothing is recorded if the change leaves the selection as it was.
+    pub fn with_selection_history(&mut self, change: impl FnOnce(&mut Self)) {
+        let before = self.selection_snapshot();
+        change(self);
+        if before == self.selection_snapshot() {
+            return;
+        }
+
+        if self.selection_undo.len() >= MAX_SELECTION_HISTORY {
+            self.selection_undo.pop_front();
+        }
+        self.selection_undo.push_back(before);
+        self.selection_redo.clear();
+    }
+
+    /// Restore the selection from before the last recorded change.
+    /// Returns `true` if there was a change to undo.
+    pub fn undo_selection(&mut self) -> bool {
+        let Some(previous) = self.selection_undo.pop_back() else {
+            return false;
+        };
+        self.selection_redo.push(self.selection_snapshot());
+        self.restore_selection_snapshot(previous);
+        true
+    }
+
+    /// Reapply the last undone selection change.
+    /// Returns `true` if there was a change to redo.
+    pub fn redo_selection(&mut self) -> bool {
+        let Some(next) = self.selection_redo.pop() else {
+            return false;
+        };
+        self.selection_undo.push_back(self.selection_snapshot());
+        self.restore_selection_snapshot(next);
+        true
+    }
+
     /// Get the count of selected config files.
     #[inline]
     #[must_use]
@@ -291,20 +638,6 @@ impl Character {
         let content = self.display_name(show_realm);
         Span::from(content).fg(self.class_colour())
     }
-
-    /// Get a styled span for the character's display name, using the appropriate class colour,
-    /// and displaying relevant meta info, if available.
-    #[inline]
-    #[must_use]
-    pub fn display_span_with_meta(&self, show_realm: bool) -> Span<'static> {
-        self.level().map_or_else(
-            || self.display_span(show_realm),
-            |level| {
-                Span::from(format!("{} ({})", self.display_name(show_realm), level))
-                    .fg(self.class_colour())
-            },
-        )
-    }
 }
 
 // Character with meta data..
@@ -329,3 +662,95 @@ impl AsMut<Character> for CharacterWithIndex {
 
 /// Type alias for a character with its associated `WoW` installation.
 pub type CharacterWithInstall<'a> = (&'a Character, &'a WoWInstall);
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::widgets::test_harness::character;
+
+    #[test]
+    fn duplicate_names_across_accounts_are_suffixed_with_the_account() {
+        let mut characters = vec![
+            character("ACC1", "Draenor", "Alpha", &[], &[]),
+            character("ACC2", "Draenor", "Alpha", &[], &[]),
+            character("ACC1", "Silvermoon", "Alpha", &[], &[]),
+        ];
+        assert_eq!(mark_duplicate_characters(&mut characters), 2);
+        assert_eq!(characters[0].display_name(false), "Alpha [ACC1]");
+        assert_eq!(characters[1].display_name(false), "Alpha [ACC2]");
+        assert_eq!(characters[2].display_name(false), "Alpha");
+    }
+
+    #[test]
+    fn duplicate_names_in_fixture_accounts_are_distinct_characters() {
+        let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+        let install = crate::wow::locate_fixture_wow_installs(&fixtures, false)
+            .expect("Fixtures should be readable")
+            .into_iter()
+            .find(|install| install.branch_ident == "retail")
+            .expect("Fixtures should have a retail install");
+        let mut characters = install
+            .find_all_characters()
+            .expect("Fixture characters should be found")
+            .iter()
+            .filter(|character| character.name == "Zephyra")
+            .map(Character::new)
+            .sorted_by(|a, b| a.account().cmp(b.account()))
+            .collect::<Vec<_>>();
+        assert_eq!(mark_duplicate_characters(&mut characters), 2);
+
+        let accounts = ["12345678#1", "12345678#2"];
+        assert_eq!(characters.len(), accounts.len());
+        for (character, account) in characters.iter().zip(accounts) {
+            assert_eq!(character.account(), account);
+            assert_eq!(
+                character.display_name(false),
+                format!("Zephyra [{account}]")
+            );
+            assert!(
+                character
+                    .character
+                    .get_character_path(&install)
+                    .starts_with(install.get_account_path().join(account))
+            );
+            assert!(
+                character
+                    .character
+                    .get_backups_dir(&install, crate::wow::BackupLayout::default())
+                    .starts_with(install.get_character_backups_dir().join(account))
+            );
+        }
+    }
+
+    #[test]
+    fn selection_follows_files_by_name_across_a_refresh() {
+        let mut old = character(
+            "ACC",
+            "Draenor",
+            "Alpha",
+            &["bindings-cache.wtf", "config-cache.wtf"],
+            &["Details.lua", "WeakAuras.lua"],
+        );
+        old.toggle_config_file_selected(0);
+        old.toggle_config_file_selected(1);
+        old.toggle_addon_file_selected(1);
+
+        // `bindings-cache.wtf` and `Details.lua` were removed, while two files were added.
+        let mut refreshed = character(
+            "ACC",
+            "Draenor",
+            "Alpha",
+            &["config-cache.wtf", "macros-cache.txt"],
+            &["Bartender4.lua", "WeakAuras.lua"],
+        );
+        refreshed.merge_selection_from(&old);
+
+        assert!(refreshed.is_config_file_selected(0));
+        assert!(!refreshed.is_config_file_selected(1));
+        assert!(!refreshed.is_addon_file_selected(0));
+        assert!(refreshed.is_addon_file_selected(1));
+        assert_eq!(refreshed.total_selected_count(), 2);
+    }
+}
@@ -4,7 +4,7 @@ use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use crate::ui::Character;
 use crate::widgets::character_list::CharacterListWidget;
 use crate::widgets::file_list::{FileListConfig, FileListWidget};
-use crate::{ChronoBindAppConfig, InputMode};
+use crate::{ChronoBindAppConfig, InputMode, Pane};
 
 /// Manages the main UI drawing for the application.
 #[derive(Debug)]
@@ -38,9 +38,10 @@ impl MainCharacterUI {
         buf: &mut Buffer,
         characters: &[Character],
         input_mode: InputMode,
+        focused_pane: Pane,
         config: &ChronoBindAppConfig,
     ) {
-        self.main_screen(area, buf, characters, input_mode, config);
+        self.main_screen(area, buf, characters, input_mode, focused_pane, config);
     }
 
     /// Render the main screen UI.
@@ -50,6 +51,7 @@ impl MainCharacterUI {
         buf: &mut Buffer,
         characters: &[Character],
         input_mode: InputMode,
+        focused_pane: Pane,
         config: &ChronoBindAppConfig,
     ) {
         // Split the main screen into left and right panels
@@ -58,8 +60,17 @@ impl MainCharacterUI {
             .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
             .split(area);
 
-        self.character_list(chunks[0], buf, characters, config);
-        self.file_list(chunks[1], buf, characters, input_mode, config);
+        let characters_focused = focused_pane == Pane::Characters;
+        self.character_list(chunks[0], buf, characters, characters_focused, config);
+        let files_focused = focused_pane == Pane::Files;
+        self.file_list(
+            chunks[1],
+            buf,
+            characters,
+            input_mode,
+            files_focused,
+            config,
+        );
     }
 
     /// Render the character list panel.
@@ -68,10 +79,11 @@ impl MainCharacterUI {
         area: Rect,
         buf: &mut Buffer,
         characters: &[Character],
+        focused: bool,
         config: &ChronoBindAppConfig,
     ) {
         self.character_list_widget
-            .render(area, buf, characters, config);
+            .render(area, buf, characters, focused, config);
     }
 
     /// Render the file list panel.
@@ -81,6 +93,7 @@ impl MainCharacterUI {
         buf: &mut Buffer,
         characters: &[Character],
         input_mode: InputMode,
+        focused: bool,
         config: &ChronoBindAppConfig,
     ) {
         let char_index = self
@@ -90,6 +103,8 @@ impl MainCharacterUI {
         let show_highlight = input_mode == InputMode::FileSelection;
         let file_list_config = FileListConfig {
             show_friendly_names: config.show_friendly_names,
+            compact: config.compact_file_list,
+            group_addons: config.group_addon_files,
         };
 
         self.file_list_widget.render(
@@ -97,6 +112,7 @@ impl MainCharacterUI {
             buf,
             selected_character,
             show_highlight,
+            focused,
             &file_list_config,
         );
     }
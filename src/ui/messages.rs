@@ -1,14 +1,22 @@
 // Popup -> App communication..
 
+use std::path::PathBuf;
+
 use ratatui::text::{Line, Span, Text};
 
 use crate::{
+    config::ChronoBindAppConfig,
     popups::{
-        backup_manager_popup::BackupManagerPopupCommand, backup_popup::BackupPopupCommand,
-        branch_popup::BranchPopupCommand, export_manager_popup::ExportManagerMessage,
-        options_popup::OptionsPopupCommand, restore_popup::RestorePopupCommand,
+        alias_popup::AliasPopupCommand, backup_manager_popup::BackupManagerPopupCommand,
+        backup_popup::BackupPopupCommand, branch_popup::BranchPopupCommand,
+        export_manager_popup::ExportManagerMessage,
+        global_backup_manager_popup::GlobalBackupManagerPopupCommand,
+        options_popup::OptionsPopupCommand, realm_alias_popup::RealmAliasPopupCommand,
+        realm_summary_popup::RealmSummaryPopupCommand,
+        recent_characters_popup::RecentCharactersPopupCommand, restore_popup::RestorePopupCommand,
     },
     ui::character::{CharacterIndex, CharacterWithIndex},
+    widgets::toast::ToastLevel,
 };
 
 /// A message from a popup to the main application.
@@ -20,19 +28,40 @@ pub enum AppMessage {
     Backup(CharacterIndex, BackupPopupCommand),
     /// Commands from the restore from backup popup.
     Restore(CharacterIndex, RestorePopupCommand),
-    /// Paste from the copied character to the provided target character.
-    Paste(CharacterIndex),
+    /// Paste the given files, relative to the character directories, from the copied character
+    /// to the provided target character.
+    Paste(CharacterIndex, Vec<PathBuf>),
     /// Commands from the branch selection popup.
     Branch(BranchPopupCommand),
     /// Commands from the options popup.
     Options(OptionsPopupCommand),
     /// Commands from the backup manager popup.
     BackupManager(CharacterIndex, BackupManagerPopupCommand),
+    /// Commands from the global backup manager popup.
+    GlobalBackupManager(GlobalBackupManagerPopupCommand),
     /// Generic confirm action.
     /// Opens a confirmation popup for the given action.
-    ConfirmAction(Box<Self>, Option<ConfirmActionText>),
+    ConfirmAction(Box<Self>, Option<ConfirmActionText>, ConfirmRisk),
     /// Commands from the export manager popup.
     ExportManager(ExportManagerMessage),
+    /// Commands from the character alias popup.
+    Alias(CharacterIndex, AliasPopupCommand),
+    /// Commands from the realm alias popup, for the realm with the given folder name.
+    RealmAlias(String, RealmAliasPopupCommand),
+    /// Commands from the recent characters popup.
+    RecentCharacters(RecentCharactersPopupCommand),
+    /// Commands from the realm summary popup.
+    RealmSummary(RealmSummaryPopupCommand),
+    /// Launch the game of the given branch.
+    LaunchGame(String),
+    /// Show a transient notification, such as the summary of a completed task.
+    Toast(ToastLevel, String),
+    /// Cancel the currently running backend task.
+    CancelTask,
+    /// Release the character with the given operation key, once its task has ended.
+    ReleaseCharacter(String),
+    /// Quit the application, cancelling any running backend tasks first.
+    Quit,
 }
 
 impl AppMessage {
@@ -40,15 +69,51 @@ impl AppMessage {
     #[inline]
     #[must_use]
     pub fn with_confirm(self) -> Self {
-        Self::ConfirmAction(Box::new(self), None)
+        Self::ConfirmAction(Box::new(self), None, ConfirmRisk::Destructive)
     }
 
     /// Wrap the command in a confirmation action, and a custom line to display as the confirm action.
     #[inline]
     #[must_use]
     pub fn with_confirm_and_line(self, action_line: impl Into<ConfirmActionText>) -> Self {
-        Self::ConfirmAction(Box::new(self), Some(action_line.into()))
+        self.with_risk_confirm_and_line(ConfirmRisk::Destructive, action_line)
+    }
+
+    /// Wrap the command in a confirmation action of the given risk, with a custom line to display.
+    /// Whether the confirmation is shown depends on the configured confirmation level.
+    #[inline]
+    #[must_use]
+    pub fn with_risk_confirm_and_line(
+        self,
+        risk: ConfirmRisk,
+        action_line: impl Into<ConfirmActionText>,
+    ) -> Self {
+        Self::ConfirmAction(Box::new(self), Some(action_line.into()), risk)
     }
+
+    /// Wrap the command in two consecutive confirmation actions, the second displaying
+    /// the given warning line, for actions that should be especially hard to perform by accident.
+    #[inline]
+    #[must_use]
+    pub fn with_strong_confirm_and_line(
+        self,
+        action_line: impl Into<ConfirmActionText>,
+        warning_line: impl Into<ConfirmActionText>,
+    ) -> Self {
+        self.with_risk_confirm_and_line(ConfirmRisk::Critical, warning_line)
+            .with_confirm_and_line(action_line)
+    }
+}
+
+/// How risky a confirmed action is, deciding whether its confirmation can be skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ConfirmRisk {
+    /// Easily undone actions, such as pinning a backup.
+    Low,
+    /// Actions that delete or overwrite files.
+    Destructive,
+    /// Actions that are always confirmed, regardless of the confirmation level.
+    Critical,
 }
 
 // App to Popup communication..
@@ -57,7 +122,13 @@ impl AppMessage {
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PopupMessage {
     /// Command to update the characters data for the popup.
-    UpdateCharacter(CharacterWithIndex),
+    UpdateCharacter(Box<CharacterWithIndex>),
+    /// Command to update the data of every character for the popup.
+    UpdateCharacters(Vec<CharacterWithIndex>),
+    /// Command to update the application configuration shown by the popup.
+    UpdateConfiguration(Box<ChronoBindAppConfig>),
+    /// Command to cancel the task tracked by the popup.
+    CancelTask,
 }
 
 // Confirm action text wrapper.
@@ -1,8 +1,11 @@
 pub mod character;
 pub mod main_character_ui;
 pub mod messages;
+pub mod recent_characters;
 
-pub use character::{Character, CharacterIndex, CharacterWithIndex, CharacterWithInstall};
+pub use character::{
+    AddonGroup, Character, CharacterIndex, CharacterWithIndex, CharacterWithInstall,
+};
 use ratatui::crossterm::event::{KeyCode, KeyEvent};
 
 /// Convert a `KeyCode` to its lowercase equivalent if it's a character.
@@ -0,0 +1,109 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ui::{Character, CharacterIndex};
+
+/// Maximum number of recently used characters remembered.
+pub const MAX_RECENT_CHARACTERS: usize = 8;
+
+/// A recently used character, identified independently of its index in the character list.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct RecentCharacter {
+    /// The branch identifier of the `WoW` installation the character belongs to.
+    pub branch: String,
+    /// The root path of the `WoW` installation the character was found in.
+    pub install_path: String,
+    /// The account name associated with the character.
+    pub account: String,
+    /// The realm the character belongs to.
+    pub realm: String,
+    /// The name of the character.
+    pub name: String,
+}
+
+impl RecentCharacter {
+    #[must_use]
+    pub fn new(character: &Character) -> Self {
+        Self {
+            branch: character.branch().to_string(),
+            install_path: character.character.install_path.clone(),
+            account: character.account().to_string(),
+            realm: character.realm().to_string(),
+            name: character.name().to_string(),
+        }
+    }
+
+    /// Returns `true` if this entry refers to the given character.
+    #[must_use]
+    pub fn matches(&self, character: &Character) -> bool {
+        self.branch == character.branch()
+            && self.install_path == character.character.install_path
+            && self.account == character.account()
+            && self.realm == character.realm()
+            && self.name == character.name()
+    }
+}
+
+/// The most recently used characters across all branches, most recent first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecentCharacters {
+    /// The remembered characters, most recent first.
+    entries: VecDeque<RecentCharacter>,
+}
+
+impl RecentCharacters {
+    /// Create the list from previously remembered entries, most recent first.
+    #[must_use]
+    pub fn from_entries(entries: impl IntoIterator<Item = RecentCharacter>) -> Self {
+        Self {
+            entries: entries.into_iter().take(MAX_RECENT_CHARACTERS).collect(),
+        }
+    }
+
+    /// Get the remembered entries, most recent first.
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<RecentCharacter> {
+        self.entries.iter().cloned().collect()
+    }
+
+    /// Move the given character to the front of the list, dropping the oldest entry if full.
+    /// Returns `true` if the list changed.
+    pub fn touch(&mut self, character: &Character) -> bool {
+        if self
+            .entries
+            .front()
+            .is_some_and(|entry| entry.matches(character))
+        {
+            return false;
+        }
+        self.entries.retain(|entry| !entry.matches(character));
+        self.entries.push_front(RecentCharacter::new(character));
+        self.entries.truncate(MAX_RECENT_CHARACTERS);
+        true
+    }
+
+    /// Remove entries of the given branch which are not among its loaded characters,
+    /// returning the number of entries removed.
+    pub fn prune_branch(&mut self, branch: &str, characters: &[Character]) -> usize {
+        let previous_len = self.entries.len();
+        self.entries.retain(|entry| {
+            entry.branch != branch || characters.iter().any(|character| entry.matches(character))
+        });
+        previous_len - self.entries.len()
+    }
+
+    /// Get the indices of the recently used characters among the loaded characters,
+    /// most recent first.
+    #[must_use]
+    pub fn character_indices(&self, characters: &[Character]) -> Vec<CharacterIndex> {
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                characters
+                    .iter()
+                    .position(|character| entry.matches(character))
+            })
+            .collect()
+    }
+}
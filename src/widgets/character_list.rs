@@ -1,15 +1,18 @@
 use std::collections::{BTreeMap, BTreeSet};
+use std::time::{Duration, Instant};
 
 use ratatui::buffer::Buffer;
-use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::Rect;
 use ratatui::style::{Style, Stylize};
 use ratatui::symbols::border;
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, List, ListDirection, ListItem, ListState};
+use ratatui::widgets::{Block, List, ListDirection, ListItem, ListState, Paragraph, Widget, Wrap};
+use serde::{Deserialize, Serialize};
 
 use crate::config::ChronoBindAppConfig;
 use crate::ui::{Character, KeyCodeExt};
+use crate::wow::realm_display_name;
 
 #[allow(clippy::wildcard_imports)]
 use crate::palette::*;
@@ -19,13 +22,286 @@ use crate::popups::list_with_scrollbar;
 #[derive(Debug, Clone)]
 pub enum CharacterListItemKind {
     Character(usize),
+    /// The only character of a realm, shown in place of the realm header.
+    InlineCharacter(usize),
     RealmHeader {
         realm_ident: String,
+        /// The account of the group, set when the realm has characters on more than one
+        /// account, in which case each account's characters are grouped separately.
+        account: Option<String>,
         collapsed: bool,
         count: usize,
     },
 }
 
+/// Get the key identifying a realm group, for tracking whether it is collapsed.
+#[inline]
+#[must_use]
+fn realm_group_key(realm: &str, account: Option<&str>) -> String {
+    account.map_or_else(|| realm.to_string(), |account| format!("{realm}#{account}"))
+}
+
+/// How realms holding a single character are displayed in the character list.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum SingleCharacterRealms {
+    /// Shown like any other realm, with a collapsible header.
+    #[default]
+    Collapsible,
+    /// Shown with a header, but always expanded.
+    Expanded,
+    /// Shown as the character alone, with the realm alongside its name.
+    Inline,
+}
+
+impl SingleCharacterRealms {
+    /// Every display mode, in cycling order.
+    pub const ALL: [Self; 3] = [Self::Collapsible, Self::Expanded, Self::Inline];
+
+    /// Get the display name of the display mode.
+    #[inline]
+    #[must_use]
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::Collapsible => "Collapsible",
+            Self::Expanded => "Always expanded",
+            Self::Inline => "Inline, without header",
+        }
+    }
+
+    /// Get the next display mode, wrapping around.
+    #[inline]
+    #[must_use]
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|m| *m == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    /// Get the previous display mode, wrapping around.
+    #[inline]
+    #[must_use]
+    pub fn previous(self) -> Self {
+        let idx = Self::ALL.iter().position(|m| *m == self).unwrap_or(0);
+        Self::ALL[(idx + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+/// Why the character list is empty once the scan for characters has finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EmptyListReason {
+    /// No `WoW` installations were found, such as when Battle.net hasn't finished starting.
+    NoInstalls,
+    /// The branch has no `WTF/Account` directory, as the game has never been logged into on it.
+    NoAccountDir,
+    /// The characters of the branch could not be read.
+    ScanFailed,
+    /// The branch has no characters.
+    NoCharacters,
+}
+
+impl EmptyListReason {
+    /// Get the message shown in place of the character list.
+    #[inline]
+    #[must_use]
+    pub const fn message(self) -> &'static str {
+        match self {
+            Self::NoInstalls => {
+                "No WoW installations found, is Battle.net running? Press R to retry detection"
+            }
+            Self::NoAccountDir => {
+                "No WTF/Account directory found, have you logged into the game at least once?"
+            }
+            Self::ScanFailed => "Failed to read the characters, see the log for details",
+            Self::NoCharacters => "No characters found",
+        }
+    }
+}
+
+/// What activating a character in the character list does.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum ActivateAction {
+    /// Enter file selection for the character.
+    #[default]
+    FileSelection,
+    /// Open the backup popup for the character.
+    BackupMenu,
+    /// Show the details of the character.
+    DetailView,
+}
+
+impl ActivateAction {
+    /// Every action, in cycling order.
+    pub const ALL: [Self; 3] = [Self::FileSelection, Self::BackupMenu, Self::DetailView];
+
+    /// Get the display name of the action.
+    #[inline]
+    #[must_use]
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::FileSelection => "Select files",
+            Self::BackupMenu => "Open backups",
+            Self::DetailView => "Show details",
+        }
+    }
+
+    /// Get the next action, wrapping around.
+    #[inline]
+    #[must_use]
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|a| *a == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    /// Get the previous action, wrapping around.
+    #[inline]
+    #[must_use]
+    pub fn previous(self) -> Self {
+        let idx = Self::ALL.iter().position(|a| *a == self).unwrap_or(0);
+        Self::ALL[(idx + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+/// Optional columns which can be displayed alongside character names in the character list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum CharacterListColumn {
+    /// The level of the character, if known.
+    Level,
+    /// The class of the character.
+    Class,
+    /// The realm of the character.
+    Realm,
+    /// The account the character belongs to.
+    Account,
+    /// The time of the most recent backup of the character.
+    LastBackup,
+}
+
+impl CharacterListColumn {
+    /// All columns, in display order. When space is short, later columns are dropped first.
+    pub const ALL: [Self; 5] = [
+        Self::Level,
+        Self::Class,
+        Self::Realm,
+        Self::Account,
+        Self::LastBackup,
+    ];
+
+    /// Maximum width of any column, longer content is truncated.
+    const MAX_WIDTH: usize = 20;
+
+    /// Get the columns shown by default.
+    #[must_use]
+    pub fn default_columns() -> BTreeSet<Self> {
+        BTreeSet::from([Self::Level])
+    }
+
+    /// Get the display title for the column.
+    #[inline]
+    #[must_use]
+    pub const fn title(self) -> &'static str {
+        match self {
+            Self::Level => "Level",
+            Self::Class => "Class",
+            Self::Realm => "Realm",
+            Self::Account => "Account",
+            Self::LastBackup => "Last backup",
+        }
+    }
+
+    /// Get the text content of the column for the given character.
+    #[must_use]
+    pub fn content(self, character: &Character) -> String {
+        let content = match self {
+            Self::Level => character
+                .level()
+                .map_or_else(|| "-".to_string(), |level| level.to_string()),
+            Self::Class => character.class().class_name().into_owned(),
+            Self::Realm => character.display_realm(),
+            Self::Account => character.account().to_string(),
+            Self::LastBackup => character
+                .backups()
+                .iter()
+                .map(|b| b.timestamp)
+                .max()
+                .map_or_else(|| "Never".to_string(), |ts| display_backup_time(&ts)),
+        };
+        crate::ui::truncate_with_ellipsis(content, Self::MAX_WIDTH)
+    }
+
+    /// Get the styled span of the column for the given character, padded to `width`.
+    #[must_use]
+    pub fn cell(self, character: &Character, width: usize) -> Span<'static> {
+        let span = Span::from(format!("{:<width$}", self.content(character)));
+        match self {
+            Self::Level | Self::Class => span.fg(character.class_colour()),
+            Self::Realm | Self::Account | Self::LastBackup => span.dim(),
+        }
+    }
+
+    /// Get the width needed to display the column for all of the given characters.
+    #[must_use]
+    pub fn width(self, characters: &[Character]) -> usize {
+        characters
+            .iter()
+            .map(|c| self.content(c).chars().count())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Time after the last type-ahead key press before the typed buffer is reset.
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Characters typed to jump to a realm or character, reset after a short timeout.
+#[derive(Debug, Clone, Default)]
+pub struct TypeAhead {
+    /// The characters typed so far.
+    buffer: String,
+    /// When the last character was typed.
+    last_input: Option<Instant>,
+}
+
+impl TypeAhead {
+    /// Returns `true` if a character was typed recently enough to extend the buffer.
+    #[inline]
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        !self.buffer.is_empty()
+            && self
+                .last_input
+                .is_some_and(|last| last.elapsed() < TYPE_AHEAD_TIMEOUT)
+    }
+
+    /// Append a typed character, starting a new buffer if the previous one timed out.
+    fn push(&mut self, c: char) {
+        if !self.is_active() {
+            self.buffer.clear();
+        }
+        self.buffer.extend(c.to_lowercase());
+        self.last_input = Some(Instant::now());
+    }
+
+    /// Clear the typed buffer.
+    fn clear(&mut self) {
+        self.buffer.clear();
+        self.last_input = None;
+    }
+
+    /// Returns `true` if the given name starts with the typed buffer, ignoring case,
+    /// spaces and punctuation.
+    fn matches(&self, name: &str) -> bool {
+        let normalised = name
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect::<String>();
+        normalised.starts_with(&self.buffer)
+    }
+}
+
 /// The character list widget displays the characters grouped by realm with collapsible headers.
 #[derive(Debug, Clone)]
 pub struct CharacterListWidget {
@@ -33,8 +309,26 @@ pub struct CharacterListWidget {
     pub branch_display: Option<String>,
     /// The list state for tracking selection
     pub state: ListState,
-    /// Set of collapsed realm names
+    /// Set of collapsed realm groups, keyed by realm name, and account if the realm spans accounts.
     pub collapsed_realms: BTreeSet<String>,
+    /// Type-ahead state for jumping to a realm or character by name.
+    pub type_ahead: TypeAhead,
+    /// How realms holding a single character are displayed.
+    pub single_character_realms: SingleCharacterRealms,
+    /// What activating a character does.
+    pub activate_action: ActivateAction,
+    /// Favorite characters, keyed by `account/realm/name`.
+    pub favorite_characters: Vec<String>,
+    /// Characters skipped by bulk operations, keyed by `account/realm/name`, listed dimmed.
+    pub excluded_characters: Vec<String>,
+    /// Whether favorite characters, and the realms holding them, are listed first.
+    pub favorites_first: bool,
+    /// Names realms are displayed with, keyed by the realm's folder name.
+    pub realm_aliases: BTreeMap<String, String>,
+    /// Whether the characters are still being scanned for.
+    pub scanning: bool,
+    /// Why the list is empty, once the scan for characters has finished without finding any.
+    pub empty_reason: Option<EmptyListReason>,
 }
 
 impl Default for CharacterListWidget {
@@ -51,6 +345,15 @@ impl CharacterListWidget {
             branch_display: None,
             state: ListState::default(),
             collapsed_realms: BTreeSet::new(),
+            type_ahead: TypeAhead::default(),
+            single_character_realms: SingleCharacterRealms::default(),
+            activate_action: ActivateAction::default(),
+            favorite_characters: Vec::new(),
+            excluded_characters: Vec::new(),
+            favorites_first: false,
+            realm_aliases: BTreeMap::new(),
+            scanning: false,
+            empty_reason: None,
         }
     }
 
@@ -60,37 +363,92 @@ impl CharacterListWidget {
         self.state.selected().unwrap_or(0)
     }
 
-    /// Generate the list of character list items with realm grouping
+    /// Returns `true` if the character is a favorite.
+    #[inline]
+    #[must_use]
+    pub fn is_favorite(&self, character: &Character) -> bool {
+        self.favorite_characters.contains(&character.favorite_key())
+    }
+
+    /// Returns `true` if the character is skipped by bulk operations.
+    #[inline]
+    #[must_use]
+    pub fn is_excluded(&self, character: &Character) -> bool {
+        self.excluded_characters
+            .contains(&character.character.settings_key())
+    }
+
+    /// Generate the list of character list items with realm grouping.
+    /// A realm with characters on more than one account is split into a group per account,
+    /// so characters from different accounts are never mixed under one header.
+    /// If favorites are listed first, realms holding favorites come first, and favorites
+    /// come first within their realm.
     #[inline]
     #[must_use]
     pub fn get_character_list_items(&self, characters: &[Character]) -> Vec<CharacterListItemKind> {
-        let mut realms: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        let mut realms: BTreeMap<&str, BTreeMap<&str, Vec<usize>>> = BTreeMap::new();
         for (i, character) in characters.iter().enumerate() {
             realms
-                .entry(character.realm().to_string())
+                .entry(character.realm())
+                .or_default()
+                .entry(character.account())
                 .or_default()
                 .push(i);
         }
 
-        let mut items = Vec::with_capacity(characters.len() + realms.len());
-        for (realm, char_indices) in &realms {
-            let collapsed = self.collapsed_realms.contains(realm);
-            let count = char_indices.len();
-
-            // Add realm header
-            items.push(CharacterListItemKind::RealmHeader {
-                realm_ident: realm.clone(),
-                collapsed,
-                count,
+        let is_favorite = |char_idx: usize| self.is_favorite(&characters[char_idx]);
+        let mut realms = realms.into_iter().collect::<Vec<_>>();
+        if self.favorites_first {
+            realms.sort_by_key(|(_, accounts)| {
+                !accounts
+                    .values()
+                    .flatten()
+                    .any(|&char_idx| is_favorite(char_idx))
             });
+        }
+
+        let mut items = Vec::with_capacity(characters.len() + realms.len());
+        for (realm, accounts) in &realms {
+            let groups: Vec<(Option<&str>, Vec<usize>)> = if accounts.len() > 1 {
+                accounts
+                    .iter()
+                    .map(|(account, indices)| (Some(*account), indices.clone()))
+                    .collect()
+            } else {
+                vec![(None, accounts.values().flatten().copied().collect())]
+            };
 
-            if !collapsed {
-                items.extend_from_slice(
-                    &char_indices
-                        .iter()
-                        .map(|c| CharacterListItemKind::Character(*c))
-                        .collect::<Vec<_>>(),
-                );
+            for (account, mut char_indices) in groups {
+                if self.favorites_first {
+                    char_indices.sort_by_key(|&char_idx| !is_favorite(char_idx));
+                }
+                let single = char_indices.len() == 1;
+                if single && self.single_character_realms == SingleCharacterRealms::Inline {
+                    items.push(CharacterListItemKind::InlineCharacter(char_indices[0]));
+                    continue;
+                }
+
+                let collapsed = !(single
+                    && self.single_character_realms == SingleCharacterRealms::Expanded)
+                    && self
+                        .collapsed_realms
+                        .contains(&realm_group_key(realm, account));
+
+                // Add realm header
+                items.push(CharacterListItemKind::RealmHeader {
+                    realm_ident: (*realm).to_string(),
+                    account: account.map(str::to_string),
+                    collapsed,
+                    count: char_indices.len(),
+                });
+
+                if !collapsed {
+                    items.extend(
+                        char_indices
+                            .into_iter()
+                            .map(CharacterListItemKind::Character),
+                    );
+                }
             }
         }
 
@@ -112,11 +470,202 @@ impl CharacterListWidget {
     ) -> Option<usize> {
         let selected_index = self.selected_index();
         match item_list.get(selected_index) {
-            Some(CharacterListItemKind::Character(char_idx)) => Some(*char_idx),
+            Some(
+                CharacterListItemKind::Character(char_idx)
+                | CharacterListItemKind::InlineCharacter(char_idx),
+            ) => Some(*char_idx),
             _ => None,
         }
     }
 
+    /// Select the given character in the list, expanding its realm group if collapsed.
+    pub fn select_character(&mut self, characters: &[Character], char_idx: usize) {
+        let Some(character) = characters.get(char_idx) else {
+            return;
+        };
+        self.collapsed_realms
+            .remove(&realm_group_key(character.realm(), None));
+        self.collapsed_realms.remove(&realm_group_key(
+            character.realm(),
+            Some(character.account()),
+        ));
+
+        let position = self
+            .get_character_list_items(characters)
+            .iter()
+            .position(|item| {
+                matches!(
+                    item,
+                    CharacterListItemKind::Character(idx)
+                    | CharacterListItemKind::InlineCharacter(idx) if *idx == char_idx
+                )
+            });
+        if position.is_some() {
+            self.state.select(position);
+        }
+    }
+
+    /// Collapse or expand every realm group with a collapsible header at once.
+    /// The selection stays on the selected realm or character, or moves to its realm's header if
+    /// the character is hidden by collapsing it.
+    pub fn set_all_realms_collapsed(&mut self, characters: &[Character], collapsed: bool) {
+        let previous_items = self.get_character_list_items(characters);
+        let selected_index = self
+            .selected_index()
+            .min(previous_items.len().saturating_sub(1));
+        // Characters are listed directly below their realm's header.
+        let selected_group = previous_items
+            .get(..=selected_index)
+            .unwrap_or_default()
+            .iter()
+            .rev()
+            .find_map(|item| match item {
+                CharacterListItemKind::RealmHeader {
+                    realm_ident,
+                    account,
+                    ..
+                } => Some(realm_group_key(realm_ident, account.as_deref())),
+                _ => None,
+            });
+
+        if collapsed {
+            let keys = previous_items
+                .iter()
+                .filter_map(|item| match item {
+                    CharacterListItemKind::RealmHeader {
+                        realm_ident,
+                        account,
+                        count,
+                        ..
+                    } if *count > 1
+                        || self.single_character_realms != SingleCharacterRealms::Expanded =>
+                    {
+                        Some(realm_group_key(realm_ident, account.as_deref()))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            self.collapsed_realms.extend(keys);
+            log::debug!("Collapsed all realms");
+        } else {
+            self.collapsed_realms.clear();
+            log::debug!("Expanded all realms");
+        }
+
+        self.reselect_after_update(&previous_items, characters);
+        let Some(CharacterListItemKind::Character(char_idx)) = previous_items.get(selected_index)
+        else {
+            return;
+        };
+        let items = self.get_character_list_items(characters);
+        let still_listed = items
+            .iter()
+            .any(|item| matches!(item, CharacterListItemKind::Character(idx) if idx == char_idx));
+        if !still_listed {
+            let header = items.iter().position(|item| match item {
+                CharacterListItemKind::RealmHeader {
+                    realm_ident,
+                    account,
+                    ..
+                } => {
+                    selected_group.as_deref()
+                        == Some(realm_group_key(realm_ident, account.as_deref()).as_str())
+                }
+                _ => false,
+            });
+            if header.is_some() {
+                self.state.select(header);
+            }
+        }
+    }
+
+    /// Re-select the realm or character which was selected before characters were added, as added
+    /// characters can shift the items in the list. Keeps the selection within the list otherwise.
+    pub fn reselect_after_update(
+        &mut self,
+        previous_items: &[CharacterListItemKind],
+        characters: &[Character],
+    ) {
+        let items = self.get_character_list_items(characters);
+        let position = previous_items
+            .get(self.selected_index())
+            .and_then(|selected| {
+                items.iter().position(|item| match (selected, item) {
+                    (
+                        CharacterListItemKind::Character(previous)
+                        | CharacterListItemKind::InlineCharacter(previous),
+                        CharacterListItemKind::Character(idx)
+                        | CharacterListItemKind::InlineCharacter(idx),
+                    ) => previous == idx,
+                    (
+                        CharacterListItemKind::RealmHeader {
+                            realm_ident: previous_realm,
+                            ..
+                        },
+                        CharacterListItemKind::RealmHeader { realm_ident, .. },
+                    ) => previous_realm == realm_ident,
+                    _ => false,
+                })
+            });
+        let fallback = self.selected_index().min(items.len().saturating_sub(1));
+        self.state.select(Some(position.unwrap_or(fallback)));
+    }
+
+    /// Get the current type-ahead buffer, if characters were typed recently.
+    #[inline]
+    #[must_use]
+    pub fn type_ahead_buffer(&self) -> Option<&str> {
+        self.type_ahead
+            .is_active()
+            .then_some(self.type_ahead.buffer.as_str())
+    }
+
+    /// Returns `true` if the key should be consumed by type-ahead rather than treated as a shortcut.
+    /// Type-ahead starts with an uppercase letter, after which any letter or digit extends it
+    /// until the timeout passes.
+    #[must_use]
+    pub fn is_type_ahead_key(&self, key: &KeyEvent) -> bool {
+        if key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        {
+            return false;
+        }
+        match key.code {
+            KeyCode::Char(c) if c.is_uppercase() => true,
+            KeyCode::Char(c) => self.type_ahead.is_active() && c.is_alphanumeric(),
+            _ => false,
+        }
+    }
+
+    /// Add the key to the type-ahead buffer, and select the first realm or character matching it.
+    fn handle_type_ahead(
+        &mut self,
+        key: &KeyEvent,
+        item_list: &[CharacterListItemKind],
+        characters: &[Character],
+    ) {
+        let KeyCode::Char(c) = key.code else {
+            return;
+        };
+        self.type_ahead.push(c);
+
+        let found = item_list.iter().position(|item| match item {
+            CharacterListItemKind::RealmHeader { realm_ident, .. } => self
+                .type_ahead
+                .matches(&realm_display_name(&self.realm_aliases, realm_ident)),
+            CharacterListItemKind::Character(char_idx)
+            | CharacterListItemKind::InlineCharacter(char_idx) => characters
+                .get(*char_idx)
+                .is_some_and(|character| self.type_ahead.matches(character.name())),
+        });
+        if let Some(index) = found {
+            self.state.select(Some(index));
+        } else {
+            log::debug!("No realm or character matches `{}`", self.type_ahead.buffer);
+        }
+    }
+
     /// Handle input for the character list in navigation mode
     /// Returns true if the input mode should change to file selection
     pub fn handle_navigation_input(
@@ -126,6 +675,12 @@ impl CharacterListWidget {
     ) -> NavigationAction {
         let item_list = self.get_character_list_items(characters);
 
+        if self.is_type_ahead_key(key) {
+            self.handle_type_ahead(key, &item_list, characters);
+            return NavigationAction::None;
+        }
+        self.type_ahead.clear();
+
         match key.keycode_lower() {
             KeyCode::Up | KeyCode::Char('w') => {
                 self.state.select_previous();
@@ -137,32 +692,45 @@ impl CharacterListWidget {
             }
             KeyCode::Enter | KeyCode::Char(' ' | 'd') | KeyCode::Right => {
                 match item_list.get(self.selected_index()) {
-                    Some(CharacterListItemKind::RealmHeader { realm_ident, .. }) => {
+                    Some(CharacterListItemKind::RealmHeader {
+                        realm_ident,
+                        account,
+                        count,
+                        ..
+                    }) => {
+                        if *count == 1
+                            && self.single_character_realms == SingleCharacterRealms::Expanded
+                        {
+                            return NavigationAction::None;
+                        }
                         // Toggle realm collapse
-                        if self.collapsed_realms.contains(realm_ident) {
-                            self.collapsed_realms.remove(realm_ident);
-                        } else {
-                            self.collapsed_realms.insert(realm_ident.clone());
+                        let key = realm_group_key(realm_ident, account.as_deref());
+                        if !self.collapsed_realms.remove(&key) {
+                            self.collapsed_realms.insert(key);
                         }
                         NavigationAction::None
                     }
-                    Some(CharacterListItemKind::Character(_)) => {
-                        // Character selected, enter file selection
-                        log::debug!("Entered file selection mode");
-                        NavigationAction::EnterFileSelection
-                    }
+                    Some(
+                        CharacterListItemKind::Character(char_idx)
+                        | CharacterListItemKind::InlineCharacter(char_idx),
+                    ) => match self.activate_action {
+                        ActivateAction::FileSelection => {
+                            log::debug!("Entered file selection mode");
+                            NavigationAction::EnterFileSelection
+                        }
+                        ActivateAction::BackupMenu => NavigationAction::ShowBackup(*char_idx),
+                        ActivateAction::DetailView => NavigationAction::ShowInfo(*char_idx),
+                    },
                     None => NavigationAction::None,
                 }
             }
-            KeyCode::Char('b') => {
-                if let Some(CharacterListItemKind::Character(char_idx)) =
-                    item_list.get(self.selected_index())
-                {
-                    NavigationAction::ShowBackup(*char_idx)
-                } else {
-                    NavigationAction::None
-                }
+            KeyCode::Char(c @ ('[' | ']')) => {
+                self.set_all_realms_collapsed(characters, c == '[');
+                NavigationAction::None
             }
+            KeyCode::Char('b') => self
+                .get_selected_character_index(&item_list)
+                .map_or(NavigationAction::None, NavigationAction::ShowBackup),
             KeyCode::Char('c') => self
                 .get_selected_character_index(&item_list)
                 .map_or(NavigationAction::None, |char_idx| {
@@ -173,16 +741,99 @@ impl CharacterListWidget {
                 .map_or(NavigationAction::None, |target_char_idx| {
                     NavigationAction::Paste(target_char_idx)
                 }),
+            KeyCode::Char('i') => match item_list.get(self.selected_index()) {
+                Some(CharacterListItemKind::RealmHeader {
+                    realm_ident,
+                    account,
+                    ..
+                }) => NavigationAction::ShowRealmInfo(realm_ident.clone(), account.clone()),
+                Some(
+                    CharacterListItemKind::Character(char_idx)
+                    | CharacterListItemKind::InlineCharacter(char_idx),
+                ) => NavigationAction::ShowInfo(*char_idx),
+                None => NavigationAction::None,
+            },
+            KeyCode::Char('f') => self
+                .get_selected_character_index(&item_list)
+                .map_or(NavigationAction::None, NavigationAction::ToggleFavorite),
+            KeyCode::Char('x') => self
+                .get_selected_character_index(&item_list)
+                .map_or(NavigationAction::None, NavigationAction::ToggleExcluded),
+            KeyCode::Char('n') => match item_list.get(self.selected_index()) {
+                Some(CharacterListItemKind::RealmHeader { realm_ident, .. }) => {
+                    NavigationAction::SetRealmAlias(realm_ident.clone())
+                }
+                Some(
+                    CharacterListItemKind::Character(char_idx)
+                    | CharacterListItemKind::InlineCharacter(char_idx),
+                ) => characters
+                    .get(*char_idx)
+                    .map_or(NavigationAction::None, |character| {
+                        NavigationAction::SetRealmAlias(character.realm().to_string())
+                    }),
+                None => NavigationAction::None,
+            },
             _ => NavigationAction::None,
         }
     }
 
+    /// Get the message, and its style, shown in place of an empty list while scanning
+    /// or once the scan has found no characters.
+    fn placeholder(&self) -> Option<(&'static str, Style)> {
+        if self.scanning {
+            return Some(("Scanning for characters...", Style::new().dim()));
+        }
+        self.empty_reason.map(|reason| {
+            let style = if reason == EmptyListReason::NoCharacters {
+                Style::new().dim()
+            } else {
+                Style::new().fg(PALETTE.log_warn_fg)
+            };
+            (reason.message(), style)
+        })
+    }
+
+    /// Create the span preceding a character's name, with its highlight, favorite star and
+    /// a marker if any of its files are selected.
+    fn character_prefix(
+        &self,
+        character: &Character,
+        hovered: bool,
+        indent: usize,
+    ) -> Span<'static> {
+        let favorite = if self.is_favorite(character) {
+            format!("{FAVORITE_SYMBOL} ")
+        } else {
+            String::new()
+        };
+        let text = format!(
+            "{}{}{favorite}",
+            indentation(indent),
+            highlight_symbol(hovered)
+        );
+        if character.any_file_selected() {
+            Span::from(format!("{text}• ")).fg(PALETTE.selected_fg)
+        } else {
+            Span::from(text)
+        }
+    }
+
+    /// Get the style of a character's row, dimmed if the character is excluded from bulk operations.
+    fn row_style(&self, character: &Character) -> Style {
+        if self.is_excluded(character) {
+            Style::new().dim()
+        } else {
+            Style::new()
+        }
+    }
+
     /// Render the character list widget
     pub fn render(
         &mut self,
         area: Rect,
         buf: &mut Buffer,
         characters: &[Character],
+        focused: bool,
         config: &ChronoBindAppConfig,
     ) {
         const PADDING: usize = 1;
@@ -192,11 +843,39 @@ impl CharacterListWidget {
             || " Characters ".to_string(),
             |branch| format!(" Characters - {branch} "),
         );
+        let mut title = Line::from(title_content).bold();
+        if self.scanning {
+            title.push_span(Span::from(format!("Scanning... ({}) ", characters.len())).dim());
+        }
         let block = Block::bordered()
-            .title(Line::from(title_content).bold())
-            .border_set(border::THICK);
+            .title(title)
+            .border_set(border::THICK)
+            .border_style(Style::new().fg(PALETTE.selection_fg(focused)));
 
         let char_list_items = self.get_character_list_items(characters);
+        if char_list_items.is_empty()
+            && let Some((message, style)) = self.placeholder()
+        {
+            Paragraph::new(format!("{}{message}", indentation(PADDING)))
+                .style(style)
+                .wrap(Wrap { trim: false })
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+
+        // Widest prefix possible; a hovered favorite character with files selected.
+        let max_prefix_width = PADDING
+            + INDENT
+            + highlight_symbol(true).chars().count()
+            + FAVORITE_SYMBOL.chars().count()
+            + 3;
+        let name_width = name_column_width(&char_list_items, characters, INDENT);
+        let columns = fit_columns(
+            &config.character_list_columns,
+            characters,
+            (area.width as usize).saturating_sub(2 + max_prefix_width + name_width),
+        );
 
         let selected_index = self.selected_index();
         let items = char_list_items
@@ -207,39 +886,53 @@ impl CharacterListWidget {
                 match li {
                     CharacterListItemKind::RealmHeader {
                         realm_ident,
+                        account,
                         collapsed,
                         ..
                     } => {
+                        let account_suffix = account
+                            .as_ref()
+                            .map_or_else(String::new, |account| format!(" - {account}"));
                         let content = format!(
-                            "{pad}{} {}[{realm_ident}]",
+                            "{pad}{} {}[{}{account_suffix}]",
                             expandable_icon(*collapsed),
                             highlight_symbol(hovered),
+                            realm_display_name(&self.realm_aliases, realm_ident),
                             pad = indentation(PADDING)
                         );
                         ListItem::new(content).bold().fg(PALETTE.std_fg).dim()
                     }
-                    CharacterListItemKind::Character(char_idx) => {
+                    CharacterListItemKind::Character(char_idx)
+                    | CharacterListItemKind::InlineCharacter(char_idx) => {
                         let character = &characters[*char_idx];
-                        let files_selected = character.any_file_selected();
-
-                        let ui_span_text = format!(
-                            "{pad}{}",
-                            highlight_symbol(hovered),
-                            pad = indentation(PADDING + INDENT)
+                        let inline = matches!(li, CharacterListItemKind::InlineCharacter(_));
+                        let ui_span_source = self.character_prefix(
+                            character,
+                            hovered,
+                            if inline { PADDING } else { PADDING + INDENT },
                         );
-                        let ui_span_source = if files_selected {
-                            Span::from(format!("{ui_span_text}• ")).fg(PALETTE.selected_fg)
-                        } else {
-                            Span::from(ui_span_text)
-                        };
-
-                        let main_span = if config.display_character_levels {
-                            character.display_span_with_meta(false)
-                        } else {
-                            character.display_span(false)
-                        };
-
-                        ListItem::new(Line::from(vec![ui_span_source, main_span]))
+
+                        // Pad the name by the prefix difference so columns line up on every row.
+                        let padded_name_width =
+                            name_width + max_prefix_width - ui_span_source.content.chars().count();
+                        let mut spans = vec![
+                            ui_span_source,
+                            Span::from(format!(
+                                "{:<padded_name_width$}",
+                                character.display_name(inline)
+                            ))
+                            .fg(character.class_colour()),
+                        ];
+                        for (column, width) in &columns {
+                            spans.push(Span::from(" "));
+                            spans.push(column.cell(character, *width));
+                        }
+                        spans.extend(backup_warning_span(
+                            character,
+                            config.backup_warning_threshold,
+                        ));
+
+                        ListItem::new(Line::from(spans)).style(self.row_style(character))
                     }
                 }
             })
@@ -256,8 +949,67 @@ impl CharacterListWidget {
     }
 }
 
+/// Get the width of the widest character name in the list.
+/// Inline characters show their realm too, in the space saved by having no indent.
+fn name_column_width(
+    items: &[CharacterListItemKind],
+    characters: &[Character],
+    indent: usize,
+) -> usize {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            CharacterListItemKind::Character(char_idx) => {
+                Some(characters[*char_idx].display_name(false).chars().count())
+            }
+            CharacterListItemKind::InlineCharacter(char_idx) => Some(
+                characters[*char_idx]
+                    .display_name(true)
+                    .chars()
+                    .count()
+                    .saturating_sub(indent),
+            ),
+            CharacterListItemKind::RealmHeader { .. } => None,
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Create a span flagging a character with more backups than the warning threshold, if it has.
+fn backup_warning_span(character: &Character, threshold: Option<usize>) -> Option<Span<'static>> {
+    character.exceeds_backup_threshold(threshold).then(|| {
+        Span::from(format!(
+            " {WARNING_SYMBOL} {} backups",
+            character.backups().len()
+        ))
+        .fg(PALETTE.log_warn_fg)
+    })
+}
+
+/// Select the enabled columns, with their widths, which fit within `available_width`.
+/// Columns are considered in display order, skipping any which no longer fit.
+fn fit_columns(
+    enabled: &BTreeSet<CharacterListColumn>,
+    characters: &[Character],
+    available_width: usize,
+) -> Vec<(CharacterListColumn, usize)> {
+    let mut remaining = available_width;
+    CharacterListColumn::ALL
+        .into_iter()
+        .filter(|column| enabled.contains(column))
+        .filter_map(|column| {
+            let width = column.width(characters);
+            let needed = width + 1;
+            (width > 0 && needed <= remaining).then(|| {
+                remaining -= needed;
+                (column, width)
+            })
+        })
+        .collect()
+}
+
 /// Action to be taken after handling navigation input
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum NavigationAction {
     /// No action needed
     None,
@@ -269,4 +1021,112 @@ pub enum NavigationAction {
     Copy(usize),
     /// Paste files to the given character index
     Paste(usize),
+    /// Show the details of the given character index
+    ShowInfo(usize),
+    /// Show the summary of the realm with the given folder name, and account if the realm
+    /// spans accounts
+    ShowRealmInfo(String, Option<String>),
+    /// Star or unstar the given character index as a favorite
+    ToggleFavorite(usize),
+    /// Exclude or include the given character index in bulk operations
+    ToggleExcluded(usize),
+    /// Set the display name of the realm with the given folder name
+    SetRealmAlias(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::test_harness::{buffer_to_string, character};
+
+    fn characters() -> Vec<Character> {
+        vec![
+            character("ACC", "Draenor", "Alpha", &["config-cache.wtf"], &[]),
+            character("ACC", "Draenor", "Bravo", &[], &[]),
+            character("ACC", "Silvermoon", "Charlie", &[], &[]),
+            character("ACC", "Silvermoon", "Delta", &[], &[]),
+        ]
+    }
+
+    fn render(widget: &mut CharacterListWidget, characters: &[Character]) -> String {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 8));
+        widget.render(
+            buf.area,
+            &mut buf,
+            characters,
+            true,
+            &ChronoBindAppConfig::default(),
+        );
+        buffer_to_string(&buf)
+    }
+
+    #[test]
+    fn expanded_realms_list_their_characters_with_aligned_columns() {
+        let characters = characters();
+        let mut widget = CharacterListWidget::new();
+        widget.state.select(Some(2));
+        assert_eq!(
+            render(&mut widget, &characters),
+            [
+                "┏ Characters ━━━━━━━━━━━━━━━━┓",
+                "┃ ▼ [Draenor]                ┃",
+                "┃    Alpha         -         ┃",
+                "┃    > Bravo       -         ┃",
+                "┃ ▼ [Silvermoon]             ┃",
+                "┃    Charlie       -         ┃",
+                "┃    Delta         -         ┃",
+                "┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn collapsed_realms_hide_their_characters() {
+        let characters = characters();
+        let mut widget = CharacterListWidget::new();
+        widget.set_all_realms_collapsed(&characters, true);
+        widget.collapsed_realms.remove("Silvermoon");
+        assert_eq!(
+            render(&mut widget, &characters),
+            [
+                "┏ Characters ━━━━━━━━━━━━━━━━┓",
+                "┃ ▶ > [Draenor]              ┃",
+                "┃ ▼ [Silvermoon]             ┃",
+                "┃    Charlie       -         ┃",
+                "┃    Delta         -         ┃",
+                "┃                            ┃",
+                "┃                            ┃",
+                "┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn realms_on_two_accounts_are_grouped_and_selected_per_account() {
+        let characters = vec![
+            character("ACC2", "Realm A", "Bravo", &[], &[]),
+            character("ACC1", "Realm A", "Alpha", &[], &[]),
+        ];
+        let mut widget = CharacterListWidget::new();
+        let items = widget.get_character_list_items(&characters);
+        assert!(matches!(
+            &items[..],
+            [
+                CharacterListItemKind::RealmHeader { account: Some(first), .. },
+                CharacterListItemKind::Character(1),
+                CharacterListItemKind::RealmHeader { account: Some(second), .. },
+                CharacterListItemKind::Character(0),
+            ] if first == "ACC1" && second == "ACC2"
+        ));
+
+        widget.state.select(Some(1));
+        assert_eq!(widget.get_selected_character_index(&items), Some(1));
+        widget.state.select(Some(3));
+        assert_eq!(widget.get_selected_character_index(&items), Some(0));
+
+        widget.select_character(&characters, 0);
+        assert_eq!(widget.selected_index(), 3);
+    }
 }
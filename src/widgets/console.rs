@@ -1,7 +1,7 @@
 use ratatui::buffer::Buffer;
 use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::Rect;
-use ratatui::style::Stylize;
+use ratatui::style::{Style, Stylize};
 use ratatui::text::Line;
 use ratatui::widgets::{Block, Padding, Paragraph, Widget, Wrap};
 
@@ -75,11 +75,13 @@ impl ConsoleWidget {
     }
 
     /// Render the console output panel.
-    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+    /// The border is highlighted while the console has focus.
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, focused: bool) {
         let title = Line::from(" Console Output ").bold();
         let block = Block::bordered()
             .title(title)
             .border_set(ratatui::symbols::border::THICK)
+            .border_style(Style::new().fg(PALETTE.selection_fg(focused)))
             .padding(Padding::symmetric(1, 0));
 
         let log_lines: Option<Vec<Line>> = tui_log::with_debug_logs(|logs| {
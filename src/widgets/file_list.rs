@@ -6,7 +6,8 @@ use ratatui::symbols::border;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, List, ListDirection, ListItem, ListState, Paragraph, Widget};
 
-use crate::ui::{Character, KeyCodeExt};
+use crate::ui::{AddonGroup, Character, KeyCodeExt};
+use crate::wow::WoWCharacterFile;
 
 #[allow(clippy::wildcard_imports)]
 use crate::palette::*;
@@ -16,13 +17,118 @@ use crate::popups::list_with_scrollbar;
 #[derive(Debug, Clone, Copy)]
 pub enum FileRowKind {
     File(usize),
-    AddonHeader { collapsed: bool, count: usize },
+    AddonHeader {
+        collapsed: bool,
+        count: usize,
+    },
+    /// Header of the files of one addon, indexing into [`Character::addon_groups`].
+    AddonGroupHeader {
+        group: usize,
+        collapsed: bool,
+        count: usize,
+    },
     AddonFile(usize),
 }
 
+/// Quick-select categories for selecting groups of files at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum QuickSelect {
+    /// Files with a friendly name, I.e. the commonly important files.
+    Important,
+    /// All config files.
+    Config,
+    /// All addon files.
+    Addons,
+}
+
+impl QuickSelect {
+    /// All quick-select categories, in the order of their shortcut keys.
+    pub const ALL: [Self; 3] = [Self::Important, Self::Config, Self::Addons];
+
+    /// Get the quick-select category for a given shortcut key.
+    #[inline]
+    #[must_use]
+    pub const fn from_key(key: char) -> Option<Self> {
+        match key {
+            '1' => Some(Self::Important),
+            '2' => Some(Self::Config),
+            '3' => Some(Self::Addons),
+            _ => None,
+        }
+    }
+
+    /// Get the display name of the category.
+    #[inline]
+    #[must_use]
+    pub const fn display_name(&self) -> &'static str {
+        match self {
+            Self::Important => "Important",
+            Self::Config => "Config",
+            Self::Addons => "Addons",
+        }
+    }
+
+    /// Returns `true` if the character's current selection is exactly this category.
+    #[inline]
+    #[must_use]
+    pub fn is_active(&self, character: &Character) -> bool {
+        match self {
+            Self::Important => character.only_friendly_selected(),
+            Self::Config => {
+                !character.config_files().is_empty()
+                    && character.all_config_files_selected()
+                    && !character.any_addon_file_selected()
+            }
+            Self::Addons => {
+                !character.addon_files().is_empty()
+                    && character.all_addon_files_selected()
+                    && !character.any_config_file_selected()
+            }
+        }
+    }
+
+    /// Get the quick-select category matching the character's current selection, if any.
+    #[inline]
+    #[must_use]
+    pub fn active_for(character: &Character) -> Option<Self> {
+        Self::ALL.into_iter().find(|qs| qs.is_active(character))
+    }
+
+    /// Apply the quick-select to the character, replacing its current selection.
+    /// If the category is already active, the selection is cleared instead.
+    pub fn apply(self, character: &mut Character) {
+        if self.is_active(character) {
+            character.set_all_selected(false);
+            log::debug!("Cleared quick-select `{}`", self.display_name());
+            return;
+        }
+
+        match self {
+            Self::Important => character.set_only_friendly_selected(),
+            Self::Config => {
+                character.set_all_config_selected(true);
+                character.set_all_addon_selected(false);
+            }
+            Self::Addons => {
+                character.set_all_config_selected(false);
+                character.set_all_addon_selected(true);
+            }
+        }
+        log::info!(
+            "Quick-selected {} files ({} selected)",
+            self.display_name().to_lowercase(),
+            character.total_selected_count()
+        );
+    }
+}
+
 /// Configuration for file list rendering
 pub struct FileListConfig {
     pub show_friendly_names: bool,
+    /// Always render compactly, rather than only when the list is short on height.
+    pub compact: bool,
+    /// Group addon files under a header per addon, rather than listing them flat.
+    pub group_addons: bool,
 }
 
 /// The file list widget displays the files for a selected character
@@ -41,6 +147,11 @@ impl Default for FileListWidget {
 impl FileListWidget {
     /// Padding value for the file list.
     const PADDING: usize = 1;
+    /// Minimum row width at which the last modified column is shown.
+    const MIN_MODIFIED_COLUMN_ROW_WIDTH: usize = 48;
+    /// Height below which the file list is rendered compactly, without icons, padding
+    /// or the last modified column.
+    const COMPACT_HEIGHT: u16 = 16;
 
     /// Create a new file list widget
     #[must_use]
@@ -50,9 +161,9 @@ impl FileListWidget {
         }
     }
 
-    /// Generate the list of file rows for a character
+    /// Generate the list of file rows for a character, grouping addon files by addon if requested.
     #[must_use]
-    pub fn file_rows_for_character(character: &Character) -> Vec<FileRowKind> {
+    pub fn file_rows_for_character(character: &Character, group_addons: bool) -> Vec<FileRowKind> {
         let mut rows = Vec::new();
 
         for idx in 0..character.config_files().len() {
@@ -65,7 +176,22 @@ impl FileListWidget {
             count: addon_count,
         });
 
-        if !character.addon_options_collapsed {
+        if character.addon_options_collapsed {
+            return rows;
+        }
+        if group_addons {
+            for (group_idx, group) in character.addon_groups().into_iter().enumerate() {
+                let collapsed = character.is_addon_group_collapsed(&group.name);
+                rows.push(FileRowKind::AddonGroupHeader {
+                    group: group_idx,
+                    collapsed,
+                    count: group.files.len(),
+                });
+                if !collapsed {
+                    rows.extend(group.files.into_iter().map(FileRowKind::AddonFile));
+                }
+            }
+        } else {
             for idx in 0..addon_count {
                 rows.push(FileRowKind::AddonFile(idx));
             }
@@ -74,14 +200,52 @@ impl FileListWidget {
         rows
     }
 
+    /// Get the file of the hovered row, `None` if the row is a header rather than a file.
+    #[must_use]
+    pub fn hovered_file<'a>(
+        &self,
+        character: &'a Character,
+        group_addons: bool,
+    ) -> Option<&'a WoWCharacterFile> {
+        let rows = Self::file_rows_for_character(character, group_addons);
+        match rows.get(self.state.selected()?)? {
+            FileRowKind::File(idx) => character.config_files().get(*idx),
+            FileRowKind::AddonFile(idx) => character.addon_files().get(*idx),
+            FileRowKind::AddonHeader { .. } | FileRowKind::AddonGroupHeader { .. } => None,
+        }
+    }
+
+    /// Get the name of the addon of the hovered addon group header or grouped addon file,
+    /// `None` if addon files aren't grouped or the row doesn't belong to an addon.
+    #[must_use]
+    pub fn hovered_addon(&self, character: &Character, group_addons: bool) -> Option<String> {
+        if !group_addons {
+            return None;
+        }
+        let rows = Self::file_rows_for_character(character, group_addons);
+        match rows.get(self.state.selected()?)? {
+            FileRowKind::AddonGroupHeader { group, .. } => character
+                .addon_groups()
+                .into_iter()
+                .nth(*group)
+                .map(|group| group.name),
+            FileRowKind::AddonFile(idx) => character
+                .addon_files()
+                .get(*idx)
+                .map(|file| file.addon_name().to_string()),
+            FileRowKind::File(_) | FileRowKind::AddonHeader { .. } => None,
+        }
+    }
+
     /// Handle input for the file list in file selection mode
     /// Returns the action to be taken
     pub fn handle_file_selection_input(
         &mut self,
         key: &KeyEvent,
         character: &mut Character,
+        group_addons: bool,
     ) -> FileSelectionAction {
-        let rows = Self::file_rows_for_character(character);
+        let rows = Self::file_rows_for_character(character, group_addons);
         let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
 
         match key.keycode_lower() {
@@ -100,60 +264,20 @@ impl FileListWidget {
                 FileSelectionAction::None
             }
             KeyCode::Char(' ' | 'd') | KeyCode::Enter | KeyCode::Right => {
-                let Some(selected_index) = self.state.selected() else {
+                let Some(&row) = self.state.selected().and_then(|idx| rows.get(idx)) else {
                     return FileSelectionAction::None;
                 };
-                if selected_index < rows.len() {
-                    match rows[selected_index] {
-                        FileRowKind::File(idx) => {
-                            if ctrl {
-                                let selected = character.all_config_files_selected();
-                                character.set_all_config_selected(!selected);
-                                log::info!(
-                                    "{} all config files",
-                                    if selected { "Deselected" } else { "Selected" }
-                                );
-                            } else {
-                                let selected = character.toggle_config_file_selected(idx);
-                                let file_name = character.config_files()[idx].get_full_filename();
-                                log::info!("File '{file_name}' toggled: {selected}");
-                            }
-                        }
-                        FileRowKind::AddonHeader { .. } => {
-                            if ctrl {
-                                let selected = character.all_addon_files_selected();
-                                character.set_all_addon_selected(!selected);
-                                log::debug!(
-                                    "{} all addon files",
-                                    if selected { "Deselected" } else { "Selected" }
-                                );
-                            } else {
-                                character.addon_options_collapsed =
-                                    !character.addon_options_collapsed;
-                            }
-                        }
-                        FileRowKind::AddonFile(idx) => {
-                            if ctrl {
-                                let selected = character.all_addon_files_selected();
-                                character.set_all_addon_selected(!selected);
-                                log::debug!(
-                                    "{} all addon files",
-                                    if selected { "Deselected" } else { "Selected" }
-                                );
-                            } else {
-                                let selected = character.toggle_addon_file_selected(idx);
-                                let file_name = character.addon_files()[idx].get_full_filename();
-                                log::debug!("Addon file '{file_name}' toggled: {selected}");
-                            }
-                        }
-                    }
+                Self::interact_with_row(character, row, ctrl, group_addons);
+                if !ctrl && matches!(row, FileRowKind::AddonHeader { .. }) {
+                    FileSelectionAction::AddonSectionToggled
+                } else {
+                    FileSelectionAction::None
                 }
-                FileSelectionAction::None
             }
             KeyCode::Char('a') if ctrl => {
                 let all_selected =
                     character.all_config_files_selected() && character.all_addon_files_selected();
-                character.set_all_selected(!all_selected);
+                character.with_selection_history(|c| c.set_all_selected(!all_selected));
                 log::debug!(
                     "All files {}",
                     if all_selected {
@@ -164,18 +288,165 @@ impl FileListWidget {
                 );
                 FileSelectionAction::None
             }
+            KeyCode::Char(c) if let Some(quick_select) = QuickSelect::from_key(c) => {
+                character.with_selection_history(|c| quick_select.apply(c));
+                FileSelectionAction::None
+            }
+            KeyCode::Char('z') if ctrl => {
+                Self::step_selection_history(character, true);
+                FileSelectionAction::None
+            }
+            KeyCode::Char('y') if ctrl => {
+                Self::step_selection_history(character, false);
+                FileSelectionAction::None
+            }
             KeyCode::Char('b') => FileSelectionAction::ShowBackup,
             KeyCode::Char('c') => FileSelectionAction::Copy,
+            KeyCode::Char('p') => FileSelectionAction::Preview,
+            KeyCode::Char('y') => FileSelectionAction::CopyPath,
+            KeyCode::Char('e') => FileSelectionAction::ExportAddonProfile,
             _ => FileSelectionAction::None,
         }
     }
 
+    /// Get the bottom bar options for the keys handled by [`Self::handle_file_selection_input`],
+    /// which depend on whether the hovered row is a file or a header.
+    #[must_use]
+    pub fn bottom_bar_options(&self, character: &Character, group_addons: bool) -> Vec<String> {
+        let rows = Self::file_rows_for_character(character, group_addons);
+        let hovered = self.state.selected().and_then(|idx| rows.get(idx));
+        let (toggle, ctrl_toggle) = match hovered {
+            Some(FileRowKind::File(_)) => ("Toggle", "Select Config Files"),
+            Some(FileRowKind::AddonHeader { .. }) => ("Collapse", "Select Addon Files"),
+            Some(FileRowKind::AddonGroupHeader { .. }) => ("Collapse", "Select Addon"),
+            Some(FileRowKind::AddonFile(_)) if group_addons => ("Toggle", "Select Addon"),
+            Some(FileRowKind::AddonFile(_)) | None => ("Toggle", "Select Addon Files"),
+        };
+        let mut options = vec![
+            "↑/↓".to_string(),
+            "←: Back".to_string(),
+            "Tab: Switch Pane".to_string(),
+            format!("{ENTER_SYMBOL}/Space/→: {toggle}"),
+            format!("Ctrl+Space: {ctrl_toggle}"),
+            "Ctrl+A: Select All".to_string(),
+            "Ctrl+Z/Y: Undo/Redo".to_string(),
+            "1/2/3: Important/Config/Addons".to_string(),
+            "(B)ackup".to_string(),
+            "(C)opy".to_string(),
+        ];
+        if self.hovered_file(character, group_addons).is_some() {
+            options.push("(P)review".to_string());
+            options.push("Y: Copy Path".to_string());
+        } else {
+            options.push("Y: Copy Folder Path".to_string());
+        }
+        if self.hovered_addon(character, group_addons).is_some() {
+            options.push("(E)xport Addon".to_string());
+        }
+        options
+    }
+
+    /// Toggle the selection of the given row, or the collapsed state of a header.
+    /// With `ctrl`, the selection of the row's whole section or addon group is toggled instead.
+    fn interact_with_row(
+        character: &mut Character,
+        row: FileRowKind,
+        ctrl: bool,
+        group_addons: bool,
+    ) {
+        match row {
+            FileRowKind::File(idx) => {
+                if ctrl {
+                    let selected = character.all_config_files_selected();
+                    character.with_selection_history(|c| {
+                        c.set_all_config_selected(!selected);
+                    });
+                    log::info!(
+                        "{} all config files",
+                        if selected { "Deselected" } else { "Selected" }
+                    );
+                } else {
+                    let selected = character.toggle_config_file_selected(idx);
+                    let file_name = character.config_files()[idx].get_full_filename();
+                    log::info!("File '{file_name}' toggled: {selected}");
+                }
+            }
+            FileRowKind::AddonHeader { .. } => {
+                if ctrl {
+                    Self::toggle_all_addons_selected(character);
+                } else {
+                    character.addon_options_collapsed = !character.addon_options_collapsed;
+                }
+            }
+            FileRowKind::AddonGroupHeader { group, .. } => {
+                if let Some(group) = character.addon_groups().get(group) {
+                    if ctrl {
+                        Self::toggle_addon_group_selected(character, group);
+                    } else {
+                        character.toggle_addon_group_collapsed(&group.name);
+                    }
+                }
+            }
+            FileRowKind::AddonFile(idx) => {
+                let group = group_addons
+                    .then(|| character.addon_groups())
+                    .and_then(|groups| groups.into_iter().find(|group| group.files.contains(&idx)));
+                if ctrl && let Some(group) = group {
+                    Self::toggle_addon_group_selected(character, &group);
+                } else if ctrl {
+                    Self::toggle_all_addons_selected(character);
+                } else {
+                    let selected = character.toggle_addon_file_selected(idx);
+                    let file_name = character.addon_files()[idx].get_full_filename();
+                    log::debug!("Addon file '{file_name}' toggled: {selected}");
+                }
+            }
+        }
+    }
+
+    /// Toggle the selection of all addon files, recording the change for undo.
+    fn toggle_all_addons_selected(character: &mut Character) {
+        let selected = character.all_addon_files_selected();
+        character.with_selection_history(|c| c.set_all_addon_selected(!selected));
+        log::debug!(
+            "{} all addon files",
+            if selected { "Deselected" } else { "Selected" }
+        );
+    }
+
+    /// Toggle the selection of the files of an addon group, recording the change for undo.
+    fn toggle_addon_group_selected(character: &mut Character, group: &AddonGroup) {
+        let selected = character.all_addon_group_files_selected(group);
+        character.with_selection_history(|c| c.set_addon_group_selected(group, !selected));
+        log::debug!(
+            "{} all {} files",
+            if selected { "Deselected" } else { "Selected" },
+            group.name
+        );
+    }
+
+    /// Undo or redo the character's last bulk selection change.
+    fn step_selection_history(character: &mut Character, undo: bool) {
+        let (changed, verb) = if undo {
+            (character.undo_selection(), "undo")
+        } else {
+            (character.redo_selection(), "redo")
+        };
+        if changed {
+            log::debug!("File selection {verb} applied");
+        } else {
+            log::debug!("No file selection changes to {verb}");
+        }
+    }
+
     /// Render a file row item
     fn file_row_file_item<'a>(
         character: &Character,
         file_idx: usize,
         hovered: bool,
         config: &FileListConfig,
+        row_width: Option<usize>,
+        compact: bool,
     ) -> ListItem<'a> {
         let file = &character.config_files()[file_idx];
         let selected = character.is_config_file_selected(file_idx);
@@ -190,12 +461,16 @@ impl FileListWidget {
         };
         let mut style = Style::default().fg(fg_colour);
 
-        let file_prefix_ui = Span::from(format!(
-            "{pad}{} {} ",
-            checkbox(selected),
-            *CONFIG_FILE_ICON,
-            pad = indentation(Self::PADDING)
-        ))
+        let file_prefix_ui = Span::from(if compact {
+            format!("{} ", checkbox(selected))
+        } else {
+            format!(
+                "{pad}{} {} ",
+                checkbox(selected),
+                *CONFIG_FILE_ICON,
+                pad = indentation(Self::PADDING)
+            )
+        })
         .style(style);
 
         if config.show_friendly_names && has_friendly {
@@ -205,10 +480,12 @@ impl FileListWidget {
         let file_name = file.display_name(config.show_friendly_names);
         let content = format!("{}{file_name}", highlight_symbol(hovered));
 
-        ListItem::new(Line::from(vec![
-            file_prefix_ui,
-            Span::from(content).style(style),
-        ]))
+        ListItem::new(with_modified_column(
+            vec![file_prefix_ui, Span::from(content).style(style)],
+            file,
+            character.modified_since_last_backup(file),
+            row_width,
+        ))
     }
 
     /// Render an addon header row
@@ -217,6 +494,7 @@ impl FileListWidget {
         count: usize,
         collapsed: bool,
         hovered: bool,
+        compact: bool,
     ) -> ListItem<'_> {
         let no_addon_files = character.addon_files().is_empty();
         let any_addon_file_selected = character.any_addon_file_selected();
@@ -235,20 +513,65 @@ impl FileListWidget {
             "{pad}{} {}{label}",
             expandable_icon(collapsed),
             highlight_symbol(hovered),
-            pad = indentation(Self::PADDING)
+            pad = indentation(if compact { 0 } else { Self::PADDING })
         );
 
         ListItem::new(Line::from(content).fg(colour).bold().italic())
     }
 
-    /// Render an addon file row item
+    /// Render the header row of an addon group
+    fn file_row_addon_group_header<'a>(
+        character: &Character,
+        group: &AddonGroup,
+        collapsed: bool,
+        hovered: bool,
+        compact: bool,
+    ) -> ListItem<'a> {
+        const GROUP_IDENT: usize = 3;
+        const COMPACT_GROUP_IDENT: usize = 1;
+
+        let colour = if character.all_addon_group_files_selected(group) {
+            PALETTE.selected_fg
+        } else if character.any_addon_group_file_selected(group) {
+            PALETTE.log_warn_fg
+        } else {
+            PALETTE.std_fg
+        };
+
+        let content = format!(
+            "{pad}{} {}{} ({})",
+            expandable_icon(collapsed),
+            highlight_symbol(hovered),
+            group.name,
+            group.files.len(),
+            pad = indentation(if compact {
+                COMPACT_GROUP_IDENT
+            } else {
+                Self::PADDING + GROUP_IDENT
+            })
+        );
+
+        ListItem::new(Line::from(content).fg(colour).bold())
+    }
+
+    /// Render an addon file row item, indented further when listed under an addon group
     fn file_row_addon_item<'a>(
         character: &Character,
         file_idx: usize,
         hovered: bool,
         config: &FileListConfig,
+        row_width: Option<usize>,
+        compact: bool,
     ) -> ListItem<'a> {
         const ADDON_IDENT: usize = 3;
+        const COMPACT_ADDON_IDENT: usize = 1;
+        const GROUPED_ADDON_IDENT: usize = 2;
+
+        let group_ident = if config.group_addons {
+            GROUPED_ADDON_IDENT
+        } else {
+            0
+        };
 
         let selected = character.is_addon_file_selected(file_idx);
         let file = &character.addon_files()[file_idx];
@@ -263,12 +586,20 @@ impl FileListWidget {
         };
         let mut style = Style::default().fg(fg_colour);
 
-        let file_prefix_ui = Span::from(format!(
-            "{pad}{} {} ",
-            checkbox(selected),
-            *ADDON_FILE_ICON,
-            pad = indentation(Self::PADDING + ADDON_IDENT)
-        ))
+        let file_prefix_ui = Span::from(if compact {
+            format!(
+                "{pad}{} ",
+                checkbox(selected),
+                pad = indentation(COMPACT_ADDON_IDENT + group_ident)
+            )
+        } else {
+            format!(
+                "{pad}{} {} ",
+                checkbox(selected),
+                *ADDON_FILE_ICON,
+                pad = indentation(Self::PADDING + ADDON_IDENT + group_ident)
+            )
+        })
         .style(style);
 
         if config.show_friendly_names && has_friendly {
@@ -278,10 +609,12 @@ impl FileListWidget {
         let file_name = file.display_stem(config.show_friendly_names);
         let content = format!("{}{file_name}", highlight_symbol(hovered));
 
-        ListItem::new(Line::from(vec![
-            file_prefix_ui,
-            Span::from(content).style(style),
-        ]))
+        ListItem::new(with_modified_column(
+            vec![file_prefix_ui, Span::from(content).style(style)],
+            file,
+            character.modified_since_last_backup(file),
+            row_width,
+        ))
     }
 
     /// Render the file list widget
@@ -291,6 +624,7 @@ impl FileListWidget {
         buf: &mut Buffer,
         character: Option<&Character>,
         show_highlight: bool,
+        focused: bool,
         config: &FileListConfig,
     ) {
         let title = character.map_or_else(
@@ -301,7 +635,17 @@ impl FileListWidget {
                 Line::from(vec![files_span, char_span, Span::from(" ")])
             },
         );
-        let block = Block::bordered().title(title).border_set(border::THICK);
+        let mut block = Block::bordered()
+            .title(title)
+            .border_set(border::THICK)
+            .border_style(Style::new().fg(PALETTE.selection_fg(focused)));
+        if let Some(quick_select) = character.and_then(QuickSelect::active_for) {
+            block = block.title_bottom(
+                Line::from(format!(" Quick Select: {} ", quick_select.display_name()))
+                    .fg(PALETTE.special_fg)
+                    .right_aligned(),
+            );
+        }
 
         let Some(character) = character else {
             Paragraph::new(format!(
@@ -313,7 +657,31 @@ impl FileListWidget {
             return;
         };
 
-        let rows = Self::file_rows_for_character(character);
+        if !character.has_files() {
+            Paragraph::new(format!(
+                "{pad}No files for this character",
+                pad = indentation(Self::PADDING)
+            ))
+            .fg(PALETTE.log_warn_fg)
+            .block(block)
+            .render(area, buf);
+            return;
+        }
+
+        let rows = Self::file_rows_for_character(character, config.group_addons);
+        let addon_groups = if config.group_addons {
+            character.addon_groups()
+        } else {
+            Vec::new()
+        };
+
+        // Short panes, such as a small split, are rendered compactly to fit more rows in view.
+        let compact = config.compact || area.height < Self::COMPACT_HEIGHT;
+
+        // Inner width, less the borders and a trailing space; too narrow hides the modified column.
+        let inner_width = usize::from(area.width.saturating_sub(3));
+        let row_width =
+            (!compact && inner_width >= Self::MIN_MODIFIED_COLUMN_ROW_WIDTH).then_some(inner_width);
 
         let items = rows
             .iter()
@@ -323,15 +691,25 @@ impl FileListWidget {
                     show_highlight && self.state.selected().is_some_and(|sel| sel == row_idx);
 
                 match *row {
-                    FileRowKind::File(file_idx) => {
-                        Self::file_row_file_item(character, file_idx, hovered, config)
-                    }
+                    FileRowKind::File(file_idx) => Self::file_row_file_item(
+                        character, file_idx, hovered, config, row_width, compact,
+                    ),
                     FileRowKind::AddonHeader { collapsed, count } => {
-                        Self::file_row_addon_header(character, count, collapsed, hovered)
-                    }
-                    FileRowKind::AddonFile(file_idx) => {
-                        Self::file_row_addon_item(character, file_idx, hovered, config)
+                        Self::file_row_addon_header(character, count, collapsed, hovered, compact)
                     }
+                    FileRowKind::AddonGroupHeader {
+                        group, collapsed, ..
+                    } => addon_groups.get(group).map_or_else(
+                        || ListItem::new(""),
+                        |group| {
+                            Self::file_row_addon_group_header(
+                                character, group, collapsed, hovered, compact,
+                            )
+                        },
+                    ),
+                    FileRowKind::AddonFile(file_idx) => Self::file_row_addon_item(
+                        character, file_idx, hovered, config, row_width, compact,
+                    ),
                 }
             })
             .collect::<Vec<ListItem>>();
@@ -350,6 +728,45 @@ impl FileListWidget {
     }
 }
 
+/// Right-align the last modified time of a file after the row's spans, if the row is wide enough.
+/// Files changed since the character's last backup are marked and highlighted.
+fn with_modified_column<'a>(
+    mut spans: Vec<Span<'a>>,
+    file: &WoWCharacterFile,
+    changed_since_backup: bool,
+    row_width: Option<usize>,
+) -> Line<'a> {
+    let Some(row_width) = row_width else {
+        return Line::from(spans);
+    };
+
+    let modified = file.modified.map_or_else(
+        || "-".to_string(),
+        |modified| {
+            let time = display_relative_time(modified);
+            if changed_since_backup {
+                format!("{CHANGED_SINCE_BACKUP_SYMBOL} {time}")
+            } else {
+                time
+            }
+        },
+    );
+    let used_width = spans
+        .iter()
+        .map(|span| span.content.chars().count())
+        .sum::<usize>();
+    let padding = row_width.saturating_sub(used_width + modified.chars().count());
+    if padding > 0 {
+        spans.push(Span::from(" ".repeat(padding)));
+        spans.push(if changed_since_backup {
+            Span::from(modified).fg(PALETTE.log_warn_fg)
+        } else {
+            Span::from(modified).dim()
+        });
+    }
+    Line::from(spans)
+}
+
 /// Action to be taken after handling file selection input
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum FileSelectionAction {
@@ -361,4 +778,98 @@ pub enum FileSelectionAction {
     ShowBackup,
     /// Copy selected files
     Copy,
+    /// Preview the hovered file
+    Preview,
+    /// Copy the path of the hovered file, or the character directory on a header, to the clipboard
+    CopyPath,
+    /// Export the `SavedVariables` of the hovered addon to an addon profile archive
+    ExportAddonProfile,
+    /// The addon section was collapsed or expanded
+    AddonSectionToggled,
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::widgets::test_harness::{buffer_to_string, character};
+
+    fn render(character: Option<&Character>, group_addons: bool, height: u16) -> String {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 40, height));
+        FileListWidget::new().render(
+            buf.area,
+            &mut buf,
+            character,
+            true,
+            true,
+            &FileListConfig {
+                show_friendly_names: false,
+                compact: false,
+                group_addons,
+            },
+        );
+        buffer_to_string(&buf)
+    }
+
+    fn alpha() -> Character {
+        character(
+            "ACC",
+            "Draenor",
+            "Alpha",
+            &["config-cache.wtf", "macros-cache.txt"],
+            &["WeakAuras.lua", "WeakAuras.lua.bak", "Details.lua"],
+        )
+    }
+
+    #[test]
+    fn config_files_are_listed_above_the_addon_section() {
+        assert_eq!(
+            render(Some(&alpha()), false, 18).lines().take(8).join("\n"),
+            [
+                "┏ Files - Alpha - Draenor ━━━━━━━━━━━━━┓",
+                "┃ [ ] ⚙  config-cache.wtf              ┃",
+                "┃ [ ] ⚙  macros-cache.txt              ┃",
+                "┃ ▼ Addon Options (3)                  ┃",
+                "┃    [ ] 📦 WeakAuras                  ┃",
+                "┃    [ ] 📦 WeakAuras.lua              ┃",
+                "┃    [ ] 📦 Details                    ┃",
+                "┃                                      ┃",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn short_lists_are_compact_and_addon_files_can_be_grouped() {
+        assert_eq!(
+            render(Some(&alpha()), true, 10),
+            [
+                "┏ Files - Alpha - Draenor ━━━━━━━━━━━━━┓",
+                "┃[ ] config-cache.wtf                  ┃",
+                "┃[ ] macros-cache.txt                  ┃",
+                "┃▼ Addon Options (3)                   ┃",
+                "┃ ▼ Details (1)                        ┃",
+                "┃   [ ] Details                        ┃",
+                "┃ ▼ WeakAuras (2)                      ┃",
+                "┃   [ ] WeakAuras                      ┃",
+                "┃   [ ] WeakAuras.lua                  ┃",
+                "┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn no_character_shows_a_placeholder() {
+        assert_eq!(
+            render(None, false, 3),
+            [
+                "┏ Files ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓",
+                "┃ No character selected                ┃",
+                "┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛",
+            ]
+            .join("\n")
+        );
+    }
 }
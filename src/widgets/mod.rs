@@ -2,4 +2,7 @@ pub mod character_list;
 pub mod console;
 pub mod file_list;
 pub mod popup;
+#[cfg(test)]
+pub mod test_harness;
 pub mod text_input;
+pub mod toast;
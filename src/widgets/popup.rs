@@ -86,6 +86,11 @@ pub trait Popup {
     /// Process a message sent to the popup.
     fn process_message(&mut self, _message: &PopupMessage) {}
 
+    /// Returns `true` if the popup is tracking a background task which has not yet finished.
+    fn is_running_task(&self) -> bool {
+        false
+    }
+
     /// Get the width percentage for the popup.
     #[inline]
     #[must_use]
@@ -0,0 +1,87 @@
+//! Rendering widgets and popups into a [`TestBackend`] for tests, with fixtures for them.
+
+use itertools::Itertools;
+use ratatui::{Terminal, backend::TestBackend, buffer::Buffer, text::Span};
+
+use crate::{
+    ui::character::Character,
+    widgets::popup::Popup,
+    wow::{WoWCharacter, WoWCharacterFile},
+};
+
+/// Snapshot the buffer to a string, one line per row with trailing whitespace trimmed.
+/// The cells covered by a wide symbol are left out, so rows read as they are displayed.
+#[must_use]
+pub fn buffer_to_string(buf: &Buffer) -> String {
+    let area = buf.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            let mut row = String::new();
+            let mut covered = 0;
+            for x in area.left()..area.right() {
+                if covered > 0 {
+                    covered -= 1;
+                    continue;
+                }
+                let symbol = buf[(x, y)].symbol();
+                covered = Span::raw(symbol).width().saturating_sub(1);
+                row.push_str(symbol);
+            }
+            row.trim_end().to_string()
+        })
+        .join("\n")
+}
+
+/// Render the popup onto an empty terminal of the given size, snapshotting it to a string.
+/// # Panics
+/// Panics if the popup cannot be drawn.
+pub fn render_popup(popup: &mut dyn Popup, width: u16, height: u16) -> String {
+    let mut terminal =
+        Terminal::new(TestBackend::new(width, height)).expect("Test backend should be created");
+    terminal
+        .draw(|frame| popup.render(frame))
+        .expect("Popup should draw");
+    buffer_to_string(terminal.backend().buffer())
+}
+
+/// The non-blank rows of a snapshot with surrounding whitespace trimmed, for content which is
+/// centred, such as popups, where only the rows drawn are of interest.
+#[must_use]
+pub fn trimmed_rows(snapshot: &str) -> Vec<&str> {
+    snapshot
+        .lines()
+        .map(str::trim)
+        .filter(|row| !row.is_empty())
+        .collect()
+}
+
+/// A character file with the given name, within the character's directory.
+#[must_use]
+pub fn character_file(name: &str) -> WoWCharacterFile {
+    let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+    WoWCharacterFile {
+        name: name.to_string(),
+        stem: stem.to_string(),
+        path: name.into(),
+        ..WoWCharacterFile::default()
+    }
+}
+
+/// A character with the given config and addon files.
+#[must_use]
+pub fn character(
+    account: &str,
+    realm: &str,
+    name: &str,
+    config_files: &[&str],
+    addon_files: &[&str],
+) -> Character {
+    Character::new(&WoWCharacter {
+        account: account.to_string(),
+        realm: realm.to_string(),
+        name: name.to_string(),
+        config_files: config_files.iter().map(|f| character_file(f)).collect(),
+        addon_files: addon_files.iter().map(|f| character_file(f)).collect(),
+        ..WoWCharacter::default()
+    })
+}
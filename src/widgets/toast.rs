@@ -0,0 +1,194 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::symbols::border;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Clear, Paragraph, Widget};
+use serde::{Deserialize, Serialize};
+
+#[allow(clippy::wildcard_imports)]
+use crate::palette::*;
+
+/// Severity of a toast, deciding its colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ToastLevel {
+    /// General information, I.e. `Mock mode on`.
+    Info,
+    /// An operation completed, I.e. `Backup complete`.
+    Success,
+    /// Something may need attention.
+    Warning,
+    /// An operation failed.
+    Error,
+}
+
+impl ToastLevel {
+    /// Get the colour of toasts of this level.
+    #[inline]
+    #[must_use]
+    pub fn colour(self) -> Color {
+        match self {
+            Self::Info => PALETTE.log_info_fg,
+            Self::Success => PALETTE.selected_fg,
+            Self::Warning => PALETTE.log_warn_fg,
+            Self::Error => PALETTE.log_error_fg,
+        }
+    }
+}
+
+/// Corner of the screen toasts are stacked in.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum ToastPosition {
+    /// Top right, below the title bar.
+    #[default]
+    TopRight,
+    /// Top left, below the title bar.
+    TopLeft,
+    /// Bottom right, above the bottom bar.
+    BottomRight,
+    /// Bottom left, above the bottom bar.
+    BottomLeft,
+}
+
+impl ToastPosition {
+    /// Every position, in cycling order.
+    pub const ALL: [Self; 4] = [
+        Self::TopRight,
+        Self::TopLeft,
+        Self::BottomRight,
+        Self::BottomLeft,
+    ];
+
+    /// Get the display name of the position.
+    #[inline]
+    #[must_use]
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::TopRight => "Top right",
+            Self::TopLeft => "Top left",
+            Self::BottomRight => "Bottom right",
+            Self::BottomLeft => "Bottom left",
+        }
+    }
+
+    /// Get the next position, wrapping around.
+    #[inline]
+    #[must_use]
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|p| *p == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    /// Get the previous position, wrapping around.
+    #[inline]
+    #[must_use]
+    pub fn previous(self) -> Self {
+        let idx = Self::ALL.iter().position(|p| *p == self).unwrap_or(0);
+        Self::ALL[(idx + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// Returns `true` if toasts are stacked down from the top of the screen.
+    #[inline]
+    #[must_use]
+    const fn is_top(self) -> bool {
+        matches!(self, Self::TopRight | Self::TopLeft)
+    }
+
+    /// Returns `true` if toasts are aligned to the right of the screen.
+    #[inline]
+    #[must_use]
+    const fn is_right(self) -> bool {
+        matches!(self, Self::TopRight | Self::BottomRight)
+    }
+}
+
+/// A transient message shown over the interface.
+#[derive(Debug, Clone)]
+struct Toast {
+    /// Severity of the message.
+    level: ToastLevel,
+    /// The message shown.
+    text: String,
+    /// When the toast was pushed.
+    shown_at: Instant,
+}
+
+/// Queue of the toasts currently shown, newest last.
+#[derive(Debug, Default)]
+pub struct ToastQueue {
+    /// The toasts shown, oldest first.
+    toasts: VecDeque<Toast>,
+}
+
+impl ToastQueue {
+    /// Maximum number of toasts shown at once, older toasts are dropped beyond this.
+    const MAX_TOASTS: usize = 5;
+    /// Maximum width of a toast, including its border.
+    const MAX_WIDTH: u16 = 64;
+    /// Height of a toast, including its border.
+    const HEIGHT: u16 = 3;
+
+    /// Show a toast with the given message.
+    pub fn push<S: Into<String>>(&mut self, level: ToastLevel, text: S) {
+        self.toasts.push_back(Toast {
+            level,
+            text: text.into(),
+            shown_at: Instant::now(),
+        });
+        while self.toasts.len() > Self::MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+    }
+
+    /// Remove toasts which have been shown for longer than the given duration.
+    pub fn expire(&mut self, duration: Duration) {
+        self.toasts
+            .retain(|toast| toast.shown_at.elapsed() < duration);
+    }
+
+    /// Render the toasts stacked in the given corner of the area, newest nearest the corner.
+    pub fn render(&self, area: Rect, buf: &mut Buffer, position: ToastPosition) {
+        let max_width = Self::MAX_WIDTH.min(area.width);
+        // Room for the border and a space either side of the text.
+        let Some(max_text_width) = max_width.checked_sub(4).filter(|w| *w > 0) else {
+            return;
+        };
+
+        let mut offset = 0;
+        for toast in self.toasts.iter().rev() {
+            if offset + Self::HEIGHT > area.height {
+                break;
+            }
+            let text = crate::ui::truncate_with_ellipsis(&toast.text, usize::from(max_text_width));
+            let width = u16::try_from(text.chars().count())
+                .unwrap_or(max_text_width)
+                .min(max_text_width)
+                + 4;
+            let x = if position.is_right() {
+                area.right() - width
+            } else {
+                area.x
+            };
+            let y = if position.is_top() {
+                area.y + offset
+            } else {
+                area.bottom() - offset - Self::HEIGHT
+            };
+            offset += Self::HEIGHT;
+
+            let toast_area = Rect::new(x, y, width, Self::HEIGHT);
+            let block = Block::bordered()
+                .border_set(border::ROUNDED)
+                .border_style(Style::new().fg(toast.level.colour()));
+            Clear.render(toast_area, buf);
+            Paragraph::new(Line::from(format!(" {text} ")).fg(PALETTE.std_fg))
+                .block(block)
+                .render(toast_area, buf);
+        }
+    }
+}
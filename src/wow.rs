@@ -1,23 +1,65 @@
 use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashSet},
+    fs::DirEntry,
     hash::Hash,
     path::{Path, PathBuf},
+    time::SystemTime,
 };
 
 use chrono::{DateTime, Local};
 use const_format::concatcp;
 use itertools::Itertools;
-use prost::Message;
+use prost::{
+    DecodeError, Message,
+    encoding::{self, WireType},
+};
 use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    backend::BACKUP_FILE_EXTENSION, files::read_folders_to_string,
-    lua_table_parser::LuaTableParser, palette::PALETTE,
+    backend::{BACKUP_FILE_EXTENSION, backup_name::BackupNameTemplate},
+    config::RuntimeSettings,
+    files::{read_files_to_depth, read_folders_to_string},
+    lua_table_parser::LuaTableParser,
+    palette::PALETTE,
 };
 
 use super::lua_table_parser::LuaValue;
 
+#[allow(clippy::all, clippy::pedantic, clippy::nursery)]
 mod productdb {
     include!(concat!(env!("OUT_DIR"), "/productdb.rs"));
+
+    /// Minimal view of a `ProductInstall`, decoding only the fields needed to locate an install.
+    /// Used to salvage entries which no longer match the full schema.
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct MinimalProductInstall {
+        #[prost(string, tag = "2")]
+        pub product_code: ::prost::alloc::string::String,
+        #[prost(message, optional, tag = "3")]
+        pub settings: ::core::option::Option<MinimalUserSettings>,
+    }
+
+    /// Minimal view of `UserSettings`, decoding only the install path.
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct MinimalUserSettings {
+        #[prost(string, tag = "1")]
+        pub install_path: ::prost::alloc::string::String,
+    }
+
+    impl From<MinimalProductInstall> for ProductInstall {
+        fn from(install: MinimalProductInstall) -> Self {
+            Self {
+                product_code: install.product_code,
+                settings: install.settings.map(|settings| UserSettings {
+                    install_path: settings.install_path,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }
+        }
+    }
 }
 
 // Locating WoW installs..
@@ -26,12 +68,46 @@ mod productdb {
 const BNET_AGENT_PRODUCT_DB_PATH: &str = "/Users/Shared/Battle.net//Agent//product.db";
 #[cfg(target_os = "windows")]
 const BNET_AGENT_PRODUCT_DB_PATH: &str = "C:\\ProgramData\\Battle.net\\Agent\\product.db";
+/// Names of the game executables found within a branch folder, one per flavour of the game.
+#[cfg(target_os = "macos")]
+const GAME_EXECUTABLE_NAMES: &[&str] = &[
+    "World of Warcraft.app",
+    "World of Warcraft Classic.app",
+    "World of Warcraft Test.app",
+    "World of Warcraft Classic Test.app",
+    "World of Warcraft Beta.app",
+];
+#[cfg(not(target_os = "macos"))]
+const GAME_EXECUTABLE_NAMES: &[&str] = &[
+    "Wow.exe",
+    "WowClassic.exe",
+    "WowT.exe",
+    "WowClassicT.exe",
+    "WowB.exe",
+    "WowClassicB.exe",
+];
 /// Identifier prefix for World of Warcraft product codes.
 const WOW_PRODUCT_CODE_IDENT: &str = "wow";
 /// Prefix for World of Warcraft product codes with branch identifiers.
 const WOW_PRODUCT_CODE_BRANCH_PREFIX: &str = concatcp!(WOW_PRODUCT_CODE_IDENT, "_");
 /// Identifier for the retail branch of World of Warcraft.
 pub const WOW_RETAIL_IDENT: &str = "retail";
+/// Product codes whose branch identifier isn't the code without the `wow_` prefix,
+/// paired with the branch identifier, which names the branch's folder.
+const SPECIAL_BRANCH_PRODUCT_CODES: [(&str, &str); 2] = [("wowt", "ptr"), ("wowxptr", "xptr")];
+/// Display names of words within branch identifiers which aren't shown capitalised.
+const BRANCH_WORD_DISPLAY_NAMES: [(&str, &str); 2] = [("ptr", "PTR"), ("xptr", "Experimental PTR")];
+
+/// Strip the `\\?\` prefix Windows adds to canonical paths, which the rest of the app does not
+/// expect. UNC paths keep the prefix, as they cannot be written without it.
+fn strip_verbatim_prefix(path: PathBuf) -> PathBuf {
+    if let Some(rest) = path.to_str().and_then(|p| p.strip_prefix(r"\\?\"))
+        && !rest.starts_with(r"UNC\")
+    {
+        return PathBuf::from(rest);
+    }
+    path
+}
 
 /// Name of the `ChronoBind` companion addon's `SavedVariables` file.
 pub const CHRONOBIND_COMPANION_FILE: &str = "ChronoBind_Companion.lua";
@@ -45,9 +121,21 @@ pub struct WoWInstall {
     pub branch_ident: String,
     /// The root installation path for this World of Warcraft installation.
     pub install_path: String,
+    /// Whether paths within this installation are resolved through symlinks to their real
+    /// location before use.
+    pub resolve_symlinks: bool,
 }
 
 impl WoWInstall {
+    /// Resolve a path through any symlinks to its real location, if enabled for this installation.
+    /// Paths which cannot be resolved, such as ones which do not exist yet, are returned as is.
+    fn resolve_path(&self, path: PathBuf) -> PathBuf {
+        if !self.resolve_symlinks {
+            return path;
+        }
+        std::fs::canonicalize(&path).map_or(path, strip_verbatim_prefix)
+    }
+
     /// Returns true if this installation is the retail version of World of Warcraft.
     #[inline]
     #[must_use]
@@ -71,7 +159,39 @@ impl WoWInstall {
     #[must_use]
     pub fn get_branch_path(&self) -> PathBuf {
         let install_path = PathBuf::from(&self.install_path);
-        install_path.join(self.get_product_dir_name())
+        self.resolve_path(install_path.join(self.get_product_dir_name()))
+    }
+
+    /// Find the game executable within this installation's branch folder, `None` if none of
+    /// the known executables exist, I.e. the game is still being installed.
+    #[must_use]
+    pub fn find_game_executable(&self) -> Option<PathBuf> {
+        let branch_path = self.get_branch_path();
+        GAME_EXECUTABLE_NAMES
+            .iter()
+            .map(|name| branch_path.join(name))
+            .find(|path| path.exists())
+    }
+
+    /// Log where the branch and `WTF` paths of this installation resolve to, if resolving
+    /// symlinks is enabled and they lead somewhere other than their literal path.
+    pub fn log_resolved_paths(&self) {
+        if !self.resolve_symlinks {
+            return;
+        }
+        let literal_branch = PathBuf::from(&self.install_path).join(self.get_product_dir_name());
+        for (literal, resolved) in [
+            (literal_branch.clone(), self.get_branch_path()),
+            (literal_branch.join(USER_DIR), self.get_wtf_path()),
+        ] {
+            if literal != resolved {
+                log::debug!(
+                    "Resolved `{}` to `{}`",
+                    literal.display(),
+                    resolved.display()
+                );
+            }
+        }
     }
 
     /// Returns the path to the `ChronoBind` directory for this installation.
@@ -91,16 +211,24 @@ impl WoWInstall {
     }
 
     /// Returns a formatted version of the branch name for display purposes.
+    /// Test branches are spelled out, I.e. `ptr` is "Public Test Realm" and `classic_ptr`
+    /// is "Classic PTR".
     #[inline]
+    #[must_use]
     pub fn display_branch_name(&self) -> String {
-        if self.is_retail() {
-            "Retail".to_string()
-        } else {
-            self.branch_ident
+        match self.branch_ident.as_str() {
+            WOW_RETAIL_IDENT => "Retail".to_string(),
+            "ptr" => "Public Test Realm".to_string(),
+            branch_ident => branch_ident
                 .split('_')
-                .map(capitalise)
+                .map(|word| {
+                    BRANCH_WORD_DISPLAY_NAMES
+                        .iter()
+                        .find(|(ident, _)| *ident == word)
+                        .map_or_else(|| capitalise(word), |(_, name)| (*name).to_string())
+                })
                 .collect::<Vec<String>>()
-                .join(" ")
+                .join(" "),
         }
     }
 
@@ -137,6 +265,13 @@ impl WoWInstalls {
         }
     }
 
+    /// Set whether paths within every installation are resolved through symlinks before use.
+    pub fn set_resolve_symlinks(&mut self, resolve: bool) {
+        for install in &mut self.installs {
+            install.resolve_symlinks = resolve;
+        }
+    }
+
     /// Get the number of located `WoW` installations.
     #[inline]
     #[must_use]
@@ -162,6 +297,43 @@ impl WoWInstalls {
             .find(|install| install.branch_ident.to_lowercase() == branch.to_lowercase())
     }
 
+    /// Get an iterator over every `WoW` installation of a branch, for users with the same
+    /// branch installed in more than one location.
+    #[inline]
+    pub fn find_branch_installs(&self, branch: &str) -> impl Iterator<Item = &WoWInstall> {
+        self.installs
+            .iter()
+            .filter(move |install| install.branch_ident.eq_ignore_ascii_case(branch))
+    }
+
+    /// Find the `WoW` installation a character was found in.
+    /// Falls back to the first installation of the character's branch.
+    #[must_use]
+    pub fn find_character_install(&self, character: &WoWCharacter) -> Option<&WoWInstall> {
+        self.find_branch_installs(&character.branch)
+            .find(|install| install.install_path == character.install_path)
+            .or_else(|| self.find_branch(&character.branch))
+    }
+
+    /// Get the distinct branch identifiers of the located installations, in order,
+    /// with the number of installations of each branch.
+    #[must_use]
+    pub fn distinct_branches(&self) -> Vec<(&WoWInstall, usize)> {
+        let mut branches: Vec<(&WoWInstall, usize)> = Vec::new();
+        for install in &self.installs {
+            if let Some((_, count)) = branches.iter_mut().find(|(first, _)| {
+                first
+                    .branch_ident
+                    .eq_ignore_ascii_case(&install.branch_ident)
+            }) {
+                *count += 1;
+            } else {
+                branches.push((install, 1));
+            }
+        }
+        branches
+    }
+
     /// Get an iterator over all located `WoW` installations.
     #[inline]
     pub fn iter(&self) -> impl Iterator<Item = &WoWInstall> {
@@ -170,49 +342,254 @@ impl WoWInstalls {
 }
 
 /// Extract World of Warcraft installation data from a Battle.net product installation entry.
-fn extract_wow_install_data(product: &productdb::ProductInstall) -> Option<WoWInstall> {
+fn extract_wow_install_data(
+    product: &productdb::ProductInstall,
+    resolve_symlinks: bool,
+) -> Option<WoWInstall> {
     if !product.product_code.starts_with(WOW_PRODUCT_CODE_IDENT) {
         return None;
     }
-    let branch_ident = if product.product_code == WOW_PRODUCT_CODE_IDENT {
-        WOW_RETAIL_IDENT.to_string()
-    } else {
-        product
-            .product_code
-            .strip_prefix(WOW_PRODUCT_CODE_BRANCH_PREFIX)
-            .or_else(|| product.product_code.strip_prefix(WOW_PRODUCT_CODE_IDENT))?
-            .to_string()
+    let branch_ident = branch_ident_for_product_code(&product.product_code)?;
+    let Some(install_path) = product
+        .settings
+        .as_ref()
+        .map(|settings| settings.install_path.clone())
+        .filter(|path| !path.is_empty())
+    else {
+        log::warn!(
+            "Skipping `{}` in the Battle.net product database, it has no install path",
+            product.product_code
+        );
+        return None;
     };
     Some(WoWInstall {
         product_code: product.product_code.clone(),
         branch_ident,
-        install_path: product
-            .settings
-            .as_ref()
-            .map(|settings| settings.install_path.clone())?,
+        install_path,
+        resolve_symlinks,
     })
 }
 
-/// Locate all World of Warcraft installations on the system.
+/// Get the branch identifier of a World of Warcraft product code, I.e. `wow_classic` is
+/// `classic` and `wowt` is `ptr`.
+/// Returns `None` if the product code isn't a World of Warcraft product code.
+fn branch_ident_for_product_code(product_code: &str) -> Option<String> {
+    if product_code == WOW_PRODUCT_CODE_IDENT {
+        return Some(WOW_RETAIL_IDENT.to_string());
+    }
+    if let Some((_, branch_ident)) = SPECIAL_BRANCH_PRODUCT_CODES
+        .iter()
+        .find(|(code, _)| *code == product_code)
+    {
+        return Some((*branch_ident).to_string());
+    }
+    product_code
+        .strip_prefix(WOW_PRODUCT_CODE_BRANCH_PREFIX)
+        .or_else(|| product_code.strip_prefix(WOW_PRODUCT_CODE_IDENT))
+        .filter(|branch_ident| !branch_ident.is_empty())
+        .map(ToString::to_string)
+}
+
+/// Get the product code of a World of Warcraft branch identifier, the reverse of
+/// `branch_ident_for_product_code`.
+fn product_code_for_branch_ident(branch_ident: &str) -> String {
+    if branch_ident == WOW_RETAIL_IDENT {
+        return WOW_PRODUCT_CODE_IDENT.to_string();
+    }
+    SPECIAL_BRANCH_PRODUCT_CODES
+        .iter()
+        .find(|(_, ident)| *ident == branch_ident)
+        .map_or_else(
+            || format!("{WOW_PRODUCT_CODE_BRANCH_PREFIX}{branch_ident}"),
+            |(code, _)| (*code).to_string(),
+        )
+}
+
+/// Locate all World of Warcraft installations on the system, resolving paths within them through
+/// symlinks if `resolve_symlinks` is set.
 /// # Errors
 /// This function will return an error if the Battle.net product database cannot be read or decoded.
-pub fn locate_wow_installs() -> Result<Vec<WoWInstall>, Box<dyn std::error::Error>> {
-    let product_db = get_product_db()?;
+pub fn locate_wow_installs(
+    resolve_symlinks: bool,
+) -> Result<Vec<WoWInstall>, Box<dyn std::error::Error>> {
+    let product_installs = get_product_installs()?;
 
-    Ok(product_db
-        .product_install
+    let installs = product_installs
         .iter()
-        .filter_map(extract_wow_install_data)
+        .filter_map(|product| extract_wow_install_data(product, resolve_symlinks))
+        .collect_vec();
+    let listed = installs.len();
+    let installs = drop_stale_installs(installs);
+    if installs.len() < listed {
+        log::info!(
+            "Dropped {} stale or duplicate installs listed in the Battle.net product database",
+            listed - installs.len()
+        );
+    }
+    Ok(installs)
+}
+
+/// Remove the installs whose branch folder doesn't exist, such as removed installs Battle.net
+/// still lists, and all but the first of the installs whose branch folders are the same folder.
+fn drop_stale_installs(installs: Vec<WoWInstall>) -> Vec<WoWInstall> {
+    let mut seen_paths = HashSet::new();
+    installs
+        .into_iter()
+        .filter(|install| {
+            let branch_path = install.get_branch_path();
+            if !branch_path.is_dir() {
+                log::debug!(
+                    "Skipping `{}`, its folder `{}` doesn't exist",
+                    install.product_code,
+                    branch_path.display()
+                );
+                return false;
+            }
+            let resolved = std::fs::canonicalize(&branch_path).unwrap_or(branch_path);
+            if !seen_paths.insert(resolved) {
+                log::debug!(
+                    "Skipping `{}`, its folder is already listed by another install",
+                    install.product_code
+                );
+                return false;
+            }
+            true
+        })
+        .collect()
+}
+
+/// Locate all World of Warcraft installations within a fixture directory.
+///
+/// Used in place of the Battle.net product database, every branch folder (I.e. `_retail_`)
+/// in `root` is treated as an installed branch, allowing the app to be driven without a real install.
+/// # Errors
+/// This function will return an error if the fixture directory cannot be read.
+pub fn locate_fixture_wow_installs(
+    root: &Path,
+    resolve_symlinks: bool,
+) -> Result<Vec<WoWInstall>, Box<dyn std::error::Error>> {
+    let install_path = root.to_string_lossy().to_string();
+
+    Ok(read_folders_to_string(root, resolve_symlinks)?
+        .filter_map(|dir_name| {
+            let branch_ident = dir_name.strip_prefix('_')?.strip_suffix('_')?.to_string();
+            if branch_ident.is_empty() {
+                return None;
+            }
+            let product_code = product_code_for_branch_ident(&branch_ident);
+            Some(WoWInstall {
+                product_code,
+                branch_ident,
+                install_path: install_path.clone(),
+                resolve_symlinks,
+            })
+        })
+        .sorted()
         .collect())
 }
 
-/// Get the product database from the Battle.net agent 'product.db' file, used to find
+/// Get the product installs from the Battle.net agent 'product.db' file, used to find
 /// the install location of World of Warcraft.
 /// # Errors
-/// This function will return an error if the 'product.db' file cannot be decoded.
-fn get_product_db() -> Result<productdb::Database, Box<dyn std::error::Error>> {
-    let product_db_bytes = std::fs::read(BNET_AGENT_PRODUCT_DB_PATH)?;
-    Ok(productdb::Database::decode(product_db_bytes.as_slice())?)
+/// This function will return an error if the 'product.db' file cannot be read, or no product
+/// installs can be decoded from it.
+fn get_product_installs() -> Result<Vec<productdb::ProductInstall>, Box<dyn std::error::Error>> {
+    let product_db_bytes = std::fs::read(BNET_AGENT_PRODUCT_DB_PATH).map_err(|e| {
+        format!("Failed to read Battle.net product database `{BNET_AGENT_PRODUCT_DB_PATH}`: {e}")
+    })?;
+    decode_product_installs(&product_db_bytes).map_err(|e| {
+        format!(
+            "Failed to decode Battle.net product database `{BNET_AGENT_PRODUCT_DB_PATH}` \
+            ({} bytes, starting `{}`): {e}",
+            product_db_bytes.len(),
+            hex_preview(&product_db_bytes)
+        )
+        .into()
+    })
+}
+
+/// Decode the product installs from the bytes of a 'product.db' file.
+/// The full schema is tried first. If Battle.net has changed it in a way which breaks decoding,
+/// each install entry is salvaged individually, reading only the fields needed to locate installs.
+/// # Errors
+/// Returns an error if the database cannot be decoded and no install entries can be salvaged.
+fn decode_product_installs(bytes: &[u8]) -> Result<Vec<productdb::ProductInstall>, DecodeError> {
+    let error = match productdb::Database::decode(bytes) {
+        Ok(product_db) => return Ok(product_db.product_install),
+        Err(e) => e,
+    };
+    log::warn!(
+        "Battle.net product database does not match the expected schema, salvaging installs: {error} \
+        ({} bytes, starting `{}`)",
+        bytes.len(),
+        hex_preview(bytes)
+    );
+
+    let installs = salvage_product_installs(bytes);
+    if installs.is_empty() {
+        return Err(error);
+    }
+    log::info!(
+        "Salvaged {} product installs from the Battle.net product database",
+        installs.len()
+    );
+    Ok(installs)
+}
+
+/// Salvage the product install entries from a 'product.db' file, walking the top level fields
+/// and decoding each install entry on its own, so one bad or truncated entry does not lose the rest.
+fn salvage_product_installs(mut buf: &[u8]) -> Vec<productdb::ProductInstall> {
+    /// Field number of the repeated `productInstall` field in the `Database` message.
+    const PRODUCT_INSTALL_TAG: u32 = 1;
+
+    let mut installs = Vec::new();
+    while !buf.is_empty() {
+        let Ok((tag, wire_type)) = encoding::decode_key(&mut buf) else {
+            break;
+        };
+        let len = match wire_type {
+            WireType::Varint => match encoding::decode_varint(&mut buf) {
+                Ok(_) => 0,
+                Err(_) => break,
+            },
+            WireType::SixtyFourBit => 8,
+            WireType::ThirtyTwoBit => 4,
+            WireType::LengthDelimited => {
+                match encoding::decode_varint(&mut buf).map(usize::try_from) {
+                    Ok(Ok(len)) => len,
+                    _ => break,
+                }
+            }
+            WireType::StartGroup | WireType::EndGroup => break,
+        };
+        let Some((field, rest)) = buf.split_at_checked(len) else {
+            log::warn!("Battle.net product database is truncated, ignoring the remainder");
+            break;
+        };
+        buf = rest;
+
+        if tag != PRODUCT_INSTALL_TAG || wire_type != WireType::LengthDelimited {
+            continue;
+        }
+        match productdb::ProductInstall::decode(field) {
+            Ok(install) => installs.push(install),
+            Err(e) => match productdb::MinimalProductInstall::decode(field) {
+                Ok(install) => installs.push(install.into()),
+                Err(_) => log::warn!("Skipping unreadable product install entry: {e}"),
+            },
+        }
+    }
+    installs
+}
+
+/// Format the first bytes of a buffer as hex, for diagnosing decoding errors.
+fn hex_preview(bytes: &[u8]) -> String {
+    const PREVIEW_LEN: usize = 16;
+    bytes
+        .iter()
+        .take(PREVIEW_LEN)
+        .map(|b| format!("{b:02x}"))
+        .join(" ")
 }
 
 /// Capitalises the first letter of the string
@@ -227,6 +604,8 @@ fn capitalise(s: &str) -> String {
 pub const USER_DIR: &str = "WTF";
 /// Name of the user directory for `WoW` settings.
 pub const INTERFACE_DIR: &str = "Interface";
+/// Name of the directory installed addons are kept in, within the interface directory.
+pub const ADDONS_DIR: &str = "AddOns";
 /// Name of the account directory within the `WoW` user settings.
 pub const ACCOUNT_DIR: &str = "Account";
 /// Name of the `SavedVariables` directory within the `WoW` user settings.
@@ -247,7 +626,7 @@ fn find_accounts_in_install(
         // Filter all valid results from the directory,
         // ..then filter to only valid directories
         // ..then filter to only valid account directory names
-        let accounts = read_folders_to_string(account_path)?
+        let accounts = read_folders_to_string(account_path, install.resolve_symlinks)?
             .filter(|d| is_account_dir(d))
             .collect::<Vec<String>>();
         Ok(accounts)
@@ -262,7 +641,7 @@ impl WoWInstall {
     #[must_use]
     pub fn get_wtf_path(&self) -> PathBuf {
         let install_path = self.get_branch_path();
-        install_path.join(USER_DIR)
+        self.resolve_path(install_path.join(USER_DIR))
     }
 
     /// Returns the path to the interface directory for this installation.
@@ -270,7 +649,14 @@ impl WoWInstall {
     #[must_use]
     pub fn get_interface_path(&self) -> PathBuf {
         let install_path = self.get_branch_path();
-        install_path.join(INTERFACE_DIR)
+        self.resolve_path(install_path.join(INTERFACE_DIR))
+    }
+
+    /// Returns the path to the directory of installed addons for this installation.
+    #[inline]
+    #[must_use]
+    pub fn get_addons_path(&self) -> PathBuf {
+        self.get_interface_path().join(ADDONS_DIR)
     }
 
     /// Returns the path to the user accounts settings directory for this installation.
@@ -281,6 +667,14 @@ impl WoWInstall {
         install_path.join(ACCOUNT_DIR)
     }
 
+    /// Returns `true` if the installation has a `WTF/Account` directory.
+    /// The game only creates it on first logging in, so a valid installation may not have one.
+    #[inline]
+    #[must_use]
+    pub fn has_account_dir(&self) -> bool {
+        self.get_account_path().is_dir()
+    }
+
     /// Returns the path to a specific account and realm directory within this installation.
     #[inline]
     #[must_use]
@@ -307,7 +701,7 @@ impl WoWInstall {
             .iter()
             .flat_map(|account| {
                 let account_path = self.get_account_path().join(account);
-                read_folders_to_string(account_path).map_or_else(
+                read_folders_to_string(account_path, self.resolve_symlinks).map_or_else(
                     |_| vec![],
                     |realms| {
                         realms
@@ -330,19 +724,21 @@ impl WoWInstall {
             .iter()
             .flat_map(|(account_name, realm_name)| {
                 let realm_path = self.get_realm_path(account_name, realm_name);
-                read_folders_to_string(realm_path).map_or_else(
+                read_folders_to_string(realm_path, self.resolve_symlinks).map_or_else(
                     |_| vec![],
                     |chars| {
                         chars
                             .map(|char_name| WoWCharacter {
                                 account: account_name.clone(),
                                 branch: self.branch_ident.clone(),
+                                install_path: self.install_path.clone(),
                                 name: char_name,
                                 realm: realm_name.clone(),
                                 meta: WoWCharacterMetaData::default(),
                                 config_files: vec![],
                                 addon_files: vec![],
                                 backups: vec![],
+                                previous_names: vec![],
                             })
                             .collect::<Vec<WoWCharacter>>()
                     },
@@ -354,12 +750,19 @@ impl WoWInstall {
 
     /// Find all realms characters across all realms across all accounts in this installation.
     /// This populates all file information from the character directories as well.
+    /// `aliases` maps previous character names to their current names, so that backups made
+    /// under an old name are associated with the renamed character.
     #[inline]
     #[must_use]
-    pub fn find_all_characters_and_files(&self) -> Option<Vec<WoWCharacter>> {
+    pub fn find_all_characters_and_files(
+        &self,
+        settings: &RuntimeSettings,
+        aliases: &BTreeMap<String, String>,
+    ) -> Option<Vec<WoWCharacter>> {
         let mut chars = self.find_all_characters()?;
         for c in &mut chars {
-            c.refresh_character_info(self);
+            c.set_previous_names_from_aliases(aliases);
+            c.refresh_character_info(self, settings);
         }
         Some(chars)
     }
@@ -376,6 +779,8 @@ pub struct WoWCharacterFile {
     pub path: PathBuf,
     /// An optional friendly name for the character file.
     pub friendly_name: Option<String>,
+    /// When the file was last modified, if known.
+    pub modified: Option<SystemTime>,
 }
 
 impl WoWCharacterFile {
@@ -406,6 +811,14 @@ impl WoWCharacterFile {
         self.stem.clone()
     }
 
+    /// Returns the name of the addon the file belongs to, derived from the file name,
+    /// I.e. `WeakAuras` for `WeakAuras.lua` and `WeakAuras.lua.bak`.
+    #[inline]
+    #[must_use]
+    pub fn addon_name(&self) -> &str {
+        addon_name_from_file_name(&self.name)
+    }
+
     /// Returns true if the file has a friendly name associated with it.
     #[inline]
     #[must_use]
@@ -414,6 +827,49 @@ impl WoWCharacterFile {
     }
 }
 
+/// Returns the name of the addon a `SavedVariables` file belongs to, derived from its file name,
+/// I.e. `WeakAuras` for `WeakAuras.lua` and `WeakAuras.lua.bak`.
+#[inline]
+#[must_use]
+pub fn addon_name_from_file_name(file_name: &str) -> &str {
+    file_name
+        .split_once('.')
+        .map_or(file_name, |(addon, _)| addon)
+}
+
+/// Protection tier of a backup, determining how it can be removed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BackupTier {
+    /// No protection, may be automatically removed if created automatically.
+    #[default]
+    Normal,
+    /// Pinned, never automatically removed.
+    Pinned,
+    /// Protected, never automatically or bulk removed, and requires a stronger
+    /// confirmation to delete manually.
+    Protected,
+}
+
+impl BackupTier {
+    /// Get the tier for a newly created backup from whether it should be pinned.
+    #[inline]
+    #[must_use]
+    pub const fn from_pinned(pinned: bool) -> Self {
+        if pinned { Self::Pinned } else { Self::Normal }
+    }
+
+    /// Get the display name of the tier.
+    #[inline]
+    #[must_use]
+    pub const fn display_name(&self) -> &'static str {
+        match self {
+            Self::Normal => "Normal",
+            Self::Pinned => "Pinned",
+            Self::Protected => "Protected",
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct WoWCharacterBackup {
     /// The full path to the backup file.
@@ -424,11 +880,25 @@ pub struct WoWCharacterBackup {
     pub timestamp: DateTime<Local>,
     /// Indicates whether the backup was created during a paste operation.
     pub is_paste: bool,
-    /// Indicates whether the backup is pinned to not be auto-removed.
-    pub is_pinned: bool,
+    /// The protection tier of the backup.
+    pub tier: BackupTier,
 }
 
 impl WoWCharacterBackup {
+    /// Returns `true` if the backup is pinned or protected, and so will not be auto-removed.
+    #[inline]
+    #[must_use]
+    pub fn is_pinned(&self) -> bool {
+        self.tier >= BackupTier::Pinned
+    }
+
+    /// Returns `true` if the backup is protected from all but explicit removal.
+    #[inline]
+    #[must_use]
+    pub fn is_protected(&self) -> bool {
+        self.tier == BackupTier::Protected
+    }
+
     /// Returns a formatted string representation of the backup's name, including character name and timestamp.
     #[inline]
     #[must_use]
@@ -453,6 +923,9 @@ pub struct WoWCharacter {
     pub account: String,
     /// The branch identifier of the `WoW` installation the character belongs to.
     pub branch: String,
+    /// The root path of the `WoW` installation the character was found in, telling apart
+    /// installations of the same branch in different locations.
+    pub install_path: String,
     /// The name of the character.
     pub name: String,
     /// The realm the character belongs to.
@@ -468,6 +941,9 @@ pub struct WoWCharacter {
     pub addon_files: Vec<WoWCharacterFile>,
     /// Backups associated with the character.
     pub backups: Vec<WoWCharacterBackup>,
+    /// Previous names of the character (I.e. before a rename), whose backups are
+    /// also associated with this character.
+    pub previous_names: Vec<String>,
 }
 
 /// Extensions for old (backup) config files.
@@ -492,6 +968,87 @@ pub const CHARACTER_BACKUPS_DIR: &str = "Characters";
 /// Name of the backups directory within a character's folder.
 pub const BACKUPS_DIR_NAME: &str = "Backups";
 
+/// How character backups are laid out within the `ChronoBind` backups directory.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum BackupLayout {
+    /// Each character's backups are stored in a subfolder named after the character.
+    #[default]
+    PerCharacter,
+    /// Every character's backups are stored directly in the realm's folder, and attributed
+    /// to characters by the name in the backup file name.
+    Flat,
+}
+
+impl BackupLayout {
+    /// Every layout, in cycling order.
+    pub const ALL: [Self; 2] = [Self::PerCharacter, Self::Flat];
+
+    /// Get the display name of the layout.
+    #[inline]
+    #[must_use]
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::PerCharacter => "Per-character folders",
+            Self::Flat => "Flat, one folder per realm",
+        }
+    }
+
+    /// Get the next layout, wrapping around.
+    #[inline]
+    #[must_use]
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|l| *l == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    /// Get the previous layout, wrapping around.
+    #[inline]
+    #[must_use]
+    pub fn previous(self) -> Self {
+        let idx = Self::ALL.iter().position(|l| *l == self).unwrap_or(0);
+        Self::ALL[(idx + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+/// Get the name a realm is displayed with, its alias in `realm_aliases` if one is set,
+/// otherwise its folder name.
+#[must_use]
+pub fn realm_display_name(realm_aliases: &BTreeMap<String, String>, realm: &str) -> String {
+    realm_aliases
+        .get(realm)
+        .cloned()
+        .unwrap_or_else(|| realm.to_string())
+}
+
+/// Names of folders commonly synced by cloud storage clients, matched against the start of
+/// folder names so business folders like `OneDrive - Contoso` are detected too.
+pub const DEFAULT_CLOUD_SYNC_FOLDERS: [&str; 6] = [
+    "OneDrive",
+    "Dropbox",
+    "Google Drive",
+    "GoogleDrive",
+    "iCloudDrive",
+    "iCloud Drive",
+];
+
+/// Get the folder within the path which is synced by cloud storage, if any.
+///
+/// Backups within such a folder may be caught part way through syncing, and so be incomplete.
+/// `folders` are the lowercase names of the folders synced by cloud storage.
+#[must_use]
+pub fn cloud_sync_folder(folders: &[String], path: &Path) -> Option<String> {
+    path.components().find_map(|component| {
+        let name = component.as_os_str().to_string_lossy();
+        let lower = name.to_lowercase();
+        folders
+            .iter()
+            .any(|folder| lower.starts_with(folder.as_str()))
+            .then(|| name.to_string())
+    })
+}
+
 /// Get a friendly name for a given filename, if available.
 #[inline]
 #[must_use]
@@ -502,6 +1059,34 @@ fn get_friendly_name(filename: &str) -> Option<String> {
         .map(|(_, friendly_name)| friendly_name.to_string())
 }
 
+/// Create a character file from a directory entry within the given category directory,
+/// `None` for backup archives. Files in subfolders are named by their path within the
+/// category directory, I.e. `Profiles/Default.lua`, so the name is always the file's path
+/// relative to the category directory.
+fn character_file_from_entry(entry: &DirEntry, category_dir: &Path) -> Option<WoWCharacterFile> {
+    let path = entry.path();
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    if BACKUP_EXTENSIONS.contains(&extension.as_str()) {
+        return None;
+    }
+
+    let name = path
+        .strip_prefix(category_dir)
+        .ok()?
+        .components()
+        .map(|component| component.as_os_str().to_str())
+        .collect::<Option<Vec<_>>>()?
+        .join("/");
+    let stem = path.file_stem()?.to_str()?.to_string();
+    Some(WoWCharacterFile {
+        friendly_name: get_friendly_name(&name),
+        name,
+        stem,
+        modified: entry.metadata().and_then(|m| m.modified()).ok(),
+        path,
+    })
+}
+
 impl WoWCharacter {
     /// Returns the path to the character's directory.
     #[inline]
@@ -512,14 +1097,44 @@ impl WoWCharacter {
             .join(&self.name)
     }
 
-    /// Returns the path to the character's backups directory.
+    /// Returns the path to the account-wide `SavedVariables` directory of the character's account.
     #[inline]
     #[must_use]
-    pub fn get_backups_dir(&self, install: &WoWInstall) -> PathBuf {
-        let path_segments = [&self.account, &self.realm, &self.name];
-        path_segments
-            .into_iter()
-            .fold(install.get_character_backups_dir(), |acc, p| acc.join(p))
+    pub fn get_account_saved_variables_path(&self, install: &WoWInstall) -> PathBuf {
+        install
+            .get_account_path()
+            .join(&self.account)
+            .join(SAVED_VARIABLES_DIR)
+    }
+
+    /// Returns the path to the directory new backups of the character are stored in,
+    /// according to the given backup layout.
+    #[inline]
+    #[must_use]
+    pub fn get_backups_dir(&self, install: &WoWInstall, layout: BackupLayout) -> PathBuf {
+        match layout {
+            BackupLayout::PerCharacter => self.get_backups_dir_for_name(install, &self.name),
+            BackupLayout::Flat => self.get_realm_backups_dir(install),
+        }
+    }
+
+    /// Returns the path to the per-character backups directory of a character with the given
+    /// name, on the same account and realm as this character.
+    #[inline]
+    #[must_use]
+    pub fn get_backups_dir_for_name(&self, install: &WoWInstall, name: &str) -> PathBuf {
+        self.get_realm_backups_dir(install).join(name)
+    }
+
+    /// Returns the path to the directory containing the backups of all characters on the
+    /// same account and realm as this character.
+    #[inline]
+    #[must_use]
+    pub fn get_realm_backups_dir(&self, install: &WoWInstall) -> PathBuf {
+        install
+            .get_character_backups_dir()
+            .join(&self.account)
+            .join(&self.realm)
     }
 
     /// Returns a unique identifier string for the character in the format "branch/name-realm".
@@ -528,6 +1143,22 @@ impl WoWCharacter {
     pub fn character_identifier_string(&self) -> String {
         format!("{}/{}-{}", self.branch, self.name, self.realm)
     }
+
+    /// Get the key the character is stored under in per-character settings, such as
+    /// favorite and excluded characters, I.e. `account/realm/name`.
+    #[inline]
+    #[must_use]
+    pub fn settings_key(&self) -> String {
+        self.settings_key_for_name(&self.name)
+    }
+
+    /// Get the settings key of a character with the given name on the same account and realm
+    /// as this character, such as a previous name of it, I.e. `account/realm/name`.
+    #[inline]
+    #[must_use]
+    pub fn settings_key_for_name(&self, name: &str) -> String {
+        format!("{}/{}/{name}", self.account, self.realm)
+    }
 }
 
 // File selection helper functions..
@@ -536,7 +1167,11 @@ impl WoWCharacter {
     /// Also populates the character class information if possible.
     #[inline]
     #[allow(clippy::useless_let_if_seq)]
-    pub fn refresh_character_info(&mut self, install: &WoWInstall) -> bool {
+    pub fn refresh_character_info(
+        &mut self,
+        install: &WoWInstall,
+        settings: &RuntimeSettings,
+    ) -> bool {
         let char_path = self.get_character_path(install);
         if !char_path.is_dir() || !char_path.exists() {
             return false;
@@ -544,7 +1179,7 @@ impl WoWCharacter {
 
         let mut success = true;
 
-        if !self.refresh_backups(install) {
+        if !self.refresh_backups(install, &settings.backup_name_template) {
             log::warn!(
                 "Could not read backups for character {} on realm {} (account: {})",
                 self.name,
@@ -554,7 +1189,8 @@ impl WoWCharacter {
             success = false;
         }
 
-        if !self.map_config_files(&char_path) {
+        let (depth, resolve_symlinks) = (settings.character_scan_depth, install.resolve_symlinks);
+        if !self.map_config_files(&char_path, depth, resolve_symlinks) {
             log::warn!(
                 "Could not read files for character {} on realm {} (account: {})",
                 self.name,
@@ -564,7 +1200,7 @@ impl WoWCharacter {
             success = false;
         }
 
-        if !self.map_addon_files(&char_path) {
+        if !self.map_addon_files(&char_path, depth, resolve_symlinks) {
             log::warn!(
                 "Could not read addon files for character {} on realm {} (account: {})",
                 self.name,
@@ -574,6 +1210,8 @@ impl WoWCharacter {
             success = false;
         }
 
+        self.validate_file_categories(&char_path);
+
         if self.meta.class == WoWClass::Unknown && !self.try_to_load_class() {
             log::warn!(
                 "Could not determine class for character {} on realm {} (account: {})",
@@ -587,79 +1225,105 @@ impl WoWCharacter {
         success
     }
 
-    /// Maps all `WoW` character files in the character's directory.
-    fn map_config_files(&mut self, char_path: &Path) -> bool {
+    /// Ensure each file belongs to exactly one category, so selecting every file never backs up
+    /// or pastes a file twice, or to the wrong path. Files listed more than once, or outside
+    /// their category's directory, are dropped and logged.
+    /// Config files are those within the character's directory but outside its `SavedVariables`
+    /// directory, and addon files those within it; files sharing a name are distinct files.
+    fn validate_file_categories(&mut self, char_path: &Path) {
+        let mut seen = HashSet::new();
+        let saved_variables_path = char_path.join(SAVED_VARIABLES_DIR);
+        for (files, category_dir, category, is_addon) in [
+            (&mut self.config_files, char_path, "config", false),
+            (
+                &mut self.addon_files,
+                saved_variables_path.as_path(),
+                "addon",
+                true,
+            ),
+        ] {
+            files.retain(|file| {
+                if !file.path.starts_with(category_dir)
+                    || file.path.starts_with(&saved_variables_path) != is_addon
+                {
+                    log::warn!(
+                        "Ignoring {category} file `{}`, it is not in `{}`",
+                        file.path.display(),
+                        category_dir.display()
+                    );
+                    return false;
+                }
+                if !seen.insert(file.path.clone()) {
+                    log::warn!(
+                        "Ignoring {category} file `{}`, it is already listed",
+                        file.path.display()
+                    );
+                    return false;
+                }
+                true
+            });
+        }
+
+        for file in &self.addon_files {
+            if self
+                .config_files
+                .iter()
+                .any(|config| config.name.eq_ignore_ascii_case(&file.name))
+            {
+                log::debug!(
+                    "`{}` is both a config file and an addon file of {}, kept as separate files",
+                    file.name,
+                    self.name
+                );
+            }
+        }
+    }
+
+    /// Maps all `WoW` character files in the character's directory, and its subfolders up to the
+    /// scan depth, other than the `SavedVariables` and backups folders.
+    fn map_config_files(&mut self, char_path: &Path, depth: usize, resolve_symlinks: bool) -> bool {
         self.config_files = Vec::new();
 
-        let Ok(files) = std::fs::read_dir(char_path) else {
+        let excluded_dirs = [SAVED_VARIABLES_DIR, BACKUPS_DIR_NAME];
+        let Ok(files) = read_files_to_depth(char_path, depth, &excluded_dirs, resolve_symlinks)
+        else {
             return false;
         };
 
         self.config_files = files
-            .filter_map(Result::ok)
-            .filter(|entry| entry.file_type().ok().is_some_and(|ft| ft.is_file()))
-            .filter_map(|entry| {
-                let path = entry.path();
-                let extension = path.extension()?.to_str()?.to_string();
-                if BACKUP_EXTENSIONS.contains(&extension.to_lowercase().as_str()) {
-                    return None;
-                }
-
-                let name = path.file_name()?.to_str()?.to_string();
-                let stem = path.file_stem()?.to_str()?.to_string();
-
-                Some(WoWCharacterFile {
-                    name,
-                    stem,
-                    path,
-                    friendly_name: get_friendly_name(&entry.file_name().to_string_lossy()),
-                })
-            })
+            .iter()
+            .filter_map(|entry| character_file_from_entry(entry, char_path))
             .sorted_by(|af, bf| bf.has_friendly_name().cmp(&af.has_friendly_name()))
             .collect();
 
         true
     }
 
-    /// Maps all `WoW` addon files in the character's `SavedVariables` directory.
-    fn map_addon_files(&mut self, char_path: &Path) -> bool {
+    /// Maps all `WoW` addon files in the character's `SavedVariables` directory, and its
+    /// subfolders up to the scan depth.
+    fn map_addon_files(&mut self, char_path: &Path, depth: usize, resolve_symlinks: bool) -> bool {
         self.addon_files = Vec::new();
 
         let saved_variables_path = char_path.join(SAVED_VARIABLES_DIR);
         if !saved_variables_path.is_dir() || !saved_variables_path.exists() {
             return false;
         }
-        let Ok(files) = std::fs::read_dir(&saved_variables_path) else {
+        let Ok(files) = read_files_to_depth(&saved_variables_path, depth, &[], resolve_symlinks)
+        else {
             return false;
         };
 
         self.addon_files = files
-            .filter_map(Result::ok)
-            .filter(|entry| entry.file_type().ok().is_some_and(|ft| ft.is_file()))
-            .filter_map(|entry| {
-                let path = entry.path();
-                let extension = path.extension()?.to_str()?.to_string();
-
-                if BACKUP_EXTENSIONS.contains(&extension.to_lowercase().as_str()) {
-                    return None;
-                }
-
-                let name = path.file_name()?.to_str()?.to_string();
-
-                if name.to_lowercase() == CHRONOBIND_COMPANION_FILE.to_lowercase() {
+            .iter()
+            .filter_map(|entry| character_file_from_entry(entry, &saved_variables_path))
+            .filter(|file| {
+                if file.name.eq_ignore_ascii_case(CHRONOBIND_COMPANION_FILE) {
                     // If we have this file, we don't want to show it in the list,
                     // as it would not make sense to transfer this to other characters.
                     self.meta.has_companion = true;
-                    return None;
+                    return false;
                 }
-
-                let stem = path.file_stem()?.to_str()?.to_string();
-                Some(WoWCharacterFile {
-                    name,
-                    stem,
-                    path,
-                    friendly_name: get_friendly_name(&entry.file_name().to_string_lossy()),
-                })
+                true
             })
             .sorted_by(|af, bf| bf.has_friendly_name().cmp(&af.has_friendly_name()))
             .collect();
@@ -715,35 +1379,36 @@ impl WoWCharacter {
     }
 
     /// Refresh the list of backups for this character.
-    pub fn refresh_backups(&mut self, install: &WoWInstall) -> bool {
+    /// Includes any backups made under the character's previous names.
+    /// Backups are read from both layouts, so switching layout doesn't hide existing backups.
+    /// Backup names are matched against the given template, or the default template.
+    pub fn refresh_backups(&mut self, install: &WoWInstall, template: &BackupNameTemplate) -> bool {
         self.backups = Vec::new();
 
-        let backups_dir = self.get_backups_dir(install);
-        if !backups_dir.is_dir() || !backups_dir.exists() {
-            return false;
-        }
-        let Ok(files) = crate::files::read_files(&backups_dir) else {
-            return false;
-        };
+        let own_dir = self.get_backups_dir_for_name(install, &self.name);
+        let own_backups_found = own_dir.is_dir();
 
-        self.backups = files
-            .map(|entry| entry.path())
-            .filter(|p| {
-                p.extension()
-                    .is_some_and(|txt| txt.to_str().is_some_and(|txt| txt == BACKUP_FILE_EXTENSION))
-            })
-            .filter_map(|p| Some((p.clone(), p.file_stem()?.to_str()?.to_string())))
-            .filter_map(|(p, stem)| {
-                let (char_name, timestamp, is_paste, is_pinned) =
-                    crate::backend::extract_backup_name(&stem)?;
-                Some(WoWCharacterBackup {
-                    char_name,
-                    timestamp,
-                    is_paste,
-                    is_pinned,
-                    path: p,
-                })
+        let read_backups =
+            |dir: &Path| read_backups_in_dir(dir, install.resolve_symlinks, template);
+        let mut backups = read_backups(&own_dir).unwrap_or_default();
+        for previous_name in &self.previous_names {
+            let alias_dir = self.get_backups_dir_for_name(install, previous_name);
+            if let Some(alias_backups) = read_backups(&alias_dir) {
+                backups.extend(alias_backups);
+            }
+        }
+        // Flat backups share the realm's folder, so are attributed by the name in the file name.
+        let flat_backups = read_backups(&self.get_realm_backups_dir(install))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|backup| {
+                backup.char_name == self.name || self.previous_names.contains(&backup.char_name)
             })
+            .collect_vec();
+        backups.extend(flat_backups);
+
+        self.backups = backups
+            .into_iter()
             .sorted_by(|a, b| {
                 b.timestamp
                     .partial_cmp(&a.timestamp)
@@ -751,7 +1416,7 @@ impl WoWCharacter {
             })
             .collect();
 
-        true
+        own_backups_found || !self.backups.is_empty()
     }
 
     /// Attempts to load the character class from the config file.
@@ -769,7 +1434,13 @@ impl WoWCharacter {
                 let parts: Vec<&str> = loot_class_line.split_whitespace().collect();
                 if parts.len() >= 3 {
                     let id = parts[2].trim_matches(|c| c == '\"').parse::<u8>().ok()?;
-                    self.meta.class = WoWClass::from_id(id);
+                    // A loot class of 0 means no class filter is set, which says nothing
+                    // about the character's class, so leave it unknown.
+                    let class = WoWClass::from_id(id);
+                    if class == WoWClass::Unknown {
+                        return None;
+                    }
+                    self.meta.class = class;
                     Some(())
                 } else {
                     None
@@ -779,9 +1450,89 @@ impl WoWCharacter {
     }
 }
 
+// Rename aliases..
+impl WoWCharacter {
+    /// Populate the character's previous names from the given alias map of
+    /// `account/realm/old name -> new name`, only taking aliases on the character's own
+    /// account and realm.
+    pub fn set_previous_names_from_aliases(&mut self, aliases: &BTreeMap<String, String>) {
+        let prefix = self.settings_key_for_name("");
+        self.previous_names = aliases
+            .iter()
+            .filter(|(_, new_name)| **new_name == self.name)
+            .filter_map(|(key, _)| key.strip_prefix(&prefix))
+            .filter(|old_name| !old_name.is_empty() && *old_name != self.name)
+            .map(ToString::to_string)
+            .collect();
+    }
+
+    /// Get the backups made under the given name on the same account and realm as this
+    /// character, from both backup layouts, matching backup names against the given template.
+    #[must_use]
+    pub fn backups_for_name(
+        &self,
+        install: &WoWInstall,
+        template: &BackupNameTemplate,
+        name: &str,
+    ) -> Vec<WoWCharacterBackup> {
+        let read_backups =
+            |dir: &Path| read_backups_in_dir(dir, install.resolve_symlinks, template);
+        let mut backups =
+            read_backups(&self.get_backups_dir_for_name(install, name)).unwrap_or_default();
+        backups.extend(
+            read_backups(&self.get_realm_backups_dir(install))
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|backup| backup.char_name == name),
+        );
+        backups
+    }
+
+    /// Find names on the same account and realm that have backups, but no longer
+    /// correspond to a known character, I.e. candidates for a character rename.
+    /// `realm_characters` is the list of character names currently present on the realm, and
+    /// backup names are matched against the given template.
+    #[must_use]
+    pub fn find_orphaned_backup_names(
+        &self,
+        install: &WoWInstall,
+        template: &BackupNameTemplate,
+        realm_characters: &[String],
+    ) -> Vec<String> {
+        let realm_backups_dir = self.get_realm_backups_dir(install);
+        let Ok(folders) = read_folders_to_string(&realm_backups_dir, install.resolve_symlinks)
+        else {
+            return vec![];
+        };
+
+        let read_backups =
+            |dir: &Path| read_backups_in_dir(dir, install.resolve_symlinks, template);
+        let folder_names = folders
+            .filter(|name| {
+                read_backups(&realm_backups_dir.join(name))
+                    .is_some_and(|backups| !backups.is_empty())
+            })
+            .collect_vec();
+        let flat_names = read_backups(&realm_backups_dir)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|backup| backup.char_name);
+
+        folder_names
+            .into_iter()
+            .chain(flat_names)
+            .filter(|name| *name != self.name)
+            .filter(|name| !realm_characters.contains(name))
+            .filter(|name| !self.previous_names.contains(name))
+            .sorted()
+            .dedup()
+            .collect()
+    }
+}
+
 impl WoWCharacter {
     /// Returns `true` if the other character represents the same character
-    /// (same `name`, `realm`, `account`, and `branch`).
+    /// (same `name`, `realm`, `account`, `branch` and `install_path`).
     #[inline]
     #[must_use]
     pub fn is_same_character(&self, other: &Self) -> bool {
@@ -789,6 +1540,14 @@ impl WoWCharacter {
             && self.realm == other.realm
             && self.account == other.account
             && self.branch == other.branch
+            && self.install_path == other.install_path
+    }
+
+    /// Returns `true` if the character has any config or addon files.
+    #[inline]
+    #[must_use]
+    pub const fn has_files(&self) -> bool {
+        !self.config_files.is_empty() || !self.addon_files.is_empty()
     }
 
     /// Returns a vector of unpinned & automatically generated backups for the character.
@@ -797,7 +1556,7 @@ impl WoWCharacter {
     pub fn unpinned_auto_backups(&self) -> Vec<WoWCharacterBackup> {
         self.backups
             .iter()
-            .filter(|b| !b.is_pinned && b.is_paste)
+            .filter(|b| !b.is_pinned() && b.is_paste)
             .cloned()
             .collect()
     }
@@ -808,7 +1567,7 @@ impl WoWCharacter {
     pub fn unpinned_auto_backups_count(&self) -> usize {
         self.backups
             .iter()
-            .filter(|b| !b.is_pinned && b.is_paste)
+            .filter(|b| !b.is_pinned() && b.is_paste)
             .count()
     }
 
@@ -818,11 +1577,46 @@ impl WoWCharacter {
     pub fn oldest_unpinned_auto_backup(&self) -> Option<&WoWCharacterBackup> {
         self.backups
             .iter()
-            .filter(|b| !b.is_pinned && b.is_paste)
+            .filter(|b| !b.is_pinned() && b.is_paste)
             .min_by_key(|b| b.timestamp)
     }
 }
 
+/// Read all valid backups in the given directory, matching their names against the given
+/// template, returning `None` if the directory cannot be read.
+fn read_backups_in_dir(
+    backups_dir: &Path,
+    resolve_symlinks: bool,
+    template: &BackupNameTemplate,
+) -> Option<Vec<WoWCharacterBackup>> {
+    if !backups_dir.is_dir() {
+        return None;
+    }
+    let files = crate::files::read_files(backups_dir, resolve_symlinks).ok()?;
+
+    Some(
+        files
+            .map(|entry| entry.path())
+            .filter(|p| {
+                p.extension()
+                    .is_some_and(|txt| txt.to_str().is_some_and(|txt| txt == BACKUP_FILE_EXTENSION))
+            })
+            .filter_map(|p| Some((p.clone(), p.file_stem()?.to_str()?.to_string())))
+            .filter_map(|(p, stem)| {
+                let (char_name, timestamp, is_paste, tier) =
+                    crate::backend::extract_backup_name(template, &stem)?;
+                Some(WoWCharacterBackup {
+                    char_name,
+                    timestamp,
+                    is_paste,
+                    tier,
+                    path: p,
+                })
+            })
+            .collect(),
+    )
+}
+
 /// Metadata associated with a World of Warcraft character.
 /// Most of this is stored in the `ChronoBind Companion` addon data.
 /// However some of this may be inferred from other sources.
@@ -855,8 +1649,10 @@ impl WoWCharacterMetaData {
     pub fn try_parse_from_lua_table(lua_table: &str, char_name_and_realm: &str) -> Option<Self> {
         let globals = LuaTableParser::new(lua_table.trim()).parse_globals()?;
 
-        let root_table = globals.get(Self::ROOT_GLOBAL)?.as_table()?;
-        let char_table = root_table.get(char_name_and_realm)?.as_table()?;
+        let char_table = globals
+            .get(Self::ROOT_GLOBAL)?
+            .get(char_name_and_realm)
+            .filter(|value| value.as_table().is_some())?;
 
         let class = char_table
             .get(Self::CLASS_ID_NAME)
@@ -883,56 +1679,68 @@ impl WoWCharacterMetaData {
 
 /// Represents the class of a World of Warcraft character.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[repr(u8)]
 pub enum WoWClass {
     #[default]
-    Unknown = 0,
-    Warrior = 1,
-    Paladin = 2,
-    Hunter = 3,
-    Rogue = 4,
-    Priest = 5,
-    DeathKnight = 6,
-    Shaman = 7,
-    Mage = 8,
-    Warlock = 9,
-    Monk = 10,
-    Druid = 11,
-    DemonHunter = 12,
-    Evoker = 13,
+    Unknown,
+    Warrior,
+    Paladin,
+    Hunter,
+    Rogue,
+    Priest,
+    DeathKnight,
+    Shaman,
+    Mage,
+    Warlock,
+    Monk,
+    Druid,
+    DemonHunter,
+    Evoker,
+    /// A class added to the game since, with the given ID, so it still displays distinctly.
+    Other(u8),
 }
 
 impl WoWClass {
-    /// Minimum valid Class ID.
-    pub const MIN: u8 = Self::Warrior as u8;
-    /// Maximum valid Class ID.
-    pub const MAX: u8 = Self::Evoker as u8;
+    /// Minimum known Class ID.
+    pub const MIN: u8 = 1;
+    /// Maximum known Class ID.
+    pub const MAX: u8 = 13;
 
-    /// Checks if the given class ID is valid.
+    /// Checks if the given class ID is a known class.
     #[inline]
     #[must_use]
     pub fn is_valid_class_id(id: u8) -> bool {
         (Self::MIN..=Self::MAX).contains(&id)
     }
 
-    /// Creates a `Class` from a given ID.
+    /// Creates a `Class` from a given ID, `Unknown` for an ID of 0,
+    /// or `Other` for IDs beyond the known classes.
     #[inline]
     #[must_use]
-    pub fn from_id(id: u8) -> Self {
-        if Self::is_valid_class_id(id) {
-            unsafe { std::mem::transmute::<u8, Self>(id) }
-        } else if id == 0 {
-            Self::Priest
-        } else {
-            Self::Unknown
+    pub const fn from_id(id: u8) -> Self {
+        match id {
+            0 => Self::Unknown,
+            1 => Self::Warrior,
+            2 => Self::Paladin,
+            3 => Self::Hunter,
+            4 => Self::Rogue,
+            5 => Self::Priest,
+            6 => Self::DeathKnight,
+            7 => Self::Shaman,
+            8 => Self::Mage,
+            9 => Self::Warlock,
+            10 => Self::Monk,
+            11 => Self::Druid,
+            12 => Self::DemonHunter,
+            13 => Self::Evoker,
+            id => Self::Other(id),
         }
     }
 
-    /// Returns the name of the class as a static string.
+    /// Returns the name of the class, including the ID of classes added since.
     #[inline]
     #[must_use]
-    pub const fn class_name(&self) -> &'static str {
-        match self {
+    pub fn class_name(&self) -> Cow<'static, str> {
+        Cow::Borrowed(match self {
             Self::Unknown => "Unknown",
             Self::Warrior => "Warrior",
             Self::Paladin => "Paladin",
@@ -947,15 +1755,16 @@ impl WoWClass {
             Self::Druid => "Druid",
             Self::DemonHunter => "Demon Hunter",
             Self::Evoker => "Evoker",
-        }
+            Self::Other(id) => return Cow::Owned(format!("Class {id}")),
+        })
     }
 
-    /// Returns the short name (abbreviation) of the class as a static
-    /// string.
+    /// Returns the short name (abbreviation) of the class,
+    /// including the ID of classes added since.
     #[inline]
     #[must_use]
-    pub const fn class_short_name(&self) -> &'static str {
-        match self {
+    pub fn class_short_name(&self) -> Cow<'static, str> {
+        Cow::Borrowed(match self {
             Self::Unknown => "Unk",
             Self::Warrior => "War",
             Self::Paladin => "Pala",
@@ -970,7 +1779,8 @@ impl WoWClass {
             Self::Druid => "Druid",
             Self::DemonHunter => "DH",
             Self::Evoker => "Evoker",
-        }
+            Self::Other(id) => return Cow::Owned(format!("#{id}")),
+        })
     }
 
     /// Returns the RGB colour associated with the class.
@@ -978,7 +1788,7 @@ impl WoWClass {
     #[must_use]
     pub fn class_colour(&self) -> Color {
         match self {
-            Self::Unknown => PALETTE.unknown_col,
+            Self::Unknown | Self::Other(_) => PALETTE.unknown_col,
             Self::Warrior => PALETTE.warrior_col,
             Self::Paladin => PALETTE.paladin_col,
             Self::Hunter => PALETTE.hunter_col,
@@ -995,3 +1805,254 @@ impl WoWClass {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::character::Character;
+
+    fn character(account: &str, realm: &str, name: &str) -> WoWCharacter {
+        WoWCharacter {
+            account: account.to_string(),
+            realm: realm.to_string(),
+            name: name.to_string(),
+            ..WoWCharacter::default()
+        }
+    }
+
+    #[test]
+    fn characters_in_different_installs_are_not_the_same_character() {
+        let first_install = WoWCharacter {
+            install_path: "C:/Games/World of Warcraft".to_string(),
+            ..character("ACCOUNT1", "Draenor", "Alpha")
+        };
+        let second_install = WoWCharacter {
+            install_path: "D:/World of Warcraft".to_string(),
+            ..first_install.clone()
+        };
+        assert!(first_install.is_same_character(&first_install.clone()));
+        assert!(!first_install.is_same_character(&second_install));
+    }
+
+    #[test]
+    fn previous_names_only_come_from_aliases_on_the_same_account_and_realm() {
+        let mut renamed = character("ACCOUNT1", "Draenor", "Newname");
+        let aliases = BTreeMap::from([
+            (
+                renamed.settings_key_for_name("Oldname"),
+                "Newname".to_string(),
+            ),
+            ("ACCOUNT2/Draenor/Other".to_string(), "Newname".to_string()),
+            (
+                "ACCOUNT1/Silvermoon/Third".to_string(),
+                "Newname".to_string(),
+            ),
+            (
+                "ACCOUNT1/Draenor/Unrelated".to_string(),
+                "Someone".to_string(),
+            ),
+        ]);
+        renamed.set_previous_names_from_aliases(&aliases);
+        assert_eq!(renamed.previous_names, vec!["Oldname".to_string()]);
+    }
+
+    #[test]
+    fn characters_without_a_config_cache_have_an_unknown_class() {
+        let mut alpha = character("ACCOUNT1", "Draenor", "Alpha");
+        assert!(!alpha.try_to_load_class());
+        assert_eq!(alpha.meta.class, WoWClass::Unknown);
+        assert_eq!(alpha.meta.class.class_name(), "Unknown");
+        assert_eq!(WoWClass::from_id(0), WoWClass::Unknown);
+    }
+
+    fn product_install(product_code: &str, install_path: &str) -> productdb::ProductInstall {
+        productdb::ProductInstall {
+            product_code: product_code.to_string(),
+            settings: Some(productdb::UserSettings {
+                install_path: install_path.to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn truncated_product_db_does_not_panic_and_keeps_whole_installs() {
+        let first = product_install("wow", "C:/Games/World of Warcraft");
+        let bytes = productdb::Database {
+            product_install: vec![first.clone(), product_install("wow_classic", "D:/Classic")],
+            ..Default::default()
+        }
+        .encode_to_vec();
+        assert_eq!(decode_product_installs(&bytes).map(|i| i.len()), Ok(2));
+
+        for len in 0..bytes.len() {
+            let _ = decode_product_installs(&bytes[..len]);
+        }
+        // The first entry's key and length each take a single byte.
+        let first_len = first.encoded_len() + 2;
+        let salvaged = decode_product_installs(&bytes[..first_len + 4])
+            .expect("The whole first install should be salvaged");
+        assert_eq!(salvaged, [first]);
+    }
+
+    #[test]
+    fn installs_with_a_nonexistent_or_duplicate_folder_are_dropped() {
+        let root = std::env::temp_dir().join(format!("chronobind-installs-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("_retail_")).expect("Branch folder should be created");
+        let install_path = root.to_string_lossy().to_string();
+        let missing_path = root.join("missing").to_string_lossy().to_string();
+
+        let installs = [
+            product_install("wow", &install_path),
+            product_install("wow", &install_path),
+            product_install("wow_classic", &install_path),
+            product_install("wow", &missing_path),
+        ]
+        .iter()
+        .filter_map(|product| extract_wow_install_data(product, false))
+        .collect_vec();
+        assert_eq!(installs.len(), 4);
+        let kept = drop_stale_installs(installs);
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(
+            kept.iter()
+                .map(|install| (install.branch_ident.as_str(), install.install_path.as_str()))
+                .collect_vec(),
+            [("retail", install_path.as_str())]
+        );
+    }
+
+    #[test]
+    fn special_product_codes_map_to_their_branch_folders_and_names() {
+        for (product_code, folder, name) in [
+            ("wow", "_retail_", "Retail"),
+            ("wowt", "_ptr_", "Public Test Realm"),
+            ("wowxptr", "_xptr_", "Experimental PTR"),
+            ("wow_beta", "_beta_", "Beta"),
+            ("wow_classic_ptr", "_classic_ptr_", "Classic PTR"),
+        ] {
+            let install = extract_wow_install_data(&product_install(product_code, "/wow"), false)
+                .unwrap_or_else(|| panic!("`{product_code}` should be a WoW install"));
+            assert_eq!(install.get_product_dir_name(), folder);
+            assert_eq!(install.display_branch_name(), name);
+            assert_eq!(
+                product_code_for_branch_ident(&install.branch_ident),
+                product_code
+            );
+        }
+        assert_eq!(branch_ident_for_product_code("hs_beta"), None);
+    }
+
+    #[test]
+    fn class_ids_beyond_the_known_classes_keep_their_id() {
+        let class = WoWClass::from_id(14);
+        assert_eq!(class, WoWClass::Other(14));
+        assert_eq!(class.class_name(), "Class 14");
+        assert_eq!(class.class_short_name(), "#14");
+        assert_eq!(class.class_colour(), PALETTE.unknown_col);
+        assert_eq!(WoWClass::from_id(WoWClass::MAX), WoWClass::Evoker);
+    }
+
+    fn file_in(dir: &Path, name: &str) -> WoWCharacterFile {
+        WoWCharacterFile {
+            name: name.to_string(),
+            stem: name
+                .rsplit_once('.')
+                .map_or(name, |(stem, _)| stem)
+                .to_string(),
+            path: dir.join(name),
+            ..WoWCharacterFile::default()
+        }
+    }
+
+    #[test]
+    fn selecting_every_file_never_yields_a_path_twice() {
+        let char_path = Path::new("/wow/WTF/Account/ACC/Draenor/Alpha");
+        let saved_variables_path = char_path.join(SAVED_VARIABLES_DIR);
+        let mut alpha = character("ACC", "Draenor", "Alpha");
+        alpha.config_files = vec![
+            file_in(char_path, "config-cache.wtf"),
+            file_in(char_path, "config-cache.wtf"),
+            file_in(&saved_variables_path, "Details.lua"),
+            file_in(char_path, "layout-local.txt"),
+        ];
+        alpha.addon_files = vec![
+            file_in(&saved_variables_path, "Details.lua"),
+            file_in(&saved_variables_path, "Details.lua"),
+            file_in(&saved_variables_path, "layout-local.txt"),
+        ];
+        alpha.validate_file_categories(char_path);
+
+        let mut selected = Character::new(&alpha);
+        selected.set_all_selected(true);
+        let files = selected.get_all_selected_files();
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("config-cache.wtf"),
+                PathBuf::from("layout-local.txt"),
+                PathBuf::from(SAVED_VARIABLES_DIR).join("Details.lua"),
+                PathBuf::from(SAVED_VARIABLES_DIR).join("layout-local.txt"),
+            ]
+        );
+        assert_eq!(files.iter().unique().count(), files.len());
+    }
+
+    fn mapped_file_names(char_path: &Path, depth: usize) -> (Vec<String>, Vec<String>) {
+        let mut alpha = character("ACC", "Draenor", "Alpha");
+        assert!(alpha.map_config_files(char_path, depth, false));
+        assert!(alpha.map_addon_files(char_path, depth, false));
+        alpha.validate_file_categories(char_path);
+        let names =
+            |files: &[WoWCharacterFile]| files.iter().map(|f| f.name.clone()).sorted().collect();
+        (names(&alpha.config_files), names(&alpha.addon_files))
+    }
+
+    #[test]
+    fn files_are_split_by_directory_up_to_the_scan_depth() {
+        let char_path =
+            std::env::temp_dir().join(format!("chronobind-scan-depth-{}", std::process::id()));
+        for file in [
+            "config-cache.wtf",
+            "layout-local.txt",
+            "Profiles/ui.txt",
+            "Profiles/Old/older.txt",
+            &format!("{BACKUPS_DIR_NAME}/notes.txt"),
+            &format!("{SAVED_VARIABLES_DIR}/Details.lua"),
+            &format!("{SAVED_VARIABLES_DIR}/Plater/profile.lua"),
+        ] {
+            let path = char_path.join(file);
+            std::fs::create_dir_all(path.parent().expect("File should have a parent"))
+                .expect("Folder should be created");
+            std::fs::write(&path, "").expect("File should be written");
+        }
+
+        let shallow = mapped_file_names(&char_path, 0);
+        let nested = mapped_file_names(&char_path, 1);
+        let _ = std::fs::remove_dir_all(&char_path);
+
+        assert_eq!(
+            shallow,
+            (
+                vec![
+                    "config-cache.wtf".to_string(),
+                    "layout-local.txt".to_string()
+                ],
+                vec!["Details.lua".to_string()]
+            )
+        );
+        assert_eq!(
+            nested,
+            (
+                vec![
+                    "Profiles/ui.txt".to_string(),
+                    "config-cache.wtf".to_string(),
+                    "layout-local.txt".to_string()
+                ],
+                vec!["Details.lua".to_string(), "Plater/profile.lua".to_string()]
+            )
+        );
+    }
+}